@@ -43,7 +43,7 @@ use reth_rpc_engine_api::EngineApi;
 use reth_tasks::{TaskExecutor, TaskManager};
 use reth_transaction_pool::TransactionPool;
 use std::{path::PathBuf, sync::Arc};
-use tokio::sync::{mpsc::unbounded_channel, oneshot};
+use tokio::sync::{mpsc::unbounded_channel, oneshot, watch};
 use tracing::*;
 
 /// Re-export `NodeConfig` from `reth_node_core`.
@@ -130,8 +130,10 @@ impl<DB: Database + DatabaseMetrics + DatabaseMetadata + 'static> NodeBuilderWit
         let snapshotter = reth_snapshot::Snapshotter::new(
             provider_factory.clone(),
             self.data_dir.snapshots_path(),
-            self.config.chain.snapshot_block_interval,
-        )?;
+            config.snapshots.block_interval.unwrap_or(self.config.chain.snapshot_block_interval),
+        )?
+        .with_segment_configs(config.snapshots.segment_configs())
+        .with_bytecodes(config.snapshots.snapshot_bytecodes);
 
         provider_factory = provider_factory.with_snapshots(
             self.data_dir.snapshots_path(),
@@ -192,8 +194,13 @@ impl<DB: Database + DatabaseMetrics + DatabaseMetadata + 'static> NodeBuilderWit
             BlockchainProvider::new(provider_factory.clone(), blockchain_tree.clone())?;
 
         // build transaction pool
-        let transaction_pool =
-            self.config.build_and_spawn_txpool(&blockchain_db, head, &executor, &self.data_dir)?;
+        let transaction_pool = self.config.build_and_spawn_txpool(
+            &blockchain_db,
+            head,
+            &executor,
+            &self.data_dir,
+            self.config.admission_policy_config(&config.txpool),
+        )?;
 
         // build network
         let mut network_builder = self
@@ -232,6 +239,16 @@ impl<DB: Database + DatabaseMetrics + DatabaseMetadata + 'static> NodeBuilderWit
         debug!(target: "reth::cli", peer_id = ?network.peer_id(), "Full peer ID");
         let network_client = network.fetch_client().await?;
 
+        let (snap_pivot_tx, snap_pivot_rx) =
+            watch::channel(self.config.chain.sealed_genesis_header());
+        let snap_sync = self.config.install_snap(
+            &network,
+            provider_factory.clone(),
+            &executor,
+            snap_pivot_rx,
+        )?;
+        let snap_syncing = snap_sync.is_some();
+
         ext.on_components_initialized(&components)?;
 
         debug!(target: "reth::cli", "Spawning payload builder service");
@@ -295,6 +312,7 @@ impl<DB: Database + DatabaseMetrics + DatabaseMetadata + 'static> NodeBuilderWit
                     prune_config.clone(),
                     max_block,
                     evm_config,
+                    None,
                 )
                 .await?;
 
@@ -317,10 +335,11 @@ impl<DB: Database + DatabaseMetrics + DatabaseMetadata + 'static> NodeBuilderWit
                     prune_config.clone(),
                     max_block,
                     evm_config,
+                    snap_sync,
                 )
                 .await?;
 
-            (pipeline, EitherDownloader::Right(network_client))
+            (pipeline, EitherDownloader::Right(network_client.clone()))
         };
 
         let pipeline_events = pipeline.events();
@@ -361,6 +380,15 @@ impl<DB: Database + DatabaseMetrics + DatabaseMetadata + 'static> NodeBuilderWit
         )?;
         info!(target: "reth::cli", "Consensus engine initialized");
 
+        if snap_syncing {
+            self.config.spawn_snap_pivot(
+                network_client,
+                beacon_engine_handle.event_listener(),
+                snap_pivot_tx,
+                &executor,
+            );
+        }
+
         let events = stream_select!(
             network.event_listener().map(Into::into),
             beacon_engine_handle.event_listener().map(Into::into),
@@ -6,11 +6,12 @@ use crate::{
 use clap::Parser;
 use reth_db::{
     cursor::DbCursorRO, database::Database, mdbx::DatabaseArguments, open_db_read_only,
-    table::Table, transaction::DbTx, AccountChangeSet, AccountHistory, AccountsTrie,
+    table::Table, transaction::DbTx, AccountChangeSet, AccountHistory, AccountsTrie, BadBlocks,
     BlockBodyIndices, BlockOmmers, BlockWithdrawals, Bytecodes, CanonicalHeaders, DatabaseEnv,
     HashedAccount, HashedStorage, HeaderNumbers, HeaderTD, Headers, PlainAccountState,
-    PlainStorageState, PruneCheckpoints, Receipts, StorageChangeSet, StorageHistory, StoragesTrie,
-    SyncStage, SyncStageProgress, Tables, TransactionBlock, Transactions, TxHashNumber, TxSenders,
+    PlainStorageState, PruneCheckpoints, PruneRetainedAddresses, Receipts, StorageChangeSet,
+    StorageHistory, StoragesTrie, SyncStage, SyncStageProgress, Tables, TransactionBlock,
+    Transactions, TxHashNumber, TxSenders,
 };
 use std::{
     collections::HashMap,
@@ -141,6 +142,12 @@ impl Command {
                 Tables::PruneCheckpoints => {
                     find_diffs::<PruneCheckpoints>(primary_tx, secondary_tx, output_dir)?
                 }
+                Tables::PruneRetainedAddresses => {
+                    find_diffs::<PruneRetainedAddresses>(primary_tx, secondary_tx, output_dir)?
+                }
+                Tables::BadBlocks => {
+                    find_diffs::<BadBlocks>(primary_tx, secondary_tx, output_dir)?
+                }
             };
         }
 
@@ -1,11 +1,16 @@
+use crate::dirs::{ChainPath, DataDirPath};
 use clap::{builder::RangedU64ValueParser, Parser};
+use eyre::WrapErr;
 use human_bytes::human_bytes;
 use itertools::Itertools;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use reth_config::Config;
 use reth_db::{
     database::Database,
     mdbx::{DatabaseArguments, MaxReadTransactionDuration},
-    open_db_read_only, DatabaseEnv,
+    open_db_read_only,
+    snapshot::iter_snapshots,
+    DatabaseEnv,
 };
 use reth_interfaces::db::LogLevel;
 use reth_nippy_jar::{NippyJar, NippyJarCursor};
@@ -14,13 +19,16 @@ use reth_primitives::{
     BlockNumber, ChainSpec, SnapshotSegment,
 };
 use reth_provider::{BlockNumReader, ProviderFactory, TransactionsProviderExt};
-use reth_snapshot::{segments as snap_segments, segments::Segment};
+use reth_snapshot::{
+    finish_reencoded_snapshots, reencode_snapshot, segments as snap_segments, segments::Segment,
+};
 use std::{
     ops::RangeInclusive,
     path::{Path, PathBuf},
     sync::Arc,
     time::{Duration, Instant},
 };
+use tracing::{info, warn};
 
 mod bench;
 mod headers;
@@ -73,16 +81,30 @@ pub struct Command {
     /// Specifies the perfect hashing function to use.
     #[arg(long, value_delimiter = ',', default_value_if("with_filters", "true", "fmph"))]
     phf: Vec<PerfectHashingFunction>,
+
+    /// Flag to re-encode the existing snapshots of the data directory with the snapshot
+    /// configuration of `reth.toml`, instead of generating new ones from the database.
+    #[arg(long, default_value = "false", conflicts_with_all = ["only_stats", "bench", "only_bench"])]
+    reencode: bool,
+
+    /// The path to the configuration file used by `--reencode`.
+    #[arg(long, value_name = "FILE", requires = "reencode")]
+    config: Option<PathBuf>,
 }
 
 impl Command {
     /// Execute `db snapshot` command
     pub fn execute(
         self,
-        db_path: &Path,
+        data_dir: &ChainPath<DataDirPath>,
         log_level: Option<LogLevel>,
         chain: Arc<ChainSpec>,
     ) -> eyre::Result<()> {
+        if self.reencode {
+            return self.reencode_snapshots(data_dir, chain)
+        }
+
+        let db_path = data_dir.db_path();
         let all_combinations =
             self.segments.iter().cartesian_product(self.compression.iter()).cartesian_product(
                 if self.phf.is_empty() {
@@ -94,7 +116,7 @@ impl Command {
 
         {
             let db = open_db_read_only(
-                db_path,
+                &db_path,
                 DatabaseArguments::default()
                     .max_read_transaction_duration(Some(MaxReadTransactionDuration::Unbounded)),
             )?;
@@ -121,6 +143,24 @@ impl Command {
                             factory.clone(),
                             snap_segments::Receipts::new(*compression, filters),
                         )?,
+                        SnapshotSegment::AccountChangeSets => self
+                            .generate_snapshot::<DatabaseEnv>(
+                                factory.clone(),
+                                snap_segments::AccountChangeSets::new(*compression),
+                            )?,
+                        SnapshotSegment::StorageChangeSets => self
+                            .generate_snapshot::<DatabaseEnv>(
+                                factory.clone(),
+                                snap_segments::StorageChangeSets::new(*compression),
+                            )?,
+                        SnapshotSegment::TxSenders => self.generate_snapshot::<DatabaseEnv>(
+                            factory.clone(),
+                            snap_segments::TxSenders::new(*compression, filters),
+                        )?,
+                        SnapshotSegment::Bytecodes => self.generate_snapshot::<DatabaseEnv>(
+                            factory.clone(),
+                            snap_segments::Bytecodes::new(*compression, filters),
+                        )?,
                     }
                 }
             }
@@ -130,7 +170,7 @@ impl Command {
             for ((mode, compression), phf) in all_combinations.clone() {
                 match mode {
                     SnapshotSegment::Headers => self.bench_headers_snapshot(
-                        db_path,
+                        &db_path,
                         log_level,
                         chain.clone(),
                         *compression,
@@ -138,7 +178,7 @@ impl Command {
                         phf,
                     )?,
                     SnapshotSegment::Transactions => self.bench_transactions_snapshot(
-                        db_path,
+                        &db_path,
                         log_level,
                         chain.clone(),
                         *compression,
@@ -146,13 +186,19 @@ impl Command {
                         phf,
                     )?,
                     SnapshotSegment::Receipts => self.bench_receipts_snapshot(
-                        db_path,
+                        &db_path,
                         log_level,
                         chain.clone(),
                         *compression,
                         InclusionFilter::Cuckoo,
                         phf,
                     )?,
+                    SnapshotSegment::AccountChangeSets |
+                    SnapshotSegment::StorageChangeSets |
+                    SnapshotSegment::TxSenders |
+                    SnapshotSegment::Bytecodes => {
+                        warn!(target: "reth::cli", segment = %mode, "Benchmarks are not available for this segment")
+                    }
                 }
             }
         }
@@ -182,7 +228,13 @@ impl Command {
         segment: impl Segment + Send + Sync,
     ) -> eyre::Result<()> {
         let dir = PathBuf::default();
-        let ranges = self.block_ranges(factory.best_block_number()?);
+        let tip = factory.best_block_number()?;
+        // A snapshot of a cumulative segment holds all data up to its last block.
+        let ranges = if segment.segment().is_cumulative() {
+            vec![0..=tip]
+        } else {
+            self.block_ranges(tip)
+        };
 
         let mut created_snapshots = vec![];
 
@@ -210,6 +262,55 @@ impl Command {
         self.stats(created_snapshots)
     }
 
+    /// Re-encodes the existing snapshots of the selected segments with the snapshot configuration
+    /// of `reth.toml`.
+    fn reencode_snapshots(
+        &self,
+        data_dir: &ChainPath<DataDirPath>,
+        chain: Arc<ChainSpec>,
+    ) -> eyre::Result<()> {
+        let config_path = self.config.clone().unwrap_or(data_dir.config_path());
+        // Re-encoding with default settings by accident would rewrite every selected snapshot
+        if !config_path.exists() {
+            eyre::bail!("Config file {config_path:?} does not exist")
+        }
+        let config = confy::load_path::<Config>(&config_path)
+            .wrap_err_with(|| format!("Could not load config file {:?}", config_path))?;
+        let snapshots_path = data_dir.snapshots_path();
+        finish_reencoded_snapshots(&snapshots_path)?;
+
+        let db = open_db_read_only(
+            &data_dir.db_path(),
+            DatabaseArguments::default()
+                .max_read_transaction_duration(Some(MaxReadTransactionDuration::Unbounded)),
+        )?;
+        let factory = ProviderFactory::new(db, chain);
+        let provider = factory.provider()?;
+
+        let mut reencoded_snapshots = vec![];
+        for (segment, ranges) in iter_snapshots(&snapshots_path)? {
+            if !self.segments.contains(&segment) {
+                continue
+            }
+
+            let segment_config = config.snapshots.segment_config(segment);
+            for (block_range, tx_range) in ranges {
+                let path = snapshots_path.join(segment.filename(&block_range, &tx_range));
+                info!(target: "reth::cli", ?path, ?segment_config, "Re-encoding snapshot");
+
+                reencode_snapshot(&provider, &path, segment_config)?;
+                reencoded_snapshots.push(path);
+            }
+        }
+
+        if reencoded_snapshots.is_empty() {
+            warn!(target: "reth::cli", path = ?snapshots_path, "No snapshots to re-encode");
+            return Ok(())
+        }
+
+        self.stats(reencoded_snapshots)
+    }
+
     /// Prints detailed statistics for each snapshot, including loading time.
     ///
     /// This function loads each snapshot from the provided paths and prints
@@ -7,6 +7,7 @@ pub mod import;
 pub mod init_cmd;
 pub mod node;
 pub mod p2p;
+pub mod prune;
 pub mod recover;
 pub mod stage;
 pub mod test_vectors;
@@ -0,0 +1,166 @@
+//! Command that prunes the database offline.
+
+use crate::{
+    args::{
+        utils::{chain_help, genesis_value_parser, SUPPORTED_CHAINS},
+        DatabaseArgs, PruningArgs,
+    },
+    dirs::{DataDirPath, MaybePlatformPath},
+};
+use clap::Parser;
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use eyre::Context;
+use human_bytes::human_bytes;
+use reth_config::Config;
+use reth_db::{mdbx::DatabaseArguments, open_db};
+use reth_primitives::{BlockNumber, ChainSpec, PruneProgress};
+use reth_provider::{BlockNumReader, ProviderFactory};
+use reth_prune::{PrunerBuilder, PrunerEvent};
+use std::{path::PathBuf, sync::Arc};
+use tokio::sync::watch;
+use tracing::info;
+
+/// `reth prune` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The path to the configuration file to use.
+    #[arg(long, value_name = "FILE", verbatim_doc_comment)]
+    config: Option<PathBuf>,
+
+    /// The path to the data dir for all reth files and subdirectories.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - macOS: `$HOME/Library/Application Support/reth/`
+    #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t)]
+    datadir: MaybePlatformPath<DataDirPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    #[clap(flatten)]
+    db: DatabaseArgs,
+
+    #[clap(flatten)]
+    pruning: PruningArgs,
+
+    /// The tip block number the prune targets are calculated from.
+    ///
+    /// Defaults to the highest fully synced block in the database, and can't be higher than it.
+    #[arg(long, value_name = "BLOCK")]
+    to_block: Option<BlockNumber>,
+
+    /// Maximum number of entries to delete in a single database transaction.
+    #[arg(long, value_name = "LIMIT", default_value_t = 100_000)]
+    delete_limit: usize,
+
+    /// Only estimate the number of rows and bytes that would be pruned for every segment,
+    /// without deleting anything.
+    #[arg(long, verbatim_doc_comment)]
+    dry_run: bool,
+}
+
+impl Command {
+    /// Execute `prune` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        // add network name to data dir
+        let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
+        let config_path = self.config.clone().unwrap_or(data_dir.config_path());
+        let config = confy::load_path::<Config>(&config_path)
+            .wrap_err_with(|| format!("Could not load config file {:?}", config_path))?;
+
+        let prune_config = self
+            .pruning
+            .prune_config(Arc::clone(&self.chain))?
+            .or(config.prune)
+            .ok_or_else(|| {
+                eyre::eyre!("no pruning configuration, use --full or the [prune] config section")
+            })?;
+
+        let db_path = data_dir.db_path();
+        let db = open_db(&db_path, DatabaseArguments::default().log_level(self.db.log_level))?;
+        let provider_factory = ProviderFactory::new(db, self.chain.clone());
+
+        let best_block_number = provider_factory.provider()?.best_block_number()?;
+        let tip_block_number = match self.to_block {
+            Some(block_number) if block_number > best_block_number => eyre::bail!(
+                "--to-block {block_number} is above the highest synced block {best_block_number}"
+            ),
+            Some(block_number) => block_number,
+            None => best_block_number,
+        };
+
+        info!(target: "reth::cli", ?prune_config, %tip_block_number, "Pruning database");
+
+        let mut pruner =
+            PrunerBuilder::new(prune_config).build(provider_factory, watch::channel(None).1);
+
+        if self.dry_run {
+            let estimates = pruner.dry_run(tip_block_number)?;
+
+            let mut table = ComfyTable::new();
+            table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+            table.set_header(["Segment", "Prune To Block", "Rows", "Size"]);
+
+            let (mut total_rows, mut total_bytes) = (0, 0);
+            for (segment, estimate) in estimates {
+                total_rows += estimate.rows;
+                total_bytes += estimate.bytes;
+
+                let mut row = Row::new();
+                row.add_cell(Cell::new(segment))
+                    .add_cell(Cell::new(estimate.to_block))
+                    .add_cell(Cell::new(estimate.rows))
+                    .add_cell(Cell::new(human_bytes(estimate.bytes as f64)));
+                table.add_row(row);
+            }
+
+            let mut row = Row::new();
+            row.add_cell(Cell::new("Total"))
+                .add_cell(Cell::new(""))
+                .add_cell(Cell::new(total_rows))
+                .add_cell(Cell::new(human_bytes(total_bytes as f64)));
+            table.add_row(row);
+
+            println!("{table}");
+            return Ok(())
+        }
+
+        let mut events = pruner.events().into_inner();
+        let mut runs = 0;
+        loop {
+            runs += 1;
+            let progress = pruner.run_with_delete_limit(tip_block_number, self.delete_limit)?;
+            let pruned = std::iter::from_fn(|| events.try_recv().ok())
+                .map(|PrunerEvent::Finished { stats, .. }| {
+                    stats.values().map(|(_, pruned)| pruned).sum::<usize>()
+                })
+                .sum::<usize>();
+            info!(target: "reth::cli", %runs, ?progress, %pruned, "Pruner run finished");
+
+            if progress == PruneProgress::Finished {
+                break
+            }
+
+            // A run that doesn't delete anything would be repeated forever
+            if pruned == 0 {
+                eyre::bail!("pruner made no progress in run {runs}, some segments can't be pruned")
+            }
+        }
+
+        info!(target: "reth::cli", %tip_block_number, "Pruning finished");
+
+        Ok(())
+    }
+}
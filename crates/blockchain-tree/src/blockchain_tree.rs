@@ -23,9 +23,10 @@ use reth_primitives::{
 };
 use reth_provider::{
     chain::{ChainSplit, ChainSplitTarget},
-    BlockExecutionWriter, BlockNumReader, BlockWriter, BundleStateWithReceipts,
-    CanonStateNotification, CanonStateNotificationSender, CanonStateNotifications, Chain,
-    ChainSpecProvider, DisplayBlocksChain, ExecutorFactory, HeaderProvider, ProviderError,
+    BadBlock, BadBlockWriter, BlockExecutionWriter, BlockNumReader, BlockWriter,
+    BundleStateWithReceipts, CanonStateNotification, CanonStateNotificationSender,
+    CanonStateNotifications, Chain, ChainSpecProvider, DisplayBlocksChain, ExecutorFactory,
+    HeaderProvider, ProviderError,
 };
 use reth_stages::{MetricEvent, MetricEventsSender};
 use std::{
@@ -620,7 +621,11 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTree<DB, EF> {
     ) -> Result<InsertPayloadOk, InsertBlockError> {
         match block.try_seal_with_senders() {
             Ok(block) => self.insert_block(block, BlockValidationKind::Exhaustive),
-            Err(block) => Err(InsertBlockError::sender_recovery_error(block)),
+            Err(block) => {
+                let err = InsertBlockError::sender_recovery_error(block);
+                self.on_insert_block_error(&err);
+                Err(err)
+            }
         }
     }
 
@@ -710,6 +715,32 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTree<DB, EF> {
         &mut self,
         block: SealedBlockWithSenders,
         block_validation_kind: BlockValidationKind,
+    ) -> Result<InsertPayloadOk, InsertBlockError> {
+        let res = self.try_insert_block(block, block_validation_kind);
+        if let Err(err) = &res {
+            self.on_insert_block_error(err);
+        }
+        res
+    }
+
+    /// Records the block in the database if it failed to be inserted because it's invalid.
+    ///
+    /// Blocks are only inserted while the pipeline is idle, so the write lock is available.
+    fn on_insert_block_error(&self, err: &InsertBlockError) {
+        if !err.kind().is_invalid_block() {
+            return
+        }
+        let bad_block = BadBlock { block: err.block().clone(), error: err.kind().to_string() };
+        if let Err(error) = self.externals.provider_factory.insert_bad_block(bad_block) {
+            warn!(target: "blockchain_tree", %error, hash = ?err.block().hash(), "Failed to record bad block");
+        }
+    }
+
+    /// See [BlockchainTree::insert_block].
+    fn try_insert_block(
+        &mut self,
+        block: SealedBlockWithSenders,
+        block_validation_kind: BlockValidationKind,
     ) -> Result<InsertPayloadOk, InsertBlockError> {
         // check if we already have this block
         match self.is_block_known(block.num_hash()) {
@@ -1069,7 +1100,7 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTree<DB, EF> {
             }
         }
 
-        let head = chain_notification.tip().header.clone();
+        let head = chain_notification.tip().expect("committed at least one block").header.clone();
 
         // send notification about new canonical chain.
         let _ = self.canon_state_notification_sender.send(chain_notification);
@@ -1159,6 +1190,8 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTree<DB, EF> {
     }
 
     /// Unwind tables and put it inside state
+    ///
+    /// Sends a [CanonStateNotification::Revert] with the unwound blocks.
     pub fn unwind(&mut self, unwind_to: BlockNumber) -> RethResult<()> {
         // nothing to be done if unwind_to is higher then the tip
         if self.block_indices().canonical_tip().number <= unwind_to {
@@ -1170,8 +1203,15 @@ impl<DB: Database, EF: ExecutorFactory> BlockchainTree<DB, EF> {
         // check if there is block in chain
         if let Some(old_canon_chain) = old_canon_chain {
             self.block_indices_mut().unwind_canonical_chain(unwind_to);
+
+            let chain_notification =
+                CanonStateNotification::Revert { old: Arc::new(old_canon_chain.clone()) };
+
             // insert old canonical chain to BlockchainTree.
             self.insert_unwound_chain(AppendableChain::new(old_canon_chain));
+
+            // send notification about the unwound canonical chain.
+            let _ = self.canon_state_notification_sender.send(chain_notification);
         }
 
         Ok(())
@@ -2058,6 +2098,12 @@ mod tests {
 
         // unwind canonical
         assert_eq!(tree.unwind(block1.number), Ok(()));
+
+        // check notification.
+        assert_matches!(canon_notif.try_recv(),
+            Ok(CanonStateNotification::Revert{ old })
+            if *old.blocks() == BTreeMap::from([(block2.number,block2.clone())]) &&
+                old.fork_block().hash == block1.hash());
         // Trie state:
         //    b2   b2a (pending block)
         //   /    /
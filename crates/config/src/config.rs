@@ -2,7 +2,11 @@
 
 use reth_discv4::Discv4Config;
 use reth_network::{NetworkConfigBuilder, PeersConfig, SessionsConfig};
-use reth_primitives::PruneModes;
+use reth_primitives::{
+    snapshot::{Compression, Filters, InclusionFilter, PerfectHashingFunction, SegmentConfig},
+    Address, PruneModes, Selector, SnapshotSegment,
+};
+use reth_transaction_pool::validate::{AddressList, AdmissionPolicyConfig};
 use secp256k1::SecretKey;
 use serde::{Deserialize, Serialize};
 use std::{path::PathBuf, time::Duration};
@@ -17,10 +21,14 @@ pub struct Config {
     /// Configuration for pruning.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub prune: Option<PruneConfig>,
+    /// Configuration for snapshots.
+    pub snapshots: SnapshotConfig,
     /// Configuration for the discovery service.
     pub peers: PeersConfig,
     /// Configuration for peer sessions.
     pub sessions: SessionsConfig,
+    /// Configuration for the transaction pool.
+    pub txpool: TxPoolConfig,
 }
 
 impl Config {
@@ -56,6 +64,8 @@ pub struct StageConfig {
     pub total_difficulty: TotalDifficultyConfig,
     /// Body stage configuration.
     pub bodies: BodiesConfig,
+    /// Receipts stage configuration.
+    pub receipts: ReceiptsConfig,
     /// Sender Recovery stage configuration.
     pub sender_recovery: SenderRecoveryConfig,
     /// Execution stage configuration.
@@ -161,6 +171,47 @@ impl Default for BodiesConfig {
     }
 }
 
+/// Receipts stage configuration.
+///
+/// The receipts stage downloads the receipts of blocks that were not executed locally because
+/// execution started from a trusted state.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct ReceiptsConfig {
+    /// Whether the receipts of the snap synced blocks are downloaded.
+    ///
+    /// Default: false
+    pub enabled: bool,
+    /// The batch size of blocks with receipts per one request
+    ///
+    /// Default: 64
+    pub downloader_request_limit: u64,
+    /// The maximum number of block receipts returned at once from the stream
+    ///
+    /// Default: 1_000
+    pub downloader_stream_batch_size: usize,
+    /// The minimum number of requests to send concurrently.
+    ///
+    /// Default: 5
+    pub downloader_min_concurrent_requests: usize,
+    /// The maximum number of requests to send concurrently.
+    ///
+    /// Default: 100
+    pub downloader_max_concurrent_requests: usize,
+}
+
+impl Default for ReceiptsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            downloader_request_limit: 64,
+            downloader_stream_batch_size: 1_000,
+            downloader_min_concurrent_requests: 5,
+            downloader_max_concurrent_requests: 100,
+        }
+    }
+}
+
 /// Sender recovery stage configuration.
 #[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default)]
@@ -279,9 +330,171 @@ impl Default for PruneConfig {
     }
 }
 
+/// Snapshot configuration.
+#[derive(Debug, Clone, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct SnapshotConfig {
+    /// Number of blocks in each snapshot. If not set, the default of the chain is used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_interval: Option<u64>,
+    /// Whether bytecodes are snapshotted. Every bytecode snapshot holds all bytecodes and
+    /// replaces the previous one, so each snapshot rewrites the whole table.
+    pub snapshot_bytecodes: bool,
+    /// Headers segment configuration.
+    pub headers: SnapshotSegmentConfig,
+    /// Transactions segment configuration.
+    pub transactions: SnapshotSegmentConfig,
+    /// Receipts segment configuration.
+    pub receipts: SnapshotSegmentConfig,
+    /// Account changesets segment configuration.
+    pub account_changesets: SnapshotSegmentConfig,
+    /// Storage changesets segment configuration.
+    pub storage_changesets: SnapshotSegmentConfig,
+    /// Transaction senders segment configuration.
+    pub senders: SnapshotSegmentConfig,
+    /// Bytecodes segment configuration.
+    pub bytecodes: SnapshotSegmentConfig,
+}
+
+impl SnapshotConfig {
+    /// Returns the [SegmentConfig] of the given segment.
+    pub fn segment_config(&self, segment: SnapshotSegment) -> SegmentConfig {
+        match segment {
+            SnapshotSegment::Headers => self.headers,
+            SnapshotSegment::Transactions => self.transactions,
+            SnapshotSegment::Receipts => self.receipts,
+            SnapshotSegment::AccountChangeSets => self.account_changesets,
+            SnapshotSegment::StorageChangeSets => self.storage_changesets,
+            SnapshotSegment::TxSenders => self.senders,
+            SnapshotSegment::Bytecodes => self.bytecodes,
+        }
+        .into()
+    }
+
+    /// Returns the [SegmentConfig] of every segment.
+    pub fn segment_configs(&self) -> Vec<(SnapshotSegment, SegmentConfig)> {
+        [
+            SnapshotSegment::Headers,
+            SnapshotSegment::Transactions,
+            SnapshotSegment::Receipts,
+            SnapshotSegment::AccountChangeSets,
+            SnapshotSegment::StorageChangeSets,
+            SnapshotSegment::TxSenders,
+            SnapshotSegment::Bytecodes,
+        ]
+        .into_iter()
+        .map(|segment| (segment, self.segment_config(segment)))
+        .collect()
+    }
+}
+
+impl Default for SnapshotConfig {
+    fn default() -> Self {
+        Self {
+            block_interval: None,
+            snapshot_bytecodes: false,
+            headers: SnapshotSegment::Headers.config().into(),
+            transactions: SnapshotSegment::Transactions.config().into(),
+            receipts: SnapshotSegment::Receipts.config().into(),
+            account_changesets: SnapshotSegment::AccountChangeSets.config().into(),
+            storage_changesets: SnapshotSegment::StorageChangeSets.config().into(),
+            senders: SnapshotSegment::TxSenders.config().into(),
+            bytecodes: SnapshotSegment::Bytecodes.config().into(),
+        }
+    }
+}
+
+/// Transaction pool configuration.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct TxPoolConfig {
+    /// If not empty, only transactions of these senders are admitted.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allowed_senders: Vec<Address>,
+    /// Senders whose transactions are rejected.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub denied_senders: Vec<Address>,
+    /// If not empty, only transactions to these recipients are admitted.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allowed_recipients: Vec<Address>,
+    /// Recipients whose transactions are rejected.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub denied_recipients: Vec<Address>,
+    /// Minimum priority fee, or gas price for legacy transactions, of non-local transactions in
+    /// wei.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum_priority_fee: Option<u64>,
+    /// Maximum size in bytes of a transaction's input.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_calldata_size: Option<usize>,
+    /// Function selectors that must not be called.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub blocked_selectors: Vec<Selector>,
+}
+
+impl TxPoolConfig {
+    /// Returns the [AdmissionPolicyConfig] of the pool.
+    pub fn admission_policy_config(&self) -> AdmissionPolicyConfig {
+        AdmissionPolicyConfig {
+            senders: AddressList {
+                allow: self.allowed_senders.iter().copied().collect(),
+                deny: self.denied_senders.iter().copied().collect(),
+            },
+            recipients: AddressList {
+                allow: self.allowed_recipients.iter().copied().collect(),
+                deny: self.denied_recipients.iter().copied().collect(),
+            },
+            minimum_priority_fee: self.minimum_priority_fee.map(u128::from),
+            max_calldata_size: self.max_calldata_size,
+            blocked_selectors: self.blocked_selectors.iter().copied().collect(),
+        }
+    }
+}
+
+/// Snapshot configuration of a single segment.
+///
+/// Filters are only used if both `inclusion_filter` and `phf` are set. Changeset segments are only
+/// queried by block number, so they never use filters.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+pub struct SnapshotSegmentConfig {
+    /// Compression algorithm.
+    pub compression: Compression,
+    /// Inclusion filter for lookups by hash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inclusion_filter: Option<InclusionFilter>,
+    /// Perfect hashing function for lookups by hash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phf: Option<PerfectHashingFunction>,
+}
+
+impl From<SegmentConfig> for SnapshotSegmentConfig {
+    fn from(config: SegmentConfig) -> Self {
+        let (inclusion_filter, phf) = match config.filters {
+            Filters::WithFilters(inclusion_filter, phf) => (Some(inclusion_filter), Some(phf)),
+            Filters::WithoutFilters => (None, None),
+        };
+        Self { compression: config.compression, inclusion_filter, phf }
+    }
+}
+
+impl From<SnapshotSegmentConfig> for SegmentConfig {
+    fn from(config: SnapshotSegmentConfig) -> Self {
+        let filters = match (config.inclusion_filter, config.phf) {
+            (Some(inclusion_filter), Some(phf)) => Filters::WithFilters(inclusion_filter, phf),
+            _ => Filters::WithoutFilters,
+        };
+        Self { filters, compression: config.compression }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Config;
+    use super::{Config, SnapshotConfig};
+    use reth_primitives::{
+        address,
+        snapshot::{Compression, Filters},
+        Selector, SnapshotSegment,
+    };
 
     const EXTENSION: &str = "toml";
 
@@ -430,4 +643,72 @@ storage_history = { distance = 16384 }
 #";
         let _conf: Config = toml::from_str(alpha_0_0_11).unwrap();
     }
+
+    #[test]
+    fn test_snapshot_config() {
+        let config: Config = toml::from_str(
+            r#"
+[snapshots]
+block_interval = 100000
+snapshot_bytecodes = true
+
+[snapshots.receipts]
+compression = "lz4"
+
+[snapshots.headers]
+compression = "zstd"
+inclusion_filter = "cuckoo"
+phf = "gofmph"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.snapshots.block_interval, Some(100_000));
+        assert!(config.snapshots.snapshot_bytecodes);
+        assert!(!SnapshotConfig::default().snapshot_bytecodes);
+
+        let receipts = config.snapshots.segment_config(SnapshotSegment::Receipts);
+        assert_eq!(receipts.compression, Compression::Lz4);
+        assert_eq!(receipts.filters, Filters::WithoutFilters);
+
+        let headers = config.snapshots.segment_config(SnapshotSegment::Headers);
+        assert_eq!(headers.compression, Compression::Zstd);
+        assert!(headers.filters.has_filters());
+
+        // Segments that are not configured keep their defaults
+        assert_eq!(
+            config.snapshots.segment_config(SnapshotSegment::Transactions),
+            SnapshotConfig::default().segment_config(SnapshotSegment::Transactions)
+        );
+        assert_eq!(
+            config.snapshots.segment_config(SnapshotSegment::Transactions).compression,
+            Compression::ZstdWithDictionary
+        );
+    }
+
+    #[test]
+    fn test_txpool_config() {
+        let config: Config = toml::from_str(
+            r#"
+[txpool]
+denied_senders = ["0xdac17f958d2ee523a2206206994597c13d831ec7"]
+minimum_priority_fee = 1000000000
+blocked_selectors = ["0xa9059cbb"]
+"#,
+        )
+        .unwrap();
+
+        let admission = config.txpool.admission_policy_config();
+        assert!(admission.senders.allow.is_empty());
+        assert!(admission
+            .senders
+            .deny
+            .contains(&address!("dac17f958d2ee523a2206206994597c13d831ec7")));
+        assert!(admission.recipients.is_empty());
+        assert_eq!(admission.minimum_priority_fee, Some(1_000_000_000));
+        assert_eq!(admission.max_calldata_size, None);
+        assert!(admission.blocked_selectors.contains(&Selector::from([0xa9, 0x05, 0x9c, 0xbb])));
+
+        assert!(Config::default().txpool.admission_policy_config().is_empty());
+    }
 }
@@ -6,6 +6,7 @@ use reth_interfaces::p2p::{
     download::DownloadClient,
     headers::client::{HeadersClient, HeadersFut, HeadersRequest},
     priority::Priority,
+    receipts::client::{ReceiptsClient, ReceiptsFut},
 };
 use reth_primitives::{
     BlockBody, BlockHashOrNumber, Header, HeadersDirection, PeerId, WithPeerId, B256,
@@ -118,6 +119,16 @@ impl BodiesClient for AutoSealClient {
     }
 }
 
+impl ReceiptsClient for AutoSealClient {
+    type Output = ReceiptsFut;
+
+    /// The miner doesn't buffer receipts, mined blocks are executed locally.
+    fn get_receipts_with_priority(&self, hashes: Vec<B256>, _priority: Priority) -> Self::Output {
+        trace!(target: "consensus::auto", ?hashes, "received receipts request");
+        Box::pin(async move { Ok(WithPeerId::new(PeerId::random(), Vec::new())) })
+    }
+}
+
 impl DownloadClient for AutoSealClient {
     fn report_bad_message(&self, _peer_id: PeerId) {
         warn!("Reported a bad message on a miner, we should never produce bad blocks");
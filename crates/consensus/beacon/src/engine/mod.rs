@@ -27,12 +27,14 @@ use reth_primitives::{
     SealedHeader, B256,
 };
 use reth_provider::{
-    BlockIdReader, BlockReader, BlockSource, CanonChainTracker, ChainSpecProvider, ProviderError,
-    StageCheckpointReader,
+    BadBlock, BadBlockWriter, BlockIdReader, BlockReader, BlockSource, CanonChainTracker,
+    ChainSpecProvider, ProviderError, StageCheckpointReader,
 };
 use reth_rpc_types::engine::{
-    CancunPayloadFields, ExecutionPayload, PayloadStatus, PayloadStatusEnum, PayloadValidationError,
+    CancunPayloadFields, ExecutionPayload, PayloadError, PayloadStatus, PayloadStatusEnum,
+    PayloadValidationError,
 };
+use reth_rpc_types_compat::engine::payload::try_into_block;
 
 use reth_stages::{ControlFlow, Pipeline, PipelineError};
 use reth_tasks::TaskSpawner;
@@ -172,7 +174,8 @@ where
         + BlockReader
         + BlockIdReader
         + CanonChainTracker
-        + StageCheckpointReader,
+        + StageCheckpointReader
+        + BadBlockWriter,
     EngineT: EngineTypes,
 {
     /// Controls syncing triggered by engine updates.
@@ -221,6 +224,7 @@ where
         + BlockIdReader
         + CanonChainTracker
         + StageCheckpointReader
+        + BadBlockWriter
         + ChainSpecProvider
         + 'static,
     Client: HeadersClient + BodiesClient + Clone + Unpin + 'static,
@@ -1167,11 +1171,16 @@ where
         cancun_fields: Option<CancunPayloadFields>,
     ) -> Result<SealedBlock, PayloadStatus> {
         let parent_hash = payload.parent_hash();
+        let parent_beacon_block_root = cancun_fields.as_ref().map(|f| f.parent_beacon_block_root);
 
-        match self.payload_validator.ensure_well_formed_payload(payload, cancun_fields.into()) {
+        match self
+            .payload_validator
+            .ensure_well_formed_payload(payload.clone(), cancun_fields.into())
+        {
             Ok(block) => Ok(block),
             Err(error) => {
                 error!(target: "consensus::engine", ?error, "Invalid payload");
+                self.record_bad_payload(payload, parent_beacon_block_root, &error);
                 // we need to convert the error to a payload status (response to the CL)
 
                 let latest_valid_hash =
@@ -1190,6 +1199,28 @@ where
         }
     }
 
+    /// Records a payload that was rejected by [Self::ensure_well_formed_payload] as a bad block.
+    ///
+    /// The block is sealed with the hash claimed by the payload. This is skipped if the payload
+    /// can't be converted into a block, or if the pipeline is running, because it holds exclusive
+    /// access to the database.
+    fn record_bad_payload(
+        &self,
+        payload: ExecutionPayload,
+        parent_beacon_block_root: Option<B256>,
+        error: &PayloadError,
+    ) {
+        if !self.sync.is_pipeline_idle() {
+            return
+        }
+        let block_hash = payload.block_hash();
+        let Ok(block) = try_into_block(payload, parent_beacon_block_root) else { return };
+        let bad_block = BadBlock { block: block.seal(block_hash), error: error.to_string() };
+        if let Err(error) = self.blockchain.insert_bad_block(bad_block) {
+            warn!(target: "consensus::engine", %error, ?block_hash, "Failed to record bad block");
+        }
+    }
+
     /// Validates the payload attributes with respect to the header and fork choice state.
     ///
     /// Note: At this point, the fork choice update is considered to be VALID, however, we can still
@@ -1752,6 +1783,7 @@ where
         + BlockIdReader
         + CanonChainTracker
         + StageCheckpointReader
+        + BadBlockWriter
         + ChainSpecProvider
         + Unpin
         + 'static,
@@ -1893,7 +1925,7 @@ mod tests {
     use reth_primitives::{
         stage::StageCheckpoint, ChainSpec, ChainSpecBuilder, B256, MAINNET, U256,
     };
-    use reth_provider::{BlockWriter, ProviderFactory};
+    use reth_provider::{BadBlockProvider, BlockWriter, ProviderFactory};
     use reth_rpc_types::engine::{ForkchoiceState, ForkchoiceUpdated, PayloadStatus};
     use reth_rpc_types_compat::engine::payload::try_block_to_payload_v1;
     use reth_stages::{ExecOutput, PipelineError, StageError};
@@ -2626,6 +2658,21 @@ mod tests {
             .with_latest_valid_hash(B256::ZERO);
             assert_eq!(result, expected_result);
 
+            // Send a payload with a mismatching block hash
+            let claimed_hash = B256::with_last_byte(1);
+            let mut malformed = try_block_to_payload_v1(block2.clone());
+            malformed.block_hash = claimed_hash;
+            let result = env.send_new_payload(malformed, None).await.unwrap();
+            assert_matches!(result.status, PayloadStatusEnum::Invalid { .. });
+
+            // both rejected blocks are recorded, most recent first
+            let factory = ProviderFactory::new(env.db.as_ref(), chain_spec);
+            let bad_blocks = factory.bad_blocks().unwrap();
+            assert_eq!(
+                bad_blocks.iter().map(|bad| bad.block.hash()).collect::<Vec<_>>(),
+                vec![claimed_hash, block2.hash()]
+            );
+
             assert_matches!(engine_rx.try_recv(), Err(TryRecvError::Empty));
         }
     }
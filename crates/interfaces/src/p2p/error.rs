@@ -178,6 +178,32 @@ pub enum DownloadError {
         /// Invalid block number range.
         range: RangeInclusive<BlockNumber>,
     },
+    /* ==================== RECEIPTS ERRORS ==================== */
+    /// The receipts root of the received receipts doesn't match the header.
+    #[error("receipts root mismatch for block {hash}: {root}")]
+    ReceiptsRootMismatch {
+        /// Hash of the block the receipts were requested for
+        hash: B256,
+        /// The computed and the expected receipts root
+        root: GotExpectedBoxed<B256>,
+    },
+    /// Received receipts for more blocks than requested.
+    #[error("received receipts for more blocks than requested: {0}")]
+    TooManyReceipts(GotExpected<usize>),
+    /// The number of received receipts of a block doesn't match its number of transactions.
+    #[error("receipts count mismatch for block {block_number}: {count}")]
+    ReceiptsCountMismatch {
+        /// Number of the block the receipts were requested for
+        block_number: BlockNumber,
+        /// The received and the expected number of receipts
+        count: GotExpected<usize>,
+    },
+    /// Receipts range invalid
+    #[error("requested receipts range is invalid: {range:?}")]
+    InvalidReceiptsRange {
+        /// Invalid block number range.
+        range: RangeInclusive<BlockNumber>,
+    },
     /* ==================== COMMON ERRORS ==================== */
     /// Timed out while waiting for request id response.
     #[error("timed out while waiting for response")]
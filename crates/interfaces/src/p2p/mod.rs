@@ -4,6 +4,9 @@ pub mod download;
 /// Traits for implementing P2P block body clients.
 pub mod bodies;
 
+/// Traits for implementing P2P block receipts clients.
+pub mod receipts;
+
 /// A downloader that combines two different downloaders/client implementations.
 pub mod either;
 
@@ -0,0 +1,27 @@
+use std::pin::Pin;
+
+use crate::p2p::{download::DownloadClient, error::PeerRequestResult, priority::Priority};
+use futures::Future;
+use reth_primitives::{ReceiptWithBloom, B256};
+
+/// The receipts future type
+pub type ReceiptsFut =
+    Pin<Box<dyn Future<Output = PeerRequestResult<Vec<Vec<ReceiptWithBloom>>>> + Send + Sync>>;
+
+/// A client capable of downloading block receipts.
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait ReceiptsClient: DownloadClient {
+    /// The output of the request future for querying block receipts.
+    type Output: Future<Output = PeerRequestResult<Vec<Vec<ReceiptWithBloom>>>>
+        + Sync
+        + Send
+        + Unpin;
+
+    /// Fetches the receipts of the requested blocks.
+    fn get_receipts(&self, hashes: Vec<B256>) -> Self::Output {
+        self.get_receipts_with_priority(hashes, Priority::Normal)
+    }
+
+    /// Fetches the receipts of the requested blocks with priority
+    fn get_receipts_with_priority(&self, hashes: Vec<B256>, priority: Priority) -> Self::Output;
+}
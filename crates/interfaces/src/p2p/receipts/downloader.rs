@@ -0,0 +1,18 @@
+use super::response::BlockReceipts;
+use crate::p2p::error::DownloadResult;
+use futures::Stream;
+use reth_primitives::BlockNumber;
+use std::ops::RangeInclusive;
+
+/// Receipt downloader return type.
+pub type ReceiptDownloaderResult = DownloadResult<Vec<BlockReceipts>>;
+
+/// A downloader capable of fetching and yielding block receipts from block headers.
+///
+/// A downloader represents a distinct strategy for submitting requests to download block receipts,
+/// while a [ReceiptsClient][crate::p2p::receipts::client::ReceiptsClient] represents a client
+/// capable of fulfilling these requests.
+pub trait ReceiptDownloader: Send + Sync + Stream<Item = ReceiptDownloaderResult> + Unpin {
+    /// Method for setting the download range.
+    fn set_download_range(&mut self, range: RangeInclusive<BlockNumber>) -> DownloadResult<()>;
+}
@@ -0,0 +1,8 @@
+/// Traits and types for receipts clients.
+pub mod client;
+
+/// Receipts downloaders.
+pub mod downloader;
+
+/// Receipts response
+pub mod response;
@@ -0,0 +1,17 @@
+use reth_primitives::{BlockNumber, Receipt, SealedHeader};
+
+/// The receipts of a block, validated against the `receipts_root` of its header.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct BlockReceipts {
+    /// The header of the block.
+    pub header: SealedHeader,
+    /// The receipts of the block's transactions, in order.
+    pub receipts: Vec<Receipt>,
+}
+
+impl BlockReceipts {
+    /// Return the block number
+    pub fn block_number(&self) -> BlockNumber {
+        self.header.number
+    }
+}
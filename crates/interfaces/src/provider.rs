@@ -110,6 +110,15 @@ pub enum ProviderError {
     /// State is not available for the given block number because it is pruned.
     #[error("state at block #{0} is pruned")]
     StateAtBlockPruned(BlockNumber),
+    /// History of the address is not available for the given block number because it is pruned,
+    /// and the address is not retained by the history address filter.
+    #[error("history of {address} at block #{block_number} is pruned")]
+    AddressHistoryAtBlockPruned {
+        /// The address whose history is pruned.
+        address: Address,
+        /// The block number at which the history was requested.
+        block_number: BlockNumber,
+    },
     /// Provider does not support this particular request.
     #[error("this provider does not support this request")]
     UnsupportedProvider,
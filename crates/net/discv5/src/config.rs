@@ -0,0 +1,142 @@
+//! Configuration of the discv5 discovery service.
+
+use discv5::{Discv5ConfigBuilder as Discv5ServiceConfigBuilder, Enr, ListenConfig};
+use reth_primitives::{Chain, ForkId};
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+
+/// The default port of discv5, which is different from the discv4 port so both can run alongside.
+pub const DEFAULT_DISCOVERY_V5_PORT: u16 = 9000;
+
+/// The default address of discv5.
+pub const DEFAULT_DISCOVERY_V5_ADDR: SocketAddr =
+    SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), DEFAULT_DISCOVERY_V5_PORT);
+
+/// The ENR key of the fork id of Ethereum nodes, see <https://eips.ethereum.org/EIPS/eip-2124>.
+pub const ETH: &[u8] = b"eth";
+
+/// The ENR key of the fork id of OP stack nodes.
+pub const OPSTACK: &[u8] = b"opstack";
+
+/// Returns the ENR key under which the nodes of the given chain advertise their fork id.
+pub fn fork_id_key(chain: Chain) -> &'static [u8] {
+    if chain.is_optimism() {
+        OPSTACK
+    } else {
+        ETH
+    }
+}
+
+/// Configuration of [Discv5](crate::Discv5).
+#[derive(Clone, Debug)]
+pub struct Discv5Config {
+    /// The configuration of the underlying [discv5::Discv5] service.
+    pub discv5_config: discv5::Discv5Config,
+    /// The UDP address to listen on, which is advertised in the local ENR unless unspecified.
+    pub listen_addr: SocketAddr,
+    /// The TCP port of the RLPx listener to advertise in the local ENR.
+    pub tcp_port: u16,
+    /// Nodes to boot from.
+    pub bootstrap_nodes: Vec<Enr>,
+    /// The ENR key and value of the local fork id.
+    ///
+    /// If set, discovered nodes are only reported if they advertise a fork id under the same key.
+    /// Whether the fork id is compatible is left to the network, like with discv4.
+    pub fork: Option<(&'static [u8], ForkId)>,
+    /// The rate at which new random lookups are triggered.
+    pub lookup_interval: Duration,
+}
+
+impl Discv5Config {
+    /// Returns a new default builder instance
+    pub fn builder() -> Discv5ConfigBuilder {
+        Default::default()
+    }
+
+    /// Sets the fork id to advertise, under the ENR key of the given chain.
+    pub fn set_fork(&mut self, chain: Chain, fork_id: ForkId) -> &mut Self {
+        self.fork = Some((fork_id_key(chain), fork_id));
+        self
+    }
+}
+
+impl Default for Discv5Config {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// Builder type for [`Discv5Config`]
+#[derive(Clone, Debug)]
+pub struct Discv5ConfigBuilder {
+    listen_addr: SocketAddr,
+    tcp_port: u16,
+    bootstrap_nodes: Vec<Enr>,
+    fork: Option<(&'static [u8], ForkId)>,
+    lookup_interval: Duration,
+}
+
+impl Default for Discv5ConfigBuilder {
+    fn default() -> Self {
+        Self {
+            listen_addr: DEFAULT_DISCOVERY_V5_ADDR,
+            tcp_port: 30303,
+            bootstrap_nodes: Vec::new(),
+            fork: None,
+            lookup_interval: Duration::from_secs(20),
+        }
+    }
+}
+
+impl Discv5ConfigBuilder {
+    /// Sets the UDP address to listen on.
+    pub fn listen_addr(&mut self, listen_addr: SocketAddr) -> &mut Self {
+        self.listen_addr = listen_addr;
+        self
+    }
+
+    /// Sets the TCP port of the RLPx listener to advertise.
+    pub fn tcp_port(&mut self, tcp_port: u16) -> &mut Self {
+        self.tcp_port = tcp_port;
+        self
+    }
+
+    /// Adds a node to boot from.
+    pub fn add_boot_node(&mut self, node: Enr) -> &mut Self {
+        self.bootstrap_nodes.push(node);
+        self
+    }
+
+    /// Adds multiple nodes to boot from.
+    pub fn add_boot_nodes(&mut self, nodes: impl IntoIterator<Item = Enr>) -> &mut Self {
+        self.bootstrap_nodes.extend(nodes);
+        self
+    }
+
+    /// Sets the fork id to advertise, under the ENR key of the given chain.
+    pub fn fork(&mut self, chain: Chain, fork_id: ForkId) -> &mut Self {
+        self.fork = Some((fork_id_key(chain), fork_id));
+        self
+    }
+
+    /// Sets the rate at which new random lookups are triggered.
+    pub fn lookup_interval(&mut self, interval: Duration) -> &mut Self {
+        self.lookup_interval = interval;
+        self
+    }
+
+    /// Returns the configured [`Discv5Config`]
+    pub fn build(&self) -> Discv5Config {
+        let listen_config = ListenConfig::from_ip(self.listen_addr.ip(), self.listen_addr.port());
+        Discv5Config {
+            discv5_config: Discv5ServiceConfigBuilder::new(listen_config).build(),
+            listen_addr: self.listen_addr,
+            tcp_port: self.tcp_port,
+            bootstrap_nodes: self.bootstrap_nodes.clone(),
+            fork: self.fork,
+            lookup_interval: self.lookup_interval,
+        }
+    }
+}
@@ -0,0 +1,374 @@
+//! Discovery support for the network using [discv5](https://github.com/ethereum/devp2p/blob/master/discv5/discv5.md).
+//!
+//! This wraps the [discv5] service. Nodes are found by random lookups in the DHT and are only
+//! reported if they advertise a fork id under the configured ENR key, e.g. `eth` for Ethereum or
+//! `opstack` for OP stack chains, see [`Discv5Config::fork`].
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use alloy_rlp::{Decodable, Encodable, Header};
+use discv5::{
+    enr::{CombinedKey, EnrBuilder, EnrPublicKey, NodeId},
+    Discv5Event, Enr,
+};
+use reth_discv4::EnrForkIdEntry;
+use reth_primitives::{Bytes, ForkId, NodeRecord, PeerId};
+use secp256k1::SecretKey;
+use std::{
+    net::IpAddr,
+    sync::{Arc, Weak},
+};
+use tokio::{sync::mpsc, task::JoinHandle};
+use tracing::{debug, trace};
+
+mod config;
+pub use config::{
+    fork_id_key, Discv5Config, Discv5ConfigBuilder, DEFAULT_DISCOVERY_V5_ADDR,
+    DEFAULT_DISCOVERY_V5_PORT, ETH, OPSTACK,
+};
+
+/// Re-export of the underlying discv5 crate.
+pub use discv5;
+
+/// Errors of [Discv5].
+#[derive(Debug, thiserror::Error)]
+pub enum Discv5Error {
+    /// Failed to build the local ENR.
+    #[error("failed to build local ENR: {0}")]
+    Enr(String),
+    /// Failed to create the discv5 service.
+    #[error("failed to create discv5 service: {0}")]
+    Init(&'static str),
+    /// Failed to start the discv5 service.
+    #[error("failed to start discv5 service: {0:?}")]
+    Start(discv5::Discv5Error),
+}
+
+/// A node discovered over discv5.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveredPeer {
+    /// The node record derived from the node's ENR.
+    pub node_record: NodeRecord,
+    /// The fork id the node advertises, if any.
+    pub fork_id: Option<ForkId>,
+}
+
+/// Handle to the running discv5 service.
+///
+/// Random lookups are triggered by a spawned task for as long as this handle exists.
+pub struct Discv5 {
+    /// The discv5 service.
+    discv5: Arc<discv5::Discv5>,
+    /// The ENR key that discovered nodes must advertise their fork id under.
+    fork_key: Option<&'static [u8]>,
+    /// The handle to the spawned lookup task.
+    _lookup_task: JoinHandle<()>,
+}
+
+impl Discv5 {
+    /// Starts the discv5 service and returns the handle and the stream of its events.
+    ///
+    /// The events are to be passed to [Discv5::on_discv5_update] to obtain the discovered nodes.
+    pub async fn start(
+        sk: &SecretKey,
+        config: Discv5Config,
+    ) -> Result<(Self, mpsc::Receiver<Discv5Event>), Discv5Error> {
+        let Discv5Config {
+            discv5_config,
+            listen_addr,
+            tcp_port,
+            bootstrap_nodes,
+            fork,
+            lookup_interval,
+        } = config;
+
+        let mut secret_bytes = sk.secret_bytes();
+        let enr_key = CombinedKey::secp256k1_from_bytes(&mut secret_bytes)
+            .map_err(|err| Discv5Error::Enr(err.to_string()))?;
+
+        let local_enr = {
+            let mut builder = EnrBuilder::new("v4");
+            // An unspecified address is updated by discv5 once peers report the external address.
+            if !listen_addr.ip().is_unspecified() {
+                builder.ip(listen_addr.ip());
+                match listen_addr.ip() {
+                    IpAddr::V4(_) => builder.udp4(listen_addr.port()).tcp4(tcp_port),
+                    IpAddr::V6(_) => builder.udp6(listen_addr.port()).tcp6(tcp_port),
+                };
+            }
+            if let Some((key, fork_id)) = fork {
+                let mut buf = Vec::new();
+                EnrForkIdEntry::from(fork_id).encode(&mut buf);
+                builder.add_value_rlp(key, buf.into());
+            }
+            builder.build(&enr_key).map_err(|err| Discv5Error::Enr(err.to_string()))?
+        };
+
+        let mut discv5 =
+            discv5::Discv5::new(local_enr, enr_key, discv5_config).map_err(Discv5Error::Init)?;
+        discv5.start().await.map_err(Discv5Error::Start)?;
+        let events = discv5.event_stream().await.map_err(Discv5Error::Start)?;
+
+        for node in bootstrap_nodes {
+            if let Err(err) = discv5.add_enr(node) {
+                debug!(target: "net::discv5", %err, "Failed to add boot node");
+            }
+        }
+
+        let discv5 = Arc::new(discv5);
+        let _lookup_task = tokio::spawn(lookup(Arc::downgrade(&discv5), lookup_interval));
+
+        Ok((Self { discv5, fork_key: fork.map(|(key, _)| key), _lookup_task }, events))
+    }
+
+    /// Returns the local ENR of the node.
+    pub fn local_enr(&self) -> Enr {
+        self.discv5.local_enr()
+    }
+
+    /// Returns the id with which the local node identifies itself in the network.
+    pub fn local_id(&self) -> PeerId {
+        enr_to_peer_id(&self.local_enr()).expect("local ENR uses a secp256k1 key")
+    }
+
+    /// Updates the fork id in the local ENR, if the fork id is advertised.
+    ///
+    /// The ENR's sequence number is bumped, so peers fetch the updated record.
+    pub fn set_fork_id(&self, fork_id: ForkId) {
+        let Some(key) = self.fork_key else { return };
+        let key = std::str::from_utf8(key).expect("fork id keys are ASCII");
+        let mut buf = Vec::new();
+        EnrForkIdEntry::from(fork_id).encode(&mut buf);
+        if let Err(err) = self.discv5.enr_insert(key, &buf) {
+            debug!(target: "net::discv5", %err, "Failed to update fork id in local ENR");
+        }
+    }
+
+    /// Adds a node to the routing table.
+    pub fn add_node(&self, node: Enr) {
+        if let Err(err) = self.discv5.add_enr(node) {
+            trace!(target: "net::discv5", %err, "Failed to add node");
+        }
+    }
+
+    /// Bans the [`IpAddr`] in the discovery service.
+    pub fn ban_ip(&self, ip: IpAddr) {
+        self.discv5.ban_ip(ip, None)
+    }
+
+    /// Bans the [`PeerId`] and [`IpAddr`] in the discovery service.
+    pub fn ban(&self, peer_id: PeerId, ip: IpAddr) {
+        if let Some(node_id) = peer_id_to_node_id(peer_id) {
+            self.discv5.ban_node(&node_id, None);
+        }
+        self.discv5.ban_ip(ip, None)
+    }
+
+    /// Returns the node discovered by the given event, if it passes the fork id filter.
+    pub fn on_discv5_update(&self, event: Discv5Event) -> Option<DiscoveredPeer> {
+        match event {
+            Discv5Event::Discovered(enr) | Discv5Event::SessionEstablished(enr, _) => {
+                self.on_discovered_enr(&enr)
+            }
+            _ => None,
+        }
+    }
+
+    /// Converts the ENR of a discovered node, if it passes the fork id filter.
+    fn on_discovered_enr(&self, enr: &Enr) -> Option<DiscoveredPeer> {
+        let fork_id = match self.fork_key {
+            Some(key) => {
+                let Some(fork_id) = enr_fork_id(enr, key) else {
+                    trace!(target: "net::discv5", node_id=%enr.node_id(), "Skipping node without fork id");
+                    return None
+                };
+                Some(fork_id)
+            }
+            None => None,
+        };
+        let node_record = enr_to_node_record(enr)?;
+        Some(DiscoveredPeer { node_record, fork_id })
+    }
+}
+
+impl std::fmt::Debug for Discv5 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Discv5")
+            .field("local_enr", &self.local_enr())
+            .field("fork_key", &self.fork_key)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Triggers random lookups until the service is dropped.
+async fn lookup(discv5: Weak<discv5::Discv5>, interval: std::time::Duration) {
+    let mut interval = tokio::time::interval(interval);
+    loop {
+        interval.tick().await;
+        let Some(discv5) = discv5.upgrade() else { return };
+        // the found nodes are reported as events
+        if let Err(err) = discv5.find_node(NodeId::random()).await {
+            trace!(target: "net::discv5", ?err, "Random lookup failed");
+        }
+    }
+}
+
+/// Returns the fork id the ENR advertises under the given key.
+///
+/// The entry is either stored as RLP list, or wrapped in an RLP string if it was inserted into the
+/// ENR of a running service, see [Discv5::set_fork_id].
+pub fn enr_fork_id(enr: &Enr, key: &[u8]) -> Option<ForkId> {
+    let mut maybe_fork_id = enr.get_raw_rlp(key)?;
+    let entry = if Header::decode(&mut &maybe_fork_id[..]).ok()?.list {
+        EnrForkIdEntry::decode(&mut maybe_fork_id)
+    } else {
+        let wrapped = Bytes::decode(&mut maybe_fork_id).ok()?;
+        EnrForkIdEntry::decode(&mut &wrapped[..])
+    };
+    entry.ok().map(|entry| entry.fork_id)
+}
+
+/// Returns the [PeerId] of the ENR's public key, if it's a secp256k1 key.
+pub fn enr_to_peer_id(enr: &Enr) -> Option<PeerId> {
+    let public_key = enr.public_key().encode_uncompressed();
+    (public_key.len() == PeerId::len_bytes()).then(|| PeerId::from_slice(&public_key))
+}
+
+/// Converts the ENR into a [NodeRecord], if it has a secp256k1 key and an address.
+///
+/// If the ENR has no TCP port, the UDP port is assumed.
+pub fn enr_to_node_record(enr: &Enr) -> Option<NodeRecord> {
+    let (address, udp_port, tcp_port) = if let Some(ip) = enr.ip4() {
+        let udp_port = enr.udp4()?;
+        (IpAddr::from(ip), udp_port, enr.tcp4().unwrap_or(udp_port))
+    } else {
+        let ip = enr.ip6()?;
+        let udp_port = enr.udp6()?;
+        (IpAddr::from(ip), udp_port, enr.tcp6().unwrap_or(udp_port))
+    };
+    Some(NodeRecord { address, tcp_port, udp_port, id: enr_to_peer_id(enr)? }.into_ipv4_mapped())
+}
+
+/// Converts the [PeerId] into the discv5 node id of its key.
+fn peer_id_to_node_id(peer_id: PeerId) -> Option<NodeId> {
+    let mut uncompressed = [0u8; 65];
+    uncompressed[0] = 4;
+    uncompressed[1..].copy_from_slice(peer_id.as_slice());
+    let public_key = discv5::enr::k256::ecdsa::VerifyingKey::from_sec1_bytes(&uncompressed).ok()?;
+    Some(NodeId::from(public_key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{ForkHash, MAINNET};
+    use secp256k1::rand::thread_rng;
+    use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
+
+    fn unused_udp_addr() -> SocketAddr {
+        UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap().local_addr().unwrap()
+    }
+
+    async fn start_discv5(fork: Option<ForkId>) -> (Discv5, mpsc::Receiver<Discv5Event>) {
+        let sk = SecretKey::new(&mut thread_rng());
+        let mut builder = Discv5Config::builder();
+        builder
+            .listen_addr(unused_udp_addr())
+            .lookup_interval(std::time::Duration::from_millis(100));
+        if let Some(fork_id) = fork {
+            builder.fork(MAINNET.chain, fork_id);
+        }
+        Discv5::start(&sk, builder.build()).await.unwrap()
+    }
+
+    #[test]
+    fn peer_id_roundtrip() {
+        let sk = SecretKey::new(&mut thread_rng());
+        let mut secret_bytes = sk.secret_bytes();
+        let enr_key = CombinedKey::secp256k1_from_bytes(&mut secret_bytes).unwrap();
+        let enr =
+            EnrBuilder::new("v4").ip4(Ipv4Addr::LOCALHOST).udp4(30303).build(&enr_key).unwrap();
+
+        let peer_id = enr_to_peer_id(&enr).unwrap();
+        assert_eq!(
+            peer_id,
+            PeerId::from_slice(&sk.public_key(secp256k1::SECP256K1).serialize_uncompressed()[1..])
+        );
+        assert_eq!(peer_id_to_node_id(peer_id), Some(enr.node_id()));
+
+        let record = enr_to_node_record(&enr).unwrap();
+        assert_eq!(record.address, IpAddr::V4(Ipv4Addr::LOCALHOST));
+        assert_eq!(record.udp_port, 30303);
+        assert_eq!(record.tcp_port, 30303);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn discover_over_localhost() {
+        reth_tracing::init_test_tracing();
+
+        let fork_id = ForkId { hash: ForkHash([0xdc, 0xe9, 0x6c, 0x2d]), next: 0 };
+        let (node, _) = start_discv5(Some(fork_id)).await;
+        let (bootnode, _) = start_discv5(Some(fork_id)).await;
+        let (other, _) = start_discv5(None).await;
+        assert_eq!(enr_fork_id(&node.local_enr(), ETH), Some(fork_id));
+
+        // the bootnode knows a node with and a node without fork id
+        bootnode.add_node(node.local_enr());
+        bootnode.add_node(other.local_enr());
+
+        let sk = SecretKey::new(&mut thread_rng());
+        let mut builder = Discv5Config::builder();
+        builder
+            .listen_addr(unused_udp_addr())
+            .fork(MAINNET.chain, fork_id)
+            .add_boot_node(bootnode.local_enr())
+            .lookup_interval(std::time::Duration::from_millis(100));
+        let (discv5, mut events) = Discv5::start(&sk, builder.build()).await.unwrap();
+
+        let expected = DiscoveredPeer {
+            node_record: enr_to_node_record(&node.local_enr()).unwrap(),
+            fork_id: Some(fork_id),
+        };
+        let other_id = other.local_id();
+        let discover = async {
+            loop {
+                let event = events.recv().await.unwrap();
+                if let Some(peer) = discv5.on_discv5_update(event) {
+                    assert_ne!(
+                        peer.node_record.id, other_id,
+                        "node without fork id was not filtered"
+                    );
+                    if peer == expected {
+                        break
+                    }
+                }
+            }
+        };
+        tokio::time::timeout(std::time::Duration::from_secs(30), discover)
+            .await
+            .expect("node was not discovered");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn update_fork_id() {
+        let fork_id = ForkId { hash: ForkHash([0xdc, 0xe9, 0x6c, 0x2d]), next: 0 };
+        let (node, _) = start_discv5(Some(fork_id)).await;
+        let seq = node.local_enr().seq();
+
+        let next = ForkId { hash: ForkHash([0x9f, 0x3d, 0x22, 0x54]), next: 0 };
+        node.set_fork_id(next);
+        assert_eq!(enr_fork_id(&node.local_enr(), ETH), Some(next));
+        assert!(node.local_enr().seq() > seq);
+
+        // nodes that don't advertise a fork id keep their ENR
+        let (other, _) = start_discv5(None).await;
+        let enr = other.local_enr();
+        other.set_fork_id(next);
+        assert_eq!(other.local_enr(), enr);
+    }
+}
@@ -17,6 +17,9 @@ pub mod bodies;
 /// The collection of algorithms for downloading block headers.
 pub mod headers;
 
+/// The collection of algorithms for downloading block receipts.
+pub mod receipts;
+
 /// Common downloader metrics.
 pub mod metrics;
 
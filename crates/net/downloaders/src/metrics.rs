@@ -132,3 +132,45 @@ impl HeaderDownloaderMetrics {
         }
     }
 }
+
+/// Common receipt downloader metrics.
+///
+/// These metrics will be initialized with the `downloaders.receipts` scope.
+/// ```
+/// use reth_downloaders::metrics::ReceiptDownloaderMetrics;
+/// use reth_interfaces::p2p::error::DownloadError;
+///
+/// // Initialize metrics.
+/// let metrics = ReceiptDownloaderMetrics::default();
+/// // Increment `downloaders.receipts.timeout_errors` counter by 1.
+/// metrics.increment_errors(&DownloadError::Timeout);
+/// ```
+#[derive(Clone, Metrics)]
+#[metrics(scope = "downloaders.receipts")]
+pub struct ReceiptDownloaderMetrics {
+    /// The number of items that were successfully sent to the poller (stage)
+    pub total_flushed: Counter,
+    /// Number of items that were successfully downloaded
+    pub total_downloaded: Counter,
+    /// The number of requests (can contain more than 1 item) currently in-flight.
+    pub in_flight_requests: Gauge,
+    /// The number blocks that are contiguous and are queued for insertion into the db.
+    pub queued_blocks: Gauge,
+    /// Number of timeout errors while requesting items
+    pub timeout_errors: Counter,
+    /// Number of validation errors while requesting items
+    pub validation_errors: Counter,
+    /// Number of unexpected errors while requesting items
+    pub unexpected_errors: Counter,
+}
+
+impl ReceiptDownloaderMetrics {
+    /// Increment errors counter.
+    pub fn increment_errors(&self, error: &DownloadError) {
+        match error {
+            DownloadError::Timeout => self.timeout_errors.increment(1),
+            DownloadError::ReceiptsRootMismatch { .. } => self.validation_errors.increment(1),
+            _error => self.unexpected_errors.increment(1),
+        }
+    }
+}
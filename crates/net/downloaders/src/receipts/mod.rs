@@ -0,0 +1,5 @@
+/// A naive concurrent downloader.
+#[allow(clippy::module_inception)]
+pub mod receipts;
+
+mod request;
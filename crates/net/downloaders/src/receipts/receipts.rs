@@ -0,0 +1,459 @@
+use super::request::ReceiptsRequestFuture;
+use crate::metrics::ReceiptDownloaderMetrics;
+use futures::{stream::FuturesOrdered, Stream};
+use futures_util::StreamExt;
+use reth_config::ReceiptsConfig;
+use reth_interfaces::p2p::{
+    error::{DownloadError, DownloadResult},
+    receipts::{
+        client::ReceiptsClient,
+        downloader::{ReceiptDownloader, ReceiptDownloaderResult},
+        response::BlockReceipts,
+    },
+};
+use reth_primitives::{constants::EMPTY_ROOT_HASH, BlockNumber, SealedHeader};
+use reth_provider::HeaderProvider;
+use std::{
+    ops::RangeInclusive,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tracing::info;
+
+/// Downloads receipts in batches.
+///
+/// The receipts of every block are validated against the `receipts_root` of its header, which is
+/// read from the database. Responses are yielded in block order.
+#[must_use = "Stream does nothing unless polled"]
+#[derive(Debug)]
+pub struct ReceiptsDownloader<C: ReceiptsClient, Provider> {
+    /// The receipts client
+    client: Arc<C>,
+    /// The database handle
+    provider: Provider,
+    /// The maximum number of blocks with receipts per one request
+    request_limit: u64,
+    /// The maximum number of block receipts returned at once from the stream
+    stream_batch_size: usize,
+    /// The allowed range for number of concurrent requests.
+    concurrent_requests_range: RangeInclusive<usize>,
+    /// The range of block numbers for receipts download.
+    download_range: RangeInclusive<BlockNumber>,
+    /// The last requested block number.
+    last_requested_block_number: Option<BlockNumber>,
+    /// Requests in progress, in the order they were submitted.
+    in_progress_queue: FuturesOrdered<ReceiptsRequestFuture<C>>,
+    /// Queued receipts that can be returned for insertion into the database.
+    queued_receipts: Vec<BlockReceipts>,
+    /// The receipts downloader metrics.
+    metrics: ReceiptDownloaderMetrics,
+}
+
+impl<C, Provider> ReceiptsDownloader<C, Provider>
+where
+    C: ReceiptsClient + 'static,
+    Provider: HeaderProvider + Unpin + 'static,
+{
+    /// Returns the next contiguous request.
+    ///
+    /// The batch ends once it contains [Self::request_limit] blocks with receipts or
+    /// [Self::stream_batch_size] blocks in total.
+    fn next_headers_request(&mut self) -> DownloadResult<Option<Vec<SealedHeader>>> {
+        let start_at = match self.last_requested_block_number {
+            Some(num) => num + 1,
+            None => *self.download_range.start(),
+        };
+        let range = start_at..=*self.download_range.end();
+        if range.is_empty() {
+            return Ok(None)
+        }
+
+        let mut collected = 0;
+        let mut non_empty_headers = 0;
+        let headers = self.provider.sealed_headers_while(range.clone(), |header| {
+            let should_take = range.contains(&header.number) &&
+                non_empty_headers < self.request_limit &&
+                collected < self.stream_batch_size;
+
+            if should_take {
+                collected += 1;
+                if header.receipts_root != EMPTY_ROOT_HASH {
+                    non_empty_headers += 1;
+                }
+            }
+            should_take
+        })?;
+
+        // The headers of the range are expected to be present.
+        match headers.first() {
+            Some(first) if first.number == start_at => {}
+            _ => return Err(DownloadError::MissingHeader { block_number: start_at }),
+        }
+
+        self.last_requested_block_number = headers.last().map(|header| header.number);
+        Ok(Some(headers))
+    }
+
+    /// Max requests to handle at the same time
+    ///
+    /// This depends on the number of active peers but will always be
+    /// [`min_concurrent_requests`..`max_concurrent_requests`]
+    #[inline]
+    fn concurrent_request_limit(&self) -> usize {
+        let num_peers = self.client.num_connected_peers();
+        num_peers
+            .clamp(*self.concurrent_requests_range.start(), *self.concurrent_requests_range.end())
+    }
+
+    /// Returns true if all receipts of the range were requested.
+    fn is_fully_requested(&self) -> bool {
+        self.download_range.is_empty() ||
+            self.last_requested_block_number
+                .map(|last| last == *self.download_range.end())
+                .unwrap_or_default()
+    }
+
+    // Check if the stream is terminated
+    fn is_terminated(&self) -> bool {
+        self.is_fully_requested() &&
+            self.in_progress_queue.is_empty() &&
+            self.queued_receipts.is_empty()
+    }
+
+    /// Clear all download related data.
+    ///
+    /// Should be invoked upon encountering fatal error.
+    fn clear(&mut self) {
+        self.download_range = RangeInclusive::new(1, 0);
+        self.last_requested_block_number.take();
+        self.in_progress_queue = FuturesOrdered::new();
+        self.queued_receipts = Vec::new();
+
+        // reset metrics
+        self.metrics.in_flight_requests.set(0.);
+        self.metrics.queued_blocks.set(0.);
+    }
+
+    /// Returns the next batch of block receipts that can be returned if enough receipts are
+    /// queued.
+    fn try_split_next_batch(&mut self) -> Option<Vec<BlockReceipts>> {
+        if self.queued_receipts.len() >= self.stream_batch_size {
+            return Some(self.split_next_batch())
+        }
+        None
+    }
+
+    /// Removes up to [Self::stream_batch_size] block receipts from the queue.
+    fn split_next_batch(&mut self) -> Vec<BlockReceipts> {
+        let batch_size = self.stream_batch_size.min(self.queued_receipts.len());
+        let next_batch = self.queued_receipts.drain(..batch_size).collect::<Vec<_>>();
+        self.queued_receipts.shrink_to_fit();
+        self.metrics.total_flushed.increment(next_batch.len() as u64);
+        self.metrics.queued_blocks.set(self.queued_receipts.len() as f64);
+        next_batch
+    }
+}
+
+impl<C, Provider> ReceiptDownloader for ReceiptsDownloader<C, Provider>
+where
+    C: ReceiptsClient + 'static,
+    Provider: HeaderProvider + Unpin + 'static,
+{
+    /// Set a new download range (inclusive).
+    ///
+    /// If the range continues the current range, the download proceeds. Otherwise all queued and
+    /// in progress requests are dropped and the download restarts at the start of the range.
+    fn set_download_range(&mut self, range: RangeInclusive<BlockNumber>) -> DownloadResult<()> {
+        // Check if the range is valid.
+        if range.is_empty() {
+            tracing::error!(target: "downloaders::receipts", ?range, "Receipts download range is invalid (empty)");
+            return Err(DownloadError::InvalidReceiptsRange { range })
+        }
+
+        // Check if the provided range is the subset of the existing range.
+        let is_current_range_subset = self.download_range.contains(range.start()) &&
+            *range.end() == *self.download_range.end();
+        if is_current_range_subset {
+            tracing::trace!(target: "downloaders::receipts", ?range, "Download range already in progress");
+            // The current range already includes requested.
+            return Ok(())
+        }
+
+        // Check if the provided range is the next expected range.
+        let count = *range.end() - *range.start() + 1; // range is inclusive
+        let is_next_consecutive_range = *range.start() == *self.download_range.end() + 1;
+        if is_next_consecutive_range {
+            // New range received.
+            tracing::trace!(target: "downloaders::receipts", ?range, "New download range set");
+            info!(target: "downloaders::receipts", count, ?range, "Downloading receipts");
+            self.download_range = range;
+            return Ok(())
+        }
+
+        // The block range is reset. This can happen after an unwind.
+        tracing::trace!(target: "downloaders::receipts", ?range, prev_range = ?self.download_range, "Download range reset");
+        info!(target: "downloaders::receipts", count, ?range, "Downloading receipts");
+        self.clear();
+        self.download_range = range;
+        Ok(())
+    }
+}
+
+impl<C, Provider> Stream for ReceiptsDownloader<C, Provider>
+where
+    C: ReceiptsClient + 'static,
+    Provider: HeaderProvider + Unpin + 'static,
+{
+    type Item = ReceiptDownloaderResult;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.is_terminated() {
+            return Poll::Ready(None)
+        }
+        // Submit new requests and poll any in progress
+        loop {
+            // Yield next batch if ready
+            if let Some(next_batch) = this.try_split_next_batch() {
+                return Poll::Ready(Some(Ok(next_batch)))
+            }
+
+            // Poll requests, responses are yielded in submission order
+            while let Poll::Ready(Some(response)) = this.in_progress_queue.poll_next_unpin(cx) {
+                this.metrics.in_flight_requests.decrement(1.);
+                match response {
+                    Ok(response) => {
+                        this.queued_receipts.extend(response);
+                        this.metrics.queued_blocks.set(this.queued_receipts.len() as f64);
+                    }
+                    Err(error) => {
+                        tracing::debug!(target: "downloaders::receipts", ?error, "Request failed");
+                        this.clear();
+                        return Poll::Ready(Some(Err(error)))
+                    }
+                };
+            }
+
+            // Loop exit condition
+            let mut new_request_submitted = false;
+            // Submit new requests
+            let concurrent_requests_limit = this.concurrent_request_limit();
+            while this.in_progress_queue.len() < concurrent_requests_limit &&
+                this.queued_receipts.len() < this.stream_batch_size
+            {
+                match this.next_headers_request() {
+                    Ok(Some(request)) => {
+                        this.metrics.in_flight_requests.increment(1.);
+                        this.in_progress_queue.push_back(
+                            ReceiptsRequestFuture::new(
+                                Arc::clone(&this.client),
+                                this.metrics.clone(),
+                            )
+                            .with_headers(request),
+                        );
+                        new_request_submitted = true;
+                    }
+                    Ok(None) => break,
+                    Err(error) => {
+                        tracing::error!(target: "downloaders::receipts", ?error, "Failed to download from next request");
+                        this.clear();
+                        return Poll::Ready(Some(Err(error)))
+                    }
+                };
+            }
+
+            if !new_request_submitted {
+                break
+            }
+        }
+
+        // All requests are handled, stream is finished
+        if this.in_progress_queue.is_empty() && this.is_fully_requested() {
+            if this.queued_receipts.is_empty() {
+                return Poll::Ready(None)
+            }
+            return Poll::Ready(Some(Ok(this.split_next_batch())))
+        }
+
+        Poll::Pending
+    }
+}
+
+/// Builder for [ReceiptsDownloader].
+#[derive(Debug, Clone)]
+pub struct ReceiptsDownloaderBuilder {
+    /// The batch size of blocks with receipts per one request
+    pub request_limit: u64,
+    /// The maximum number of block receipts returned at once from the stream
+    pub stream_batch_size: usize,
+    /// The maximum number of requests to send concurrently.
+    pub concurrent_requests_range: RangeInclusive<usize>,
+}
+
+impl ReceiptsDownloaderBuilder {
+    /// Creates a new [ReceiptsDownloaderBuilder] with configurations based on the provided
+    /// [ReceiptsConfig].
+    pub fn new(config: ReceiptsConfig) -> Self {
+        ReceiptsDownloaderBuilder::default()
+            .with_stream_batch_size(config.downloader_stream_batch_size)
+            .with_request_limit(config.downloader_request_limit)
+            .with_concurrent_requests_range(
+                config.downloader_min_concurrent_requests..=
+                    config.downloader_max_concurrent_requests,
+            )
+    }
+}
+
+impl Default for ReceiptsDownloaderBuilder {
+    fn default() -> Self {
+        Self { request_limit: 64, stream_batch_size: 1_000, concurrent_requests_range: 5..=100 }
+    }
+}
+
+impl ReceiptsDownloaderBuilder {
+    /// Set request batch size on the downloader.
+    pub fn with_request_limit(mut self, request_limit: u64) -> Self {
+        self.request_limit = request_limit;
+        self
+    }
+
+    /// Set stream batch size on the downloader.
+    pub fn with_stream_batch_size(mut self, stream_batch_size: usize) -> Self {
+        self.stream_batch_size = stream_batch_size;
+        self
+    }
+
+    /// Set concurrent requests range on the downloader.
+    pub fn with_concurrent_requests_range(
+        mut self,
+        concurrent_requests_range: RangeInclusive<usize>,
+    ) -> Self {
+        self.concurrent_requests_range = concurrent_requests_range;
+        self
+    }
+
+    /// Consume self and return the concurrent downloader.
+    pub fn build<C, Provider>(
+        self,
+        client: C,
+        provider: Provider,
+    ) -> ReceiptsDownloader<C, Provider>
+    where
+        C: ReceiptsClient + 'static,
+        Provider: HeaderProvider,
+    {
+        let Self { request_limit, stream_batch_size, concurrent_requests_range } = self;
+        ReceiptsDownloader {
+            client: Arc::new(client),
+            provider,
+            request_limit,
+            stream_batch_size,
+            concurrent_requests_range,
+            download_range: RangeInclusive::new(1, 0),
+            last_requested_block_number: None,
+            in_progress_queue: FuturesOrdered::new(),
+            queued_receipts: Default::default(),
+            metrics: ReceiptDownloaderMetrics::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        bodies::test_utils::insert_headers,
+        test_utils::{generate_receipts, TestReceiptsClient},
+    };
+    use assert_matches::assert_matches;
+    use reth_db::test_utils::create_test_rw_db;
+    use reth_primitives::{ReceiptWithBloom, B256, MAINNET};
+    use reth_provider::ProviderFactory;
+    use std::collections::HashMap;
+
+    fn expected_receipts(
+        headers: &[SealedHeader],
+        receipts: &HashMap<B256, Vec<ReceiptWithBloom>>,
+    ) -> Vec<BlockReceipts> {
+        headers
+            .iter()
+            .map(|header| BlockReceipts {
+                header: header.clone(),
+                receipts: receipts
+                    .get(&header.hash())
+                    .map(|receipts| receipts.iter().map(|r| r.receipt.clone()).collect())
+                    .unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    // Check that the receipts are emitted in order of block number.
+    #[tokio::test]
+    async fn streams_receipts_in_order() {
+        let db = create_test_rw_db();
+        let (headers, receipts) = generate_receipts(0..=99);
+
+        insert_headers(db.db(), &headers);
+
+        let client = Arc::new(TestReceiptsClient::default().with_receipts(receipts.clone()));
+        let mut downloader = ReceiptsDownloaderBuilder::default()
+            .with_request_limit(10)
+            .build(client.clone(), ProviderFactory::new(db, MAINNET.clone()));
+        downloader.set_download_range(0..=99).expect("failed to set download range");
+
+        let downloaded = downloader
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .flat_map(|res| res.expect("download succeeds"))
+            .collect::<Vec<_>>();
+        assert_eq!(downloaded, expected_receipts(&headers, &receipts));
+
+        let non_empty = receipts.len() as u64;
+        assert_eq!(client.times_requested(), non_empty.div_ceil(10));
+    }
+
+    // Check that receipts are returned in correct order after resetting the download range.
+    #[tokio::test]
+    async fn streams_receipts_in_order_after_range_reset() {
+        let db = create_test_rw_db();
+        let (headers, receipts) = generate_receipts(0..=99);
+
+        insert_headers(db.db(), &headers);
+
+        let stream_batch_size = 20;
+        let client = Arc::new(TestReceiptsClient::default().with_receipts(receipts.clone()));
+        let mut downloader = ReceiptsDownloaderBuilder::default()
+            .with_stream_batch_size(stream_batch_size)
+            .with_request_limit(5)
+            .build(client.clone(), ProviderFactory::new(db, MAINNET.clone()));
+
+        let mut range_start = 0;
+        while range_start < 100 {
+            downloader.set_download_range(range_start..=99).expect("failed to set download range");
+
+            let expected = expected_receipts(
+                &headers[range_start as usize..(range_start as usize + stream_batch_size)],
+                &receipts,
+            );
+            assert_matches!(downloader.next().await, Some(Ok(res)) => assert_eq!(res, expected));
+            range_start += stream_batch_size as u64;
+        }
+    }
+
+    // Check that the downloader fails if the headers of the range are missing.
+    #[tokio::test]
+    async fn fails_on_missing_headers() {
+        let db = create_test_rw_db();
+        let client = Arc::new(TestReceiptsClient::default());
+        let mut downloader = ReceiptsDownloaderBuilder::default()
+            .build(client, ProviderFactory::new(db, MAINNET.clone()));
+        downloader.set_download_range(0..=9).expect("failed to set download range");
+
+        assert_matches!(
+            downloader.next().await,
+            Some(Err(DownloadError::MissingHeader { block_number: 0 }))
+        );
+    }
+}
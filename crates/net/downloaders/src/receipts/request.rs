@@ -0,0 +1,307 @@
+use crate::metrics::ReceiptDownloaderMetrics;
+use futures::{Future, FutureExt};
+use reth_interfaces::p2p::{
+    error::{DownloadError, DownloadResult},
+    priority::Priority,
+    receipts::{client::ReceiptsClient, response::BlockReceipts},
+};
+use reth_primitives::{
+    constants::EMPTY_ROOT_HASH, proofs::calculate_receipt_root, GotExpected, PeerId,
+    ReceiptWithBloom, SealedHeader, WithPeerId, B256,
+};
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    sync::Arc,
+    task::{ready, Context, Poll},
+};
+
+/// Receipts request implemented as a [Future].
+///
+/// The future will poll the underlying request until fulfilled.
+/// If the response arrived with insufficient number of receipts, the future
+/// will issue another request until the receipts of all blocks are collected.
+///
+/// The receipts of every block are verified against the `receipts_root` of its header. In case of
+/// a validation error, the peer is penalized and the remaining blocks are requested again.
+///
+/// Blocks without transactions don't have receipts, no request is dispatched for them.
+///
+/// NB: This assumes that peers respond with receipts in the order that they were requested.
+pub(crate) struct ReceiptsRequestFuture<C: ReceiptsClient> {
+    client: Arc<C>,
+    metrics: ReceiptDownloaderMetrics,
+    // Headers to download the receipts for. The collection is shrunk as responses are buffered.
+    pending_headers: VecDeque<SealedHeader>,
+    /// Internal buffer for all block receipts
+    buffer: Vec<BlockReceipts>,
+    fut: Option<C::Output>,
+    /// Tracks how many blocks we requested the receipts for in the last request.
+    last_request_len: Option<usize>,
+}
+
+impl<C> ReceiptsRequestFuture<C>
+where
+    C: ReceiptsClient + 'static,
+{
+    /// Returns an empty future. Use [ReceiptsRequestFuture::with_headers] to set the request.
+    pub(crate) fn new(client: Arc<C>, metrics: ReceiptDownloaderMetrics) -> Self {
+        Self {
+            client,
+            metrics,
+            pending_headers: Default::default(),
+            buffer: Default::default(),
+            last_request_len: None,
+            fut: None,
+        }
+    }
+
+    pub(crate) fn with_headers(mut self, headers: Vec<SealedHeader>) -> Self {
+        self.buffer.reserve_exact(headers.len());
+        self.pending_headers = VecDeque::from(headers);
+        // Submit the request only if there are any receipts to download.
+        // Otherwise, the future will immediately be resolved.
+        if let Some(req) = self.next_request() {
+            self.submit_request(req, Priority::Normal);
+        }
+        self
+    }
+
+    fn on_error(&mut self, error: DownloadError, peer_id: Option<PeerId>) {
+        self.metrics.increment_errors(&error);
+        tracing::debug!(target: "downloaders::receipts", ?peer_id, %error, "Error requesting receipts");
+        if let Some(peer_id) = peer_id {
+            self.client.report_bad_message(peer_id);
+        }
+        self.submit_request(
+            self.next_request().expect("existing hashes to resubmit"),
+            Priority::High,
+        );
+    }
+
+    /// Retrieve block hashes for the next request.
+    fn next_request(&self) -> Option<Vec<B256>> {
+        let mut hashes = self
+            .pending_headers
+            .iter()
+            .filter(|h| !has_no_receipts(h))
+            .map(|h| h.hash())
+            .peekable();
+        hashes.peek().is_some().then(|| hashes.collect())
+    }
+
+    /// Submit the request with the given priority.
+    fn submit_request(&mut self, req: Vec<B256>, priority: Priority) {
+        tracing::trace!(target: "downloaders::receipts", request_len = req.len(), "Requesting receipts");
+        let client = Arc::clone(&self.client);
+        self.last_request_len = Some(req.len());
+        self.fut = Some(client.get_receipts_with_priority(req, priority));
+    }
+
+    /// Process receipts response.
+    /// Returns an error if the response is invalid.
+    fn on_receipts_response(
+        &mut self,
+        response: WithPeerId<Vec<Vec<ReceiptWithBloom>>>,
+    ) -> DownloadResult<()> {
+        let (peer_id, receipts) = response.split();
+        let request_len = self.last_request_len.unwrap_or_default();
+        let response_len = receipts.len();
+
+        tracing::trace!(target: "downloaders::receipts", request_len, response_len, ?peer_id, "Received receipts");
+
+        // Increment total downloaded metric
+        self.metrics.total_downloaded.increment(response_len as u64);
+
+        if receipts.is_empty() {
+            return Err(DownloadError::EmptyResponse)
+        }
+
+        if response_len > request_len {
+            return Err(DownloadError::TooManyReceipts(GotExpected {
+                got: response_len,
+                expected: request_len,
+            }))
+        }
+
+        // Buffer block receipts
+        self.try_buffer_receipts(receipts)?;
+
+        // Submit next request if any
+        if let Some(req) = self.next_request() {
+            self.submit_request(req, Priority::High);
+        } else {
+            self.fut = None;
+        }
+
+        Ok(())
+    }
+
+    /// Attempt to buffer receipts responses. Returns an error if the receipts of a block fail
+    /// validation. The receipts of every block preceding the failed one will be buffered.
+    ///
+    /// This method removes headers from the internal collection.
+    /// If the response fails validation, then the header will be put back.
+    fn try_buffer_receipts(&mut self, receipts: Vec<Vec<ReceiptWithBloom>>) -> DownloadResult<()> {
+        let mut receipts = receipts.into_iter().peekable();
+        while receipts.peek().is_some() {
+            let next_header = match self.pending_headers.pop_front() {
+                Some(header) => header,
+                None => return Ok(()), // no more headers
+            };
+
+            if has_no_receipts(&next_header) {
+                self.buffer.push(BlockReceipts { header: next_header, receipts: Vec::new() });
+                continue
+            }
+
+            let next_receipts = receipts.next().unwrap();
+            let root = calculate_receipt_root(&next_receipts);
+            if root != next_header.receipts_root {
+                // Receipts are invalid, put the header back and return an error
+                let hash = next_header.hash();
+                let expected = next_header.receipts_root;
+                self.pending_headers.push_front(next_header);
+                return Err(DownloadError::ReceiptsRootMismatch {
+                    hash,
+                    root: GotExpected { got: root, expected }.into(),
+                })
+            }
+
+            self.buffer.push(BlockReceipts {
+                header: next_header,
+                receipts: next_receipts.into_iter().map(|receipt| receipt.receipt).collect(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl<C> Future for ReceiptsRequestFuture<C>
+where
+    C: ReceiptsClient + 'static,
+{
+    type Output = DownloadResult<Vec<BlockReceipts>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        loop {
+            // Buffer the blocks without receipts
+            while this.pending_headers.front().map(has_no_receipts).unwrap_or_default() {
+                let header = this.pending_headers.pop_front().unwrap();
+                this.buffer.push(BlockReceipts { header, receipts: Vec::new() });
+            }
+
+            if this.pending_headers.is_empty() {
+                return Poll::Ready(Ok(std::mem::take(&mut this.buffer)))
+            }
+
+            // The next header has receipts, submit a request if none is in progress.
+            let Some(fut) = this.fut.as_mut() else {
+                this.submit_request(
+                    this.next_request().expect("existing hashes to request"),
+                    Priority::Normal,
+                );
+                continue
+            };
+            match ready!(fut.poll_unpin(cx)) {
+                Ok(response) => {
+                    let peer_id = response.peer_id();
+                    if let Err(error) = this.on_receipts_response(response) {
+                        this.on_error(error, Some(peer_id));
+                    }
+                }
+                Err(error) => {
+                    if error.is_channel_closed() {
+                        return Poll::Ready(Err(error.into()))
+                    }
+
+                    this.on_error(error.into(), None);
+                }
+            }
+        }
+    }
+}
+
+/// Returns true if the block has no transactions and therefore no receipts.
+fn has_no_receipts(header: &SealedHeader) -> bool {
+    header.receipts_root == EMPTY_ROOT_HASH
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{generate_receipts, TestReceiptsClient};
+    use assert_matches::assert_matches;
+
+    /// Check if future returns empty receipts without dispatching any requests.
+    #[tokio::test]
+    async fn request_returns_empty_receipts() {
+        let (mut headers, _) = generate_receipts(0..=19);
+        for header in &mut headers {
+            let mut unsealed = header.clone().unseal();
+            unsealed.receipts_root = EMPTY_ROOT_HASH;
+            *header = unsealed.seal_slow();
+        }
+
+        let client = Arc::new(TestReceiptsClient::default());
+        let fut = ReceiptsRequestFuture::new(client.clone(), ReceiptDownloaderMetrics::default())
+            .with_headers(headers.clone());
+
+        let receipts = fut.await.unwrap();
+        assert_eq!(receipts.len(), headers.len());
+        assert!(receipts.iter().all(|block| block.receipts.is_empty()));
+        assert_eq!(client.times_requested(), 0);
+    }
+
+    /// Check that the request future re-requests the receipts the peer didn't respond with.
+    #[tokio::test]
+    async fn request_submits_until_fulfilled() {
+        let (headers, receipts) = generate_receipts(0..=19);
+        let batch_size = 2;
+        let client = Arc::new(
+            TestReceiptsClient::default()
+                .with_receipts(receipts.clone())
+                .with_max_batch_size(batch_size),
+        );
+        let fut = ReceiptsRequestFuture::new(client.clone(), ReceiptDownloaderMetrics::default())
+            .with_headers(headers.clone());
+
+        let downloaded = fut.await.unwrap();
+        assert_eq!(downloaded.len(), headers.len());
+        for block in &downloaded {
+            let expected = receipts
+                .get(&block.header.hash())
+                .map(|receipts| receipts.iter().map(|r| r.receipt.clone()).collect())
+                .unwrap_or_default();
+            assert_eq!(block.receipts, expected);
+        }
+
+        let non_empty = headers.iter().filter(|h| !has_no_receipts(h)).count() as u64;
+        assert_eq!(client.times_requested(), non_empty.div_ceil(batch_size as u64));
+    }
+
+    /// Check that receipts that don't match the receipts root are rejected.
+    #[tokio::test]
+    async fn request_rejects_invalid_receipts() {
+        let (headers, receipts) = generate_receipts(0..=19);
+        let header = headers.iter().find(|h| !has_no_receipts(h)).unwrap().clone();
+
+        let client = Arc::new(TestReceiptsClient::default().with_receipts(receipts.clone()));
+        let mut fut = ReceiptsRequestFuture::new(client, ReceiptDownloaderMetrics::default())
+            .with_headers(vec![header.clone()]);
+
+        assert_matches!(
+            fut.try_buffer_receipts(vec![Vec::new()]),
+            Err(DownloadError::ReceiptsRootMismatch { hash, .. }) => assert_eq!(hash, header.hash())
+        );
+        assert_eq!(fut.pending_headers.len(), 1);
+        assert!(fut.buffer.is_empty());
+
+        fut.try_buffer_receipts(vec![receipts[&header.hash()].clone()]).unwrap();
+        assert!(fut.pending_headers.is_empty());
+        assert_eq!(fut.buffer.len(), 1);
+    }
+}
@@ -4,8 +4,13 @@
 
 use crate::{bodies::test_utils::create_raw_bodies, file_codec::BlockFileCodec};
 use futures::SinkExt;
-use reth_interfaces::test_utils::{generators, generators::random_block_range};
-use reth_primitives::{BlockBody, SealedHeader, B256};
+use reth_interfaces::test_utils::{
+    generators,
+    generators::{random_block_range, random_header, random_receipt, random_signed_tx},
+};
+use reth_primitives::{
+    proofs::calculate_receipt_root, BlockBody, ReceiptWithBloom, SealedHeader, B256,
+};
 use std::{collections::HashMap, io::SeekFrom, ops::RangeInclusive};
 use tokio::{fs::File, io::AsyncSeekExt};
 use tokio_util::codec::FramedWrite;
@@ -13,6 +18,9 @@ use tokio_util::codec::FramedWrite;
 mod bodies_client;
 pub use bodies_client::TestBodiesClient;
 
+mod receipts_client;
+pub use receipts_client::TestReceiptsClient;
+
 /// Metrics scope used for testing.
 pub(crate) const TEST_SCOPE: &str = "downloaders.test";
 
@@ -41,6 +49,29 @@ pub(crate) fn generate_bodies(
     (headers, bodies)
 }
 
+/// Generate a set of headers and the receipts of the blocks with transactions, keyed by block hash
+pub(crate) fn generate_receipts(
+    range: RangeInclusive<u64>,
+) -> (Vec<SealedHeader>, HashMap<B256, Vec<ReceiptWithBloom>>) {
+    let mut rng = generators::rng();
+    let mut headers: Vec<SealedHeader> = Vec::new();
+    let mut receipts = HashMap::new();
+    for number in range {
+        let parent = headers.last().map(|header| header.hash());
+        let mut header = random_header(&mut rng, number, parent).unseal();
+        let block_receipts = (0..number % 3)
+            .map(|_| random_receipt(&mut rng, &random_signed_tx(&mut rng), Some(1)).with_bloom())
+            .collect::<Vec<_>>();
+        header.receipts_root = calculate_receipt_root(&block_receipts);
+        let header = header.seal_slow();
+        if !block_receipts.is_empty() {
+            receipts.insert(header.hash(), block_receipts);
+        }
+        headers.push(header);
+    }
+    (headers, receipts)
+}
+
 /// Generate a set of bodies, write them to a temporary file, and return the file along with the
 /// bodies and corresponding block hashes
 pub(crate) async fn generate_bodies_file(
@@ -0,0 +1,77 @@
+use reth_interfaces::p2p::{
+    download::DownloadClient,
+    priority::Priority,
+    receipts::client::{ReceiptsClient, ReceiptsFut},
+};
+use reth_primitives::{PeerId, ReceiptWithBloom, B256};
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+use tokio::sync::Mutex;
+
+/// A [ReceiptsClient] for testing.
+#[derive(Debug, Default)]
+pub struct TestReceiptsClient {
+    receipts: Arc<Mutex<HashMap<B256, Vec<ReceiptWithBloom>>>>,
+    max_batch_size: Option<usize>,
+    times_requested: AtomicU64,
+}
+
+impl TestReceiptsClient {
+    pub(crate) fn with_receipts(mut self, receipts: HashMap<B256, Vec<ReceiptWithBloom>>) -> Self {
+        self.receipts = Arc::new(Mutex::new(receipts));
+        self
+    }
+
+    pub(crate) fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = Some(max_batch_size);
+        self
+    }
+
+    pub(crate) fn times_requested(&self) -> u64 {
+        self.times_requested.load(Ordering::Relaxed)
+    }
+}
+
+impl DownloadClient for TestReceiptsClient {
+    fn report_bad_message(&self, _peer_id: PeerId) {
+        // noop
+    }
+
+    fn num_connected_peers(&self) -> usize {
+        0
+    }
+}
+
+impl ReceiptsClient for TestReceiptsClient {
+    type Output = ReceiptsFut;
+
+    fn get_receipts_with_priority(&self, hashes: Vec<B256>, _priority: Priority) -> Self::Output {
+        let receipts = self.receipts.clone();
+        let max_batch_size = self.max_batch_size;
+
+        self.times_requested.fetch_add(1, Ordering::Relaxed);
+
+        Box::pin(async move {
+            let receipts = &mut *receipts.lock().await;
+            Ok((
+                PeerId::default(),
+                hashes
+                    .into_iter()
+                    .take(max_batch_size.unwrap_or(usize::MAX))
+                    .map(|hash| {
+                        receipts
+                            .remove(&hash)
+                            .expect("Downloader asked for receipts it should not ask for")
+                    })
+                    .collect(),
+            )
+                .into())
+        })
+    }
+}
@@ -7,17 +7,20 @@ use reth_eth_wire::{
     Receipts,
 };
 use reth_interfaces::p2p::error::RequestResult;
-use reth_primitives::{BlockBody, BlockHashOrNumber, Header, HeadersDirection, PeerId};
-use reth_provider::{BlockReader, HeaderProvider, ReceiptProvider};
+use reth_primitives::{BlockBody, BlockHashOrNumber, Bytes, Header, HeadersDirection, PeerId};
+use reth_provider::{BlockReader, HeaderProvider, NodeDataProvider, ReceiptProvider};
 use std::{
     borrow::Borrow,
+    collections::HashMap,
     future::Future,
     hash::Hash,
     pin::Pin,
     task::{Context, Poll},
+    time::{Duration, Instant},
 };
 use tokio::sync::{mpsc::Receiver, oneshot};
 use tokio_stream::wrappers::ReceiverStream;
+use tracing::trace;
 
 // Limits: <https://github.com/ethereum/go-ethereum/blob/b0d44338bbcefee044f1f635a84487cbbd8f0538/eth/protocols/eth/handler.go#L34-L56>
 
@@ -50,6 +53,19 @@ const SOFT_RESPONSE_LIMIT: usize = 2 * 1024 * 1024;
 /// Estimated size in bytes of an RLP encoded header.
 const APPROX_HEADER_SIZE: usize = 500;
 
+/// Maximum number of node data entries to look up.
+const MAX_NODE_DATA_LOOKUPS: usize = 1024;
+
+/// Maximum number of node data requests of a peer served per [NODE_DATA_RATE_LIMIT_INTERVAL].
+///
+/// Node data lookups hit the database for every requested hash, the requests of a peer beyond
+/// this limit are answered with an empty response.
+const MAX_NODE_DATA_REQUESTS_PER_INTERVAL: usize = 8;
+
+/// The interval in which at most [MAX_NODE_DATA_REQUESTS_PER_INTERVAL] node data requests of a
+/// peer are served.
+const NODE_DATA_RATE_LIMIT_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Manages eth related requests on top of the p2p network.
 ///
 /// This can be spawned to another task and is supposed to be run as background service.
@@ -66,6 +82,10 @@ pub struct EthRequestHandler<C> {
     incoming_requests: ReceiverStream<IncomingEthRequest>,
     /// Metrics for the eth request handler.
     metrics: EthRequestHandlerMetrics,
+    /// The start of the current node data rate limit interval.
+    node_data_interval_start: Instant,
+    /// The number of node data requests served per peer in the current interval.
+    node_data_requests: HashMap<PeerId, usize>,
 }
 
 // === impl EthRequestHandler ===
@@ -73,7 +93,29 @@ impl<C> EthRequestHandler<C> {
     /// Create a new instance
     pub fn new(client: C, peers: PeersHandle, incoming: Receiver<IncomingEthRequest>) -> Self {
         let metrics = Default::default();
-        Self { client, peers, incoming_requests: ReceiverStream::new(incoming), metrics }
+        Self {
+            client,
+            peers,
+            incoming_requests: ReceiverStream::new(incoming),
+            metrics,
+            node_data_interval_start: Instant::now(),
+            node_data_requests: HashMap::new(),
+        }
+    }
+
+    /// Returns true if the peer exceeded the number of node data requests allowed per interval.
+    ///
+    /// This counts the request towards the limit.
+    fn is_node_data_rate_limited(&mut self, peer_id: PeerId) -> bool {
+        let now = Instant::now();
+        if now.duration_since(self.node_data_interval_start) >= NODE_DATA_RATE_LIMIT_INTERVAL {
+            self.node_data_interval_start = now;
+            self.node_data_requests.clear();
+        }
+
+        let requests = self.node_data_requests.entry(peer_id).or_default();
+        *requests += 1;
+        *requests > MAX_NODE_DATA_REQUESTS_PER_INTERVAL
     }
 }
 
@@ -228,12 +270,59 @@ where
     }
 }
 
+impl<C> EthRequestHandler<C>
+where
+    C: NodeDataProvider,
+{
+    /// Returns the requested node data that is available.
+    ///
+    /// Unknown hashes are skipped, the peer matches the served entries by their hash.
+    fn get_node_data_response(&self, request: GetNodeData) -> Vec<Bytes> {
+        let mut data = Vec::new();
+
+        let mut total_bytes = 0;
+
+        for hash in request.0.into_iter().take(MAX_NODE_DATA_LOOKUPS) {
+            if let Some(entry) = self.client.node_data_by_hash(hash).unwrap_or_default() {
+                total_bytes += entry.len();
+                data.push(entry);
+
+                if total_bytes > SOFT_RESPONSE_LIMIT {
+                    break
+                }
+            }
+        }
+
+        data
+    }
+
+    fn on_node_data_request(
+        &mut self,
+        peer_id: PeerId,
+        request: GetNodeData,
+        response: oneshot::Sender<RequestResult<NodeData>>,
+    ) {
+        self.metrics.received_node_data_requests.increment(1);
+
+        if self.is_node_data_rate_limited(peer_id) {
+            trace!(target: "net::eth", ?peer_id, "Node data request rate limited");
+            self.metrics.rate_limited_node_data_requests.increment(1);
+            let _ = response.send(Ok(NodeData(Vec::new())));
+            return
+        }
+
+        let data = self.get_node_data_response(request);
+        self.metrics.served_node_data.increment(data.len() as u64);
+        let _ = response.send(Ok(NodeData(data)));
+    }
+}
+
 /// An endless future.
 ///
 /// This should be spawned or used as part of `tokio::select!`.
 impl<C> Future for EthRequestHandler<C>
 where
-    C: BlockReader + HeaderProvider + Unpin,
+    C: BlockReader + HeaderProvider + NodeDataProvider + Unpin,
 {
     type Output = ();
 
@@ -251,7 +340,9 @@ where
                     IncomingEthRequest::GetBlockBodies { peer_id, request, response } => {
                         this.on_bodies_request(peer_id, request, response)
                     }
-                    IncomingEthRequest::GetNodeData { .. } => {}
+                    IncomingEthRequest::GetNodeData { peer_id, request, response } => {
+                        this.on_node_data_request(peer_id, request, response)
+                    }
                     IncomingEthRequest::GetReceipts { peer_id, request, response } => {
                         this.on_receipts_request(peer_id, request, response)
                     }
@@ -324,3 +415,72 @@ pub enum IncomingEthRequest {
         response: oneshot::Sender<RequestResult<Receipts>>,
     },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::peers::{PeersConfig, PeersManager};
+    use reth_primitives::{keccak256, Address, B256, U256};
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use tokio::sync::mpsc;
+
+    fn request_handler(client: MockEthProvider) -> EthRequestHandler<MockEthProvider> {
+        let (_tx, rx) = mpsc::channel(1);
+        let peers = PeersManager::new(PeersConfig::default()).handle();
+        EthRequestHandler::new(client, peers, rx)
+    }
+
+    fn request_node_data(
+        handler: &mut EthRequestHandler<MockEthProvider>,
+        peer_id: PeerId,
+        hashes: Vec<B256>,
+    ) -> NodeData {
+        let (tx, mut rx) = oneshot::channel();
+        handler.on_node_data_request(peer_id, GetNodeData(hashes), tx);
+        rx.try_recv().unwrap().unwrap()
+    }
+
+    #[test]
+    fn serves_bytecode_node_data() {
+        let client = MockEthProvider::default();
+        let code = Bytes::from_static(&[0x60, 0x00, 0x60, 0x00, 0xf3]);
+        client.add_account(
+            Address::random(),
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(code.clone()),
+        );
+        let mut handler = request_handler(client);
+
+        // unknown hashes are skipped
+        let data = request_node_data(
+            &mut handler,
+            PeerId::random(),
+            vec![B256::random(), keccak256(&code)],
+        );
+        assert_eq!(data, NodeData(vec![code]));
+    }
+
+    #[test]
+    fn rate_limits_node_data_requests() {
+        let client = MockEthProvider::default();
+        let code = Bytes::from_static(&[0x60, 0x00, 0x60, 0x00, 0xf3]);
+        client.add_account(
+            Address::random(),
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(code.clone()),
+        );
+        let mut handler = request_handler(client);
+        let peer_id = PeerId::random();
+
+        for _ in 0..MAX_NODE_DATA_REQUESTS_PER_INTERVAL {
+            let data = request_node_data(&mut handler, peer_id, vec![keccak256(&code)]);
+            assert_eq!(data, NodeData(vec![code.clone()]));
+        }
+
+        // requests beyond the limit are answered with an empty response
+        let data = request_node_data(&mut handler, peer_id, vec![keccak256(&code)]);
+        assert_eq!(data, NodeData::default());
+
+        // other peers are not affected
+        let data = request_node_data(&mut handler, PeerId::random(), vec![keccak256(&code)]);
+        assert_eq!(data, NodeData(vec![code]));
+    }
+}
@@ -9,6 +9,7 @@ use reth_interfaces::p2p::{
     error::{PeerRequestResult, RequestError},
     headers::client::{HeadersClient, HeadersRequest},
     priority::Priority,
+    receipts::client::{ReceiptsClient, ReceiptsFut},
 };
 use reth_network_api::ReputationChangeKind;
 use reth_primitives::{Header, PeerId, B256};
@@ -22,7 +23,8 @@ use tokio::sync::{mpsc::UnboundedSender, oneshot};
 /// Front-end API for fetching data from the network.
 ///
 /// Following diagram illustrates how a request, See [`HeadersClient::get_headers`] and
-/// [`BodiesClient::get_block_bodies`] is handled internally.
+/// [`BodiesClient::get_block_bodies`] is handled internally. [`ReceiptsClient::get_receipts`]
+/// requests are handled the same way.
 ///
 /// include_mmd!("docs/mermaid/fetch-client.mmd")
 #[derive(Debug, Clone)]
@@ -92,3 +94,21 @@ impl BodiesClient for FetchClient {
         }
     }
 }
+
+impl ReceiptsClient for FetchClient {
+    type Output = ReceiptsFut;
+
+    /// Sends a `GetReceipts` request to an available peer.
+    fn get_receipts_with_priority(&self, request: Vec<B256>, priority: Priority) -> Self::Output {
+        let (response, rx) = oneshot::channel();
+        if self
+            .request_tx
+            .send(DownloadRequest::GetReceipts { request, response, priority })
+            .is_ok()
+        {
+            Box::pin(FlattenedResponse::from(rx))
+        } else {
+            Box::pin(future::err(RequestError::ChannelClosed))
+        }
+    }
+}
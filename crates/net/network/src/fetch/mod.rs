@@ -2,14 +2,14 @@
 
 use crate::{message::BlockRequest, peers::PeersHandle};
 use futures::StreamExt;
-use reth_eth_wire::{GetBlockBodies, GetBlockHeaders};
+use reth_eth_wire::{GetBlockBodies, GetBlockHeaders, GetReceipts};
 use reth_interfaces::p2p::{
     error::{EthResponseValidator, PeerRequestResult, RequestError, RequestResult},
     headers::client::HeadersRequest,
     priority::Priority,
 };
 use reth_network_api::ReputationChangeKind;
-use reth_primitives::{BlockBody, Header, PeerId, B256};
+use reth_primitives::{BlockBody, Header, PeerId, ReceiptWithBloom, B256};
 use std::{
     collections::{HashMap, VecDeque},
     sync::{
@@ -38,6 +38,9 @@ pub struct StateFetcher {
     /// Currently active [`GetBlockBodies`] requests
     inflight_bodies_requests:
         HashMap<PeerId, Request<Vec<B256>, PeerRequestResult<Vec<BlockBody>>>>,
+    /// Currently active [`GetReceipts`] requests
+    inflight_receipts_requests:
+        HashMap<PeerId, Request<Vec<B256>, PeerRequestResult<Vec<Vec<ReceiptWithBloom>>>>>,
     /// The list of _available_ peers for requests.
     peers: HashMap<PeerId, Peer>,
     /// The handle to the peers manager
@@ -60,6 +63,7 @@ impl StateFetcher {
         Self {
             inflight_headers_requests: Default::default(),
             inflight_bodies_requests: Default::default(),
+            inflight_receipts_requests: Default::default(),
             peers: Default::default(),
             peers_handle,
             num_active_peers,
@@ -95,6 +99,9 @@ impl StateFetcher {
         if let Some(req) = self.inflight_bodies_requests.remove(peer) {
             let _ = req.response.send(Err(RequestError::ConnectionDropped));
         }
+        if let Some(req) = self.inflight_receipts_requests.remove(peer) {
+            let _ = req.response.send(Err(RequestError::ConnectionDropped));
+        }
     }
 
     /// Updates the block information for the peer.
@@ -211,6 +218,11 @@ impl StateFetcher {
                 self.inflight_bodies_requests.insert(peer_id, inflight);
                 BlockRequest::GetBlockBodies(GetBlockBodies(request))
             }
+            DownloadRequest::GetReceipts { request, response, .. } => {
+                let inflight = Request { request: request.clone(), response };
+                self.inflight_receipts_requests.insert(peer_id, inflight);
+                BlockRequest::GetReceipts(GetReceipts(request))
+            }
         }
     }
 
@@ -279,6 +291,23 @@ impl StateFetcher {
         None
     }
 
+    /// Called on a `Receipts` response from a peer
+    pub(crate) fn on_receipts_response(
+        &mut self,
+        peer_id: PeerId,
+        res: RequestResult<Vec<Vec<ReceiptWithBloom>>>,
+    ) -> Option<BlockResponseOutcome> {
+        if let Some(resp) = self.inflight_receipts_requests.remove(&peer_id) {
+            let _ = resp.response.send(res.map(|r| (peer_id, r).into()));
+        }
+        if let Some(peer) = self.peers.get_mut(&peer_id) {
+            if peer.state.on_request_finished() {
+                return self.followup_request(peer_id)
+            }
+        }
+        None
+    }
+
     /// Returns a new [`FetchClient`] that can send requests to this type.
     pub(crate) fn client(&self) -> FetchClient {
         FetchClient {
@@ -324,6 +353,8 @@ enum PeerState {
     GetBlockHeaders,
     /// Peer is handling a `GetBlockBodies` request.
     GetBlockBodies,
+    /// Peer is handling a `GetReceipts` request.
+    GetReceipts,
     /// Peer session is about to close
     Closing,
 }
@@ -376,6 +407,12 @@ pub(crate) enum DownloadRequest {
         response: oneshot::Sender<PeerRequestResult<Vec<BlockBody>>>,
         priority: Priority,
     },
+    /// Download the requested receipts and send response through channel
+    GetReceipts {
+        request: Vec<B256>,
+        response: oneshot::Sender<PeerRequestResult<Vec<Vec<ReceiptWithBloom>>>>,
+        priority: Priority,
+    },
 }
 
 // === impl DownloadRequest ===
@@ -386,6 +423,7 @@ impl DownloadRequest {
         match self {
             DownloadRequest::GetBlockHeaders { .. } => PeerState::GetBlockHeaders,
             DownloadRequest::GetBlockBodies { .. } => PeerState::GetBlockBodies,
+            DownloadRequest::GetReceipts { .. } => PeerState::GetReceipts,
         }
     }
 
@@ -394,6 +432,7 @@ impl DownloadRequest {
         match self {
             DownloadRequest::GetBlockHeaders { priority, .. } => priority,
             DownloadRequest::GetBlockBodies { priority, .. } => priority,
+            DownloadRequest::GetReceipts { priority, .. } => priority,
         }
     }
 
@@ -25,7 +25,7 @@ use crate::{
     message::{NewBlockMessage, PeerMessage, PeerRequest, PeerRequestSender},
     metrics::{DisconnectMetrics, NetworkMetrics, NETWORK_POOL_TRANSACTIONS_SCOPE},
     network::{NetworkHandle, NetworkHandleMessage},
-    peers::{PeersHandle, PeersManager},
+    peers::{PeersHandle, PeersManager, PersistedPeer},
     protocol::IntoRlpxSubProtocol,
     session::SessionManager,
     state::NetworkState,
@@ -162,6 +162,7 @@ where
             client,
             secret_key,
             mut discovery_v4_config,
+            mut discovery_v5_config,
             discovery_addr,
             listener_addr,
             peers_config,
@@ -197,9 +198,20 @@ where
             disc_config
         });
 
-        let discovery =
-            Discovery::new(discovery_addr, secret_key, discovery_v4_config, dns_discovery_config)
-                .await?;
+        discovery_v5_config = discovery_v5_config.map(|mut disc_config| {
+            disc_config.set_fork(chain_spec.chain, status.forkid);
+            disc_config.tcp_port = incoming.local_address().port();
+            disc_config
+        });
+
+        let discovery = Discovery::new(
+            discovery_addr,
+            secret_key,
+            discovery_v4_config,
+            discovery_v5_config,
+            dns_discovery_config,
+        )
+        .await?;
         // need to retrieve the addr here since provided port could be `0`
         let local_peer_id = discovery.local_id();
 
@@ -323,6 +335,11 @@ where
         self.swarm.state().peers().iter_peers()
     }
 
+    /// Returns the state of all known and banned peers that should be persisted across restarts.
+    pub fn persisted_peers(&self) -> impl Iterator<Item = PersistedPeer> + '_ {
+        self.swarm.state().peers().persisted_peers()
+    }
+
     /// Returns a new [`PeersHandle`] that can be cloned and shared.
     ///
     /// The [`PeersHandle`] can be used to interact with the network's peer set.
@@ -746,6 +763,10 @@ where
                                     .peers_mut()
                                     .on_incoming_session_established(peer_id, remote_addr);
                             }
+                            this.swarm
+                                .state_mut()
+                                .peers_mut()
+                                .on_session_established(peer_id, Arc::clone(&client_version));
                             this.event_listeners.notify(NetworkEvent::SessionEstablished {
                                 peer_id,
                                 remote_addr,
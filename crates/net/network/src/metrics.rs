@@ -169,4 +169,14 @@ pub struct EthRequestHandlerMetrics {
 
     /// Number of received bodies requests
     pub(crate) received_bodies_requests: Counter,
+
+    /// Number of received node data requests
+    pub(crate) received_node_data_requests: Counter,
+
+    /// Number of node data requests that were answered with an empty response because the peer
+    /// exceeded the rate limit
+    pub(crate) rate_limited_node_data_requests: Counter,
+
+    /// Number of served node data entries
+    pub(crate) served_node_data: Counter,
 }
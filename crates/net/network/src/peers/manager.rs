@@ -19,8 +19,9 @@ use std::{
     io::{self, ErrorKind},
     net::{IpAddr, SocketAddr},
     path::Path,
+    sync::Arc,
     task::{Context, Poll},
-    time::Duration,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use thiserror::Error;
 use tokio::{
@@ -100,6 +101,8 @@ pub struct PeersManager {
     connection_info: ConnectionInfo,
     /// Tracks unwanted ips/peer ids.
     ban_list: BanList,
+    /// Tracks the peers banned by this node, with the reason and expiry of the ban.
+    bans: HashMap<PeerId, PeerBan>,
     /// Tracks currently backed off peers.
     backed_off_peers: HashMap<PeerId, std::time::Instant>,
     /// Interval at which to check for peers to unban and release from the backoff map.
@@ -126,12 +129,13 @@ impl PeersManager {
             refill_slots_interval,
             connection_info,
             reputation_weights,
-            ban_list,
+            mut ban_list,
             ban_duration,
             backoff_durations,
             trusted_nodes,
             connect_trusted_nodes_only,
             basic_nodes,
+            persisted_peers,
             max_backoff_count,
         } = config;
         let (manager_tx, handle_rx) = mpsc::unbounded_channel();
@@ -150,6 +154,34 @@ impl PeersManager {
             peers.entry(id).or_insert_with(|| Peer::new(SocketAddr::from((address, tcp_port))));
         }
 
+        // restore the peers of the previous run, peers that are still banned are not added to the
+        // set until their ban expires
+        let mut bans = HashMap::new();
+        let system_now = SystemTime::now();
+        for persisted in persisted_peers {
+            let NodeRecord { address, tcp_port, udp_port: _, id } = persisted.record;
+            let addr = SocketAddr::from((address, tcp_port));
+
+            if let Some(ban) = persisted.ban {
+                let expires_at = UNIX_EPOCH + Duration::from_secs(ban.expires_at);
+                if let Ok(remaining) = expires_at.duration_since(system_now) {
+                    let until = std::time::Instant::now() + remaining;
+                    ban_list.ban_peer_until(id, until);
+                    bans.insert(id, PeerBan { addr, until, reason: ban.reason });
+                    continue
+                }
+            }
+
+            let peer = peers.entry(id).or_insert_with(|| Peer::new(addr));
+            if !is_banned_reputation(persisted.reputation) {
+                peer.reputation = persisted.reputation;
+            }
+            peer.fork_id = persisted.fork_id;
+            peer.last_connected =
+                persisted.last_connected.map(|secs| UNIX_EPOCH + Duration::from_secs(secs));
+            peer.client_version = persisted.client_version.map(Arc::from);
+        }
+
         Self {
             peers,
             manager_tx,
@@ -163,6 +195,7 @@ impl PeersManager {
             release_interval: tokio::time::interval_at(now + unban_interval, unban_interval),
             connection_info,
             ban_list,
+            bans,
             backed_off_peers: Default::default(),
             ban_duration,
             backoff_durations,
@@ -189,6 +222,32 @@ impl PeersManager {
         self.peers.iter().map(|(peer_id, v)| NodeRecord::new(v.addr, *peer_id))
     }
 
+    /// Returns an iterator over the state of all peers that should be persisted across restarts.
+    ///
+    /// This includes the banned peers that were already removed from the set.
+    pub(crate) fn persisted_peers(&self) -> impl Iterator<Item = PersistedPeer> + '_ {
+        let now = std::time::Instant::now();
+        let system_now = SystemTime::now();
+        let known = self
+            .peers
+            .iter()
+            .filter(|(peer_id, _)| !self.bans.contains_key(peer_id))
+            .map(|(peer_id, peer)| peer.persisted(*peer_id));
+        let banned = self.bans.iter().map(move |(peer_id, ban)| {
+            let mut persisted = match self.peers.get(peer_id) {
+                Some(peer) => peer.persisted(*peer_id),
+                None => Peer::new(ban.addr).persisted(*peer_id),
+            };
+            let expires_at = system_now + ban.until.saturating_duration_since(now);
+            persisted.ban = Some(PersistedBan {
+                expires_at: unix_secs(expires_at),
+                reason: ban.reason.clone(),
+            });
+            persisted
+        });
+        known.chain(banned)
+    }
+
     /// Returns an iterator over all peer ids for peers with the given kind
     pub(crate) fn peers_by_kind(&self, kind: PeerKind) -> impl Iterator<Item = PeerId> + '_ {
         self.peers.iter().filter_map(move |(peer_id, peer)| (peer.kind == kind).then_some(*peer_id))
@@ -297,9 +356,21 @@ impl PeersManager {
         }
     }
 
+    /// Called when an active session was established to the given peer, in either direction.
+    ///
+    /// Records the time of the connection and the client version the peer announced.
+    pub(crate) fn on_session_established(&mut self, peer_id: PeerId, client_version: Arc<str>) {
+        if let Some(peer) = self.peers.get_mut(&peer_id) {
+            peer.last_connected = Some(SystemTime::now());
+            peer.client_version = Some(client_version);
+        }
+    }
+
     /// Bans the peer temporarily with the configured ban timeout
-    fn ban_peer(&mut self, peer_id: PeerId) {
-        self.ban_list.ban_peer_until(peer_id, std::time::Instant::now() + self.ban_duration);
+    fn ban_peer(&mut self, peer_id: PeerId, addr: SocketAddr, reason: BanReason) {
+        let until = std::time::Instant::now() + self.ban_duration;
+        self.ban_list.ban_peer_until(peer_id, until);
+        self.bans.insert(peer_id, PeerBan { addr, until, reason });
         self.queued_actions.push_back(PeerAction::BanPeer { peer_id });
     }
 
@@ -321,6 +392,7 @@ impl PeersManager {
     /// Unbans the peer
     fn unban_peer(&mut self, peer_id: PeerId) {
         self.ban_list.unban_peer(&peer_id);
+        self.bans.remove(&peer_id);
         self.queued_actions.push_back(PeerAction::UnBanPeer { peer_id });
     }
 
@@ -354,14 +426,15 @@ impl PeersManager {
 
     /// Apply the corresponding reputation change to the given peer
     pub(crate) fn apply_reputation_change(&mut self, peer_id: &PeerId, rep: ReputationChangeKind) {
-        let outcome = if let Some(peer) = self.peers.get_mut(peer_id) {
+        let (outcome, addr) = if let Some(peer) = self.peers.get_mut(peer_id) {
             // First check if we should reset the reputation
-            if rep.is_reset() {
+            let outcome = if rep.is_reset() {
                 peer.reset_reputation()
             } else {
                 let reputation_change = self.reputation_weights.change(rep);
                 peer.apply_reputation(reputation_change.as_i32())
-            }
+            };
+            (outcome, peer.addr)
         } else {
             return
         };
@@ -369,7 +442,7 @@ impl PeersManager {
         match outcome {
             ReputationChangeOutcome::None => {}
             ReputationChangeOutcome::Ban => {
-                self.ban_peer(*peer_id);
+                self.ban_peer(*peer_id, addr, BanReason::Reputation);
             }
             ReputationChangeOutcome::Unban => self.unban_peer(*peer_id),
             ReputationChangeOutcome::DisconnectAndBan => {
@@ -377,7 +450,7 @@ impl PeersManager {
                     peer_id: *peer_id,
                     reason: Some(DisconnectReason::DisconnectRequested),
                 });
-                self.ban_peer(*peer_id);
+                self.ban_peer(*peer_id, addr, BanReason::Reputation);
             }
         }
     }
@@ -466,13 +539,15 @@ impl PeersManager {
             trace!(target: "net::peers", ?remote_addr, ?peer_id, ?err, "fatal connection error");
             // remove the peer to which we can't establish a connection due to protocol related
             // issues.
+            let mut addr = *remote_addr;
             if let Some((peer_id, peer)) = self.peers.remove_entry(peer_id) {
                 self.connection_info.decr_state(peer.state);
                 self.queued_actions.push_back(PeerAction::PeerRemoved(peer_id));
+                addr = peer.addr;
             }
 
             // ban the peer
-            self.ban_peer(*peer_id);
+            self.ban_peer(*peer_id, addr, BanReason::ProtocolError(format!("{err:?}")));
 
             // If the error is caused by a peer that should be banned from discovery
             if err.merits_discovery_ban() {
@@ -649,7 +724,8 @@ impl PeersManager {
     /// Returns the idle peer with the highest reputation.
     ///
     /// Peers that are `trusted`, see [PeerKind], are prioritized as long as they're not currently
-    /// marked as banned or backed off.
+    /// marked as banned or backed off. Between peers with the same reputation, the peer that was
+    /// connected to most recently is preferred.
     ///
     /// If `connect_trusted_nodes_only` is enabled, see [PeersConfig], then this will only consider
     /// `trusted` peers.
@@ -676,8 +752,10 @@ impl PeersManager {
                 return Some((*maybe_better.0, maybe_better.1))
             }
 
-            // otherwise we keep track of the best peer using the reputation
-            if maybe_better.1.reputation > best_peer.1.reputation {
+            // otherwise we keep track of the best peer using the reputation and last connection
+            if (maybe_better.1.reputation, maybe_better.1.last_connected) >
+                (best_peer.1.reputation, best_peer.1.last_connected)
+            {
                 best_peer = maybe_better;
             }
         }
@@ -777,6 +855,7 @@ impl PeersManager {
                 let (_, unbanned_peers) = self.ban_list.evict(now);
 
                 for peer_id in unbanned_peers {
+                    self.bans.remove(&peer_id);
                     if let Some(peer) = self.peers.get_mut(&peer_id) {
                         peer.unban();
                     } else {
@@ -903,6 +982,10 @@ pub struct Peer {
     backed_off: bool,
     /// Counts number of times the peer was backed off due to a severe [BackoffKind].
     severe_backoff_counter: u32,
+    /// When a session to the peer was last established.
+    last_connected: Option<SystemTime>,
+    /// The client version the peer announced in its last session.
+    client_version: Option<Arc<str>>,
 }
 
 // === impl Peer ===
@@ -931,6 +1014,8 @@ impl Peer {
             kind: Default::default(),
             backed_off: false,
             severe_backoff_counter: 0,
+            last_connected: None,
+            client_version: None,
         }
     }
 
@@ -938,6 +1023,18 @@ impl Peer {
         Self { kind, ..Self::new(addr) }
     }
 
+    /// Returns the state of the peer that is persisted across restarts.
+    fn persisted(&self, peer_id: PeerId) -> PersistedPeer {
+        PersistedPeer {
+            record: NodeRecord::new(self.addr, peer_id),
+            reputation: self.reputation,
+            last_connected: self.last_connected.map(unix_secs),
+            client_version: self.client_version.as_deref().map(String::from),
+            fork_id: self.fork_id,
+            ban: None,
+        }
+    }
+
     /// Resets the reputation of the peer to the default value. This always returns
     /// [`ReputationChangeOutcome::None`].
     fn reset_reputation(&mut self) -> ReputationChangeOutcome {
@@ -994,6 +1091,65 @@ impl Peer {
     }
 }
 
+/// Returns the seconds since the unix epoch of the given time.
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+/// Why a peer was banned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BanReason {
+    /// The reputation of the peer dropped below the banned threshold.
+    Reputation,
+    /// A session with the peer failed with a fatal protocol error.
+    ProtocolError(String),
+}
+
+/// A ban applied to a peer by the [`PeersManager`].
+#[derive(Debug, Clone)]
+struct PeerBan {
+    /// Where to reach the peer, the peer may no longer be in the set.
+    addr: SocketAddr,
+    /// When the ban expires.
+    until: std::time::Instant,
+    /// Why the peer was banned.
+    reason: BanReason,
+}
+
+/// The state of a peer that is persisted across restarts, see
+/// [`PeersConfig::with_basic_nodes_from_file`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PersistedPeer {
+    /// Where to reach the peer.
+    pub record: NodeRecord,
+    /// Reputation of the peer.
+    pub reputation: i32,
+    /// When a session to the peer was last established, in seconds since the unix epoch.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub last_connected: Option<u64>,
+    /// The client version the peer announced in its last session.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub client_version: Option<String>,
+    /// The [`ForkId`] that the peer announced via discovery.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fork_id: Option<ForkId>,
+    /// The ban of the peer, if it was banned.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ban: Option<PersistedBan>,
+}
+
+/// A persisted ban of a [`PersistedPeer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PersistedBan {
+    /// When the ban expires, in seconds since the unix epoch.
+    pub expires_at: u64,
+    /// Why the peer was banned.
+    pub reason: BanReason,
+}
+
 /// Outcomes when a reputation change is applied to a peer
 enum ReputationChangeOutcome {
     /// Nothing to do.
@@ -1147,6 +1303,9 @@ pub struct PeersConfig {
     /// Basic nodes to connect to.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub basic_nodes: HashSet<NodeRecord>,
+    /// Peers of a previous run, restored with their reputation and bans.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub persisted_peers: Vec<PersistedPeer>,
     /// How long to ban bad peers.
     #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
     pub ban_duration: Duration,
@@ -1177,6 +1336,7 @@ impl Default for PeersConfig {
             trusted_nodes: Default::default(),
             connect_trusted_nodes_only: false,
             basic_nodes: Default::default(),
+            persisted_peers: Default::default(),
             max_backoff_count: 5,
         }
     }
@@ -1259,6 +1419,12 @@ impl PeersConfig {
         self
     }
 
+    /// Peers of a previous run to restore at launch.
+    pub fn with_persisted_peers(mut self, peers: Vec<PersistedPeer>) -> Self {
+        self.persisted_peers = peers;
+        self
+    }
+
     /// Configures the max allowed backoff count.
     pub fn with_max_backoff_count(mut self, max_backoff_count: u32) -> Self {
         self.max_backoff_count = max_backoff_count;
@@ -1278,18 +1444,32 @@ impl PeersConfig {
     }
 
     /// Read from file nodes available at launch. Ignored if None.
+    ///
+    /// The file is expected to contain the [`PersistedPeer`]s of a previous run, a file with a
+    /// plain list of [`NodeRecord`]s is loaded as basic nodes.
     pub fn with_basic_nodes_from_file(
         self,
         optional_file: Option<impl AsRef<Path>>,
     ) -> Result<Self, io::Error> {
         let Some(file_path) = optional_file else { return Ok(self) };
-        let reader = match std::fs::File::open(file_path.as_ref()) {
-            Ok(file) => io::BufReader::new(file),
+        let contents = match std::fs::read_to_string(file_path.as_ref()) {
+            Ok(contents) => contents,
             Err(e) if e.kind() == ErrorKind::NotFound => return Ok(self),
             Err(e) => Err(e)?,
         };
         info!(target: "net::peers", file = %file_path.as_ref().display(), "Loading saved peers");
-        let nodes: HashSet<NodeRecord> = serde_json::from_reader(reader)?;
+
+        // Persisted peers are objects, while basic nodes are plain node record strings
+        let value: serde_json::Value = serde_json::from_str(&contents)?;
+        let is_persisted = value
+            .as_array()
+            .and_then(|entries| entries.first())
+            .is_some_and(serde_json::Value::is_object);
+        if is_persisted {
+            let peers: Vec<PersistedPeer> = serde_json::from_value(value)?;
+            return Ok(self.with_persisted_peers(peers))
+        }
+        let nodes: HashSet<NodeRecord> = serde_json::from_value(value)?;
         Ok(self.with_basic_nodes(nodes))
     }
 }
@@ -1365,7 +1545,7 @@ impl Display for InboundConnectionError {
 
 #[cfg(test)]
 mod tests {
-    use super::PeersManager;
+    use super::{BanReason, PeersManager, PersistedPeer};
     use crate::{
         error::BackoffKind,
         peers::{
@@ -2283,4 +2463,102 @@ mod tests {
             .count();
         assert_eq!(dials, peer_manager.connection_info.max_concurrent_outbound_dials);
     }
+
+    #[tokio::test]
+    async fn test_persisted_peers_restored() {
+        let good_peer = PeerId::random();
+        let good_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let bad_peer = PeerId::random();
+        let bad_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 3)), 8008);
+        let mut peers = PeersManager::default();
+        peers.add_peer(good_peer, good_addr, None);
+        peers.add_peer(bad_peer, bad_addr, None);
+
+        peers.on_session_established(good_peer, "reth/v0.1.0".into());
+        peers.on_active_session_dropped(
+            &bad_addr,
+            &bad_peer,
+            &EthStreamError::P2PStreamError(P2PStreamError::Disconnected(
+                DisconnectReason::UselessPeer,
+            )),
+        );
+        assert!(peers.peers.get(&bad_peer).is_none());
+
+        let persisted = peers.persisted_peers().collect::<Vec<_>>();
+        assert_eq!(persisted.len(), 2);
+
+        let peers = PeersManager::new(PeersConfig::default().with_persisted_peers(persisted));
+
+        let good = peers.peers.get(&good_peer).unwrap();
+        assert_eq!(good.addr, good_addr);
+        assert!(good.last_connected.is_some());
+        assert_eq!(good.client_version.as_deref(), Some("reth/v0.1.0"));
+
+        // the banned peer is not dialed again until its ban expires
+        assert!(peers.peers.get(&bad_peer).is_none());
+        assert!(peers.ban_list.is_banned_peer(&bad_peer));
+        let bad = peers.persisted_peers().find(|p| p.record.id == bad_peer).unwrap();
+        assert!(matches!(bad.ban.unwrap().reason, BanReason::ProtocolError(_)));
+    }
+
+    #[test]
+    fn test_persisted_peers_file_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("known-peers.json");
+
+        let record = NodeRecord::new(
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008),
+            PeerId::random(),
+        );
+        let mut peer = serde_json::to_value(PersistedPeer {
+            record,
+            reputation: DEFAULT_REPUTATION,
+            last_connected: None,
+            client_version: None,
+            fork_id: None,
+            ban: None,
+        })
+        .unwrap();
+        peer["reputation"] = "high".into();
+        std::fs::write(&path, serde_json::to_string(&[peer]).unwrap()).unwrap();
+
+        // the error of the persisted peers is reported, not that it isn't a node record
+        let err = PeersConfig::default().with_basic_nodes_from_file(Some(&path)).unwrap_err();
+        assert!(err.to_string().contains("invalid type: string \"high\""), "{err}");
+
+        std::fs::write(&path, serde_json::to_string(&[record]).unwrap()).unwrap();
+        let config = PeersConfig::default().with_basic_nodes_from_file(Some(&path)).unwrap();
+        assert_eq!(config.basic_nodes, HashSet::from([record]));
+    }
+
+    #[tokio::test]
+    async fn test_persisted_peers_ranked() {
+        let ip = IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2));
+        let persisted = |port, reputation, last_connected| PersistedPeer {
+            record: NodeRecord::new(SocketAddr::new(ip, port), PeerId::random()),
+            reputation,
+            last_connected,
+            client_version: None,
+            fork_id: None,
+            ban: None,
+        };
+        let stale = persisted(8008, DEFAULT_REPUTATION, Some(1_000));
+        let recent = persisted(8009, DEFAULT_REPUTATION, Some(2_000));
+        let unreliable = persisted(8010, DEFAULT_REPUTATION - 1_000, Some(3_000));
+        let config = PeersConfig::default().with_persisted_peers(vec![
+            unreliable.clone(),
+            stale.clone(),
+            recent.clone(),
+        ]);
+        let mut peers = PeersManager::new(config);
+
+        for expected in [recent, stale, unreliable] {
+            match event!(peers) {
+                PeerAction::Connect { peer_id, .. } => {
+                    assert_eq!(peer_id, expected.record.id);
+                }
+                _ => unreachable!(),
+            }
+        }
+    }
 }
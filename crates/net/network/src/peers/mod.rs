@@ -4,7 +4,10 @@ mod manager;
 mod reputation;
 
 pub(crate) use manager::InboundConnectionError;
-pub use manager::{ConnectionInfo, Peer, PeerAction, PeersConfig, PeersHandle, PeersManager};
+pub use manager::{
+    BanReason, ConnectionInfo, Peer, PeerAction, PeersConfig, PeersHandle, PeersManager,
+    PersistedBan, PersistedPeer,
+};
 pub use reputation::ReputationChangeWeights;
 pub use reth_network_api::PeerKind;
 
@@ -357,6 +357,12 @@ where
                     let response = PeerResponse::BlockBodies { response: rx };
                     (request, response)
                 }
+                BlockRequest::GetReceipts(request) => {
+                    let (response, rx) = oneshot::channel();
+                    let request = PeerRequest::GetReceipts { request, response };
+                    let response = PeerResponse::Receipts { response: rx };
+                    (request, response)
+                }
             };
             let _ = peer.request_tx.to_session_tx.try_send(request);
             peer.pending_response = Some(response);
@@ -391,6 +397,10 @@ where
                 let outcome = self.state_fetcher.on_block_bodies_response(peer, res)?;
                 self.on_block_response_outcome(outcome)
             }
+            PeerResponseResult::Receipts(res) => {
+                let outcome = self.state_fetcher.on_receipts_response(peer, res)?;
+                self.on_block_response_outcome(outcome)
+            }
             _ => None,
         }
     }
@@ -16,7 +16,8 @@ use reth_eth_wire::{protocol::Protocol, DisconnectReason, HelloMessageWithProtoc
 use reth_network_api::{NetworkInfo, Peers};
 use reth_primitives::{PeerId, MAINNET};
 use reth_provider::{
-    test_utils::NoopProvider, BlockReader, BlockReaderIdExt, HeaderProvider, StateProviderFactory,
+    test_utils::NoopProvider, BlockReader, BlockReaderIdExt, HeaderProvider, NodeDataProvider,
+    StateProviderFactory,
 };
 use reth_tasks::TokioTaskExecutor;
 use reth_transaction_pool::{
@@ -193,7 +194,7 @@ where
 
 impl<C, Pool> Testnet<C, Pool>
 where
-    C: BlockReader + HeaderProvider + Clone + Unpin + 'static,
+    C: BlockReader + HeaderProvider + NodeDataProvider + Clone + Unpin + 'static,
     Pool: TransactionPool + Unpin + 'static,
 {
     /// Spawns the testnet to a separate task
@@ -252,7 +253,7 @@ impl<C, Pool> fmt::Debug for Testnet<C, Pool> {
 
 impl<C, Pool> Future for Testnet<C, Pool>
 where
-    C: BlockReader + HeaderProvider + Unpin,
+    C: BlockReader + HeaderProvider + NodeDataProvider + Unpin,
     Pool: TransactionPool + Unpin + 'static,
 {
     type Output = ();
@@ -441,7 +442,7 @@ where
 
 impl<C, Pool> Future for Peer<C, Pool>
 where
-    C: BlockReader + HeaderProvider + Unpin,
+    C: BlockReader + HeaderProvider + NodeDataProvider + Unpin,
     Pool: TransactionPool + Unpin + 'static,
 {
     type Output = ();
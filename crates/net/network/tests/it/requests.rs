@@ -5,6 +5,7 @@ use rand::Rng;
 use reth_interfaces::p2p::{
     bodies::client::BodiesClient,
     headers::client::{HeadersClient, HeadersRequest},
+    receipts::client::ReceiptsClient,
 };
 use reth_network::{
     test_utils::{NetworkEventStream, Testnet},
@@ -79,6 +80,39 @@ async fn test_get_body() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_receipts() {
+    reth_tracing::init_test_tracing();
+    let mut rng = rand::thread_rng();
+    let mock_provider = Arc::new(MockEthProvider::default());
+
+    let mut net = Testnet::create_with(2, mock_provider.clone()).await;
+
+    // install request handlers
+    net.for_each_mut(|peer| peer.install_request_handler());
+
+    let handle0 = net.peers()[0].handle();
+    let mut events0 = NetworkEventStream::new(handle0.event_listener());
+
+    let handle1 = net.peers()[1].handle();
+
+    let _handle = net.spawn();
+
+    let fetch0 = handle0.fetch_client().await.unwrap();
+
+    handle0.add_peer(*handle1.peer_id(), handle1.local_addr());
+    let connected = events0.next_session_established().await.unwrap();
+    assert_eq!(connected, *handle1.peer_id());
+
+    // the mock storage has no receipts, so the peer responds with an empty list
+    let res = fetch0.get_receipts(vec![rng.gen()]).await;
+    assert!(res.is_ok(), "{res:?}");
+
+    let (peer_id, receipts) = res.unwrap().split();
+    assert_eq!(peer_id, *handle1.peer_id());
+    assert!(receipts.is_empty());
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_get_header() {
     reth_tracing::init_test_tracing();
@@ -0,0 +1,353 @@
+//! The `snap/1` RLPx subprotocol.
+
+use crate::{
+    message::{
+        AccountRange, ByteCodes, GetAccountRange, GetByteCodes, GetStorageRanges, GetTrieNodes,
+        SnapMessage, StorageRanges, TrieNodes,
+    },
+    server::IncomingSnapRequest,
+};
+use futures::{future::BoxFuture, stream::FuturesUnordered, FutureExt, Stream, StreamExt};
+use reth_eth_wire::{
+    capability::SharedCapabilities, multiplex::ProtocolConnection, protocol::Protocol,
+};
+use reth_network::protocol::{ConnectionHandler, OnNotSupported, ProtocolHandler};
+use reth_network_api::Direction;
+use reth_primitives::{BytesMut, PeerId};
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::sync::{mpsc, oneshot};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::trace;
+
+/// Errors of a request sent to a `snap/1` peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum SnapRequestError {
+    /// The connection to the peer was closed before the response arrived.
+    #[error("connection to peer closed")]
+    ConnectionClosed,
+    /// The peer responded with a message that doesn't match the request.
+    #[error("received unexpected response")]
+    UnexpectedResponse,
+}
+
+/// Events emitted by the [SnapProtocolHandler].
+#[derive(Debug)]
+pub enum SnapEvent {
+    /// A `snap/1` connection was established with a peer.
+    Established {
+        /// The direction of the connection.
+        direction: Direction,
+        /// The id of the peer.
+        peer_id: PeerId,
+        /// The handle to send requests to the peer.
+        handle: SnapPeerHandle,
+    },
+}
+
+/// A command sent to a [SnapConnection].
+#[derive(Debug)]
+struct SnapCommand {
+    /// The request to send to the peer.
+    request: SnapMessage,
+    /// The response will be sent to this channel.
+    response: oneshot::Sender<SnapMessage>,
+}
+
+/// A handle to send `snap/1` requests to a connected peer.
+#[derive(Debug, Clone)]
+pub struct SnapPeerHandle {
+    /// The id of the peer.
+    peer_id: PeerId,
+    /// Sender half of the connection's command channel.
+    to_connection: mpsc::UnboundedSender<SnapCommand>,
+}
+
+impl SnapPeerHandle {
+    /// Returns the id of the peer.
+    pub const fn peer_id(&self) -> &PeerId {
+        &self.peer_id
+    }
+
+    /// Returns true if the connection to the peer is closed.
+    pub fn is_closed(&self) -> bool {
+        self.to_connection.is_closed()
+    }
+
+    /// Sends a request to the peer and waits for the response.
+    ///
+    /// The request id of the request is assigned by the connection.
+    async fn request(&self, request: SnapMessage) -> Result<SnapMessage, SnapRequestError> {
+        let (response, rx) = oneshot::channel();
+        self.to_connection
+            .send(SnapCommand { request, response })
+            .map_err(|_| SnapRequestError::ConnectionClosed)?;
+        rx.await.map_err(|_| SnapRequestError::ConnectionClosed)
+    }
+
+    /// Requests an account range from the peer.
+    pub async fn get_account_range(
+        &self,
+        request: GetAccountRange,
+    ) -> Result<AccountRange, SnapRequestError> {
+        match self.request(SnapMessage::GetAccountRange(request)).await? {
+            SnapMessage::AccountRange(response) => Ok(response),
+            _ => Err(SnapRequestError::UnexpectedResponse),
+        }
+    }
+
+    /// Requests storage ranges from the peer.
+    pub async fn get_storage_ranges(
+        &self,
+        request: GetStorageRanges,
+    ) -> Result<StorageRanges, SnapRequestError> {
+        match self.request(SnapMessage::GetStorageRanges(request)).await? {
+            SnapMessage::StorageRanges(response) => Ok(response),
+            _ => Err(SnapRequestError::UnexpectedResponse),
+        }
+    }
+
+    /// Requests bytecodes from the peer.
+    pub async fn get_byte_codes(
+        &self,
+        request: GetByteCodes,
+    ) -> Result<ByteCodes, SnapRequestError> {
+        match self.request(SnapMessage::GetByteCodes(request)).await? {
+            SnapMessage::ByteCodes(response) => Ok(response),
+            _ => Err(SnapRequestError::UnexpectedResponse),
+        }
+    }
+
+    /// Requests trie nodes from the peer.
+    pub async fn get_trie_nodes(
+        &self,
+        request: GetTrieNodes,
+    ) -> Result<TrieNodes, SnapRequestError> {
+        match self.request(SnapMessage::GetTrieNodes(request)).await? {
+            SnapMessage::TrieNodes(response) => Ok(response),
+            _ => Err(SnapRequestError::UnexpectedResponse),
+        }
+    }
+}
+
+/// The [ProtocolHandler] of the `snap/1` protocol.
+///
+/// Incoming requests are forwarded to the [SnapRequestHandler](crate::SnapRequestHandler) and
+/// every established connection is announced as a [SnapEvent].
+#[derive(Debug, Clone)]
+pub struct SnapProtocolHandler {
+    /// Sender half of the request handler's channel, if requests are served.
+    to_request_handler: Option<mpsc::Sender<IncomingSnapRequest>>,
+    /// Sender half of the event channel.
+    events: mpsc::UnboundedSender<SnapEvent>,
+}
+
+impl SnapProtocolHandler {
+    /// Creates a new handler that announces established connections to the given channel.
+    ///
+    /// Requests of peers are not served unless a request handler is set with
+    /// [SnapProtocolHandler::with_request_handler].
+    pub const fn new(events: mpsc::UnboundedSender<SnapEvent>) -> Self {
+        Self { to_request_handler: None, events }
+    }
+
+    /// Forwards requests of peers to the given request handler channel.
+    pub fn with_request_handler(
+        mut self,
+        to_request_handler: mpsc::Sender<IncomingSnapRequest>,
+    ) -> Self {
+        self.to_request_handler = Some(to_request_handler);
+        self
+    }
+
+    fn connection_handler(&self) -> SnapConnectionHandler {
+        SnapConnectionHandler {
+            to_request_handler: self.to_request_handler.clone(),
+            events: self.events.clone(),
+        }
+    }
+}
+
+impl ProtocolHandler for SnapProtocolHandler {
+    type ConnectionHandler = SnapConnectionHandler;
+
+    fn on_incoming(&self, _socket_addr: SocketAddr) -> Option<Self::ConnectionHandler> {
+        Some(self.connection_handler())
+    }
+
+    fn on_outgoing(
+        &self,
+        _socket_addr: SocketAddr,
+        _peer_id: PeerId,
+    ) -> Option<Self::ConnectionHandler> {
+        Some(self.connection_handler())
+    }
+}
+
+/// The [ConnectionHandler] of the `snap/1` protocol.
+#[derive(Debug)]
+pub struct SnapConnectionHandler {
+    to_request_handler: Option<mpsc::Sender<IncomingSnapRequest>>,
+    events: mpsc::UnboundedSender<SnapEvent>,
+}
+
+impl ConnectionHandler for SnapConnectionHandler {
+    type Connection = SnapConnection;
+
+    fn protocol(&self) -> Protocol {
+        SnapMessage::protocol()
+    }
+
+    fn on_unsupported_by_peer(
+        self,
+        _supported: &SharedCapabilities,
+        _direction: Direction,
+        _peer_id: PeerId,
+    ) -> OnNotSupported {
+        OnNotSupported::KeepAlive
+    }
+
+    fn into_connection(
+        self,
+        direction: Direction,
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+    ) -> Self::Connection {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let handle = SnapPeerHandle { peer_id, to_connection: tx };
+        self.events.send(SnapEvent::Established { direction, peer_id, handle }).ok();
+        SnapConnection {
+            peer_id,
+            conn,
+            to_request_handler: self.to_request_handler,
+            commands: UnboundedReceiverStream::new(rx),
+            next_request_id: 0,
+            inflight_requests: HashMap::new(),
+            pending_responses: FuturesUnordered::new(),
+        }
+    }
+}
+
+/// A `snap/1` connection to a peer.
+///
+/// Yields the encoded messages to send to the peer: requests of the local [SnapPeerHandle]s and
+/// responses of the request handler to the peer's requests.
+pub struct SnapConnection {
+    /// The id of the peer.
+    peer_id: PeerId,
+    /// The incoming messages of the peer.
+    conn: ProtocolConnection,
+    /// Sender half of the request handler's channel.
+    to_request_handler: Option<mpsc::Sender<IncomingSnapRequest>>,
+    /// Requests to send to the peer.
+    commands: UnboundedReceiverStream<SnapCommand>,
+    /// The id of the next request sent to the peer.
+    next_request_id: u64,
+    /// Requests sent to the peer that await a response, keyed by request id.
+    inflight_requests: HashMap<u64, oneshot::Sender<SnapMessage>>,
+    /// Responses of the request handler to the peer's requests.
+    pending_responses: FuturesUnordered<BoxFuture<'static, Option<SnapMessage>>>,
+}
+
+impl SnapConnection {
+    /// Forwards a request of the peer to the request handler.
+    fn on_request(&mut self, request: SnapMessage) {
+        let Some(to_request_handler) = &self.to_request_handler else { return };
+        let peer_id = self.peer_id;
+
+        macro_rules! forward {
+            ($variant:ident, $request:ident, $response:ident) => {{
+                let (tx, rx) = oneshot::channel();
+                let incoming =
+                    IncomingSnapRequest::$variant { peer_id, request: $request, response: tx };
+                if to_request_handler.try_send(incoming).is_err() {
+                    trace!(target: "net::snap", ?peer_id, "Request handler is busy, dropping request");
+                    return
+                }
+                self.pending_responses
+                    .push(rx.map(|res| res.ok().map(SnapMessage::$response)).boxed());
+            }};
+        }
+
+        match request {
+            SnapMessage::GetAccountRange(request) => {
+                forward!(GetAccountRange, request, AccountRange)
+            }
+            SnapMessage::GetStorageRanges(request) => {
+                forward!(GetStorageRanges, request, StorageRanges)
+            }
+            SnapMessage::GetByteCodes(request) => forward!(GetByteCodes, request, ByteCodes),
+            SnapMessage::GetTrieNodes(request) => forward!(GetTrieNodes, request, TrieNodes),
+            _ => {}
+        }
+    }
+
+    /// Resolves the inflight request the response belongs to.
+    fn on_response(&mut self, response: SnapMessage) {
+        if let Some(tx) = self.inflight_requests.remove(&response.request_id()) {
+            tx.send(response).ok();
+        } else {
+            trace!(target: "net::snap", peer_id=?self.peer_id, request_id=response.request_id(), "Received unsolicited response");
+        }
+    }
+}
+
+impl std::fmt::Debug for SnapConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnapConnection")
+            .field("peer_id", &self.peer_id)
+            .field("next_request_id", &self.next_request_id)
+            .field("inflight_requests", &self.inflight_requests.len())
+            .field("pending_responses", &self.pending_responses.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Stream for SnapConnection {
+    type Item = BytesMut;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Poll::Ready(Some(SnapCommand { mut request, response })) =
+                this.commands.poll_next_unpin(cx)
+            {
+                let request_id = this.next_request_id;
+                this.next_request_id += 1;
+                request.set_request_id(request_id);
+                this.inflight_requests.insert(request_id, response);
+                return Poll::Ready(Some(request.encoded()))
+            }
+
+            if let Poll::Ready(Some(response)) = this.pending_responses.poll_next_unpin(cx) {
+                match response {
+                    Some(response) => return Poll::Ready(Some(response.encoded())),
+                    None => continue,
+                }
+            }
+
+            let Some(msg) = futures::ready!(this.conn.poll_next_unpin(cx)) else {
+                return Poll::Ready(None)
+            };
+
+            let msg = match SnapMessage::decode_message(&mut &msg[..]) {
+                Ok(msg) => msg,
+                Err(err) => {
+                    trace!(target: "net::snap", peer_id=?this.peer_id, %err, "Failed to decode message");
+                    return Poll::Ready(None)
+                }
+            };
+
+            if msg.is_request() {
+                this.on_request(msg);
+            } else {
+                this.on_response(msg);
+            }
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! Implementation of the `snap/1` protocol as an RLPx subprotocol.
+//!
+//! The protocol is specified in [devp2p](https://github.com/ethereum/devp2p/blob/master/caps/snap.md).
+//!
+//! - [SnapProtocolHandler] plugs into the network through the
+//!   [RlpxSubProtocols](reth_network::protocol::RlpxSubProtocols) extension point and announces
+//!   every `snap/1` connection as a [SnapEvent] with a [SnapPeerHandle] to send requests.
+//! - [SnapRequestHandler] serves the requests of peers from the hashed state and trie tables of the
+//!   latest block.
+//! - [SnapSync] downloads the state of a pivot state root from peers.
+//! - [SnapSyncStage] runs [SnapSync] as the first stage of the pipeline on a fresh node, with a
+//!   pivot that [follow_tip] moves along with the sync target.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! let (to_server, from_peers) = tokio::sync::mpsc::channel(1024);
+//! let (events_tx, events) = tokio::sync::mpsc::unbounded_channel();
+//! network.add_rlpx_sub_protocol(
+//!     SnapProtocolHandler::new(events_tx).with_request_handler(to_server),
+//! );
+//! tokio::spawn(SnapRequestHandler::new(provider_factory, from_peers));
+//! ```
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod connection;
+pub mod message;
+mod server;
+mod stage;
+mod sync;
+
+pub use connection::{
+    SnapConnection, SnapConnectionHandler, SnapEvent, SnapPeerHandle, SnapProtocolHandler,
+    SnapRequestError,
+};
+pub use server::{IncomingSnapRequest, SnapRequestHandler, SNAP_REQUEST_CHANNEL_CAPACITY};
+pub use stage::{follow_tip, SnapSyncStage};
+pub use sync::{SnapSync, SnapSyncConfig, SnapSyncError};
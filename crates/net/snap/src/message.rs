@@ -0,0 +1,498 @@
+//! Implements the `snap/1` message types.
+//!
+//! See also <https://github.com/ethereum/devp2p/blob/master/caps/snap.md>
+
+use alloy_rlp::{Decodable, Encodable, Header, RlpDecodable, RlpEncodable, EMPTY_STRING_CODE};
+use reth_eth_wire::{capability::Capability, protocol::Protocol};
+use reth_primitives::{
+    constants::EMPTY_ROOT_HASH, Account, Buf, BufMut, Bytes, BytesMut, B256, KECCAK_EMPTY, U256,
+};
+
+/// The `snap/1` message ids.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SnapMessageId {
+    /// Requests an account range.
+    GetAccountRange = 0x00,
+    /// Response to [SnapMessageId::GetAccountRange].
+    AccountRange = 0x01,
+    /// Requests the storage ranges of a set of accounts.
+    GetStorageRanges = 0x02,
+    /// Response to [SnapMessageId::GetStorageRanges].
+    StorageRanges = 0x03,
+    /// Requests bytecodes by hash.
+    GetByteCodes = 0x04,
+    /// Response to [SnapMessageId::GetByteCodes].
+    ByteCodes = 0x05,
+    /// Requests trie nodes by path.
+    GetTrieNodes = 0x06,
+    /// Response to [SnapMessageId::GetTrieNodes].
+    TrieNodes = 0x07,
+}
+
+impl SnapMessageId {
+    /// The number of message ids reserved by `snap/1`.
+    pub const COUNT: u8 = 8;
+
+    /// Returns the message id for the given byte, if any.
+    pub const fn from_u8(id: u8) -> Option<Self> {
+        let id = match id {
+            0x00 => Self::GetAccountRange,
+            0x01 => Self::AccountRange,
+            0x02 => Self::GetStorageRanges,
+            0x03 => Self::StorageRanges,
+            0x04 => Self::GetByteCodes,
+            0x05 => Self::ByteCodes,
+            0x06 => Self::GetTrieNodes,
+            0x07 => Self::TrieNodes,
+            _ => return None,
+        };
+        Some(id)
+    }
+}
+
+/// Requests an unknown number of accounts from the given account trie, starting at the given
+/// hash and capped by the limit hash and the response size.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct GetAccountRange {
+    /// The request id.
+    pub request_id: u64,
+    /// The root of the account trie to serve.
+    pub root_hash: B256,
+    /// The account hash of the first account to retrieve.
+    pub starting_hash: B256,
+    /// The account hash after which to stop serving data.
+    pub limit_hash: B256,
+    /// The soft limit in bytes of the response.
+    pub response_bytes: u64,
+}
+
+/// An account in the "slim" encoding of `snap/1`, where the empty storage root and the empty code
+/// hash are encoded as empty strings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SlimAccount {
+    /// The account nonce.
+    pub nonce: u64,
+    /// The account balance.
+    pub balance: U256,
+    /// The root of the account's storage trie.
+    pub storage_root: B256,
+    /// The hash of the account's bytecode.
+    pub code_hash: B256,
+}
+
+impl SlimAccount {
+    /// Creates the slim account of the given account and its storage root.
+    pub fn new(account: Account, storage_root: B256) -> Self {
+        Self {
+            nonce: account.nonce,
+            balance: account.balance,
+            storage_root,
+            code_hash: account.bytecode_hash.unwrap_or(KECCAK_EMPTY),
+        }
+    }
+
+    /// Returns the account without its storage root.
+    pub fn account(&self) -> Account {
+        Account {
+            nonce: self.nonce,
+            balance: self.balance,
+            bytecode_hash: (self.code_hash != KECCAK_EMPTY).then_some(self.code_hash),
+        }
+    }
+
+    fn slim_storage_root(&self) -> Option<&B256> {
+        (self.storage_root != EMPTY_ROOT_HASH).then_some(&self.storage_root)
+    }
+
+    fn slim_code_hash(&self) -> Option<&B256> {
+        (self.code_hash != KECCAK_EMPTY).then_some(&self.code_hash)
+    }
+
+    fn payload_length(&self) -> usize {
+        self.nonce.length() +
+            self.balance.length() +
+            self.slim_storage_root().map_or(1, Encodable::length) +
+            self.slim_code_hash().map_or(1, Encodable::length)
+    }
+}
+
+impl Encodable for SlimAccount {
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.payload_length() }.encode(out);
+        self.nonce.encode(out);
+        self.balance.encode(out);
+        match self.slim_storage_root() {
+            Some(storage_root) => storage_root.encode(out),
+            None => out.put_u8(EMPTY_STRING_CODE),
+        }
+        match self.slim_code_hash() {
+            Some(code_hash) => code_hash.encode(out),
+            None => out.put_u8(EMPTY_STRING_CODE),
+        }
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.payload_length();
+        payload_length + alloy_rlp::length_of_length(payload_length)
+    }
+}
+
+impl Decodable for SlimAccount {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        let started_len = buf.len();
+
+        let nonce = u64::decode(buf)?;
+        let balance = U256::decode(buf)?;
+        let storage_root = decode_slim_hash(buf)?.unwrap_or(EMPTY_ROOT_HASH);
+        let code_hash = decode_slim_hash(buf)?.unwrap_or(KECCAK_EMPTY);
+
+        let consumed = started_len - buf.len();
+        if consumed != header.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: consumed,
+            })
+        }
+        Ok(Self { nonce, balance, storage_root, code_hash })
+    }
+}
+
+/// Decodes a hash that is encoded as an empty string if it's the default.
+fn decode_slim_hash(buf: &mut &[u8]) -> alloy_rlp::Result<Option<B256>> {
+    if buf.first() == Some(&EMPTY_STRING_CODE) {
+        buf.advance(1);
+        return Ok(None)
+    }
+    B256::decode(buf).map(Some)
+}
+
+/// An account of an [AccountRange] response.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct AccountData {
+    /// The hash of the account address.
+    pub hash: B256,
+    /// The account.
+    pub body: SlimAccount,
+}
+
+/// The response to [GetAccountRange].
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct AccountRange {
+    /// The id of the request this responds to.
+    pub request_id: u64,
+    /// The consecutive accounts of the requested range, ordered by hash.
+    pub accounts: Vec<AccountData>,
+    /// The trie nodes proving the first and last account of the range.
+    pub proof: Vec<Bytes>,
+}
+
+/// Requests the storage slots of multiple accounts' storage tries.
+///
+/// The starting and limit hashes only apply to the first account, the remaining accounts are
+/// served from the start of their storage.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct GetStorageRanges {
+    /// The request id.
+    pub request_id: u64,
+    /// The root of the account trie to serve.
+    pub root_hash: B256,
+    /// The hashes of the accounts whose storage to retrieve.
+    pub account_hashes: Vec<B256>,
+    /// The storage slot hash of the first slot to retrieve.
+    pub starting_hash: B256,
+    /// The storage slot hash after which to stop serving data.
+    pub limit_hash: B256,
+    /// The soft limit in bytes of the response.
+    pub response_bytes: u64,
+}
+
+/// A storage slot of a [StorageRanges] response.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct StorageData {
+    /// The hash of the storage slot key.
+    pub hash: B256,
+    /// The RLP encoded storage value.
+    pub data: Bytes,
+}
+
+/// The response to [GetStorageRanges].
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct StorageRanges {
+    /// The id of the request this responds to.
+    pub request_id: u64,
+    /// The consecutive storage slots of each requested account, ordered by hash.
+    pub slots: Vec<Vec<StorageData>>,
+    /// The trie nodes proving the last storage range, if it's not the complete storage.
+    pub proof: Vec<Bytes>,
+}
+
+/// Requests bytecodes by hash.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct GetByteCodes {
+    /// The request id.
+    pub request_id: u64,
+    /// The code hashes to retrieve.
+    pub hashes: Vec<B256>,
+    /// The soft limit in bytes of the response.
+    pub response_bytes: u64,
+}
+
+/// The response to [GetByteCodes].
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct ByteCodes {
+    /// The id of the request this responds to.
+    pub request_id: u64,
+    /// The requested bytecodes, in request order. Unavailable codes are skipped.
+    pub codes: Vec<Bytes>,
+}
+
+/// Requests trie nodes by path.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct GetTrieNodes {
+    /// The request id.
+    pub request_id: u64,
+    /// The root of the account trie to serve.
+    pub root_hash: B256,
+    /// The compact encoded paths of the trie nodes to retrieve.
+    ///
+    /// A path set with a single path refers to a node of the account trie. Otherwise the first
+    /// path is the full path to an account and the remaining paths refer to nodes of its storage
+    /// trie.
+    pub paths: Vec<Vec<Bytes>>,
+    /// The soft limit in bytes of the response.
+    pub response_bytes: u64,
+}
+
+/// The response to [GetTrieNodes].
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct TrieNodes {
+    /// The id of the request this responds to.
+    pub request_id: u64,
+    /// The requested trie nodes, in request order. Serving stops at the first unavailable node.
+    pub nodes: Vec<Bytes>,
+}
+
+/// A `snap/1` protocol message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SnapMessage {
+    /// Represents a [GetAccountRange] request.
+    GetAccountRange(GetAccountRange),
+    /// Represents an [AccountRange] response.
+    AccountRange(AccountRange),
+    /// Represents a [GetStorageRanges] request.
+    GetStorageRanges(GetStorageRanges),
+    /// Represents a [StorageRanges] response.
+    StorageRanges(StorageRanges),
+    /// Represents a [GetByteCodes] request.
+    GetByteCodes(GetByteCodes),
+    /// Represents a [ByteCodes] response.
+    ByteCodes(ByteCodes),
+    /// Represents a [GetTrieNodes] request.
+    GetTrieNodes(GetTrieNodes),
+    /// Represents a [TrieNodes] response.
+    TrieNodes(TrieNodes),
+}
+
+impl SnapMessage {
+    /// Returns the capability of the `snap/1` protocol.
+    pub const fn capability() -> Capability {
+        Capability::new_static("snap", 1)
+    }
+
+    /// Returns the `snap/1` protocol.
+    pub const fn protocol() -> Protocol {
+        Protocol::new(Self::capability(), SnapMessageId::COUNT)
+    }
+
+    /// Returns the id of the message.
+    pub const fn message_id(&self) -> SnapMessageId {
+        match self {
+            Self::GetAccountRange(_) => SnapMessageId::GetAccountRange,
+            Self::AccountRange(_) => SnapMessageId::AccountRange,
+            Self::GetStorageRanges(_) => SnapMessageId::GetStorageRanges,
+            Self::StorageRanges(_) => SnapMessageId::StorageRanges,
+            Self::GetByteCodes(_) => SnapMessageId::GetByteCodes,
+            Self::ByteCodes(_) => SnapMessageId::ByteCodes,
+            Self::GetTrieNodes(_) => SnapMessageId::GetTrieNodes,
+            Self::TrieNodes(_) => SnapMessageId::TrieNodes,
+        }
+    }
+
+    /// Returns true if the message is a request.
+    pub const fn is_request(&self) -> bool {
+        matches!(
+            self,
+            Self::GetAccountRange(_) |
+                Self::GetStorageRanges(_) |
+                Self::GetByteCodes(_) |
+                Self::GetTrieNodes(_)
+        )
+    }
+
+    /// Returns the request id of the message.
+    pub const fn request_id(&self) -> u64 {
+        match self {
+            Self::GetAccountRange(msg) => msg.request_id,
+            Self::AccountRange(msg) => msg.request_id,
+            Self::GetStorageRanges(msg) => msg.request_id,
+            Self::StorageRanges(msg) => msg.request_id,
+            Self::GetByteCodes(msg) => msg.request_id,
+            Self::ByteCodes(msg) => msg.request_id,
+            Self::GetTrieNodes(msg) => msg.request_id,
+            Self::TrieNodes(msg) => msg.request_id,
+        }
+    }
+
+    /// Sets the request id of the message.
+    pub fn set_request_id(&mut self, request_id: u64) {
+        match self {
+            Self::GetAccountRange(msg) => msg.request_id = request_id,
+            Self::AccountRange(msg) => msg.request_id = request_id,
+            Self::GetStorageRanges(msg) => msg.request_id = request_id,
+            Self::StorageRanges(msg) => msg.request_id = request_id,
+            Self::GetByteCodes(msg) => msg.request_id = request_id,
+            Self::ByteCodes(msg) => msg.request_id = request_id,
+            Self::GetTrieNodes(msg) => msg.request_id = request_id,
+            Self::TrieNodes(msg) => msg.request_id = request_id,
+        }
+    }
+
+    /// Encodes the message as the message id followed by the RLP encoded payload.
+    pub fn encoded(&self) -> BytesMut {
+        let mut buf = BytesMut::new();
+        buf.put_u8(self.message_id() as u8);
+        match self {
+            Self::GetAccountRange(msg) => msg.encode(&mut buf),
+            Self::AccountRange(msg) => msg.encode(&mut buf),
+            Self::GetStorageRanges(msg) => msg.encode(&mut buf),
+            Self::StorageRanges(msg) => msg.encode(&mut buf),
+            Self::GetByteCodes(msg) => msg.encode(&mut buf),
+            Self::ByteCodes(msg) => msg.encode(&mut buf),
+            Self::GetTrieNodes(msg) => msg.encode(&mut buf),
+            Self::TrieNodes(msg) => msg.encode(&mut buf),
+        }
+        buf
+    }
+
+    /// Decodes a message from the given buffer, see [SnapMessage::encoded].
+    pub fn decode_message(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        if buf.is_empty() {
+            return Err(alloy_rlp::Error::InputTooShort)
+        }
+        let id = SnapMessageId::from_u8(buf[0])
+            .ok_or(alloy_rlp::Error::Custom("unknown snap message id"))?;
+        buf.advance(1);
+        let message = match id {
+            SnapMessageId::GetAccountRange => Self::GetAccountRange(Decodable::decode(buf)?),
+            SnapMessageId::AccountRange => Self::AccountRange(Decodable::decode(buf)?),
+            SnapMessageId::GetStorageRanges => Self::GetStorageRanges(Decodable::decode(buf)?),
+            SnapMessageId::StorageRanges => Self::StorageRanges(Decodable::decode(buf)?),
+            SnapMessageId::GetByteCodes => Self::GetByteCodes(Decodable::decode(buf)?),
+            SnapMessageId::ByteCodes => Self::ByteCodes(Decodable::decode(buf)?),
+            SnapMessageId::GetTrieNodes => Self::GetTrieNodes(Decodable::decode(buf)?),
+            SnapMessageId::TrieNodes => Self::TrieNodes(Decodable::decode(buf)?),
+        };
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::hex;
+
+    fn roundtrip(message: SnapMessage) {
+        let encoded = message.encoded();
+        assert_eq!(encoded[0], message.message_id() as u8);
+        assert_eq!(SnapMessage::decode_message(&mut &encoded[..]).unwrap(), message);
+    }
+
+    #[test]
+    fn slim_account_encoding() {
+        let account = SlimAccount {
+            nonce: 1,
+            balance: U256::from(2),
+            storage_root: EMPTY_ROOT_HASH,
+            code_hash: KECCAK_EMPTY,
+        };
+        let encoded = alloy_rlp::encode(account);
+        assert_eq!(encoded, hex!("c401028080"));
+        assert_eq!(encoded.len(), account.length());
+        assert_eq!(SlimAccount::decode(&mut &encoded[..]).unwrap(), account);
+
+        let account = SlimAccount {
+            storage_root: B256::repeat_byte(0x11),
+            code_hash: B256::repeat_byte(0x22),
+            ..account
+        };
+        let encoded = alloy_rlp::encode(account);
+        assert_eq!(encoded.len(), account.length());
+        assert_eq!(SlimAccount::decode(&mut &encoded[..]).unwrap(), account);
+        assert_eq!(account.account().bytecode_hash, Some(B256::repeat_byte(0x22)));
+    }
+
+    #[test]
+    fn snap_message_roundtrip() {
+        roundtrip(SnapMessage::GetAccountRange(GetAccountRange {
+            request_id: 1,
+            root_hash: B256::repeat_byte(1),
+            starting_hash: B256::ZERO,
+            limit_hash: B256::repeat_byte(0xff),
+            response_bytes: 512 * 1024,
+        }));
+        roundtrip(SnapMessage::AccountRange(AccountRange {
+            request_id: 1,
+            accounts: vec![AccountData {
+                hash: B256::repeat_byte(2),
+                body: SlimAccount::new(Account::default(), EMPTY_ROOT_HASH),
+            }],
+            proof: vec![Bytes::from_static(&[0xc0])],
+        }));
+        roundtrip(SnapMessage::GetStorageRanges(GetStorageRanges {
+            request_id: 2,
+            root_hash: B256::repeat_byte(1),
+            account_hashes: vec![B256::repeat_byte(2), B256::repeat_byte(3)],
+            starting_hash: B256::ZERO,
+            limit_hash: B256::repeat_byte(0xff),
+            response_bytes: 512 * 1024,
+        }));
+        roundtrip(SnapMessage::StorageRanges(StorageRanges {
+            request_id: 2,
+            slots: vec![
+                vec![StorageData { hash: B256::repeat_byte(4), data: Bytes::from_static(&[1]) }],
+                vec![],
+            ],
+            proof: vec![],
+        }));
+        roundtrip(SnapMessage::GetByteCodes(GetByteCodes {
+            request_id: 3,
+            hashes: vec![B256::repeat_byte(5)],
+            response_bytes: 512 * 1024,
+        }));
+        roundtrip(SnapMessage::ByteCodes(ByteCodes {
+            request_id: 3,
+            codes: vec![Bytes::from_static(&[0x60, 0x00])],
+        }));
+        roundtrip(SnapMessage::GetTrieNodes(GetTrieNodes {
+            request_id: 4,
+            root_hash: B256::repeat_byte(1),
+            paths: vec![vec![Bytes::from_static(&[0x00])]],
+            response_bytes: 512 * 1024,
+        }));
+        roundtrip(SnapMessage::TrieNodes(TrieNodes {
+            request_id: 4,
+            nodes: vec![Bytes::from_static(&[0xc0])],
+        }));
+    }
+
+    #[test]
+    fn decode_unknown_message_id() {
+        assert!(SnapMessage::decode_message(&mut &[0x08, 0xc0][..]).is_err());
+        assert!(SnapMessage::decode_message(&mut &[][..]).is_err());
+    }
+}
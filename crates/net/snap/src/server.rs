@@ -0,0 +1,421 @@
+//! Serves `snap/1` requests from the hashed state and trie tables.
+
+use crate::message::{
+    AccountData, AccountRange, ByteCodes, GetAccountRange, GetByteCodes, GetStorageRanges,
+    GetTrieNodes, SlimAccount, StorageData, StorageRanges, TrieNodes,
+};
+use futures::StreamExt;
+use reth_db::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    database::Database,
+    tables,
+    transaction::DbTx,
+    DatabaseError,
+};
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{trie::Nibbles, Bytes, PeerId, B256};
+use reth_provider::{BlockNumReader, DatabaseProviderRO, HeaderProvider, ProviderFactory};
+use reth_trie::{proof::Proof, StorageRoot};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::sync::{mpsc::Receiver, oneshot};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::trace;
+
+// Limits: <https://github.com/ethereum/go-ethereum/blob/v1.13.14/eth/protocols/snap/handler.go#L34-L56>
+
+/// Maximum size of replies to data retrievals.
+const SOFT_RESPONSE_LIMIT: usize = 2 * 1024 * 1024;
+
+/// Maximum number of bytecodes to serve.
+///
+/// Used to limit lookups.
+const MAX_CODE_LOOKUPS: usize = 1024;
+
+/// Maximum number of trie nodes to serve.
+///
+/// Used to limit lookups.
+const MAX_TRIE_NODE_LOOKUPS: usize = 1024;
+
+/// The capacity of the channel of incoming requests of the [SnapRequestHandler].
+pub const SNAP_REQUEST_CHANNEL_CAPACITY: usize = 256;
+
+/// Estimated size in bytes of an RLP encoded slim account, including its hash.
+const APPROX_ACCOUNT_SIZE: usize = 110;
+
+/// Estimated size in bytes of an RLP encoded storage slot, including its hash.
+const APPROX_SLOT_SIZE: usize = 68;
+
+/// Manages `snap/1` requests on top of the p2p network.
+///
+/// Requests are only served for the state root of the latest block, older states are not
+/// available in the hashed state and trie tables.
+///
+/// This can be spawned to another task and is supposed to be run as background service.
+#[derive(Debug)]
+#[must_use = "Manager does nothing unless polled."]
+pub struct SnapRequestHandler<DB> {
+    /// The factory for database providers.
+    factory: ProviderFactory<DB>,
+    /// Incoming requests from the [SnapConnection](crate::SnapConnection)s.
+    incoming_requests: ReceiverStream<IncomingSnapRequest>,
+}
+
+// === impl SnapRequestHandler ===
+
+impl<DB> SnapRequestHandler<DB> {
+    /// Create a new instance
+    pub fn new(factory: ProviderFactory<DB>, incoming: Receiver<IncomingSnapRequest>) -> Self {
+        Self { factory, incoming_requests: ReceiverStream::new(incoming) }
+    }
+}
+
+impl<DB: Database> SnapRequestHandler<DB> {
+    /// Returns a provider if the given root is the state root of the latest block.
+    fn provider_at(&self, root: B256) -> ProviderResult<Option<DatabaseProviderRO<DB>>> {
+        let provider = self.factory.provider()?;
+        let best_number = provider.best_block_number()?;
+        let state_root = provider.header_by_number(best_number)?.map(|header| header.state_root);
+        Ok((state_root == Some(root)).then_some(provider))
+    }
+
+    /// Returns the accounts of the requested range and the proof of its boundaries.
+    fn get_account_range(&self, request: &GetAccountRange) -> ProviderResult<AccountRange> {
+        let GetAccountRange { request_id, root_hash, starting_hash, limit_hash, response_bytes } =
+            *request;
+        let mut response = AccountRange { request_id, accounts: Vec::new(), proof: Vec::new() };
+        let Some(provider) = self.provider_at(root_hash)? else { return Ok(response) };
+        let tx = provider.tx_ref();
+        let response_bytes = (response_bytes as usize).min(SOFT_RESPONSE_LIMIT);
+
+        let mut cursor = tx.cursor_read::<tables::HashedAccount>()?;
+        let mut entry = cursor.seek(starting_hash)?;
+        let mut total_bytes = 0;
+        while let Some((hash, account)) = entry {
+            let storage_root = StorageRoot::from_tx_hashed(tx, hash)
+                .root()
+                .map_err(Into::<DatabaseError>::into)?;
+            response
+                .accounts
+                .push(AccountData { hash, body: SlimAccount::new(account, storage_root) });
+
+            total_bytes += APPROX_ACCOUNT_SIZE;
+            // The first account past the limit is included to prove there are no more accounts in
+            // the range.
+            if hash >= limit_hash || total_bytes > response_bytes {
+                break
+            }
+            entry = cursor.next()?;
+        }
+
+        // The complete trie needs no proof.
+        if starting_hash != B256::ZERO || entry.is_some() {
+            let mut targets = vec![Nibbles::unpack(starting_hash)];
+            if let Some(last) = response.accounts.last() {
+                targets.push(Nibbles::unpack(last.hash));
+            }
+            let (_, nodes) =
+                Proof::new(tx).account_trie_nodes(targets).map_err(Into::<DatabaseError>::into)?;
+            response.proof = nodes.into_values().collect();
+        }
+
+        Ok(response)
+    }
+
+    /// Returns the storage slots of the requested accounts and the proof of the last range if it
+    /// was cut off.
+    fn get_storage_ranges(&self, request: &GetStorageRanges) -> ProviderResult<StorageRanges> {
+        let GetStorageRanges {
+            request_id,
+            root_hash,
+            ref account_hashes,
+            starting_hash,
+            limit_hash,
+            response_bytes,
+        } = *request;
+        let mut response = StorageRanges { request_id, slots: Vec::new(), proof: Vec::new() };
+        let Some(provider) = self.provider_at(root_hash)? else { return Ok(response) };
+        let tx = provider.tx_ref();
+        let response_bytes = (response_bytes as usize).min(SOFT_RESPONSE_LIMIT);
+
+        let mut cursor = tx.cursor_dup_read::<tables::HashedStorage>()?;
+        let mut total_bytes = 0;
+        for (idx, hashed_address) in account_hashes.iter().copied().enumerate() {
+            // The requested range only applies to the first account.
+            let (origin, limit) = if idx == 0 {
+                (starting_hash, limit_hash)
+            } else {
+                (B256::ZERO, B256::repeat_byte(0xff))
+            };
+
+            let mut slots = Vec::new();
+            let mut aborted = false;
+            let mut entry = cursor
+                .seek_by_key_subkey(hashed_address, origin)?
+                .map(|entry| (hashed_address, entry));
+            while let Some((_, slot)) = entry {
+                slots.push(StorageData {
+                    hash: slot.key,
+                    data: alloy_rlp::encode(slot.value).into(),
+                });
+
+                total_bytes += APPROX_SLOT_SIZE;
+                if slot.key >= limit {
+                    break
+                }
+                if total_bytes > response_bytes {
+                    aborted = true;
+                    break
+                }
+                entry = cursor.next_dup()?;
+            }
+
+            // A partial storage range needs to be proven.
+            let partial = aborted || origin != B256::ZERO;
+            if partial {
+                let mut targets = vec![Nibbles::unpack(origin)];
+                if let Some(last) = slots.last() {
+                    targets.push(Nibbles::unpack(last.hash));
+                }
+                let (_, nodes) = Proof::new(tx)
+                    .storage_trie_nodes(hashed_address, targets)
+                    .map_err(Into::<DatabaseError>::into)?;
+                response.proof = nodes.into_values().collect();
+            }
+            response.slots.push(slots);
+
+            if partial || total_bytes > response_bytes {
+                break
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Returns the requested bytecodes.
+    fn get_byte_codes(&self, request: &GetByteCodes) -> ProviderResult<ByteCodes> {
+        let mut response = ByteCodes { request_id: request.request_id, codes: Vec::new() };
+        let provider = self.factory.provider()?;
+        let response_bytes = (request.response_bytes as usize).min(SOFT_RESPONSE_LIMIT);
+
+        let mut total_bytes = 0;
+        for hash in request.hashes.iter().copied().take(MAX_CODE_LOOKUPS) {
+            if let Some(bytecode) = provider.tx_ref().get::<tables::Bytecodes>(hash)? {
+                let code = bytecode.original_bytes();
+                total_bytes += code.len();
+                response.codes.push(code);
+
+                if total_bytes > response_bytes {
+                    break
+                }
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Returns the requested trie nodes.
+    fn get_trie_nodes(&self, request: &GetTrieNodes) -> ProviderResult<TrieNodes> {
+        let mut response = TrieNodes { request_id: request.request_id, nodes: Vec::new() };
+        let Some(provider) = self.provider_at(request.root_hash)? else { return Ok(response) };
+        let proof = Proof::new(provider.tx_ref());
+        let response_bytes = (request.response_bytes as usize).min(SOFT_RESPONSE_LIMIT);
+
+        let mut total_bytes = 0;
+        'paths: for paths in &request.paths {
+            let Some((account_path, storage_paths)) = paths.split_first() else { continue };
+            let account_path = compact_to_nibbles(account_path);
+
+            let nodes: Vec<Option<Bytes>> = if storage_paths.is_empty() {
+                let (_, mut nodes) = proof
+                    .account_trie_nodes(vec![account_path.clone()])
+                    .map_err(Into::<DatabaseError>::into)?;
+                vec![nodes.remove(&account_path)]
+            } else {
+                // The account path of a storage request is the full path to the account.
+                let hashed_address = B256::from_slice(&account_path.pack());
+                let storage_paths =
+                    storage_paths.iter().map(|path| compact_to_nibbles(path)).collect::<Vec<_>>();
+                let (_, mut nodes) = proof
+                    .storage_trie_nodes(hashed_address, storage_paths.clone())
+                    .map_err(Into::<DatabaseError>::into)?;
+                storage_paths.iter().map(|path| nodes.remove(path)).collect()
+            };
+
+            for node in nodes {
+                // Serving stops at the first missing node.
+                let Some(node) = node else { break 'paths };
+                total_bytes += node.len();
+                response.nodes.push(node);
+
+                if response.nodes.len() >= MAX_TRIE_NODE_LOOKUPS || total_bytes > response_bytes {
+                    break 'paths
+                }
+            }
+        }
+
+        Ok(response)
+    }
+
+    fn on_account_range_request(
+        &mut self,
+        peer_id: PeerId,
+        request: GetAccountRange,
+        response: oneshot::Sender<AccountRange>,
+    ) {
+        let accounts = self.get_account_range(&request).unwrap_or_else(|err| {
+            trace!(target: "net::snap", ?peer_id, %err, "Failed to serve account range");
+            AccountRange { request_id: request.request_id, accounts: Vec::new(), proof: Vec::new() }
+        });
+        let _ = response.send(accounts);
+    }
+
+    fn on_storage_ranges_request(
+        &mut self,
+        peer_id: PeerId,
+        request: GetStorageRanges,
+        response: oneshot::Sender<StorageRanges>,
+    ) {
+        let slots = self.get_storage_ranges(&request).unwrap_or_else(|err| {
+            trace!(target: "net::snap", ?peer_id, %err, "Failed to serve storage ranges");
+            StorageRanges { request_id: request.request_id, slots: Vec::new(), proof: Vec::new() }
+        });
+        let _ = response.send(slots);
+    }
+
+    fn on_byte_codes_request(
+        &mut self,
+        peer_id: PeerId,
+        request: GetByteCodes,
+        response: oneshot::Sender<ByteCodes>,
+    ) {
+        let codes = self.get_byte_codes(&request).unwrap_or_else(|err| {
+            trace!(target: "net::snap", ?peer_id, %err, "Failed to serve bytecodes");
+            ByteCodes { request_id: request.request_id, codes: Vec::new() }
+        });
+        let _ = response.send(codes);
+    }
+
+    fn on_trie_nodes_request(
+        &mut self,
+        peer_id: PeerId,
+        request: GetTrieNodes,
+        response: oneshot::Sender<TrieNodes>,
+    ) {
+        let nodes = self.get_trie_nodes(&request).unwrap_or_else(|err| {
+            trace!(target: "net::snap", ?peer_id, %err, "Failed to serve trie nodes");
+            TrieNodes { request_id: request.request_id, nodes: Vec::new() }
+        });
+        let _ = response.send(nodes);
+    }
+}
+
+/// An endless future.
+///
+/// This should be spawned or used as part of `tokio::select!`.
+impl<DB> Future for SnapRequestHandler<DB>
+where
+    DB: Database + Unpin,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        loop {
+            match this.incoming_requests.poll_next_unpin(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(None) => return Poll::Ready(()),
+                Poll::Ready(Some(incoming)) => match incoming {
+                    IncomingSnapRequest::GetAccountRange { peer_id, request, response } => {
+                        this.on_account_range_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetStorageRanges { peer_id, request, response } => {
+                        this.on_storage_ranges_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetByteCodes { peer_id, request, response } => {
+                        this.on_byte_codes_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetTrieNodes { peer_id, request, response } => {
+                        this.on_trie_nodes_request(peer_id, request, response)
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// All `snap` requests received from peers.
+#[derive(Debug)]
+pub enum IncomingSnapRequest {
+    /// Request an account range from the peer.
+    GetAccountRange {
+        /// The ID of the peer to request the accounts from.
+        peer_id: PeerId,
+        /// The specific request.
+        request: GetAccountRange,
+        /// The channel sender for the response.
+        response: oneshot::Sender<AccountRange>,
+    },
+    /// Request storage ranges from the peer.
+    GetStorageRanges {
+        /// The ID of the peer to request the storage slots from.
+        peer_id: PeerId,
+        /// The specific request.
+        request: GetStorageRanges,
+        /// The channel sender for the response.
+        response: oneshot::Sender<StorageRanges>,
+    },
+    /// Request bytecodes from the peer.
+    GetByteCodes {
+        /// The ID of the peer to request the bytecodes from.
+        peer_id: PeerId,
+        /// The specific request.
+        request: GetByteCodes,
+        /// The channel sender for the response.
+        response: oneshot::Sender<ByteCodes>,
+    },
+    /// Request trie nodes from the peer.
+    GetTrieNodes {
+        /// The ID of the peer to request the trie nodes from.
+        peer_id: PeerId,
+        /// The specific request.
+        request: GetTrieNodes,
+        /// The channel sender for the response.
+        response: oneshot::Sender<TrieNodes>,
+    },
+}
+
+/// Decodes a compact (hex-prefix) encoded path into nibbles.
+pub(crate) fn compact_to_nibbles(compact: &[u8]) -> Nibbles {
+    let Some((first, rest)) = compact.split_first() else { return Nibbles::default() };
+    let mut nibbles = Vec::with_capacity(compact.len() * 2);
+    // The odd flag is the lowest bit of the first nibble.
+    if first & 0x10 != 0 {
+        nibbles.push(first & 0x0f);
+    }
+    for byte in rest {
+        nibbles.push(byte >> 4);
+        nibbles.push(byte & 0x0f);
+    }
+    Nibbles::from_nibbles_unchecked(nibbles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_compact_path() {
+        let nibbles = Nibbles::from_nibbles_unchecked([0x1, 0x2, 0x3]);
+        assert_eq!(compact_to_nibbles(&nibbles.encode_path_leaf(false)), nibbles);
+        assert_eq!(compact_to_nibbles(&nibbles.encode_path_leaf(true)), nibbles);
+
+        let nibbles = Nibbles::from_nibbles_unchecked([0xa, 0xb]);
+        assert_eq!(compact_to_nibbles(&nibbles.encode_path_leaf(false)), nibbles);
+        assert_eq!(compact_to_nibbles(&[]), Nibbles::default());
+    }
+}
@@ -0,0 +1,171 @@
+//! The pipeline stage that syncs the state of a pivot block with [SnapSync].
+
+use crate::sync::{SnapSync, SnapSyncError};
+use futures::{FutureExt, Stream, StreamExt};
+use reth_db::database::Database;
+use reth_interfaces::p2p::headers::client::HeadersClient;
+use reth_primitives::{
+    stage::{StageCheckpoint, StageId},
+    PruneCheckpoint, PruneMode, PruneSegment, SealedHeader, B256,
+};
+use reth_provider::{DatabaseProviderRW, PruneCheckpointWriter, StageCheckpointWriter};
+use reth_stages::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
+use reth_tasks::TaskSpawner;
+use std::task::{ready, Context, Poll};
+use tokio::sync::{oneshot, watch};
+use tracing::{debug, trace};
+
+/// The stages whose work up to the pivot is done by the snap sync.
+const SYNCED_STAGES: [StageId; 7] = [
+    StageId::SenderRecovery,
+    StageId::Execution,
+    StageId::AccountHashing,
+    StageId::StorageHashing,
+    StageId::MerkleExecute,
+    StageId::IndexAccountHistory,
+    StageId::IndexStorageHistory,
+];
+
+/// The prune segments whose data is missing up to the pivot.
+const MISSING_SEGMENTS: [PruneSegment; 4] = [
+    PruneSegment::SenderRecovery,
+    PruneSegment::Receipts,
+    PruneSegment::AccountHistory,
+    PruneSegment::StorageHistory,
+];
+
+/// The result of a [SnapSync] task, along with the sync to retry it.
+type SyncResult<DB> = (SnapSync<DB>, Result<SealedHeader, SnapSyncError>);
+
+/// The snap sync stage.
+///
+/// On a fresh node, this stage runs before the headers stage and downloads the state of the
+/// pivot with [SnapSync] on a spawned task. The checkpoints of the stages that derive the state
+/// are then set to the pivot, so the pipeline only executes the blocks after it. The senders,
+/// receipts and history of the blocks up to the pivot are never created and are marked as pruned.
+/// If the receipts stage downloads the receipts of these blocks, it moves their prune checkpoint
+/// back once they're written.
+///
+/// The checkpoint of this stage is the pivot. It's never unwound, because there's no state to
+/// unwind to before the pivot.
+#[derive(Debug)]
+pub struct SnapSyncStage<DB> {
+    /// The sync, while it's not running.
+    sync: Option<SnapSync<DB>>,
+    /// The running sync.
+    task: Option<oneshot::Receiver<SyncResult<DB>>>,
+    /// The synced pivot, until the checkpoints are written.
+    pivot: Option<SealedHeader>,
+    /// The spawner of the sync task.
+    task_spawner: Box<dyn TaskSpawner>,
+}
+
+impl<DB> SnapSyncStage<DB> {
+    /// Creates a new stage that runs the given sync on the given spawner.
+    pub fn new(sync: SnapSync<DB>, task_spawner: Box<dyn TaskSpawner>) -> Self {
+        Self { sync: Some(sync), task: None, pivot: None, task_spawner }
+    }
+}
+
+impl<DB: Database + 'static> Stage<DB> for SnapSyncStage<DB> {
+    fn id(&self) -> StageId {
+        StageId::SNAP_SYNC
+    }
+
+    fn poll_execute_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+        input: ExecInput,
+    ) -> Poll<Result<(), StageError>> {
+        if input.checkpoint().block_number > 0 || self.pivot.is_some() {
+            return Poll::Ready(Ok(()))
+        }
+
+        if self.task.is_none() {
+            let mut sync = self.sync.take().ok_or(StageError::MissingDownloadBuffer)?;
+            let (tx, rx) = oneshot::channel();
+            self.task_spawner.spawn(Box::pin(async move {
+                let result = sync.run().await;
+                let _ = tx.send((sync, result));
+            }));
+            self.task = Some(rx);
+        }
+
+        let task = self.task.as_mut().expect("is set");
+        let (sync, result) = ready!(task.poll_unpin(cx)).map_err(|_| StageError::ChannelClosed)?;
+        self.task = None;
+        self.sync = Some(sync);
+        match result {
+            Ok(pivot) => {
+                self.pivot = Some(pivot);
+                Poll::Ready(Ok(()))
+            }
+            Err(err) => Poll::Ready(Err(StageError::Recoverable(Box::new(err)))),
+        }
+    }
+
+    fn execute(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if input.checkpoint().block_number > 0 {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let pivot = self.pivot.take().ok_or(StageError::MissingDownloadBuffer)?;
+        debug!(target: "sync::stages::snap", pivot = ?pivot.num_hash(), "Setting checkpoints to pivot");
+
+        let checkpoint = StageCheckpoint::new(pivot.number);
+        for stage_id in SYNCED_STAGES {
+            provider.save_stage_checkpoint(stage_id, checkpoint)?;
+        }
+        for segment in MISSING_SEGMENTS {
+            provider.save_prune_checkpoint(
+                segment,
+                PruneCheckpoint {
+                    block_number: Some(pivot.number),
+                    tx_number: None,
+                    prune_mode: PruneMode::Before(pivot.number + 1),
+                },
+            )?;
+        }
+
+        Ok(ExecOutput::done(checkpoint))
+    }
+
+    fn unwind(
+        &mut self,
+        _provider: &DatabaseProviderRW<DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        Ok(UnwindOutput { checkpoint: input.checkpoint })
+    }
+}
+
+/// Sets the pivot of a [SnapSync] to the header of every new sync target.
+///
+/// The headers are requested from the given client. Targets whose header can't be fetched are
+/// skipped, the pivot moves on with the next target.
+pub async fn follow_tip<C, S>(client: C, mut tips: S, pivot: watch::Sender<SealedHeader>)
+where
+    C: HeadersClient,
+    S: Stream<Item = B256> + Unpin,
+{
+    while let Some(tip) = tips.next().await {
+        if tip.is_zero() || pivot.borrow().hash() == tip {
+            continue
+        }
+        match client.get_header(tip.into()).await.map(|response| response.into_data()) {
+            Ok(Some(header)) => {
+                let header = header.seal_slow();
+                if header.hash() == tip {
+                    trace!(target: "sync::snap", pivot = ?header.num_hash(), "Moving pivot");
+                    pivot.send_replace(header);
+                }
+            }
+            Ok(None) => trace!(target: "sync::snap", ?tip, "Missing pivot header"),
+            Err(err) => trace!(target: "sync::snap", ?tip, %err, "Failed to fetch pivot header"),
+        }
+    }
+}
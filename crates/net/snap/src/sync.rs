@@ -0,0 +1,991 @@
+//! Downloads the state of a pivot block over `snap/1`.
+
+use crate::{
+    connection::{SnapPeerHandle, SnapRequestError},
+    message::{
+        GetAccountRange, GetByteCodes, GetStorageRanges, GetTrieNodes, SlimAccount, StorageData,
+    },
+    SnapEvent,
+};
+use alloy_rlp::Decodable;
+use reth_db::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO},
+    database::Database,
+    tables,
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
+use reth_interfaces::provider::ProviderError;
+use reth_network::peers::PeersHandle;
+use reth_network_api::ReputationChangeKind;
+use reth_primitives::{
+    constants::EMPTY_ROOT_HASH,
+    keccak256,
+    trie::{Nibbles, TrieAccount},
+    Bytecode, Bytes, PeerId, SealedHeader, StorageEntry, B256, KECCAK_EMPTY, U256,
+};
+use reth_provider::ProviderFactory;
+use reth_trie::{
+    prefix_set::{PrefixSetMut, TriePrefixSets},
+    proof::Proof,
+    range_proof::verify_range_proof,
+    trie_node::{ChildRef, TrieNode},
+    StateRoot, StorageRoot,
+};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    future::Future,
+    time::Duration,
+};
+use tokio::sync::{mpsc, watch};
+use tracing::{debug, trace};
+
+/// The configuration of [SnapSync].
+#[derive(Debug, Clone)]
+pub struct SnapSyncConfig {
+    /// The soft limit in bytes of a response.
+    pub response_bytes: u64,
+    /// The maximum number of accounts to request storage ranges for at once.
+    pub max_storage_accounts: usize,
+    /// The maximum number of bytecodes to request at once.
+    pub max_codes: usize,
+    /// The maximum number of trie nodes to request at once while healing.
+    pub max_trie_nodes: usize,
+    /// The timeout of a request, after which the peer is dropped.
+    pub request_timeout: Duration,
+    /// The time to wait for a new pivot or a new peer once no peer serves the pivot.
+    pub peer_timeout: Duration,
+}
+
+impl Default for SnapSyncConfig {
+    fn default() -> Self {
+        Self {
+            response_bytes: 512 * 1024,
+            max_storage_accounts: 128,
+            max_codes: 64,
+            max_trie_nodes: 128,
+            request_timeout: Duration::from_secs(10),
+            peer_timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Errors of [SnapSync].
+#[derive(Debug, thiserror::Error)]
+pub enum SnapSyncError {
+    /// No peer serves the pivot.
+    #[error("no snap peers available")]
+    NoPeers,
+    /// The source of the pivot was dropped before a pivot was set.
+    #[error("no pivot to sync to")]
+    NoPivot,
+    /// Failed to write the downloaded state.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
+impl From<DatabaseError> for SnapSyncError {
+    fn from(err: DatabaseError) -> Self {
+        Self::Provider(err.into())
+    }
+}
+
+/// Downloads the state of a moving pivot from `snap/1` peers into the hashed state tables and
+/// computes the trie.
+///
+/// The sync starts from empty hashed state and trie tables, any state left over by a previous
+/// attempt is discarded. Accounts and storage slots are downloaded in ranges of the latest pivot,
+/// every range is verified against its range proof and peers serving invalid ranges are dropped.
+/// As the pivot moves on, the downloaded ranges belong to different states, which are healed by
+/// walking the trie of the latest pivot and requesting the nodes that differ from the local trie.
+#[derive(Debug)]
+pub struct SnapSync<DB> {
+    /// The factory for database providers.
+    factory: ProviderFactory<DB>,
+    /// The pivot to sync to.
+    pivot: watch::Receiver<SealedHeader>,
+    /// The peers to download from, in the order they are requested from.
+    peers: VecDeque<SnapPeerHandle>,
+    /// New `snap/1` connections to download from.
+    events: Option<mpsc::UnboundedReceiver<SnapEvent>>,
+    /// The handle to report misbehaving peers to.
+    peers_handle: Option<PeersHandle>,
+    /// The peers that don't serve the state root [Self::stale_root].
+    stale: HashSet<PeerId>,
+    /// The state root the stale peers were requested for.
+    stale_root: B256,
+    /// The download configuration.
+    config: SnapSyncConfig,
+}
+
+impl<DB: Database> SnapSync<DB> {
+    /// Creates a new downloader that syncs to the given pivot and writes to the given database.
+    pub fn new(
+        factory: ProviderFactory<DB>,
+        pivot: watch::Receiver<SealedHeader>,
+        config: SnapSyncConfig,
+    ) -> Self {
+        Self {
+            factory,
+            pivot,
+            peers: VecDeque::new(),
+            events: None,
+            peers_handle: None,
+            stale: HashSet::new(),
+            stale_root: B256::ZERO,
+            config,
+        }
+    }
+
+    /// Sets the events of the [SnapProtocolHandler](crate::SnapProtocolHandler) to pick up new
+    /// peers from.
+    pub fn with_events(mut self, events: mpsc::UnboundedReceiver<SnapEvent>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Sets the handle to report peers that serve invalid data to.
+    pub fn with_peers_handle(mut self, peers_handle: PeersHandle) -> Self {
+        self.peers_handle = Some(peers_handle);
+        self
+    }
+
+    /// Adds a peer to download from.
+    pub fn add_peer(&mut self, peer: SnapPeerHandle) {
+        self.peers.push_back(peer);
+    }
+
+    /// Downloads the state of the pivot and returns the pivot header once the computed state
+    /// root matches it.
+    ///
+    /// Waits for the first pivot if the pivot is still the genesis header. Every call starts over
+    /// from empty state, so a failed sync can be retried.
+    pub async fn run(&mut self) -> Result<SealedHeader, SnapSyncError> {
+        while self.pivot.borrow().number == 0 {
+            self.pivot.changed().await.map_err(|_| SnapSyncError::NoPivot)?;
+        }
+
+        debug!(target: "sync::snap", pivot=?self.pivot.borrow().num_hash(), "Starting snap sync");
+        self.clear_state()?;
+
+        let (storages, codes) = self.download_accounts().await?;
+        debug!(target: "sync::snap", storages=storages.len(), codes=codes.len(), "Downloaded accounts");
+        self.download_storages(storages).await?;
+        self.download_codes(codes.into_iter().collect()).await?;
+
+        let provider = self.factory.provider_rw()?;
+        let (mut root, updates) = StateRoot::from_tx(provider.tx_ref())
+            .root_with_updates()
+            .map_err(Into::<DatabaseError>::into)?;
+        updates.flush(provider.tx_ref())?;
+        provider.commit()?;
+
+        loop {
+            let pivot = self.pivot.borrow().clone();
+            if root == pivot.state_root {
+                debug!(target: "sync::snap", pivot=?pivot.num_hash(), "Finished snap sync");
+                return Ok(pivot)
+            }
+
+            debug!(target: "sync::snap", ?root, expected=?pivot.state_root, "Healing state");
+            let changes = self.heal(pivot.state_root).await?;
+            self.download_codes(changes.codes.iter().copied().collect()).await?;
+
+            let provider = self.factory.provider_rw()?;
+            let (got, updates) = StateRoot::from_tx(provider.tx_ref())
+                .with_prefix_sets(changes.into_prefix_sets())
+                .root_with_updates()
+                .map_err(Into::<DatabaseError>::into)?;
+            updates.flush(provider.tx_ref())?;
+            provider.commit()?;
+            root = got;
+        }
+    }
+
+    /// Clears the hashed state and trie tables.
+    fn clear_state(&self) -> Result<(), SnapSyncError> {
+        let provider = self.factory.provider_rw()?;
+        let tx = provider.tx_ref();
+        tx.clear::<tables::HashedAccount>()?;
+        tx.clear::<tables::HashedStorage>()?;
+        tx.clear::<tables::AccountsTrie>()?;
+        tx.clear::<tables::StoragesTrie>()?;
+        provider.commit()?;
+        Ok(())
+    }
+
+    /// Downloads all accounts and returns the storages and the code hashes to download.
+    async fn download_accounts(
+        &mut self,
+    ) -> Result<(Vec<StorageTask>, HashSet<B256>), SnapSyncError> {
+        let mut storages = Vec::new();
+        let mut code_hashes = HashSet::new();
+        let mut origin = B256::ZERO;
+        let response_bytes = self.config.response_bytes;
+        loop {
+            let (peer_id, root, response) = self
+                .request(move |peer, root| {
+                    let request = GetAccountRange {
+                        request_id: 0,
+                        root_hash: root,
+                        starting_hash: origin,
+                        limit_hash: B256::repeat_byte(0xff),
+                        response_bytes,
+                    };
+                    async move { peer.get_account_range(request).await }
+                })
+                .await?;
+
+            // A peer without the state responds with neither accounts nor proof.
+            if response.accounts.is_empty() && response.proof.is_empty() {
+                self.mark_stale(peer_id);
+                continue
+            }
+            let leaves = response
+                .accounts
+                .iter()
+                .map(|account| {
+                    let trie_account =
+                        TrieAccount::from((account.body.account(), account.body.storage_root));
+                    (account.hash, alloy_rlp::encode(trie_account))
+                })
+                .collect::<Vec<_>>();
+            let has_more = match verify_range_proof(root, origin, &leaves, &response.proof) {
+                Ok(has_more) => has_more,
+                Err(err) => {
+                    trace!(target: "sync::snap", ?peer_id, %err, "Received invalid account range");
+                    self.report_bad_peer(peer_id);
+                    continue
+                }
+            };
+
+            let provider = self.factory.provider_rw()?;
+            for account in &response.accounts {
+                provider
+                    .tx_ref()
+                    .put::<tables::HashedAccount>(account.hash, account.body.account())?;
+                if account.body.storage_root != EMPTY_ROOT_HASH {
+                    storages.push(StorageTask {
+                        hashed_address: account.hash,
+                        storage_root: account.body.storage_root,
+                        state_root: root,
+                        origin: B256::ZERO,
+                    });
+                }
+                if account.body.code_hash != KECCAK_EMPTY {
+                    code_hashes.insert(account.body.code_hash);
+                }
+            }
+            provider.commit()?;
+
+            let next = response.accounts.last().and_then(|account| increment(account.hash));
+            match next {
+                Some(next) if has_more => origin = next,
+                _ => break,
+            }
+        }
+        Ok((storages, code_hashes))
+    }
+
+    /// Downloads the given storages.
+    ///
+    /// Storages that changed since their account was downloaded are skipped, they are healed.
+    async fn download_storages(&mut self, storages: Vec<StorageTask>) -> Result<(), SnapSyncError> {
+        let mut queue = VecDeque::from(storages);
+        let response_bytes = self.config.response_bytes;
+        while !queue.is_empty() {
+            // A partially downloaded storage is requested on its own, the origin only applies to
+            // the first account.
+            let count = if queue[0].origin == B256::ZERO {
+                queue
+                    .iter()
+                    .take(self.config.max_storage_accounts)
+                    .take_while(|task| task.origin == B256::ZERO)
+                    .count()
+            } else {
+                1
+            };
+            let batch = queue.drain(..count).collect::<Vec<_>>();
+            let account_hashes = batch.iter().map(|task| task.hashed_address).collect::<Vec<_>>();
+            let origin = batch[0].origin;
+            let (peer_id, root, response) = self
+                .request(move |peer, root| {
+                    let request = GetStorageRanges {
+                        request_id: 0,
+                        root_hash: root,
+                        account_hashes: account_hashes.clone(),
+                        starting_hash: origin,
+                        limit_hash: B256::repeat_byte(0xff),
+                        response_bytes,
+                    };
+                    async move { peer.get_storage_ranges(request).await }
+                })
+                .await?;
+
+            if response.slots.is_empty() {
+                self.mark_stale(peer_id);
+                requeue(&mut queue, batch);
+                continue
+            }
+            let Some(verified) =
+                verify_storage_ranges(root, &batch, &response.slots, &response.proof)
+            else {
+                trace!(target: "sync::snap", ?peer_id, "Received invalid storage ranges");
+                self.report_bad_peer(peer_id);
+                requeue(&mut queue, batch);
+                continue
+            };
+
+            let provider = self.factory.provider_rw()?;
+            let mut continuation = None;
+            for (task, slots, has_more) in verified {
+                let last = slots.last().map(|slot| slot.key);
+                for slot in slots {
+                    provider.tx_ref().put::<tables::HashedStorage>(task.hashed_address, slot)?;
+                }
+                if has_more {
+                    continuation =
+                        last.and_then(increment).map(|origin| StorageTask { origin, ..task });
+                }
+            }
+            provider.commit()?;
+
+            requeue(&mut queue, batch.into_iter().skip(response.slots.len()));
+            if let Some(task) = continuation {
+                queue.push_front(task);
+            }
+        }
+        Ok(())
+    }
+
+    /// Downloads the bytecodes of the given hashes.
+    async fn download_codes(&mut self, hashes: Vec<B256>) -> Result<(), SnapSyncError> {
+        let mut queue = VecDeque::from(hashes);
+        let response_bytes = self.config.response_bytes;
+        while !queue.is_empty() {
+            let count = queue.len().min(self.config.max_codes);
+            let hashes = queue.iter().take(count).copied().collect::<Vec<_>>();
+            let (peer_id, _, response) = self
+                .request(move |peer, _| {
+                    let request =
+                        GetByteCodes { request_id: 0, hashes: hashes.clone(), response_bytes };
+                    async move { peer.get_byte_codes(request).await }
+                })
+                .await?;
+
+            if response.codes.is_empty() {
+                self.mark_stale(peer_id);
+                continue
+            }
+
+            let provider = self.factory.provider_rw()?;
+            for code in response.codes {
+                let hash = keccak256(&code);
+                // Codes are served in request order but may be skipped.
+                let Some(pos) = queue.iter().take(count).position(|requested| *requested == hash)
+                else {
+                    continue
+                };
+                queue.remove(pos);
+                provider.tx_ref().put::<tables::Bytecodes>(hash, Bytecode::new_raw(code))?;
+            }
+            provider.commit()?;
+        }
+        Ok(())
+    }
+
+    /// Walks the trie of the given state root and replaces the subtries that differ from the local
+    /// trie, until the local trie matches or the pivot moves on.
+    ///
+    /// Returns the changed keys, to recompute the trie.
+    async fn heal(&mut self, state_root: B256) -> Result<HealChanges, SnapSyncError> {
+        let mut queue = VecDeque::from([HealTask::account(Nibbles::default(), state_root)]);
+        let mut changes = HealChanges::default();
+        let response_bytes = self.config.response_bytes;
+        while !queue.is_empty() {
+            // Subtries that are already in place are skipped.
+            let mut batch = Vec::new();
+            let provider = self.factory.provider()?;
+            while batch.len() < self.config.max_trie_nodes {
+                let Some(task) = queue.pop_front() else { break };
+                if local_node_hash(provider.tx_ref(), &task)? != Some(task.hash) {
+                    batch.push(task);
+                }
+            }
+            drop(provider);
+            if batch.is_empty() {
+                continue
+            }
+
+            let paths = batch.iter().map(HealTask::request_paths).collect::<Vec<_>>();
+            let (peer_id, root, response) = self
+                .request(move |peer, root| {
+                    let request = GetTrieNodes {
+                        request_id: 0,
+                        root_hash: root,
+                        paths: paths.clone(),
+                        response_bytes,
+                    };
+                    async move { peer.get_trie_nodes(request).await }
+                })
+                .await?;
+            if root != state_root {
+                trace!(target: "sync::snap", ?state_root, ?root, "Pivot moved while healing");
+                return Ok(changes)
+            }
+            if response.nodes.is_empty() {
+                self.mark_stale(peer_id);
+                requeue(&mut queue, batch);
+                continue
+            }
+            if response.nodes.len() > batch.len() ||
+                batch
+                    .iter()
+                    .zip(&response.nodes)
+                    .any(|(task, node)| keccak256(node) != task.hash)
+            {
+                trace!(target: "sync::snap", ?peer_id, "Received invalid trie nodes");
+                self.report_bad_peer(peer_id);
+                requeue(&mut queue, batch);
+                continue
+            }
+
+            // The changes of the batch are only kept if all of its nodes are valid.
+            let served = response.nodes.len();
+            let mut batch_queue = VecDeque::new();
+            let mut batch_changes = HealChanges::default();
+            let provider = self.factory.provider_rw()?;
+            let mut result = Ok(());
+            for (task, node) in batch.iter().zip(&response.nodes) {
+                result = TrieNode::decode(node).map_err(HealError::InvalidNode).and_then(|node| {
+                    heal_node(
+                        provider.tx_ref(),
+                        task.owner,
+                        task.path.clone(),
+                        node,
+                        &mut batch_queue,
+                        &mut batch_changes,
+                    )
+                });
+                if result.is_err() {
+                    break
+                }
+            }
+            match result {
+                Ok(()) => {
+                    provider.commit()?;
+                    changes.extend(batch_changes);
+                    queue.extend(batch_queue);
+                    requeue(&mut queue, batch.into_iter().skip(served));
+                }
+                Err(HealError::InvalidNode(err)) => {
+                    trace!(target: "sync::snap", ?peer_id, %err, "Received undecodable trie node");
+                    drop(provider);
+                    self.report_bad_peer(peer_id);
+                    requeue(&mut queue, batch);
+                }
+                Err(HealError::Database(err)) => return Err(err.into()),
+            }
+        }
+        Ok(changes)
+    }
+
+    /// Sends a request for the state root of the current pivot to the next peer that serves it.
+    ///
+    /// Returns the responding peer, the requested state root and the response. Peers that fail to
+    /// respond are dropped. If no peer serves the pivot, this waits for a new pivot or new peers.
+    async fn request<F, Fut, R>(&mut self, f: F) -> Result<(PeerId, B256, R), SnapSyncError>
+    where
+        F: Fn(SnapPeerHandle, B256) -> Fut,
+        Fut: Future<Output = Result<R, SnapRequestError>>,
+    {
+        loop {
+            self.poll_events();
+            let root = self.pivot.borrow().state_root;
+            if root != self.stale_root {
+                self.stale_root = root;
+                self.stale.clear();
+            }
+
+            let Some(peer) = self.next_peer() else {
+                self.wait_for_peers().await?;
+                continue
+            };
+            let peer_id = *peer.peer_id();
+            match tokio::time::timeout(self.config.request_timeout, f(peer, root)).await {
+                Ok(Ok(response)) => return Ok((peer_id, root, response)),
+                Ok(Err(err)) => {
+                    trace!(target: "sync::snap", ?peer_id, %err, "Request failed");
+                }
+                Err(_) => {
+                    trace!(target: "sync::snap", ?peer_id, "Request timed out");
+                }
+            }
+            self.remove_peer(&peer_id);
+        }
+    }
+
+    /// Returns the next peer that serves the current pivot, rotating through the peers.
+    fn next_peer(&mut self) -> Option<SnapPeerHandle> {
+        self.peers.retain(|peer| !peer.is_closed());
+        for _ in 0..self.peers.len() {
+            let peer = self.peers.pop_front()?;
+            self.peers.push_back(peer.clone());
+            if !self.stale.contains(peer.peer_id()) {
+                return Some(peer)
+            }
+        }
+        None
+    }
+
+    /// Waits for a new pivot or a new peer.
+    async fn wait_for_peers(&mut self) -> Result<(), SnapSyncError> {
+        trace!(target: "sync::snap", root=?self.stale_root, "Waiting for snap peers");
+        let timeout = tokio::time::sleep(self.config.peer_timeout);
+        let events = self.events.as_mut();
+        let events = async move {
+            match events {
+                Some(events) => events.recv().await,
+                None => futures::future::pending().await,
+            }
+        };
+        let event = tokio::select! {
+            Ok(()) = self.pivot.changed() => None,
+            Some(event) = events => Some(event),
+            _ = timeout => return Err(SnapSyncError::NoPeers),
+        };
+        if let Some(event) = event {
+            self.on_event(event);
+        }
+        Ok(())
+    }
+
+    /// Adds the peers of new connections.
+    fn poll_events(&mut self) {
+        while let Some(event) = self.events.as_mut().and_then(|events| events.try_recv().ok()) {
+            self.on_event(event);
+        }
+    }
+
+    /// Handles an event of the [SnapProtocolHandler](crate::SnapProtocolHandler).
+    fn on_event(&mut self, event: SnapEvent) {
+        match event {
+            SnapEvent::Established { handle, .. } => self.add_peer(handle),
+        }
+    }
+
+    /// Skips the given peer until the pivot changes, because it doesn't serve the current pivot.
+    fn mark_stale(&mut self, peer_id: PeerId) {
+        trace!(target: "sync::snap", ?peer_id, root=?self.stale_root, "Peer doesn't serve pivot");
+        self.stale.insert(peer_id);
+    }
+
+    /// Drops the given peer and reports it for serving invalid data.
+    fn report_bad_peer(&mut self, peer_id: PeerId) {
+        self.remove_peer(&peer_id);
+        if let Some(peers_handle) = &self.peers_handle {
+            peers_handle.reputation_change(peer_id, ReputationChangeKind::BadProtocol);
+        }
+    }
+
+    /// Stops requesting from the given peer.
+    fn remove_peer(&mut self, peer_id: &PeerId) {
+        self.peers.retain(|peer| peer.peer_id() != peer_id);
+    }
+}
+
+/// The storage of an account to download.
+#[derive(Debug, Clone, Copy)]
+struct StorageTask {
+    /// The hashed address of the account.
+    hashed_address: B256,
+    /// The storage root of the account.
+    storage_root: B256,
+    /// The state root the account was downloaded at.
+    state_root: B256,
+    /// The hashed slot to continue downloading from.
+    origin: B256,
+}
+
+/// Verifies the storage ranges of the requested storages and returns the slots to write for the
+/// served storages and whether their storage has more slots.
+///
+/// Returns `None` if the response is invalid.
+fn verify_storage_ranges(
+    state_root: B256,
+    tasks: &[StorageTask],
+    ranges: &[Vec<StorageData>],
+    proof: &[Bytes],
+) -> Option<Vec<(StorageTask, Vec<StorageEntry>, bool)>> {
+    if ranges.len() > tasks.len() {
+        return None
+    }
+
+    let mut verified = Vec::with_capacity(ranges.len());
+    for (idx, (task, slots)) in tasks.iter().zip(ranges).enumerate() {
+        let entries = slots
+            .iter()
+            .map(|slot| {
+                let value = U256::decode(&mut &slot.data[..])?;
+                Ok(StorageEntry { key: slot.hash, value })
+            })
+            .collect::<alloy_rlp::Result<Vec<_>>>()
+            .ok()?;
+        let leaves = slots.iter().map(|slot| (slot.hash, slot.data.to_vec())).collect::<Vec<_>>();
+
+        // Only the last storage may be incomplete, which is proven.
+        let proof = if idx + 1 == ranges.len() { proof } else { &[] };
+        match verify_range_proof(task.storage_root, task.origin, &leaves, proof) {
+            Ok(has_more) => verified.push((*task, entries, has_more)),
+            // The storage changed since the account was downloaded, it is healed.
+            Err(_) if task.state_root != state_root => {
+                trace!(target: "sync::snap", hashed_address=?task.hashed_address, "Skipping changed storage");
+                verified.push((*task, Vec::new(), false))
+            }
+            Err(_) => return None,
+        }
+    }
+    Some(verified)
+}
+
+/// Pushes the given tasks back to the front of the queue, in order.
+fn requeue<T>(queue: &mut VecDeque<T>, tasks: impl IntoIterator<Item = T>) {
+    let tasks = tasks.into_iter().collect::<Vec<_>>();
+    for task in tasks.into_iter().rev() {
+        queue.push_front(task);
+    }
+}
+
+/// A trie node to heal.
+#[derive(Debug, Clone)]
+struct HealTask {
+    /// The hashed address of the account of a storage trie node, or `None` for an account trie
+    /// node.
+    owner: Option<B256>,
+    /// The path of the node.
+    path: Nibbles,
+    /// The hash of the node.
+    hash: B256,
+}
+
+impl HealTask {
+    /// Creates a task for a node of the account trie.
+    const fn account(path: Nibbles, hash: B256) -> Self {
+        Self { owner: None, path, hash }
+    }
+
+    /// Returns the compact encoded paths to request the node with.
+    fn request_paths(&self) -> Vec<Bytes> {
+        let path = Bytes::copy_from_slice(&self.path.encode_path_leaf(false));
+        match self.owner {
+            None => vec![path],
+            Some(owner) => {
+                let account_path = Nibbles::unpack(owner).encode_path_leaf(false);
+                vec![Bytes::copy_from_slice(&account_path), path]
+            }
+        }
+    }
+}
+
+/// The keys changed while healing.
+#[derive(Debug, Default)]
+struct HealChanges {
+    /// The changed accounts.
+    accounts: PrefixSetMut,
+    /// The changed storage slots by account.
+    storages: HashMap<B256, PrefixSetMut>,
+    /// The removed accounts.
+    destroyed_accounts: HashSet<B256>,
+    /// The code hashes of healed accounts that are missing locally.
+    codes: HashSet<B256>,
+}
+
+impl HealChanges {
+    /// Marks a changed account.
+    fn account(&mut self, hashed_address: B256) {
+        self.accounts.insert(Nibbles::unpack(hashed_address));
+    }
+
+    /// Marks a changed storage slot.
+    fn slot(&mut self, hashed_address: B256, hashed_slot: B256) {
+        self.account(hashed_address);
+        self.storages.entry(hashed_address).or_default().insert(Nibbles::unpack(hashed_slot));
+    }
+
+    /// Merges the given changes.
+    fn extend(&mut self, other: Self) {
+        for key in other.accounts.freeze().iter() {
+            self.accounts.insert(key.clone());
+        }
+        for (hashed_address, slots) in other.storages {
+            let storage = self.storages.entry(hashed_address).or_default();
+            for key in slots.freeze().iter() {
+                storage.insert(key.clone());
+            }
+        }
+        self.destroyed_accounts.extend(other.destroyed_accounts);
+        self.codes.extend(other.codes);
+    }
+
+    /// Returns the prefix sets to recompute the trie with.
+    fn into_prefix_sets(self) -> TriePrefixSets {
+        TriePrefixSets {
+            account_prefix_set: self.accounts.freeze(),
+            storage_prefix_sets: self
+                .storages
+                .into_iter()
+                .map(|(hashed_address, slots)| (hashed_address, slots.freeze()))
+                .collect(),
+            destroyed_accounts: self.destroyed_accounts,
+        }
+    }
+}
+
+/// Errors while applying a healed node.
+#[derive(Debug)]
+enum HealError {
+    /// The node or one of its embedded nodes is invalid.
+    InvalidNode(alloy_rlp::Error),
+    /// Failed to write the node.
+    Database(DatabaseError),
+}
+
+impl From<DatabaseError> for HealError {
+    fn from(err: DatabaseError) -> Self {
+        Self::Database(err)
+    }
+}
+
+impl From<alloy_rlp::Error> for HealError {
+    fn from(err: alloy_rlp::Error) -> Self {
+        Self::InvalidNode(err)
+    }
+}
+
+/// Returns the hash of the local trie node at the path of the given task, if any.
+fn local_node_hash<TX: DbTx>(tx: &TX, task: &HealTask) -> Result<Option<B256>, DatabaseError> {
+    let proof = Proof::new(tx);
+    let targets = vec![task.path.clone()];
+    let (_, nodes) = match task.owner {
+        None => proof.account_trie_nodes(targets).map_err(Into::<DatabaseError>::into)?,
+        Some(owner) => {
+            proof.storage_trie_nodes(owner, targets).map_err(Into::<DatabaseError>::into)?
+        }
+    };
+    Ok(nodes.get(&task.path).map(keccak256))
+}
+
+/// Applies the given node of the trie of `owner` at the given path to the local state.
+///
+/// Leaves are written, local leaves that are not part of the node are removed and the children
+/// referenced by hash are queued.
+fn heal_node<TX: DbTx + DbTxMut>(
+    tx: &TX,
+    owner: Option<B256>,
+    path: Nibbles,
+    node: TrieNode,
+    queue: &mut VecDeque<HealTask>,
+    changes: &mut HealChanges,
+) -> Result<(), HealError> {
+    match node {
+        TrieNode::Branch(children) => {
+            for (nibble, child) in children.into_iter().enumerate() {
+                let mut child_path = path.clone();
+                child_path.push(nibble as u8);
+                heal_child(tx, owner, child_path, child, queue, changes)?;
+            }
+        }
+        TrieNode::Extension { key, child } => {
+            let mut child_path = path.clone();
+            child_path.extend_from_slice(&key);
+            remove_leaves(tx, owner, &path, Some(&child_path), changes)?;
+            heal_child(tx, owner, child_path, child, queue, changes)?;
+        }
+        TrieNode::Leaf { key, value } => {
+            let mut full_path = path.clone();
+            full_path.extend_from_slice(&key);
+            remove_leaves(tx, owner, &path, Some(&full_path), changes)?;
+            if full_path.len() != 64 {
+                return Err(HealError::InvalidNode(alloy_rlp::Error::Custom("invalid leaf path")))
+            }
+            let key = B256::from_slice(&full_path.pack());
+            match owner {
+                None => {
+                    heal_account(tx, key, SlimAccount::decode(&mut &value[..])?, queue, changes)?
+                }
+                Some(owner) => {
+                    let value = U256::decode(&mut &value[..])?;
+                    write_slot(tx, owner, StorageEntry { key, value })?;
+                    changes.slot(owner, key);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Applies the child of a node at the given path.
+fn heal_child<TX: DbTx + DbTxMut>(
+    tx: &TX,
+    owner: Option<B256>,
+    path: Nibbles,
+    child: ChildRef,
+    queue: &mut VecDeque<HealTask>,
+    changes: &mut HealChanges,
+) -> Result<(), HealError> {
+    match child {
+        ChildRef::Empty => remove_leaves(tx, owner, &path, None, changes)?,
+        ChildRef::Hash(hash) => queue.push_back(HealTask { owner, path, hash }),
+        ChildRef::Inline(node) => {
+            heal_node(tx, owner, path, TrieNode::decode(&node)?, queue, changes)?
+        }
+    }
+    Ok(())
+}
+
+/// Writes a healed account and queues its storage if it differs from the local storage.
+fn heal_account<TX: DbTx + DbTxMut>(
+    tx: &TX,
+    hashed_address: B256,
+    account: SlimAccount,
+    queue: &mut VecDeque<HealTask>,
+    changes: &mut HealChanges,
+) -> Result<(), HealError> {
+    tx.put::<tables::HashedAccount>(hashed_address, account.account())?;
+    changes.account(hashed_address);
+
+    let storage_root = StorageRoot::from_tx_hashed(tx, hashed_address)
+        .root()
+        .map_err(Into::<DatabaseError>::into)?;
+    if storage_root != account.storage_root {
+        if account.storage_root == EMPTY_ROOT_HASH {
+            remove_slots(tx, hashed_address, &Nibbles::default(), None, changes)?;
+        } else {
+            queue.push_back(HealTask {
+                owner: Some(hashed_address),
+                path: Nibbles::default(),
+                hash: account.storage_root,
+            });
+        }
+    }
+
+    if account.code_hash != KECCAK_EMPTY &&
+        tx.get::<tables::Bytecodes>(account.code_hash)?.is_none()
+    {
+        changes.codes.insert(account.code_hash);
+    }
+    Ok(())
+}
+
+/// Writes a storage slot, replacing the previous value.
+fn write_slot<TX: DbTxMut>(
+    tx: &TX,
+    hashed_address: B256,
+    entry: StorageEntry,
+) -> Result<(), DatabaseError> {
+    let mut cursor = tx.cursor_dup_write::<tables::HashedStorage>()?;
+    if cursor
+        .seek_by_key_subkey(hashed_address, entry.key)?
+        .filter(|existing| existing.key == entry.key)
+        .is_some()
+    {
+        cursor.delete_current()?;
+    }
+    if entry.value != U256::ZERO {
+        cursor.upsert(hashed_address, entry)?;
+    }
+    Ok(())
+}
+
+/// Removes the local leaves below the given prefix of the trie of `owner`, except the leaves below
+/// `keep`.
+fn remove_leaves<TX: DbTx + DbTxMut>(
+    tx: &TX,
+    owner: Option<B256>,
+    prefix: &Nibbles,
+    keep: Option<&Nibbles>,
+    changes: &mut HealChanges,
+) -> Result<(), DatabaseError> {
+    let Some(owner) = owner else { return remove_accounts(tx, prefix, keep, changes) };
+    remove_slots(tx, owner, prefix, keep, changes)
+}
+
+/// Removes the accounts and their storage below the given prefix, except the accounts below
+/// `keep`.
+fn remove_accounts<TX: DbTx + DbTxMut>(
+    tx: &TX,
+    prefix: &Nibbles,
+    keep: Option<&Nibbles>,
+    changes: &mut HealChanges,
+) -> Result<(), DatabaseError> {
+    let mut cursor = tx.cursor_write::<tables::HashedAccount>()?;
+    let removed = cursor
+        .walk(Some(lowest_key(prefix)))?
+        .map(|entry| entry.map(|(key, _)| key))
+        .take_while(|key| key.as_ref().map_or(true, |key| Nibbles::unpack(key).starts_with(prefix)))
+        .filter(|key| key.as_ref().map_or(true, |key| !is_kept(key, keep)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut storage_cursor = tx.cursor_dup_write::<tables::HashedStorage>()?;
+    for hashed_address in removed {
+        if cursor.seek_exact(hashed_address)?.is_some() {
+            cursor.delete_current()?;
+        }
+        if storage_cursor.seek_exact(hashed_address)?.is_some() {
+            storage_cursor.delete_current_duplicates()?;
+        }
+        changes.account(hashed_address);
+        changes.destroyed_accounts.insert(hashed_address);
+    }
+    Ok(())
+}
+
+/// Removes the storage slots of an account below the given prefix, except the slots below `keep`.
+fn remove_slots<TX: DbTx + DbTxMut>(
+    tx: &TX,
+    hashed_address: B256,
+    prefix: &Nibbles,
+    keep: Option<&Nibbles>,
+    changes: &mut HealChanges,
+) -> Result<(), DatabaseError> {
+    let mut cursor = tx.cursor_dup_write::<tables::HashedStorage>()?;
+    let mut removed = Vec::new();
+    let mut entry = cursor.seek_by_key_subkey(hashed_address, lowest_key(prefix))?;
+    while let Some(slot) = entry {
+        if !Nibbles::unpack(slot.key).starts_with(prefix) {
+            break
+        }
+        if !is_kept(&slot.key, keep) {
+            removed.push(slot.key);
+        }
+        entry = cursor.next_dup_val()?;
+    }
+
+    for key in removed {
+        if cursor.seek_by_key_subkey(hashed_address, key)?.filter(|slot| slot.key == key).is_some()
+        {
+            cursor.delete_current()?;
+        }
+        changes.slot(hashed_address, key);
+    }
+    Ok(())
+}
+
+/// Returns true if the key is below the kept prefix.
+fn is_kept(key: &B256, keep: Option<&Nibbles>) -> bool {
+    keep.is_some_and(|keep| Nibbles::unpack(key).starts_with(keep))
+}
+
+/// Returns the lowest key with the given prefix.
+fn lowest_key(prefix: &Nibbles) -> B256 {
+    let mut nibbles = prefix.as_slice().to_vec();
+    nibbles.resize(64, 0);
+    B256::from_slice(&Nibbles::from_nibbles_unchecked(nibbles).pack())
+}
+
+/// Returns the hash following the given hash, if any.
+fn increment(hash: B256) -> Option<B256> {
+    U256::from_be_bytes(hash.0)
+        .checked_add(U256::from(1))
+        .map(|next| B256::from(next.to_be_bytes()))
+}
@@ -0,0 +1,192 @@
+//! Syncing state between two peers over `snap/1`.
+
+use reth_db::{
+    cursor::DbCursorRO,
+    database::Database,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_net_snap::{
+    IncomingSnapRequest, SnapEvent, SnapProtocolHandler, SnapRequestHandler, SnapSync,
+    SnapSyncConfig,
+};
+use reth_network::test_utils::Testnet;
+use reth_primitives::{
+    keccak256,
+    stage::{StageCheckpoint, StageId},
+    Account, Bytecode, Bytes, Header, SealedHeader, StorageEntry, B256, U256,
+};
+use reth_provider::{
+    test_utils::{create_test_provider_factory, MockEthProvider},
+    ProviderFactory, StageCheckpointWriter,
+};
+use reth_trie::StateRoot;
+use tokio::sync::{mpsc, watch};
+
+/// Writes accounts with storage and bytecode and the header of their state root.
+///
+/// The changed state removes, adds and modifies accounts and storage slots of the initial state.
+fn insert_state<DB: Database>(factory: &ProviderFactory<DB>, changed: bool) -> SealedHeader {
+    let provider = factory.provider_rw().unwrap();
+    let tx = provider.tx_ref();
+
+    let code = Bytes::from_static(&[0x60, 0x00, 0x60, 0x00, 0xf3]);
+    let code_hash = keccak256(&code);
+    tx.put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(code)).unwrap();
+
+    let accounts = if changed { 1..=11u64 } else { 1..=10u64 };
+    for idx in accounts.filter(|idx| !changed || *idx != 5) {
+        let hashed_address = keccak256(B256::with_last_byte(idx as u8));
+        let bytecode_hash = (idx % 2 == 0).then_some(code_hash);
+        let balance = if changed && idx % 2 == 0 { idx * 2 } else { idx };
+        let account = Account { nonce: idx, balance: U256::from(balance), bytecode_hash };
+        tx.put::<tables::HashedAccount>(hashed_address, account).unwrap();
+
+        if idx % 3 == 0 {
+            let slots = if changed && idx == 9 { 1..=idx + 1 } else { 1..=idx };
+            for slot in slots {
+                let value = if changed && idx == 6 { slot + 1 } else { slot };
+                let entry = StorageEntry {
+                    key: keccak256(B256::with_last_byte(slot as u8)),
+                    value: U256::from(value),
+                };
+                tx.put::<tables::HashedStorage>(hashed_address, entry).unwrap();
+            }
+        }
+    }
+
+    let (root, updates) = StateRoot::from_tx(tx).root_with_updates().unwrap();
+    updates.flush(tx).unwrap();
+    let header = Header { number: 1, state_root: root, ..Default::default() };
+    tx.put::<tables::Headers>(1, header.clone()).unwrap();
+    provider.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(1)).unwrap();
+    provider.commit().unwrap();
+    header.seal_slow()
+}
+
+/// Asserts that the given tables of both databases have the same entries.
+macro_rules! assert_tables_eq {
+    ($left:expr, $right:expr, $($table:ident),+) => {
+        let left = $left.provider().unwrap();
+        let right = $right.provider().unwrap();
+        $(
+            let left_entries = left
+                .tx_ref()
+                .cursor_read::<tables::$table>()
+                .unwrap()
+                .walk(None)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            let right_entries = right
+                .tx_ref()
+                .cursor_read::<tables::$table>()
+                .unwrap()
+                .walk(None)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(left_entries, right_entries);
+        )+
+    };
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_snap_sync() {
+    reth_tracing::init_test_tracing();
+
+    let server_factory = create_test_provider_factory();
+    let pivot = insert_state(&server_factory, false);
+
+    let mut net = Testnet::create_with(2, MockEthProvider::default()).await;
+
+    let (to_server, from_peers) = mpsc::channel(1024);
+    tokio::spawn(SnapRequestHandler::new(server_factory.clone(), from_peers));
+    let (tx, _from_peer0) = mpsc::unbounded_channel();
+    net.peers_mut()[0]
+        .add_rlpx_sub_protocol(SnapProtocolHandler::new(tx).with_request_handler(to_server));
+
+    let (tx, mut from_peer1) = mpsc::unbounded_channel();
+    net.peers_mut()[1].add_rlpx_sub_protocol(SnapProtocolHandler::new(tx));
+
+    let handle = net.spawn();
+    handle.connect_peers().await;
+
+    let SnapEvent::Established { peer_id, handle: peer0, .. } = from_peer1.recv().await.unwrap();
+    assert_eq!(peer_id, *handle.peers()[0].peer_id());
+
+    // Serve a single account or storage slot per response to download the state in ranges.
+    let client_factory = create_test_provider_factory();
+    let config = SnapSyncConfig { response_bytes: 1, ..Default::default() };
+    let (_pivot_tx, pivot_rx) = watch::channel(pivot.clone());
+    let mut sync = SnapSync::new(client_factory.clone(), pivot_rx, config);
+    sync.add_peer(peer0);
+    assert_eq!(sync.run().await.unwrap(), pivot);
+
+    assert_tables_eq!(server_factory, client_factory, HashedAccount, HashedStorage, Bytecodes);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_snap_sync_moved_pivot() {
+    reth_tracing::init_test_tracing();
+
+    let old_factory = create_test_provider_factory();
+    let old_pivot = insert_state(&old_factory, false);
+    let new_factory = create_test_provider_factory();
+    let new_pivot = insert_state(&new_factory, true);
+    let expected = new_pivot.clone();
+    let (pivot_tx, pivot_rx) = watch::channel(old_pivot);
+
+    let mut net = Testnet::create_with(3, MockEthProvider::default()).await;
+
+    // The pivot moves on once the accounts are downloaded from the peer serving the old state.
+    let (to_proxy, mut from_peers) = mpsc::channel(1024);
+    let (to_server, from_proxy) = mpsc::channel(1024);
+    tokio::spawn(SnapRequestHandler::new(old_factory, from_proxy));
+    tokio::spawn(async move {
+        while let Some(request) = from_peers.recv().await {
+            if matches!(request, IncomingSnapRequest::GetStorageRanges { .. }) {
+                pivot_tx.send_replace(new_pivot.clone());
+            }
+            to_server.send(request).await.unwrap();
+        }
+    });
+    let (tx, _from_peer0) = mpsc::unbounded_channel();
+    net.peers_mut()[0]
+        .add_rlpx_sub_protocol(SnapProtocolHandler::new(tx).with_request_handler(to_proxy));
+
+    let (to_server, from_peers) = mpsc::channel(1024);
+    tokio::spawn(SnapRequestHandler::new(new_factory.clone(), from_peers));
+    let (tx, _from_peer1) = mpsc::unbounded_channel();
+    net.peers_mut()[1]
+        .add_rlpx_sub_protocol(SnapProtocolHandler::new(tx).with_request_handler(to_server));
+
+    let (tx, mut from_peer2) = mpsc::unbounded_channel();
+    net.peers_mut()[2].add_rlpx_sub_protocol(SnapProtocolHandler::new(tx));
+
+    let handle = net.spawn();
+    handle.connect_peers().await;
+
+    // Only the peer serving the old state is known upfront, the other one is picked up from the
+    // events once the old state is no longer served.
+    let old_peer = *handle.peers()[0].peer_id();
+    let (events_tx, events) = mpsc::unbounded_channel();
+    let mut old_handle = None;
+    for _ in 0..2 {
+        let event = from_peer2.recv().await.unwrap();
+        let SnapEvent::Established { peer_id, handle, .. } = &event;
+        if *peer_id == old_peer {
+            old_handle = Some(handle.clone());
+        } else {
+            events_tx.send(event).unwrap();
+        }
+    }
+
+    let client_factory = create_test_provider_factory();
+    let config = SnapSyncConfig { response_bytes: 1, ..Default::default() };
+    let mut sync = SnapSync::new(client_factory.clone(), pivot_rx, config).with_events(events);
+    sync.add_peer(old_handle.unwrap());
+    assert_eq!(sync.run().await.unwrap(), expected);
+
+    assert_tables_eq!(new_factory, client_factory, HashedAccount, HashedStorage, Bytecodes);
+}
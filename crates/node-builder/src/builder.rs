@@ -47,9 +47,9 @@ use reth_revm::EvmProcessorFactory;
 use reth_rpc_engine_api::EngineApi;
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::{debug, info};
-use reth_transaction_pool::{PoolConfig, TransactionPool};
+use reth_transaction_pool::{validate::AdmissionPolicyConfig, PoolConfig, TransactionPool};
 use std::sync::Arc;
-use tokio::sync::{mpsc::unbounded_channel, oneshot};
+use tokio::sync::{mpsc::unbounded_channel, oneshot, watch};
 
 /// The builtin provider type of the reth node.
 // Note: we need to hardcode this because custom components might depend on it in associated types.
@@ -357,8 +357,10 @@ where
         let snapshotter = reth_snapshot::Snapshotter::new(
             provider_factory.clone(),
             data_dir.snapshots_path(),
-            config.chain.snapshot_block_interval,
-        )?;
+            reth_config.snapshots.block_interval.unwrap_or(config.chain.snapshot_block_interval),
+        )?
+        .with_segment_configs(reth_config.snapshots.segment_configs())
+        .with_bytecodes(reth_config.snapshots.snapshot_bytecodes);
 
         provider_factory = provider_factory
             .with_snapshots(data_dir.snapshots_path(), snapshotter.highest_snapshot_receiver())?;
@@ -438,6 +440,10 @@ where
 
         // create pipeline
         let network_client = network.fetch_client().await?;
+        let (snap_pivot_tx, snap_pivot_rx) = watch::channel(config.chain.sealed_genesis_header());
+        let snap_sync =
+            config.install_snap(&network, provider_factory.clone(), &executor, snap_pivot_rx)?;
+        let snap_syncing = snap_sync.is_some();
         let (consensus_engine_tx, consensus_engine_rx) = unbounded_channel();
         let max_block = config.max_block(&network_client, provider_factory.clone()).await?;
 
@@ -468,6 +474,7 @@ where
                     prune_config.clone(),
                     max_block,
                     evm_config,
+                    None,
                 )
                 .await?;
 
@@ -489,10 +496,11 @@ where
                     prune_config.clone(),
                     max_block,
                     evm_config,
+                    snap_sync,
                 )
                 .await?;
 
-            (pipeline, EitherDownloader::Right(network_client))
+            (pipeline, EitherDownloader::Right(network_client.clone()))
         };
 
         let pipeline_events = pipeline.events();
@@ -533,6 +541,15 @@ where
         )?;
         info!(target: "reth::cli", "Consensus engine initialized");
 
+        if snap_syncing {
+            config.spawn_snap_pivot(
+                network_client,
+                beacon_engine_handle.event_listener(),
+                snap_pivot_tx,
+                &executor,
+            );
+        }
+
         let events = stream_select!(
             network.event_listener().map(Into::into),
             beacon_engine_handle.event_listener().map(Into::into),
@@ -579,6 +596,7 @@ where
             node_components.clone(),
             engine_api,
             &config,
+            &data_dir,
             jwt_secret,
             rpc,
         )
@@ -689,6 +707,11 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         self.config().txpool.pool_config()
     }
 
+    /// Returns the configuration of the transaction pool's admission policies.
+    pub fn admission_policy_config(&self) -> AdmissionPolicyConfig {
+        self.config().admission_policy_config(&self.reth_config.txpool)
+    }
+
     /// Loads the trusted setup params from a given file path or falls back to
     /// `MAINNET_KZG_TRUSTED_SETUP`.
     pub fn kzg_settings(&self) -> eyre::Result<Arc<KzgSettings>> {
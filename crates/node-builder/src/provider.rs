@@ -1,9 +1,11 @@
 //! Helper provider traits to encapsulate all provider traits for simplicity.
 
 use reth_db::database::Database;
+use reth_interfaces::blockchain_tree::BlockchainTreeEngine;
 use reth_provider::{
-    AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    DatabaseProviderFactory, EvmEnvProvider, StateProviderFactory,
+    AccountReader, BadBlockProvider, BlockReaderIdExt, BlockchainTreePendingStateProvider,
+    CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
+    EvmEnvProvider, HistoryReader, NodeDataProvider, StateProviderFactory,
 };
 
 /// Helper trait to unify all provider traits for simplicity.
@@ -15,6 +17,11 @@ pub trait FullProvider<DB: Database>:
     + EvmEnvProvider
     + ChainSpecProvider
     + ChangeSetReader
+    + HistoryReader
+    + NodeDataProvider
+    + BlockchainTreePendingStateProvider
+    + BadBlockProvider
+    + BlockchainTreeEngine
     + CanonStateSubscriptions
     + Clone
     + Unpin
@@ -30,6 +37,11 @@ impl<T, DB: Database> FullProvider<DB> for T where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + HistoryReader
+        + NodeDataProvider
+        + BlockchainTreePendingStateProvider
+        + BadBlockProvider
+        + BlockchainTreeEngine
         + CanonStateSubscriptions
         + Clone
         + Unpin
@@ -5,12 +5,14 @@ use futures::TryFutureExt;
 use reth_network::NetworkHandle;
 use reth_node_core::{
     cli::config::RethRpcConfig,
+    dirs::{ChainPath, DataDirPath},
     node_config::NodeConfig,
     rpc::{
         api::EngineApiServer,
         builder::{
             auth::{AuthRpcModule, AuthServerHandle},
-            RethModuleRegistry, RpcModuleBuilder, RpcServerHandle, TransportRpcModules,
+            RethModuleRegistry, RpcModuleBuilder, RpcModuleConfig, RpcServerHandle,
+            TransportRpcModules,
         },
     },
 };
@@ -230,6 +232,7 @@ pub(crate) async fn launch_rpc_servers<Node, Engine>(
     node: Node,
     engine_api: Engine,
     config: &NodeConfig,
+    data_dir: &ChainPath<DataDirPath>,
     jwt_secret: JwtSecret,
     hooks: RpcHooks<Node>,
 ) -> eyre::Result<(RethRpcServerHandles, RpcRegistry<Node>)>
@@ -240,7 +243,13 @@ where
     let RpcHooks { on_rpc_started, extend_rpc_modules } = hooks;
 
     let auth_config = config.rpc.auth_server_config(jwt_secret)?;
-    let module_config = config.rpc.transport_rpc_module_config();
+    // trace files of the `debug` namespace are written into the datadir
+    let module_config = config.rpc.transport_rpc_module_config().with_config(
+        RpcModuleConfig::builder()
+            .eth(config.rpc.eth_config())
+            .trace_dir(data_dir.traces_path())
+            .build(),
+    );
     debug!(target: "reth::cli", http=?module_config.http(), ws=?module_config.ws(), "Using RPC module config");
 
     let (mut modules, mut auth_module, registry) = RpcModuleBuilder::default()
@@ -4,6 +4,7 @@ use crate::version::P2P_CLIENT_VERSION;
 use clap::Args;
 use reth_config::Config;
 use reth_discv4::{DEFAULT_DISCOVERY_ADDR, DEFAULT_DISCOVERY_PORT};
+use reth_discv5::{discv5::Enr, Discv5Config, DEFAULT_DISCOVERY_V5_PORT};
 use reth_net_nat::NatResolver;
 use reth_network::{
     transactions::{
@@ -15,7 +16,11 @@ use reth_network::{
 };
 use reth_primitives::{mainnet_nodes, ChainSpec, NodeRecord};
 use secp256k1::SecretKey;
-use std::{net::Ipv4Addr, path::PathBuf, sync::Arc};
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    path::PathBuf,
+    sync::Arc,
+};
 
 /// Parameters for configuring the network more granularity via CLI
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
@@ -97,6 +102,17 @@ pub struct NetworkArgs {
     /// is 128 KiB.
     #[arg(long = "pooled-tx-pack-soft-limit", value_name = "BYTES", default_value_t = DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE_ON_PACK_GET_POOLED_TRANSACTIONS_REQUEST)]
     pub soft_limit_byte_size_pooled_transactions_response_on_pack_request: usize,
+
+    /// Serve the state of the latest block to `snap/1` peers.
+    #[arg(long = "snap.serve")]
+    pub snap_serve: bool,
+
+    /// Download the state of a recent block from `snap/1` peers on a fresh node, instead of
+    /// executing all blocks from genesis.
+    ///
+    /// The senders, receipts and history of the blocks before that block are not available.
+    #[arg(long = "snap.sync")]
+    pub snap_sync: bool,
 }
 
 impl NetworkArgs {
@@ -191,6 +207,8 @@ impl Default for NetworkArgs {
             soft_limit_byte_size_pooled_transactions_response:
                 SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
             soft_limit_byte_size_pooled_transactions_response_on_pack_request: DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE_ON_PACK_GET_POOLED_TRANSACTIONS_REQUEST,
+            snap_serve: false,
+            snap_sync: false,
         }
     }
 }
@@ -217,6 +235,34 @@ pub struct DiscoveryArgs {
     /// The UDP port to use for P2P discovery/networking
     #[arg(long = "discovery.port", name = "discovery.port", value_name = "DISCOVERY_PORT", default_value_t = DEFAULT_DISCOVERY_PORT)]
     pub port: u16,
+
+    /// Enable Discv5 discovery alongside Discv4.
+    ///
+    /// Discovered nodes must advertise the fork id of the chain, under the `opstack` ENR key for
+    /// OP stack chains and under the `eth` key otherwise.
+    #[arg(long, conflicts_with = "disable_discovery")]
+    pub enable_discv5_discovery: bool,
+
+    /// The UDP address to use for Discv5
+    #[arg(long = "discovery.v5.addr", name = "discovery.v5.addr", value_name = "DISCOVERY_V5_ADDR", default_value_t = Ipv4Addr::UNSPECIFIED)]
+    pub discv5_addr: Ipv4Addr,
+
+    /// The UDP port to use for Discv5
+    #[arg(long = "discovery.v5.port", name = "discovery.v5.port", value_name = "DISCOVERY_V5_PORT", default_value_t = DEFAULT_DISCOVERY_V5_PORT)]
+    pub discv5_port: u16,
+
+    /// Comma separated ENRs for Discv5 bootstrap.
+    ///
+    /// --discovery.v5.bootnodes enr:-abcd
+    ///
+    /// Enables Discv5 if specified.
+    #[arg(
+        long = "discovery.v5.bootnodes",
+        value_name = "ENRS",
+        value_delimiter = ',',
+        conflicts_with = "disable_discovery"
+    )]
+    pub discv5_bootnodes: Vec<Enr>,
 }
 
 impl DiscoveryArgs {
@@ -232,6 +278,16 @@ impl DiscoveryArgs {
         if self.disable_discovery || self.disable_discv4_discovery {
             network_config_builder = network_config_builder.disable_discv4_discovery();
         }
+
+        if !self.disable_discovery &&
+            (self.enable_discv5_discovery || !self.discv5_bootnodes.is_empty())
+        {
+            let mut discv5_builder = Discv5Config::builder();
+            discv5_builder
+                .listen_addr(SocketAddr::new(self.discv5_addr.into(), self.discv5_port))
+                .add_boot_nodes(self.discv5_bootnodes.iter().cloned());
+            network_config_builder = network_config_builder.discovery_v5(discv5_builder);
+        }
         network_config_builder
     }
 
@@ -239,6 +295,7 @@ impl DiscoveryArgs {
     /// discovery binds to the socket.
     pub fn with_unused_discovery_port(mut self) -> Self {
         self.port = 0;
+        self.discv5_port = 0;
         self
     }
 }
@@ -251,6 +308,10 @@ impl Default for DiscoveryArgs {
             disable_discv4_discovery: false,
             addr: DEFAULT_DISCOVERY_ADDR,
             port: DEFAULT_DISCOVERY_PORT,
+            enable_discv5_discovery: false,
+            discv5_addr: Ipv4Addr::UNSPECIFIED,
+            discv5_port: DEFAULT_DISCOVERY_V5_PORT,
+            discv5_bootnodes: Vec::new(),
         }
     }
 }
@@ -314,6 +375,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_snap_args() {
+        let args = CommandParser::<NetworkArgs>::parse_from(["reth", "--snap.serve"]).args;
+        assert!(args.snap_serve);
+        assert!(!args.snap_sync);
+
+        let args = CommandParser::<NetworkArgs>::parse_from(["reth", "--snap.sync"]).args;
+        assert!(args.snap_sync);
+    }
+
+    #[test]
+    fn parse_discv5_args() {
+        let args = CommandParser::<NetworkArgs>::parse_from(["reth"]).args;
+        assert!(!args.discovery.enable_discv5_discovery);
+        assert!(args.discovery.discv5_bootnodes.is_empty());
+
+        let enr = "enr:-HW4QES8QIeXTYlDzbfr1WEzE-XKY4f8gJFJzjJL-9D7TC9lJb4Z3JPRRz1lP4pL_N_QpT6rGQjAU9Apnc-C1iMP36OAgmlkgnY0iXNlY3AyNTZrMaED5IdwfMxdmR8W37HqSFdQLjDkIwBd4Q_MjxgZifgKSdM";
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--discovery.v5.port",
+            "9001",
+            "--discovery.v5.bootnodes",
+            enr,
+        ])
+        .args;
+        assert_eq!(args.discovery.discv5_port, 9001);
+        assert_eq!(args.discovery.discv5_bootnodes, vec![enr.parse::<Enr>().unwrap()]);
+    }
+
     #[test]
     fn network_args_default_sanity_test() {
         let default_args = NetworkArgs::default();
@@ -18,14 +18,19 @@ use clap::{
 };
 use futures::TryFutureExt;
 use rand::Rng;
+use reth_interfaces::blockchain_tree::BlockchainTreeEngine;
 use reth_network_api::{NetworkInfo, Peers};
 use reth_node_api::{ConfigureEvmEnv, EngineTypes};
 use reth_provider::{
-    AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    EvmEnvProvider, HeaderProvider, StateProviderFactory,
+    AccountReader, BadBlockProvider, BlockReaderIdExt, BlockchainTreePendingStateProvider,
+    CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider,
+    HistoryReader, StateProviderFactory,
 };
 use reth_rpc::{
-    eth::{cache::EthStateCacheConfig, gas_oracle::GasPriceOracleConfig, RPC_DEFAULT_GAS_CAP},
+    eth::{
+        cache::EthStateCacheConfig, gas_oracle::GasPriceOracleConfig, DEFAULT_ETH_PROOF_WINDOW,
+        MAX_ETH_PROOF_WINDOW, RPC_DEFAULT_GAS_CAP,
+    },
     JwtError, JwtSecret,
 };
 use reth_rpc_builder::{
@@ -174,6 +179,15 @@ pub struct RpcServerArgs {
     )]
     pub rpc_gas_cap: u64,
 
+    /// The maximum number of blocks before the latest block for which `eth_getProof` is served.
+    #[arg(
+        long,
+        value_name = "BLOCKS",
+        value_parser = RangedU64ValueParser::<u64>::new().range(..=MAX_ETH_PROOF_WINDOW),
+        default_value_t = DEFAULT_ETH_PROOF_WINDOW
+    )]
+    pub rpc_eth_proof_window: u64,
+
     /// State cache configuration.
     #[clap(flatten)]
     pub rpc_state_cache: RpcStateCacheArgs,
@@ -361,6 +375,10 @@ impl RpcServerArgs {
             + EvmEnvProvider
             + ChainSpecProvider
             + ChangeSetReader
+            + HistoryReader
+            + BlockchainTreePendingStateProvider
+            + BadBlockProvider
+            + BlockchainTreeEngine
             + Clone
             + Unpin
             + 'static,
@@ -442,6 +460,7 @@ impl RethRpcConfig for RpcServerArgs {
             .max_blocks_per_filter(self.rpc_max_blocks_per_filter.unwrap_or_max())
             .max_logs_per_response(self.rpc_max_logs_per_response.unwrap_or_max() as usize)
             .rpc_gas_cap(self.rpc_gas_cap)
+            .eth_proof_window(self.rpc_eth_proof_window)
             .state_cache(self.state_cache_config())
             .gpo_config(self.gas_price_oracle_config())
     }
@@ -583,6 +602,7 @@ impl Default for RpcServerArgs {
             rpc_max_blocks_per_filter: constants::DEFAULT_MAX_BLOCKS_PER_FILTER.into(),
             rpc_max_logs_per_response: (constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64).into(),
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
+            rpc_eth_proof_window: DEFAULT_ETH_PROOF_WINDOW,
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
         }
@@ -650,6 +670,25 @@ mod tests {
         assert!(args.is_err());
     }
 
+    #[test]
+    fn test_rpc_eth_proof_window() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.eth_config().eth_proof_window, DEFAULT_ETH_PROOF_WINDOW);
+
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--rpc-eth-proof-window", "128"])
+                .args;
+        assert_eq!(args.eth_config().eth_proof_window, 128);
+
+        let window = (MAX_ETH_PROOF_WINDOW + 1).to_string();
+        let args = CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc-eth-proof-window",
+            window.as_str(),
+        ]);
+        assert!(args.is_err());
+    }
+
     #[test]
     fn test_rpc_server_args_parser() {
         let args =
@@ -2,10 +2,12 @@
 
 use crate::cli::config::RethTransactionPoolConfig;
 use clap::Args;
-use reth_primitives::Address;
+use reth_primitives::{Address, Selector};
 use reth_transaction_pool::{
-    validate::DEFAULT_MAX_TX_INPUT_BYTES, LocalTransactionConfig, PoolConfig, PriceBumpConfig,
-    SubPoolLimit, DEFAULT_PRICE_BUMP, REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+    validate::{AddressList, AdmissionPolicyConfig, DEFAULT_MAX_TX_INPUT_BYTES},
+    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SenderShareConfig, SubPoolLimit,
+    DEFAULT_PRICE_BUMP, REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+    TXPOOL_MAX_LOCAL_SENDER_SHARE_DEFAULT, TXPOOL_MAX_SENDER_SHARE_DEFAULT,
     TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
 /// Parameters for debugging purposes
@@ -37,6 +39,18 @@ pub struct TxPoolArgs {
     #[arg(long = "txpool.max_account_slots", default_value_t = TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER)]
     pub max_account_slots: usize,
 
+    /// Max share (in %) of a sub-pool's transaction limit a single sender may occupy before its
+    /// transactions are evicted first.
+    #[arg(long = "txpool.max_sender_share", default_value_t = TXPOOL_MAX_SENDER_SHARE_DEFAULT)]
+    pub max_sender_share: usize,
+    /// Max share (in %) of a sub-pool's transaction limit a single local sender may occupy before
+    /// its transactions are evicted first.
+    #[arg(
+        long = "txpool.max_local_sender_share",
+        default_value_t = TXPOOL_MAX_LOCAL_SENDER_SHARE_DEFAULT
+    )]
+    pub max_local_sender_share: usize,
+
     /// Price bump (in %) for the transaction pool underpriced check.
     #[arg(long = "txpool.pricebump", default_value_t = DEFAULT_PRICE_BUMP)]
     pub price_bump: u128,
@@ -58,6 +72,34 @@ pub struct TxPoolArgs {
     /// Flag to toggle local transaction propagation.
     #[arg(long = "txpool.no-local-transactions-propagation")]
     pub no_local_transactions_propagation: bool,
+    /// Flag to persist all transactions of the pool on shutdown, not only the local ones.
+    ///
+    /// The transactions are validated again when they're reinserted on startup.
+    #[arg(long = "txpool.persist-all")]
+    pub persist_all_transactions: bool,
+
+    /// Only admit transactions of these senders.
+    #[arg(long = "txpool.allowed-senders", value_delimiter = ',')]
+    pub allowed_senders: Vec<Address>,
+    /// Reject transactions of these senders.
+    #[arg(long = "txpool.denied-senders", value_delimiter = ',')]
+    pub denied_senders: Vec<Address>,
+    /// Only admit transactions to these recipients.
+    #[arg(long = "txpool.allowed-recipients", value_delimiter = ',')]
+    pub allowed_recipients: Vec<Address>,
+    /// Reject transactions to these recipients.
+    #[arg(long = "txpool.denied-recipients", value_delimiter = ',')]
+    pub denied_recipients: Vec<Address>,
+    /// Minimum priority fee, or gas price for legacy transactions, of non-local transactions in
+    /// wei.
+    #[arg(long = "txpool.min-priority-fee")]
+    pub minimum_priority_fee: Option<u128>,
+    /// Max size in bytes of a transaction's input.
+    #[arg(long = "txpool.max-calldata-size")]
+    pub max_calldata_size: Option<usize>,
+    /// Reject calls of these function selectors.
+    #[arg(long = "txpool.blocked-selectors", value_delimiter = ',')]
+    pub blocked_selectors: Vec<Selector>,
 }
 
 impl Default for TxPoolArgs {
@@ -70,12 +112,22 @@ impl Default for TxPoolArgs {
             queued_max_count: TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
             queued_max_size: TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+            max_sender_share: TXPOOL_MAX_SENDER_SHARE_DEFAULT,
+            max_local_sender_share: TXPOOL_MAX_LOCAL_SENDER_SHARE_DEFAULT,
             price_bump: DEFAULT_PRICE_BUMP,
             blob_transaction_price_bump: REPLACE_BLOB_PRICE_BUMP,
             max_tx_input_bytes: DEFAULT_MAX_TX_INPUT_BYTES,
             no_locals: false,
             locals: Default::default(),
             no_local_transactions_propagation: false,
+            persist_all_transactions: false,
+            allowed_senders: Default::default(),
+            denied_senders: Default::default(),
+            allowed_recipients: Default::default(),
+            denied_recipients: Default::default(),
+            minimum_priority_fee: None,
+            max_calldata_size: None,
+            blocked_selectors: Default::default(),
         }
     }
 }
@@ -106,12 +158,33 @@ impl RethTransactionPoolConfig for TxPoolArgs {
                 max_size: self.queued_max_size * 1024 * 1024,
             },
             max_account_slots: self.max_account_slots,
+            sender_share: SenderShareConfig {
+                max_share: self.max_sender_share,
+                max_local_share: self.max_local_sender_share,
+            },
             price_bumps: PriceBumpConfig {
                 default_price_bump: self.price_bump,
                 replace_blob_tx_price_bump: self.blob_transaction_price_bump,
             },
         }
     }
+
+    /// Returns the admission policy configuration.
+    fn admission_policy_config(&self) -> AdmissionPolicyConfig {
+        AdmissionPolicyConfig {
+            senders: AddressList {
+                allow: self.allowed_senders.iter().copied().collect(),
+                deny: self.denied_senders.iter().copied().collect(),
+            },
+            recipients: AddressList {
+                allow: self.allowed_recipients.iter().copied().collect(),
+                deny: self.denied_recipients.iter().copied().collect(),
+            },
+            minimum_priority_fee: self.minimum_priority_fee,
+            max_calldata_size: self.max_calldata_size,
+            blocked_selectors: self.blocked_selectors.iter().copied().collect(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -132,4 +205,25 @@ mod tests {
         let args = CommandParser::<TxPoolArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn txpool_args_admission_policies() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.denied-senders",
+            "0xdac17f958d2ee523a2206206994597c13d831ec7,0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+            "--txpool.min-priority-fee",
+            "1000000000",
+            "--txpool.blocked-selectors",
+            "0xa9059cbb",
+        ])
+        .args;
+
+        let config = args.admission_policy_config();
+        assert_eq!(config.senders.deny.len(), 2);
+        assert!(config.senders.allow.is_empty());
+        assert_eq!(config.minimum_priority_fee, Some(1_000_000_000));
+        assert!(config.blocked_selectors.contains(&Selector::from([0xa9, 0x05, 0x9c, 0xbb])));
+        assert!(TxPoolArgs::default().admission_policy_config().is_empty());
+    }
 }
@@ -1,13 +1,15 @@
 //! Components that are used by the node command.
 
 use reth_db::database::Database;
+use reth_interfaces::blockchain_tree::BlockchainTreeEngine;
 use reth_network::{NetworkEvents, NetworkProtocols};
 use reth_network_api::{NetworkInfo, Peers};
 use reth_node_api::ConfigureEvmEnv;
 use reth_primitives::ChainSpec;
 use reth_provider::{
-    AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    DatabaseProviderFactory, EvmEnvProvider, StateProviderFactory,
+    AccountReader, BadBlockProvider, BlockReaderIdExt, BlockchainTreePendingStateProvider,
+    CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
+    EvmEnvProvider, HistoryReader, NodeDataProvider, StateProviderFactory,
 };
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
@@ -26,6 +28,11 @@ pub trait FullProvider<DB: Database>:
     + EvmEnvProvider
     + ChainSpecProvider
     + ChangeSetReader
+    + HistoryReader
+    + NodeDataProvider
+    + BlockchainTreePendingStateProvider
+    + BadBlockProvider
+    + BlockchainTreeEngine
     + CanonStateSubscriptions
     + Clone
     + Unpin
@@ -41,6 +48,11 @@ impl<T, DB: Database> FullProvider<DB> for T where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + HistoryReader
+        + NodeDataProvider
+        + BlockchainTreePendingStateProvider
+        + BadBlockProvider
+        + BlockchainTreeEngine
         + CanonStateSubscriptions
         + Clone
         + Unpin
@@ -11,7 +11,7 @@ use reth_rpc_builder::{
     auth::AuthServerConfig, error::RpcError, EthConfig, IpcServerBuilder, RpcServerConfig,
     ServerBuilder, TransportRpcModuleConfig,
 };
-use reth_transaction_pool::PoolConfig;
+use reth_transaction_pool::{validate::AdmissionPolicyConfig, PoolConfig};
 use std::{borrow::Cow, path::PathBuf, time::Duration};
 
 /// A trait that provides a configured RPC server.
@@ -144,4 +144,7 @@ impl<C> RethNetworkConfig for reth_network::NetworkManager<C> {
 pub trait RethTransactionPoolConfig {
     /// Returns transaction pool configuration.
     fn pool_config(&self) -> PoolConfig;
+
+    /// Returns the configuration of the pool's admission policies.
+    fn admission_policy_config(&self) -> AdmissionPolicyConfig;
 }
@@ -316,6 +316,14 @@ impl<D> ChainPath<D> {
         self.0.join("txpool-transactions-backup.rlp").into()
     }
 
+    /// Returns the path to the directory where the `debug_standardTrace*` RPC methods write their
+    /// trace files.
+    ///
+    /// `<DIR>/<CHAIN_ID>/traces`
+    pub fn traces_path(&self) -> PathBuf {
+        self.0.join("traces").into()
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/reth.toml`
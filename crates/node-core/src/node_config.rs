@@ -10,21 +10,23 @@ use crate::{
     metrics::prometheus_exporter,
     utils::{get_single_header, write_peers_to_file},
 };
+use futures::{Stream, StreamExt};
 use metrics_exporter_prometheus::PrometheusHandle;
 use once_cell::sync::Lazy;
 use reth_auto_seal_consensus::{AutoSealConsensus, MiningMode};
-use reth_beacon_consensus::BeaconConsensus;
+use reth_beacon_consensus::{BeaconConsensus, BeaconConsensusEngineEvent};
 use reth_blockchain_tree::{
     config::BlockchainTreeConfig, externals::TreeExternals, BlockchainTree,
 };
 use reth_config::{
-    config::{PruneConfig, StageConfig},
+    config::{PruneConfig, StageConfig, TxPoolConfig},
     Config,
 };
 use reth_db::{database::Database, database_metrics::DatabaseMetrics};
 use reth_downloaders::{
     bodies::bodies::BodiesDownloaderBuilder,
     headers::reverse_headers::ReverseHeadersDownloaderBuilder,
+    receipts::receipts::ReceiptsDownloaderBuilder,
 };
 use reth_interfaces::{
     blockchain_tree::BlockchainTreeEngine,
@@ -32,39 +34,46 @@ use reth_interfaces::{
     p2p::{
         bodies::{client::BodiesClient, downloader::BodyDownloader},
         headers::{client::HeadersClient, downloader::HeaderDownloader},
+        receipts::{client::ReceiptsClient, downloader::ReceiptDownloader},
     },
     RethResult,
 };
+use reth_net_snap::{
+    follow_tip, SnapProtocolHandler, SnapRequestHandler, SnapSync, SnapSyncConfig, SnapSyncStage,
+    SNAP_REQUEST_CHANNEL_CAPACITY,
+};
 use reth_network::{
+    protocol::IntoRlpxSubProtocol,
     transactions::{TransactionFetcherConfig, TransactionsManagerConfig},
-    NetworkBuilder, NetworkConfig, NetworkHandle, NetworkManager,
+    NetworkBuilder, NetworkConfig, NetworkHandle, NetworkManager, NetworkProtocols,
 };
 use reth_node_api::ConfigureEvmEnv;
 use reth_primitives::{
     constants::eip4844::{LoadKzgSettingsError, MAINNET_KZG_TRUSTED_SETUP},
     kzg::KzgSettings,
     stage::StageId,
-    BlockHashOrNumber, BlockNumber, ChainSpec, Head, SealedHeader, TxHash, B256, MAINNET,
+    BlockHashOrNumber, BlockNumber, ChainSpec, ForkCondition, Hardfork, Head, SealedHeader, TxHash,
+    B256, MAINNET,
 };
 use reth_provider::{
     providers::BlockchainProvider, BlockHashReader, BlockNumReader, BlockReader,
     BlockchainTreePendingStateProvider, CanonStateSubscriptions, HeaderProvider, HeaderSyncMode,
-    ProviderFactory, StageCheckpointReader,
+    NodeDataProvider, ProviderFactory, StageCheckpointReader,
 };
 use reth_revm::EvmProcessorFactory;
 use reth_stages::{
     prelude::*,
     stages::{
         AccountHashingStage, ExecutionStage, ExecutionStageThresholds, IndexAccountHistoryStage,
-        IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage, StorageHashingStage,
-        TotalDifficultyStage, TransactionLookupStage,
+        IndexStorageHistoryStage, MerkleStage, ReceiptsStage, SenderRecoveryStage,
+        StorageHashingStage, TotalDifficultyStage, TransactionLookupStage,
     },
     MetricEvent,
 };
 use reth_tasks::TaskExecutor;
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, EthTransactionPool, TransactionPool,
-    TransactionValidationTaskExecutor,
+    blobstore::DiskFileBlobStore, validate::AdmissionPolicyConfig, EthTransactionPool,
+    TransactionPool, TransactionValidationTaskExecutor,
 };
 use revm_inspectors::stack::Hook;
 use secp256k1::SecretKey;
@@ -74,7 +83,7 @@ use std::{
     sync::Arc,
 };
 use tokio::sync::{
-    mpsc::{Receiver, UnboundedSender},
+    mpsc::{channel, unbounded_channel, Receiver, UnboundedSender},
     watch,
 };
 use tracing::*;
@@ -357,6 +366,19 @@ impl NodeConfig {
         self.pruning.prune_config(Arc::clone(&self.chain))
     }
 
+    /// Returns the configuration of the transaction pool's admission policies.
+    ///
+    /// The policies set on the command line take precedence over the `[txpool]` section of the
+    /// config file.
+    pub fn admission_policy_config(&self, config: &TxPoolConfig) -> AdmissionPolicyConfig {
+        let admission_policy_config = self.txpool.admission_policy_config();
+        if admission_policy_config.is_empty() {
+            config.admission_policy_config()
+        } else {
+            admission_policy_config
+        }
+    }
+
     /// Returns the max block that the node should run to, looking it up from the network if
     /// necessary
     pub async fn max_block<Provider, Client>(
@@ -458,6 +480,7 @@ impl NodeConfig {
         head: Head,
         executor: &TaskExecutor,
         data_dir: &ChainPath<DataDirPath>,
+        admission_policy_config: AdmissionPolicyConfig,
     ) -> eyre::Result<EthTransactionPool<BlockchainProvider<DB, Tree>, DiskFileBlobStore>>
     where
         DB: Database + Unpin + Clone + 'static,
@@ -475,6 +498,7 @@ impl NodeConfig {
             .with_additional_tasks(1)
             // set the max tx size in bytes allowed to enter the pool
             .with_max_tx_input_bytes(self.txpool.max_tx_input_bytes)
+            .with_admission_policy_config(admission_policy_config)
             .build_with_tasks(blockchain_db.clone(), executor.clone(), blob_store.clone());
 
         let transaction_pool =
@@ -488,7 +512,8 @@ impl NodeConfig {
             let chain_events = blockchain_db.canonical_state_stream();
             let client = blockchain_db.clone();
             let transactions_backup_config =
-                reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path);
+                reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path)
+                    .with_all_transactions(self.txpool.persist_all_transactions);
 
             executor.spawn_critical_with_graceful_shutdown_signal(
                 "local transactions backup task",
@@ -543,10 +568,11 @@ impl NodeConfig {
         prune_config: Option<PruneConfig>,
         max_block: Option<BlockNumber>,
         evm_config: EvmConfig,
+        snap_sync: Option<SnapSyncStage<DB>>,
     ) -> eyre::Result<Pipeline<DB>>
     where
         DB: Database + Unpin + Clone + 'static,
-        Client: HeadersClient + BodiesClient + Clone + 'static,
+        Client: HeadersClient + BodiesClient + ReceiptsClient + Clone + 'static,
         EvmConfig: ConfigureEvmEnv + Clone + 'static,
     {
         // building network downloaders using the fetch client
@@ -555,15 +581,40 @@ impl NodeConfig {
             .into_task_with(task_executor);
 
         let body_downloader = BodiesDownloaderBuilder::new(config.bodies)
-            .build(client, Arc::clone(&consensus), provider_factory.clone())
+            .build(client.clone(), Arc::clone(&consensus), provider_factory.clone())
             .into_task_with(task_executor);
 
+        // the receipts of the blocks up to the snap sync pivot are downloaded, because their state
+        // was synced instead of executed
+        let receipts_stage = if config.receipts.enabled && snap_sync.is_some() {
+            // receipts of pre-byzantium blocks contain the intermediate state root, which isn't
+            // stored, so they can't be verified against the receipts root of their header
+            let first_block = match self.chain.fork(Hardfork::Byzantium) {
+                ForkCondition::Block(block) => block,
+                _ => 0,
+            };
+            if first_block > 0 {
+                warn!(target: "reth::cli", first_block, "Receipts of pre-Byzantium blocks are not downloaded");
+            }
+
+            let receipt_downloader = ReceiptsDownloaderBuilder::new(config.receipts)
+                .build(client, provider_factory.clone());
+            Some(ReceiptsStage::new(receipt_downloader, first_block))
+        } else {
+            if config.receipts.enabled {
+                warn!(target: "reth::cli", "Receipts are only downloaded for snap synced blocks, enable snap sync to download them");
+            }
+            None
+        };
+
         let pipeline = self
             .build_pipeline(
                 provider_factory,
                 config,
                 header_downloader,
                 body_downloader,
+                receipts_stage,
+                snap_sync,
                 consensus,
                 max_block,
                 self.debug.continuous,
@@ -627,7 +678,7 @@ impl NodeConfig {
         data_dir: &ChainPath<DataDirPath>,
     ) -> NetworkHandle
     where
-        C: BlockReader + HeaderProvider + Clone + Unpin + 'static,
+        C: BlockReader + HeaderProvider + NodeDataProvider + Clone + Unpin + 'static,
         Pool: TransactionPool + Unpin + 'static,
     {
         let (handle, network, txpool, eth) = builder
@@ -661,6 +712,78 @@ impl NodeConfig {
         handle
     }
 
+    /// Installs `snap/1` on the network if the node serves or syncs the state over snap.
+    ///
+    /// Spawns the [SnapRequestHandler] if the state is served and returns the [SnapSyncStage] that
+    /// syncs to the given pivot if the node snap syncs. Snap sync is skipped if the node has
+    /// already executed blocks.
+    pub fn install_snap<DB>(
+        &self,
+        network: &NetworkHandle,
+        provider_factory: ProviderFactory<DB>,
+        task_executor: &TaskExecutor,
+        pivot: watch::Receiver<SealedHeader>,
+    ) -> eyre::Result<Option<SnapSyncStage<DB>>>
+    where
+        DB: Database + Unpin + Clone + 'static,
+    {
+        if !self.network.snap_serve && !self.network.snap_sync {
+            return Ok(None)
+        }
+
+        let (events_tx, events) = unbounded_channel();
+        let mut handler = SnapProtocolHandler::new(events_tx);
+        if self.network.snap_serve {
+            let (to_server, from_peers) = channel(SNAP_REQUEST_CHANNEL_CAPACITY);
+            handler = handler.with_request_handler(to_server);
+            task_executor.spawn_critical(
+                "snap request handler",
+                SnapRequestHandler::new(provider_factory.clone(), from_peers),
+            );
+        }
+        network.add_rlpx_sub_protocol(handler.into_rlpx_sub_protocol());
+
+        if !self.network.snap_sync {
+            return Ok(None)
+        }
+        let executed =
+            provider_factory.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default();
+        if executed.block_number > 0 {
+            info!(target: "reth::cli", executed = executed.block_number, "Skipping snap sync of an executed database");
+            return Ok(None)
+        }
+
+        let sync = SnapSync::new(provider_factory, pivot, SnapSyncConfig::default())
+            .with_events(events)
+            .with_peers_handle(network.peers_handle().clone());
+        Ok(Some(SnapSyncStage::new(sync, Box::new(task_executor.clone()))))
+    }
+
+    /// Spawns the task that moves the pivot of the snap sync to the safe block of every
+    /// forkchoice update of the consensus engine.
+    ///
+    /// The safe block is unlikely to be reorged, but recent enough to be served by snap peers.
+    pub fn spawn_snap_pivot<Client, S>(
+        &self,
+        client: Client,
+        engine_events: S,
+        pivot: watch::Sender<SealedHeader>,
+        task_executor: &TaskExecutor,
+    ) where
+        Client: HeadersClient + 'static,
+        S: Stream<Item = BeaconConsensusEngineEvent> + Send + 'static,
+    {
+        let tips = engine_events.filter_map(|event| async move {
+            match event {
+                BeaconConsensusEngineEvent::ForkchoiceUpdated(state, _) => {
+                    Some(state.safe_block_hash)
+                }
+                _ => None,
+            }
+        });
+        task_executor.spawn(Box::pin(follow_tip(client, Box::pin(tips), pivot)));
+    }
+
     /// Fetches the head block from the database.
     ///
     /// If the database is empty, returns the genesis block.
@@ -779,12 +902,14 @@ impl NodeConfig {
 
     /// Builds the [Pipeline] with the given [ProviderFactory] and downloaders.
     #[allow(clippy::too_many_arguments)]
-    pub async fn build_pipeline<DB, H, B, EvmConfig>(
+    pub async fn build_pipeline<DB, H, B, R, EvmConfig>(
         &self,
         provider_factory: ProviderFactory<DB>,
         stage_config: &StageConfig,
         header_downloader: H,
         body_downloader: B,
+        receipts_stage: Option<ReceiptsStage<R>>,
+        snap_sync: Option<SnapSyncStage<DB>>,
         consensus: Arc<dyn Consensus>,
         max_block: Option<u64>,
         continuous: bool,
@@ -796,6 +921,7 @@ impl NodeConfig {
         DB: Database + Clone + 'static,
         H: HeaderDownloader + 'static,
         B: BodyDownloader + 'static,
+        R: ReceiptDownloader + 'static,
         EvmConfig: ConfigureEvmEnv + Clone + 'static,
     {
         let mut builder = Pipeline::builder();
@@ -828,65 +954,72 @@ impl NodeConfig {
 
         let header_mode =
             if continuous { HeaderSyncMode::Continuous } else { HeaderSyncMode::Tip(tip_rx) };
+        let mut stages = DefaultStages::new(
+            provider_factory.clone(),
+            header_mode,
+            Arc::clone(&consensus),
+            header_downloader,
+            body_downloader,
+            factory.clone(),
+        )
+        .set(
+            TotalDifficultyStage::new(consensus)
+                .with_commit_threshold(stage_config.total_difficulty.commit_threshold),
+        )
+        .set(SenderRecoveryStage {
+            commit_threshold: stage_config.sender_recovery.commit_threshold,
+        })
+        .set(
+            ExecutionStage::new(
+                factory,
+                ExecutionStageThresholds {
+                    max_blocks: stage_config.execution.max_blocks,
+                    max_changes: stage_config.execution.max_changes,
+                    max_cumulative_gas: stage_config.execution.max_cumulative_gas,
+                    max_duration: stage_config.execution.max_duration,
+                },
+                stage_config
+                    .merkle
+                    .clean_threshold
+                    .max(stage_config.account_hashing.clean_threshold)
+                    .max(stage_config.storage_hashing.clean_threshold),
+                prune_modes.clone(),
+            )
+            .with_metrics_tx(metrics_tx),
+        )
+        .set(AccountHashingStage::new(
+            stage_config.account_hashing.clean_threshold,
+            stage_config.account_hashing.commit_threshold,
+        ))
+        .set(StorageHashingStage::new(
+            stage_config.storage_hashing.clean_threshold,
+            stage_config.storage_hashing.commit_threshold,
+        ))
+        .set(MerkleStage::new_execution(stage_config.merkle.clean_threshold))
+        .set(TransactionLookupStage::new(
+            stage_config.transaction_lookup.commit_threshold,
+            prune_modes.transaction_lookup,
+        ))
+        .set(IndexAccountHistoryStage::new(
+            stage_config.index_account_history.commit_threshold,
+            prune_modes.account_history,
+        ))
+        .set(IndexStorageHistoryStage::new(
+            stage_config.index_storage_history.commit_threshold,
+            prune_modes.storage_history,
+        ));
+        if let Some(receipts_stage) = receipts_stage {
+            stages = stages
+                .add_after(receipts_stage.with_prune_mode(prune_modes.receipts), StageId::Bodies);
+        }
+        if let Some(snap_sync) = snap_sync {
+            stages = stages.add_before(snap_sync, StageId::Headers);
+        }
+
         let pipeline = builder
             .with_tip_sender(tip_tx)
             .with_metrics_tx(metrics_tx.clone())
-            .add_stages(
-                DefaultStages::new(
-                    provider_factory.clone(),
-                    header_mode,
-                    Arc::clone(&consensus),
-                    header_downloader,
-                    body_downloader,
-                    factory.clone(),
-                )
-                .set(
-                    TotalDifficultyStage::new(consensus)
-                        .with_commit_threshold(stage_config.total_difficulty.commit_threshold),
-                )
-                .set(SenderRecoveryStage {
-                    commit_threshold: stage_config.sender_recovery.commit_threshold,
-                })
-                .set(
-                    ExecutionStage::new(
-                        factory,
-                        ExecutionStageThresholds {
-                            max_blocks: stage_config.execution.max_blocks,
-                            max_changes: stage_config.execution.max_changes,
-                            max_cumulative_gas: stage_config.execution.max_cumulative_gas,
-                            max_duration: stage_config.execution.max_duration,
-                        },
-                        stage_config
-                            .merkle
-                            .clean_threshold
-                            .max(stage_config.account_hashing.clean_threshold)
-                            .max(stage_config.storage_hashing.clean_threshold),
-                        prune_modes.clone(),
-                    )
-                    .with_metrics_tx(metrics_tx),
-                )
-                .set(AccountHashingStage::new(
-                    stage_config.account_hashing.clean_threshold,
-                    stage_config.account_hashing.commit_threshold,
-                ))
-                .set(StorageHashingStage::new(
-                    stage_config.storage_hashing.clean_threshold,
-                    stage_config.storage_hashing.commit_threshold,
-                ))
-                .set(MerkleStage::new_execution(stage_config.merkle.clean_threshold))
-                .set(TransactionLookupStage::new(
-                    stage_config.transaction_lookup.commit_threshold,
-                    prune_modes.transaction_lookup,
-                ))
-                .set(IndexAccountHistoryStage::new(
-                    stage_config.index_account_history.commit_threshold,
-                    prune_modes.account_history,
-                ))
-                .set(IndexStorageHistoryStage::new(
-                    stage_config.index_storage_history.commit_threshold,
-                    prune_modes.storage_history,
-                )),
-            )
+            .add_stages(stages)
             .build(provider_factory);
 
         Ok(pipeline)
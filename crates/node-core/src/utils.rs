@@ -40,12 +40,15 @@ pub fn get_or_create_jwt_secret_from_path(path: &Path) -> Result<JwtSecret, JwtE
 
 /// Collect the peers from the [NetworkManager] and write them to the given `persistent_peers_file`,
 /// if configured.
+///
+/// Next to the node records, this persists the reputation, last connection and bans of the peers,
+/// so they can be restored on the next launch.
 pub fn write_peers_to_file<C>(network: &NetworkManager<C>, persistent_peers_file: Option<PathBuf>)
 where
     C: BlockReader + Unpin,
 {
     if let Some(file_path) = persistent_peers_file {
-        let known_peers = network.all_peers().collect::<Vec<_>>();
+        let known_peers = network.persisted_peers().collect::<Vec<_>>();
         if let Ok(known_peers) = serde_json::to_string_pretty(&known_peers) {
             trace!(target: "reth::cli", peers_file =?file_path, num_peers=%known_peers.len(), "Saving current peers");
             let parent_dir = file_path.parent().map(fs::create_dir_all).transpose();
@@ -70,6 +70,7 @@ where
             .with_head_timestamp(ctx.head().timestamp)
             .kzg_settings(ctx.kzg_settings()?)
             .with_additional_tasks(1)
+            .with_admission_policy_config(ctx.admission_policy_config())
             .build_with_tasks(
                 ctx.provider().clone(),
                 ctx.task_executor().clone(),
@@ -87,7 +88,8 @@ where
             let chain_events = ctx.provider().canonical_state_stream();
             let client = ctx.provider().clone();
             let transactions_backup_config =
-                reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path);
+                reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path)
+                    .with_all_transactions(ctx.config().txpool.persist_all_transactions);
 
             ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
                 "local transactions backup task",
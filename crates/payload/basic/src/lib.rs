@@ -11,7 +11,7 @@ use crate::metrics::PayloadBuilderMetrics;
 use alloy_rlp::Encodable;
 use futures_core::ready;
 use futures_util::FutureExt;
-use reth_interfaces::RethResult;
+use reth_interfaces::{provider::ProviderResult, RethResult};
 use reth_node_api::{BuiltPayload, PayloadBuilderAttributes};
 use reth_payload_builder::{
     database::CachedReads, error::PayloadBuilderError, EthBuiltPayload, KeepPayloadJobAlive,
@@ -20,16 +20,19 @@ use reth_payload_builder::{
 use reth_primitives::{
     bytes::BytesMut,
     constants::{EMPTY_WITHDRAWALS, ETHEREUM_BLOCK_GAS_LIMIT, RETH_CLIENT_VERSION, SLOT_DURATION},
-    proofs, BlockNumberOrTag, Bytes, ChainSpec, SealedBlock, Withdrawals, B256, U256,
+    keccak256, proofs, Address, BlockNumberOrTag, Bytes, ChainSpec, SealedBlock, Withdrawals, B256,
+    U256,
 };
 use reth_provider::{
-    BlockReaderIdExt, BlockSource, CanonStateNotification, ProviderError, StateProviderFactory,
+    BlockReaderIdExt, BlockSource, CanonStateNotification, ProviderError, StateProvider,
+    StateProviderFactory,
 };
 use reth_revm::state_change::{
     apply_beacon_root_contract_call, post_block_withdrawals_balance_increments,
 };
 use reth_tasks::TaskSpawner;
-use reth_transaction_pool::TransactionPool;
+use reth_transaction_pool::{ConditionalState, TransactionPool};
+use reth_trie::HashedStorage;
 use revm::{
     primitives::{BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg},
     Database, DatabaseCommit, Evm, State,
@@ -885,6 +888,49 @@ where
     .map_err(|err| PayloadBuilderError::Internal(err.into()))
 }
 
+/// The state of a payload that's being built, which the preconditions of conditional transactions
+/// are checked against before their inclusion.
+///
+/// Storage slots are read from the builder's [State]. Storage roots are computed from the state of
+/// the parent, with the storage changes of the transactions executed so far applied on top.
+#[derive(Debug)]
+pub struct PayloadConditionalState<'a, S: ?Sized, DB> {
+    parent: &'a S,
+    db: &'a mut State<DB>,
+}
+
+impl<'a, S: ?Sized, DB> PayloadConditionalState<'a, S, DB> {
+    /// Creates the state of the payload from the state of its parent and the builder's [State].
+    pub fn new(parent: &'a S, db: &'a mut State<DB>) -> Self {
+        Self { parent, db }
+    }
+}
+
+impl<S, DB> ConditionalState for PayloadConditionalState<'_, S, DB>
+where
+    S: StateProvider + ?Sized,
+    DB: Database<Error = ProviderError>,
+{
+    fn storage_root(&mut self, address: Address) -> ProviderResult<B256> {
+        let changes = match self.db.cache.accounts.get(&address) {
+            Some(account) => HashedStorage::from_iter(
+                account.status.was_destroyed(),
+                account
+                    .account
+                    .iter()
+                    .flat_map(|account| account.storage.iter())
+                    .map(|(slot, value)| (keccak256(B256::new(slot.to_be_bytes())), *value)),
+            ),
+            None => HashedStorage::new(false),
+        };
+        self.parent.storage_root(address, changes)
+    }
+
+    fn storage(&mut self, address: Address, slot: B256) -> ProviderResult<U256> {
+        self.db.storage(address, U256::from_be_bytes(slot.0))
+    }
+}
+
 /// Checks if the new payload is better than the current best.
 ///
 /// This compares the total fees of the blocks, higher is better.
@@ -14,7 +14,7 @@ pub use builder::*;
 mod builder {
     use reth_basic_payload_builder::{
         commit_withdrawals, is_better_payload, pre_block_beacon_root_contract_call, BuildArguments,
-        BuildOutcome, PayloadBuilder, PayloadConfig, WithdrawalsOutcome,
+        BuildOutcome, PayloadBuilder, PayloadConditionalState, PayloadConfig, WithdrawalsOutcome,
     };
     use reth_payload_builder::{
         error::PayloadBuilderError, EthBuiltPayload, EthPayloadBuilderAttributes,
@@ -30,7 +30,7 @@ mod builder {
     };
     use reth_provider::{BundleStateWithReceipts, StateProviderFactory};
     use reth_revm::database::StateProviderDatabase;
-    use reth_transaction_pool::{BestTransactionsAttributes, TransactionPool};
+    use reth_transaction_pool::{BestTransactionsAttributes, PoolTransaction, TransactionPool};
     use revm::{
         db::states::bundle_state::BundleRetention,
         primitives::{EVMError, EnvWithHandlerCfg, InvalidTransaction, ResultAndState},
@@ -243,6 +243,25 @@ mod builder {
                 return Ok(BuildOutcome::Cancelled)
             }
 
+            // the preconditions of conditional transactions must hold for this block, they're
+            // checked against the state built so far
+            if let Some(conditional) = pool_tx.transaction.conditional() {
+                let payload_state = PayloadConditionalState::new(&*state_provider, &mut db);
+                match conditional.check(block_number, attributes.timestamp, payload_state) {
+                    Ok(Ok(())) => {}
+                    Ok(Err(err)) => {
+                        trace!(target: "payload_builder", %err, tx=?pool_tx.hash(), "skipping conditional transaction and its descendants");
+                        best_txs.mark_invalid(&pool_tx);
+                        continue
+                    }
+                    Err(err) => {
+                        warn!(target: "payload_builder", %err, tx=?pool_tx.hash(), "failed to check conditional transaction, skipping it and its descendants");
+                        best_txs.mark_invalid(&pool_tx);
+                        continue
+                    }
+                }
+            }
+
             // convert tx to a signed transaction
             let tx = pool_tx.to_recovered_transaction();
 
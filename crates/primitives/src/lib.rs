@@ -69,8 +69,8 @@ pub use net::{
 };
 pub use peer::{PeerId, WithPeerId};
 pub use prune::{
-    PruneCheckpoint, PruneMode, PruneModes, PruneProgress, PruneSegment, PruneSegmentError,
-    ReceiptsLogPruneConfig, MINIMUM_PRUNING_DISTANCE,
+    HistoryAddressPruneConfig, PruneCheckpoint, PruneMode, PruneModes, PruneProgress, PruneSegment,
+    PruneSegmentError, ReceiptsLogPruneConfig, RetainedHistoryCheckpoint, MINIMUM_PRUNING_DISTANCE,
 };
 pub use receipt::{Receipt, ReceiptWithBloom, ReceiptWithBloomRef, Receipts};
 pub use snapshot::SnapshotSegment;
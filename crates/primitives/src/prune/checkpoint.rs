@@ -13,3 +13,19 @@ pub struct PruneCheckpoint {
     /// Prune mode.
     pub prune_mode: PruneMode,
 }
+
+/// Saves the blocks from which the account and storage history of an address retained by the
+/// [HistoryAddressPruneConfig](crate::HistoryAddressPruneConfig) is complete.
+///
+/// An address can be added to the filter after some of its history was already pruned, so its
+/// history is only complete from the first block that wasn't pruned yet at that point.
+#[main_codec]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct RetainedHistoryCheckpoint {
+    /// Lowest block from which the account history of the address is retained. If it's [None],
+    /// the account history of the address is not retained.
+    pub account_history_from: Option<BlockNumber>,
+    /// Lowest block from which the storage history of the address is retained. If it's [None],
+    /// the storage history of the address is not retained.
+    pub storage_history_from: Option<BlockNumber>,
+}
@@ -4,11 +4,11 @@ mod segment;
 mod target;
 
 use crate::{Address, BlockNumber};
-pub use checkpoint::PruneCheckpoint;
+pub use checkpoint::{PruneCheckpoint, RetainedHistoryCheckpoint};
 pub use mode::PruneMode;
 pub use segment::{PruneSegment, PruneSegmentError};
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 pub use target::{PruneModes, MINIMUM_PRUNING_DISTANCE};
 
 /// Configuration for pruning receipts not associated with logs emitted by the specified contracts.
@@ -87,6 +87,30 @@ impl ReceiptsLogPruneConfig {
     }
 }
 
+/// Configuration for retaining the full account and storage history of the specified addresses,
+/// while the history of all other addresses is pruned according to
+/// [`PruneModes::account_history`] and [`PruneModes::storage_history`].
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct HistoryAddressPruneConfig(pub BTreeSet<Address>);
+
+impl HistoryAddressPruneConfig {
+    /// Checks if the configuration is empty
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns `true` if the history of the provided address is retained in full.
+    pub fn contains(&self, address: &Address) -> bool {
+        self.0.contains(address)
+    }
+}
+
+impl FromIterator<Address> for HistoryAddressPruneConfig {
+    fn from_iter<T: IntoIterator<Item = Address>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
 /// Progress of pruning.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum PruneProgress {
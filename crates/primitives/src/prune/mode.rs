@@ -12,17 +12,33 @@ pub enum PruneMode {
     Distance(u64),
     /// Prune blocks before the specified block number. The specified block number is not pruned.
     Before(BlockNumber),
+    /// Prune blocks older than the specified number of seconds, measured against the timestamp of
+    /// the tip block. Accepts a duration, e.g. `{ age = "30days" }`.
+    Age(#[serde(with = "age")] u64),
+    /// Prune the oldest blocks while the segment takes more than the specified number of bytes on
+    /// disk. Accepts a size, e.g. `{ size = "500GB" }`.
+    ///
+    /// Only supported by [`PruneSegment::Receipts`], [`PruneSegment::AccountHistory`] and
+    /// [`PruneSegment::StorageHistory`].
+    Size(#[serde(with = "size")] u64),
 }
 
 impl PruneMode {
     /// Returns block up to which variant pruning needs to be done, inclusive, according to the
     /// provided tip.
+    ///
+    /// Data dependent modes (see [`Self::is_data_dependent`]) never have a target here, because it
+    /// can only be resolved from the chain data by the pruner.
     pub fn prune_target_block(
         &self,
         tip: BlockNumber,
         segment: PruneSegment,
     ) -> Result<Option<(BlockNumber, PruneMode)>, PruneSegmentError> {
         let result = match self {
+            PruneMode::Size(_) if !segment.supports_size_limit() => {
+                return Err(PruneSegmentError::Configuration(segment))
+            }
+            PruneMode::Age(_) | PruneMode::Size(_) => None,
             PruneMode::Full if segment.min_blocks() == 0 => Some((tip, *self)),
             PruneMode::Distance(distance) if *distance > tip => None, // Nothing to prune yet
             PruneMode::Distance(distance) if *distance >= segment.min_blocks() => {
@@ -46,6 +62,8 @@ impl PruneMode {
                 block < tip - *distance
             }
             PruneMode::Before(n) => *n > block,
+            // The data is kept until the pruner resolves the target from the chain data.
+            PruneMode::Age(_) | PruneMode::Size(_) => false,
         }
     }
 
@@ -53,6 +71,85 @@ impl PruneMode {
     pub fn is_full(&self) -> bool {
         matches!(self, Self::Full)
     }
+
+    /// Returns true if the prune target depends on the chain data, i.e. the header timestamps for
+    /// [`PruneMode::Age`] and the table sizes for [`PruneMode::Size`].
+    pub fn is_data_dependent(&self) -> bool {
+        matches!(self, Self::Age(_) | Self::Size(_))
+    }
+}
+
+/// (De)serialization of [`PruneMode::Age`] seconds as a human readable duration.
+mod age {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub(super) fn serialize<S: Serializer>(
+        seconds: &u64,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&humantime::format_duration(Duration::from_secs(*seconds)))
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Age {
+            Seconds(u64),
+            Duration(String),
+        }
+
+        match Age::deserialize(deserializer)? {
+            Age::Seconds(seconds) => Ok(seconds),
+            Age::Duration(duration) => humantime::parse_duration(&duration)
+                .map(|duration| duration.as_secs())
+                .map_err(D::Error::custom),
+        }
+    }
+}
+
+/// (De)serialization of [`PruneMode::Size`] bytes, also accepting sizes with a unit.
+mod size {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(bytes: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(*bytes)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Size {
+            Bytes(u64),
+            WithUnit(String),
+        }
+
+        match Size::deserialize(deserializer)? {
+            Size::Bytes(bytes) => Ok(bytes),
+            Size::WithUnit(size) => parse_size(&size).map_err(D::Error::custom),
+        }
+    }
+
+    /// Parses a size like `500GB` or `1.5 TiB` into bytes.
+    fn parse_size(size: &str) -> Result<u64, String> {
+        let size = size.trim();
+        let (number, unit) = size
+            .split_at(size.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(size.len()));
+        let number = number.parse::<f64>().map_err(|_| format!("invalid size: {size}"))?;
+        let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+            "" | "b" => 1,
+            "kb" => 1_000,
+            "mb" => 1_000_000,
+            "gb" => 1_000_000_000,
+            "tb" => 1_000_000_000_000,
+            "kib" => 1 << 10,
+            "mib" => 1 << 20,
+            "gib" => 1 << 30,
+            "tib" => 1 << 40,
+            unit => return Err(format!("unknown size unit: {unit}")),
+        };
+        Ok((number * multiplier as f64) as u64)
+    }
 }
 
 #[cfg(test)]
@@ -93,6 +190,9 @@ mod tests {
                 Ok(Some(tip - MINIMUM_PRUNING_DISTANCE - 2)),
             ),
             (PruneMode::Before(tip - 1), Err(PruneSegmentError::Configuration(segment))),
+            // Resolved by the pruner
+            (PruneMode::Age(3600), Ok(None)),
+            (PruneMode::Size(1024), Ok(None)),
         ];
 
         for (index, (mode, expected_result)) in tests.into_iter().enumerate() {
@@ -109,6 +209,12 @@ mod tests {
             PruneMode::Full.prune_target_block(tip, PruneSegment::Transactions),
             Ok(Some((tip, PruneMode::Full))),
         );
+
+        // Size limits are not supported by every segment
+        assert_eq!(
+            PruneMode::Size(1024).prune_target_block(tip, PruneSegment::SenderRecovery),
+            Err(PruneSegmentError::Configuration(PruneSegment::SenderRecovery)),
+        );
     }
 
     #[test]
@@ -130,6 +236,8 @@ mod tests {
             ),
             (PruneMode::Before(tip + 1), 1, should_prune),
             (PruneMode::Before(tip + 1), tip + 1, !should_prune),
+            (PruneMode::Age(0), 1, !should_prune),
+            (PruneMode::Size(0), 1, !should_prune),
         ];
 
         for (index, (mode, block, expected_result)) in tests.into_iter().enumerate() {
@@ -145,12 +253,18 @@ mod tests {
             b: Option<PruneMode>,
             c: Option<PruneMode>,
             d: Option<PruneMode>,
+            e: Option<PruneMode>,
+            f: Option<PruneMode>,
+            g: Option<PruneMode>,
         }
 
         let toml_str = r#"
         a = "full"
         b = { distance = 10 }
         c = { before = 20 }
+        e = { age = "30days" }
+        f = { size = "1.5GiB" }
+        g = { size = 1000 }
     "#;
 
         assert_matches!(
@@ -159,8 +273,19 @@ mod tests {
                 a: Some(PruneMode::Full),
                 b: Some(PruneMode::Distance(10)),
                 c: Some(PruneMode::Before(20)),
-                d: None
+                d: None,
+                e: Some(PruneMode::Age(2_592_000)),
+                f: Some(PruneMode::Size(1_610_612_736)),
+                g: Some(PruneMode::Size(1000)),
             })
         );
     }
+
+    #[test]
+    fn prune_mode_serde_roundtrip() {
+        for mode in [PruneMode::Age(2_592_000), PruneMode::Size(500_000_000_000)] {
+            let json = serde_json::to_string(&mode).unwrap();
+            assert_eq!(serde_json::from_str::<PruneMode>(&json).unwrap(), mode);
+        }
+    }
 }
@@ -23,6 +23,8 @@ pub enum PruneSegment {
     Headers,
     /// Prune segment responsible for the `Transactions` table.
     Transactions,
+    /// Prune segment responsible for the `Bytecodes` table.
+    Bytecodes,
 }
 
 impl PruneSegment {
@@ -32,11 +34,19 @@ impl PruneSegment {
             Self::SenderRecovery | Self::TransactionLookup | Self::Headers | Self::Transactions => {
                 0
             }
-            Self::Receipts | Self::ContractLogs | Self::AccountHistory | Self::StorageHistory => {
-                MINIMUM_PRUNING_DISTANCE
-            }
+            Self::Receipts |
+            Self::ContractLogs |
+            Self::AccountHistory |
+            Self::StorageHistory |
+            Self::Bytecodes => MINIMUM_PRUNING_DISTANCE,
         }
     }
+
+    /// Returns `true` if the segment can be pruned down to a size limit with
+    /// [`PruneMode::Size`](crate::PruneMode::Size).
+    pub fn supports_size_limit(&self) -> bool {
+        matches!(self, Self::Receipts | Self::AccountHistory | Self::StorageHistory)
+    }
 }
 
 /// PruneSegment error type.
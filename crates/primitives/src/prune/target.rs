@@ -1,4 +1,4 @@
-use crate::{PruneMode, ReceiptsLogPruneConfig};
+use crate::{HistoryAddressPruneConfig, PruneMode, ReceiptsLogPruneConfig};
 use serde::{Deserialize, Deserializer, Serialize};
 
 /// Minimum distance from the tip necessary for the node to work correctly:
@@ -37,6 +37,20 @@ pub struct PruneModes {
         deserialize_with = "deserialize_opt_prune_mode_with_min_blocks::<MINIMUM_PRUNING_DISTANCE, _>"
     )]
     pub storage_history: Option<PruneMode>,
+    /// Bytecodes pruning configuration. Only the bytecodes that are referenced neither by the
+    /// current state nor by the retained account history are pruned.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_opt_prune_mode_with_min_blocks::<MINIMUM_PRUNING_DISTANCE, _>"
+    )]
+    pub bytecodes: Option<PruneMode>,
+    /// Account and storage history pruning configuration by retaining the full history of the
+    /// specified addresses, discarding the history of others according to `account_history` and
+    /// `storage_history`.
+    ///
+    /// Addresses should be added before their history is pruned, as the already pruned history
+    /// can't be restored.
+    pub history_address_filter: HistoryAddressPruneConfig,
     /// Receipts pruning configuration by retaining only those receipts that contain logs emitted
     /// by the specified addresses, discarding others. This setting is overridden by `receipts`.
     ///
@@ -59,6 +73,8 @@ impl PruneModes {
             receipts: Some(PruneMode::Full),
             account_history: Some(PruneMode::Full),
             storage_history: Some(PruneMode::Full),
+            bytecodes: Some(PruneMode::Full),
+            history_address_filter: Default::default(),
             receipts_log_filter: Default::default(),
         }
     }
@@ -101,6 +117,7 @@ fn deserialize_opt_prune_mode_with_min_blocks<'de, const MIN_BLOCKS: u64, D: Des
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::address;
     use assert_matches::assert_matches;
     use serde::Deserialize;
 
@@ -123,4 +140,20 @@ mod tests {
             Err(err) if err.to_string() == "invalid value: string \"full\", expected prune mode that leaves at least 10 blocks in the database"
         );
     }
+
+    #[test]
+    fn test_deserialize_history_address_filter() {
+        let prune_modes = serde_json::from_str::<PruneModes>(
+            r#"{
+                "account_history": {"distance": 10064},
+                "history_address_filter": ["0x00000000219ab540356cbb839cbe05303d7705fa"]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(prune_modes.account_history, Some(PruneMode::Distance(10064)));
+        assert!(prune_modes
+            .history_address_filter
+            .contains(&address!("00000000219ab540356cbb839cbe05303d7705fa")));
+    }
 }
@@ -1,20 +1,25 @@
+use serde::{Deserialize, Serialize};
 use strum::AsRefStr;
 
 /// Snapshot compression types.
-#[derive(Debug, Copy, Clone, Default, AsRefStr)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, AsRefStr, Serialize, Deserialize)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 pub enum Compression {
     /// LZ4 compression algorithm.
     #[strum(serialize = "lz4")]
+    #[serde(rename = "lz4")]
     Lz4,
     /// Zstandard (Zstd) compression algorithm.
     #[strum(serialize = "zstd")]
+    #[serde(rename = "zstd")]
     Zstd,
     /// Zstandard (Zstd) compression algorithm with a dictionary.
     #[strum(serialize = "zstd-dict")]
+    #[serde(rename = "zstd-dict")]
     ZstdWithDictionary,
     /// No compression, uncompressed snapshot.
     #[strum(serialize = "uncompressed")]
+    #[serde(rename = "uncompressed")]
     #[default]
     Uncompressed,
 }
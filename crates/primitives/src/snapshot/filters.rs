@@ -1,6 +1,7 @@
+use serde::{Deserialize, Serialize};
 use strum::AsRefStr;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 /// Snapshot filters.
 pub enum Filters {
     /// Snapshot uses filters with [InclusionFilter] and [PerfectHashingFunction].
@@ -16,23 +17,26 @@ impl Filters {
     }
 }
 
-#[derive(Debug, Copy, Clone, AsRefStr)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, AsRefStr, Serialize, Deserialize)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 /// Snapshot inclusion filter. Also see [Filters].
 pub enum InclusionFilter {
     #[strum(serialize = "cuckoo")]
+    #[serde(rename = "cuckoo")]
     /// Cuckoo filter
     Cuckoo,
 }
 
-#[derive(Debug, Copy, Clone, AsRefStr)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, AsRefStr, Serialize, Deserialize)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 /// Snapshot perfect hashing  function. Also see [Filters].
 pub enum PerfectHashingFunction {
     #[strum(serialize = "fmph")]
+    #[serde(rename = "fmph")]
     /// Fingerprint-Based Minimal Perfect Hash Function
     Fmph,
     #[strum(serialize = "gofmph")]
+    #[serde(rename = "gofmph")]
     /// Fingerprint-Based Minimal Perfect Hash Function with Group Optimization
     GoFmph,
 }
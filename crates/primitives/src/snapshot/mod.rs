@@ -24,6 +24,18 @@ pub struct HighestSnapshots {
     /// Highest snapshotted block of transactions, inclusive.
     /// If [`None`], no snapshot is available.
     pub transactions: Option<BlockNumber>,
+    /// Highest snapshotted block of account changesets, inclusive.
+    /// If [`None`], no snapshot is available.
+    pub account_changesets: Option<BlockNumber>,
+    /// Highest snapshotted block of storage changesets, inclusive.
+    /// If [`None`], no snapshot is available.
+    pub storage_changesets: Option<BlockNumber>,
+    /// Highest snapshotted block of transaction senders, inclusive.
+    /// If [`None`], no snapshot is available.
+    pub senders: Option<BlockNumber>,
+    /// Highest snapshotted block of bytecodes, inclusive.
+    /// If [`None`], no snapshot is available.
+    pub bytecodes: Option<BlockNumber>,
 }
 
 impl HighestSnapshots {
@@ -33,6 +45,10 @@ impl HighestSnapshots {
             SnapshotSegment::Headers => self.headers,
             SnapshotSegment::Transactions => self.transactions,
             SnapshotSegment::Receipts => self.receipts,
+            SnapshotSegment::AccountChangeSets => self.account_changesets,
+            SnapshotSegment::StorageChangeSets => self.storage_changesets,
+            SnapshotSegment::TxSenders => self.senders,
+            SnapshotSegment::Bytecodes => self.bytecodes,
         }
     }
 
@@ -42,6 +58,10 @@ impl HighestSnapshots {
             SnapshotSegment::Headers => &mut self.headers,
             SnapshotSegment::Transactions => &mut self.transactions,
             SnapshotSegment::Receipts => &mut self.receipts,
+            SnapshotSegment::AccountChangeSets => &mut self.account_changesets,
+            SnapshotSegment::StorageChangeSets => &mut self.storage_changesets,
+            SnapshotSegment::TxSenders => &mut self.senders,
+            SnapshotSegment::Bytecodes => &mut self.bytecodes,
         }
     }
 }
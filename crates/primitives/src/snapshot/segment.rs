@@ -35,6 +35,21 @@ pub enum SnapshotSegment {
     #[strum(serialize = "receipts")]
     /// Snapshot segment responsible for the `Receipts` table.
     Receipts,
+    #[strum(serialize = "account-changesets")]
+    #[cfg_attr(feature = "clap", value(name = "account-changesets"))]
+    /// Snapshot segment responsible for the `AccountChangeSet` table.
+    AccountChangeSets,
+    #[strum(serialize = "storage-changesets")]
+    #[cfg_attr(feature = "clap", value(name = "storage-changesets"))]
+    /// Snapshot segment responsible for the `StorageChangeSet` table.
+    StorageChangeSets,
+    #[strum(serialize = "senders")]
+    #[cfg_attr(feature = "clap", value(name = "senders"))]
+    /// Snapshot segment responsible for the `TxSenders` table.
+    TxSenders,
+    #[strum(serialize = "bytecodes")]
+    /// Snapshot segment responsible for the `Bytecodes` table.
+    Bytecodes,
 }
 
 impl SnapshotSegment {
@@ -50,11 +65,25 @@ impl SnapshotSegment {
 
         match self {
             SnapshotSegment::Headers => default_config,
-            SnapshotSegment::Transactions => default_config,
-            SnapshotSegment::Receipts => default_config,
+            // Transactions and receipts compress much better with a dictionary trained on them.
+            SnapshotSegment::Transactions | SnapshotSegment::Receipts => {
+                SegmentConfig { compression: Compression::ZstdWithDictionary, ..default_config }
+            }
+            SnapshotSegment::TxSenders => default_config,
+            SnapshotSegment::Bytecodes => default_config,
+            // Changesets are only queried by block number, so they don't need any filters.
+            SnapshotSegment::AccountChangeSets | SnapshotSegment::StorageChangeSets => {
+                SegmentConfig { filters: Filters::WithoutFilters, ..default_config }
+            }
         }
     }
 
+    /// Returns `true` if every snapshot of the segment holds all data up to its last block,
+    /// starting at genesis, and therefore replaces the previous snapshots of the segment.
+    pub const fn is_cumulative(&self) -> bool {
+        matches!(self, SnapshotSegment::Bytecodes)
+    }
+
     /// Returns the default file name for the provided segment and range.
     pub fn filename(
         &self,
@@ -174,6 +203,11 @@ impl SegmentHeader {
         *self.block_range.end()
     }
 
+    /// Returns the segment type.
+    pub fn segment(&self) -> SnapshotSegment {
+        self.segment
+    }
+
     /// Returns the first transaction number of the segment.
     pub fn tx_start(&self) -> TxNumber {
         *self.tx_range.start()
@@ -182,14 +216,20 @@ impl SegmentHeader {
     /// Returns the row offset which depends on whether the segment is block or transaction based.
     pub fn start(&self) -> u64 {
         match self.segment {
-            SnapshotSegment::Headers => self.block_start(),
-            SnapshotSegment::Transactions | SnapshotSegment::Receipts => self.tx_start(),
+            SnapshotSegment::Headers |
+            SnapshotSegment::AccountChangeSets |
+            SnapshotSegment::StorageChangeSets => self.block_start(),
+            SnapshotSegment::Transactions |
+            SnapshotSegment::Receipts |
+            SnapshotSegment::TxSenders => self.tx_start(),
+            // Bytecodes are only queried by their hash, rows are numbered from zero.
+            SnapshotSegment::Bytecodes => 0,
         }
     }
 }
 
 /// Configuration used on the segment.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct SegmentConfig {
     /// Inclusion filters used on the segment
     pub filters: Filters,
@@ -219,6 +259,20 @@ mod tests {
                 "snapshot_transactions_1123233_11223233_1123233_2123233",
                 None,
             ),
+            (
+                SnapshotSegment::AccountChangeSets,
+                500_000..=999_999,
+                0..=10,
+                "snapshot_account-changesets_500000_999999_0_10",
+                None,
+            ),
+            (
+                SnapshotSegment::TxSenders,
+                30..=300,
+                110..=1000,
+                "snapshot_senders_30_300_110_1000",
+                None,
+            ),
             (
                 SnapshotSegment::Headers,
                 2..=30,
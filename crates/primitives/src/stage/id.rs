@@ -51,6 +51,13 @@ impl StageId {
         StageId::Finish,
     ];
 
+    /// The snap sync stage, which downloads the state of a recent block instead of executing all
+    /// blocks up to it.
+    ///
+    /// Its checkpoint is the block whose state was downloaded. It's not part of [StageId::ALL], as
+    /// it only runs on nodes that opt into snap sync.
+    pub const SNAP_SYNC: StageId = StageId::Other("SnapSync");
+
     /// Return stage id formatted as string.
     pub fn as_str(&self) -> &str {
         match self {
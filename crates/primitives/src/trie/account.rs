@@ -68,4 +68,10 @@ impl TrieAccount {
     pub fn storage_root(&self) -> B256 {
         self.storage_root
     }
+
+    /// Returns the account with the given storage root.
+    pub fn with_storage_root(mut self, storage_root: B256) -> Self {
+        self.storage_root = storage_root;
+        self
+    }
 }
@@ -16,7 +16,7 @@ pub mod nodes;
 pub use nodes::StoredBranchNode;
 
 mod proofs;
-pub use proofs::{AccountProof, StorageProof};
+pub use proofs::{AccountProof, ExecutionWitness, StorageProof};
 
 mod storage;
 pub use storage::StorageTrieEntry;
@@ -2,6 +2,8 @@
 
 use super::Nibbles;
 use crate::{keccak256, Account, Address, Bytes, B256, U256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// The merkle proof with the relevant account info.
 #[derive(PartialEq, Eq, Default, Debug)]
@@ -84,3 +86,25 @@ impl StorageProof {
         self.proof = proof;
     }
 }
+
+/// The witness of the state that is accessed while executing a block.
+///
+/// Contains every account and storage trie node on the paths to the accessed accounts and
+/// storage slots, and the bytecodes of the accessed contracts.
+#[derive(Clone, PartialEq, Eq, Default, Debug, Serialize, Deserialize)]
+pub struct ExecutionWitness {
+    /// Rlp-serialized trie nodes keyed by their hash.
+    pub state: BTreeMap<B256, Bytes>,
+    /// Contract bytecodes keyed by their hash.
+    pub codes: BTreeMap<B256, Bytes>,
+}
+
+impl ExecutionWitness {
+    /// Add the trie nodes of the account proof and its storage proofs.
+    pub fn add_account_proof(&mut self, proof: &AccountProof) {
+        let storage_nodes = proof.storage_proofs.iter().flat_map(|proof| proof.proof.iter());
+        for node in proof.proof.iter().chain(storage_nodes) {
+            self.state.insert(keccak256(node), node.clone());
+        }
+    }
+}
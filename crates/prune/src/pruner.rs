@@ -5,12 +5,26 @@ use crate::{
     segments::{PruneInput, Segment},
     Metrics, PrunerError, PrunerEvent,
 };
-use reth_db::database::Database;
-use reth_primitives::{BlockNumber, PruneMode, PruneProgress, PruneSegment};
-use reth_provider::{ProviderFactory, PruneCheckpointReader};
+use reth_db::{
+    cursor::DbCursorRO, database::Database, models::BlockNumberAddress, table::Table, tables,
+    transaction::DbTx, DatabaseError,
+};
+use reth_primitives::{
+    BlockNumber, PruneCheckpoint, PruneMode, PruneProgress, PruneSegment, PruneSegmentError,
+    TxNumber,
+};
+use reth_provider::{
+    BlockReader, DatabaseProvider, HeaderProvider, ProviderError, ProviderFactory,
+    PruneCheckpointReader,
+};
 use reth_snapshot::HighestSnapshotsTracker;
 use reth_tokio_util::EventListeners;
-use std::{collections::BTreeMap, sync::Arc, time::Instant};
+use std::{
+    collections::BTreeMap,
+    ops::{RangeBounds, RangeInclusive},
+    sync::Arc,
+    time::Instant,
+};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::{debug, trace};
 
@@ -20,6 +34,17 @@ pub type PrunerResult = Result<PruneProgress, PrunerError>;
 /// The pruner type itself with the result of [Pruner::run]
 pub type PrunerWithResult<DB> = (Pruner<DB>, PrunerResult);
 
+/// Estimate of the data that would be pruned for a segment, see [Pruner::dry_run].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PruneEstimate {
+    /// Block up to which the segment would be pruned, inclusive.
+    pub to_block: BlockNumber,
+    /// Number of entries that would be deleted from the database.
+    pub rows: usize,
+    /// Number of bytes that would be freed in the tables of the segment.
+    pub bytes: u64,
+}
+
 /// Pruning routine. Main pruning logic happens in [Pruner::run].
 #[derive(Debug)]
 pub struct Pruner<DB> {
@@ -73,6 +98,35 @@ impl<DB: Database> Pruner<DB> {
 
     /// Run the pruner
     pub fn run(&mut self, tip_block_number: BlockNumber) -> PrunerResult {
+        // Multiply `self.delete_limit` (number of rows to delete per block) by number of blocks
+        // since last pruner run. `self.previous_tip_block_number` is close to
+        // `tip_block_number`, usually within `self.block_interval` blocks, so
+        // `delete_limit` will not be too high. If it's too high, we additionally limit it by
+        // `self.prune_max_blocks_per_run`.
+        //
+        // Also see docs for `self.previous_tip_block_number`.
+        let blocks_since_last_run =
+            (self.previous_tip_block_number.map_or(1, |previous_tip_block_number| {
+                // Saturating subtraction is needed for the case when the chain was reverted,
+                // meaning current block number might be less than the previous tip
+                // block number.
+                tip_block_number.saturating_sub(previous_tip_block_number) as usize
+            }))
+            .min(self.prune_max_blocks_per_run);
+        let delete_limit = self.delete_limit * blocks_since_last_run;
+
+        self.run_with_delete_limit(tip_block_number, delete_limit)
+    }
+
+    /// Run the pruner, deleting at most `delete_limit` entries in total.
+    ///
+    /// Unlike [Pruner::run], the limit doesn't depend on the number of blocks since the last run,
+    /// so it can be called repeatedly with the same tip until the pruning is finished.
+    pub fn run_with_delete_limit(
+        &mut self,
+        tip_block_number: BlockNumber,
+        mut delete_limit: usize,
+    ) -> PrunerResult {
         if tip_block_number == 0 {
             self.previous_tip_block_number = Some(tip_block_number);
 
@@ -91,23 +145,6 @@ impl<DB: Database> Pruner<DB> {
         // TODO(alexey): prune snapshotted segments of data (headers, transactions)
         let highest_snapshots = *self.highest_snapshots_tracker.borrow();
 
-        // Multiply `self.delete_limit` (number of rows to delete per block) by number of blocks
-        // since last pruner run. `self.previous_tip_block_number` is close to
-        // `tip_block_number`, usually within `self.block_interval` blocks, so
-        // `delete_limit` will not be too high. If it's too high, we additionally limit it by
-        // `self.prune_max_blocks_per_run`.
-        //
-        // Also see docs for `self.previous_tip_block_number`.
-        let blocks_since_last_run =
-            (self.previous_tip_block_number.map_or(1, |previous_tip_block_number| {
-                // Saturating subtraction is needed for the case when the chain was reverted,
-                // meaning current block number might be less than the previous tip
-                // block number.
-                tip_block_number.saturating_sub(previous_tip_block_number) as usize
-            }))
-            .min(self.prune_max_blocks_per_run);
-        let mut delete_limit = self.delete_limit * blocks_since_last_run;
-
         for segment in &self.segments {
             if delete_limit == 0 {
                 break
@@ -115,7 +152,9 @@ impl<DB: Database> Pruner<DB> {
 
             if let Some((to_block, prune_mode)) = segment
                 .mode()
-                .map(|mode| mode.prune_target_block(tip_block_number, segment.segment()))
+                .map(|mode| {
+                    prune_target_block(&provider, mode, tip_block_number, segment.segment())
+                })
                 .transpose()?
                 .flatten()
             {
@@ -238,6 +277,57 @@ impl<DB: Database> Pruner<DB> {
         Ok(PruneProgress::from_done(done))
     }
 
+    /// Estimates the data that would be pruned at the provided tip, without deleting anything.
+    ///
+    /// The entries that would be deleted are counted with read-only cursors, and the freed bytes
+    /// are estimated from the share of these entries in the size of each table. For segments that
+    /// retain some entries in the pruned range, like contract logs or the history of retained
+    /// addresses, the estimate is an upper bound.
+    pub fn dry_run(
+        &self,
+        tip_block_number: BlockNumber,
+    ) -> Result<BTreeMap<PruneSegment, PruneEstimate>, PrunerError> {
+        let provider = self.provider_factory.provider()?;
+        let mut estimates = BTreeMap::new();
+
+        if tip_block_number == 0 {
+            return Ok(estimates)
+        }
+
+        let mut targets = BTreeMap::new();
+        for segment in &self.segments {
+            if let Some((to_block, _)) = segment
+                .mode()
+                .map(|mode| {
+                    prune_target_block(&provider, mode, tip_block_number, segment.segment())
+                })
+                .transpose()?
+                .flatten()
+            {
+                targets.insert(segment.segment(), to_block);
+            }
+        }
+
+        for (&segment, &to_block) in &targets {
+            let previous_checkpoint = provider.get_prune_checkpoint(segment)?;
+            let (rows, bytes) =
+                estimate_segment(&provider, segment, previous_checkpoint, to_block, &targets)?;
+
+            trace!(
+                target: "pruner",
+                ?segment,
+                %to_block,
+                %rows,
+                %bytes,
+                "Estimated segment pruning"
+            );
+
+            estimates.insert(segment, PruneEstimate { to_block, rows, bytes });
+        }
+
+        Ok(estimates)
+    }
+
     /// Returns `true` if the pruning is needed at the provided tip block number.
     /// This determined by the check against minimum pruning interval and last pruned block number.
     pub fn is_pruning_needed(&self, tip_block_number: BlockNumber) -> bool {
@@ -261,12 +351,250 @@ impl<DB: Database> Pruner<DB> {
     }
 }
 
+/// Returns block up to which pruning of the segment needs to be done, inclusive, according to
+/// the provided tip.
+///
+/// In addition to [`PruneMode::prune_target_block`], it resolves the targets of
+/// [`PruneMode::Age`] and [`PruneMode::Size`] from the header timestamps and the table sizes.
+/// The resolved target always leaves at least [`PruneSegment::min_blocks`] in the database.
+fn prune_target_block<TX: DbTx>(
+    provider: &DatabaseProvider<TX>,
+    mode: PruneMode,
+    tip_block_number: BlockNumber,
+    segment: PruneSegment,
+) -> Result<Option<(BlockNumber, PruneMode)>, PrunerError> {
+    let before_block = match mode {
+        PruneMode::Age(age) => {
+            let timestamp = |block_number| {
+                provider
+                    .header_by_number(block_number)?
+                    .map(|header| header.timestamp)
+                    .ok_or(ProviderError::HeaderNotFound(block_number.into()))
+            };
+            let cutoff = timestamp(tip_block_number)?.saturating_sub(age);
+
+            // Timestamps increase with block numbers, so look for the first block that is not
+            // older than the cutoff.
+            let (mut low, mut high) = (0, tip_block_number);
+            while low < high {
+                let mid = low + (high - low) / 2;
+                if timestamp(mid)? < cutoff {
+                    low = mid + 1;
+                } else {
+                    high = mid;
+                }
+            }
+            low
+        }
+        PruneMode::Size(size_limit) => {
+            if !segment.supports_size_limit() {
+                return Err(PruneSegmentError::Configuration(segment).into())
+            }
+            let size = segment_size(provider.tx_ref(), segment)?;
+            if size <= size_limit {
+                return Ok(None)
+            }
+
+            // Assume the data is evenly spread across the blocks that are left, and prune
+            // enough of the oldest ones to get below the limit.
+            let first_block = provider
+                .get_prune_checkpoint(segment)?
+                .and_then(|checkpoint| checkpoint.block_number)
+                .map_or(0, |block_number| block_number + 1);
+            let blocks = (tip_block_number + 1).saturating_sub(first_block).max(1);
+            let block_size = (size / blocks).max(1);
+            first_block + (size - size_limit).div_ceil(block_size)
+        }
+        mode => return Ok(mode.prune_target_block(tip_block_number, segment)?),
+    };
+
+    let before_block = before_block.min(tip_block_number.saturating_sub(segment.min_blocks()));
+    if before_block == 0 {
+        return Ok(None)
+    }
+
+    Ok(Some((before_block - 1, mode)))
+}
+
+/// Estimates the number of entries and bytes that pruning the segment up to `to_block` would
+/// delete, using read-only cursors. See [Pruner::dry_run].
+///
+/// The prune targets of all segments are needed, because the bytecodes that are still referenced
+/// depend on the account history that's retained.
+fn estimate_segment<TX: DbTx>(
+    provider: &DatabaseProvider<TX>,
+    segment: PruneSegment,
+    previous_checkpoint: Option<PruneCheckpoint>,
+    to_block: BlockNumber,
+    targets: &BTreeMap<PruneSegment, BlockNumber>,
+) -> Result<(usize, u64), PrunerError> {
+    let tx = provider.tx_ref();
+    let from_block =
+        previous_checkpoint.and_then(|checkpoint| checkpoint.block_number).map_or(0, |b| b + 1);
+    let block_range = from_block..=to_block;
+
+    let tx_range = || -> Result<RangeInclusive<TxNumber>, PrunerError> {
+        let from_tx = match previous_checkpoint.and_then(|checkpoint| checkpoint.tx_number) {
+            Some(tx_number) => tx_number + 1,
+            None => provider.block_body_indices(from_block)?.map_or(0, |body| body.first_tx_num()),
+        };
+        let to_tx = provider
+            .block_body_indices(to_block)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(to_block))?
+            .last_tx_num();
+        Ok(from_tx..=to_tx)
+    };
+
+    let estimate = match segment {
+        PruneSegment::SenderRecovery => {
+            let rows = count_entries::<tables::TxSenders, _>(tx, tx_range()?)?;
+            (rows, table_share::<tables::TxSenders, _>(tx, rows)?)
+        }
+        PruneSegment::TransactionLookup => {
+            // Every transaction has exactly one entry keyed by its hash
+            let rows = tx_range()?.count();
+            (rows, table_share::<tables::TxHashNumber, _>(tx, rows)?)
+        }
+        PruneSegment::Receipts | PruneSegment::ContractLogs => {
+            let rows = count_entries::<tables::Receipts, _>(tx, tx_range()?)?;
+            (rows, table_share::<tables::Receipts, _>(tx, rows)?)
+        }
+        PruneSegment::Transactions => {
+            let rows = count_entries::<tables::Transactions, _>(tx, tx_range()?)?;
+            (rows, table_share::<tables::Transactions, _>(tx, rows)?)
+        }
+        PruneSegment::Headers => {
+            let headers = count_entries::<tables::Headers, _>(tx, block_range.clone())?;
+            let tds = count_entries::<tables::HeaderTD, _>(tx, block_range.clone())?;
+            let canonical = count_entries::<tables::CanonicalHeaders, _>(tx, block_range)?;
+            (
+                headers + tds + canonical,
+                table_share::<tables::Headers, _>(tx, headers)? +
+                    table_share::<tables::HeaderTD, _>(tx, tds)? +
+                    table_share::<tables::CanonicalHeaders, _>(tx, canonical)?,
+            )
+        }
+        PruneSegment::AccountHistory => {
+            let changesets = count_entries::<tables::AccountChangeSet, _>(tx, block_range)?;
+            // Every changeset has an entry in the history shards, so the same share of the shards
+            // is pruned.
+            let indices = changeset_share::<tables::AccountChangeSet, tables::AccountHistory, _>(
+                tx, changesets,
+            )?;
+            (
+                changesets + indices,
+                table_share::<tables::AccountChangeSet, _>(tx, changesets)? +
+                    table_share::<tables::AccountHistory, _>(tx, indices)?,
+            )
+        }
+        PruneSegment::StorageHistory => {
+            let changesets = count_entries::<tables::StorageChangeSet, _>(
+                tx,
+                BlockNumberAddress::range(block_range),
+            )?;
+            let indices = changeset_share::<tables::StorageChangeSet, tables::StorageHistory, _>(
+                tx, changesets,
+            )?;
+            (
+                changesets + indices,
+                table_share::<tables::StorageChangeSet, _>(tx, changesets)? +
+                    table_share::<tables::StorageHistory, _>(tx, indices)?,
+            )
+        }
+        PruneSegment::Bytecodes => {
+            // Bytecodes are referenced by the current state and by the account changesets that
+            // are retained after pruning the account history.
+            let retained_from = targets.get(&PruneSegment::AccountHistory).map_or(0, |b| b + 1);
+            let referenced = segments::referenced_bytecodes(provider, retained_from)?;
+
+            let mut rows = 0;
+            for entry in tx.cursor_read::<tables::Bytecodes>()?.walk(None)? {
+                if !referenced.contains(&entry?.0) {
+                    rows += 1;
+                }
+            }
+            (rows, table_share::<tables::Bytecodes, _>(tx, rows)?)
+        }
+    };
+
+    Ok(estimate)
+}
+
+/// Counts the entries of the table in the key range.
+fn count_entries<T: Table, TX: DbTx>(
+    tx: &TX,
+    range: impl RangeBounds<T::Key>,
+) -> Result<usize, DatabaseError> {
+    let mut count = 0;
+    for entry in tx.cursor_read::<T>()?.walk_range(range)? {
+        entry?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Returns the approximate size in bytes of the given number of entries of the table, assuming
+/// all entries have the same size.
+fn table_share<T: Table, TX: DbTx>(tx: &TX, rows: usize) -> Result<u64, DatabaseError> {
+    let entries = tx.entries::<T>()?;
+    if entries == 0 {
+        return Ok(0)
+    }
+    let size = tx.table_size::<T>()? as u128;
+    Ok((size * rows.min(entries) as u128 / entries as u128) as u64)
+}
+
+/// Returns the approximate number of entries of the history table `H` that index the given number
+/// of changesets of the table `C`.
+fn changeset_share<C: Table, H: Table, TX: DbTx>(
+    tx: &TX,
+    changesets: usize,
+) -> Result<usize, DatabaseError> {
+    let total_changesets = tx.entries::<C>()?;
+    if total_changesets == 0 {
+        return Ok(0)
+    }
+    let indices = tx.entries::<H>()? as u128;
+    Ok((indices * changesets.min(total_changesets) as u128 / total_changesets as u128) as usize)
+}
+
+/// Returns the total size of the tables the segment prunes, in bytes.
+fn segment_size<TX: DbTx>(tx: &TX, segment: PruneSegment) -> Result<u64, DatabaseError> {
+    let size = match segment {
+        PruneSegment::SenderRecovery => tx.table_size::<tables::TxSenders>()?,
+        PruneSegment::TransactionLookup => tx.table_size::<tables::TxHashNumber>()?,
+        PruneSegment::Receipts | PruneSegment::ContractLogs => {
+            tx.table_size::<tables::Receipts>()?
+        }
+        PruneSegment::AccountHistory => {
+            tx.table_size::<tables::AccountChangeSet>()? +
+                tx.table_size::<tables::AccountHistory>()?
+        }
+        PruneSegment::StorageHistory => {
+            tx.table_size::<tables::StorageChangeSet>()? +
+                tx.table_size::<tables::StorageHistory>()?
+        }
+        PruneSegment::Headers => {
+            tx.table_size::<tables::Headers>()? +
+                tx.table_size::<tables::HeaderTD>()? +
+                tx.table_size::<tables::CanonicalHeaders>()?
+        }
+        PruneSegment::Transactions => tx.table_size::<tables::Transactions>()?,
+        PruneSegment::Bytecodes => tx.table_size::<tables::Bytecodes>()?,
+    };
+    Ok(size as u64)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::Pruner;
-    use reth_db::test_utils::create_test_rw_db;
-    use reth_primitives::MAINNET;
-    use reth_provider::ProviderFactory;
+    use super::prune_target_block;
+    use crate::{segments::SenderRecovery, Pruner};
+    use reth_db::{tables, test_utils::create_test_rw_db};
+    use reth_interfaces::test_utils::{generators, generators::random_block_range};
+    use reth_primitives::{Header, PruneMode, PruneSegment, B256, MAINNET};
+    use reth_provider::{ProviderFactory, PruneCheckpointReader};
+    use reth_stages::test_utils::TestStageDB;
+    use std::sync::Arc;
     use tokio::sync::watch;
 
     #[test]
@@ -289,4 +617,77 @@ mod tests {
         let third_block_number = second_block_number;
         assert!(!pruner.is_pruning_needed(third_block_number));
     }
+
+    #[test]
+    fn prune_target_block_by_age() {
+        let db = TestStageDB::default();
+        let headers = (0..=10)
+            .map(|number| {
+                Header { number, timestamp: number * 12, ..Default::default() }.seal_slow()
+            })
+            .collect::<Vec<_>>();
+        db.insert_headers(headers.iter()).expect("insert headers");
+
+        let provider = db.factory.provider_rw().unwrap();
+        let segment = PruneSegment::SenderRecovery;
+
+        // Blocks 7..=10 are not older than 36 seconds before the tip
+        let mode = PruneMode::Age(36);
+        assert_eq!(prune_target_block(&provider, mode, 10, segment).unwrap(), Some((6, mode)));
+
+        // Nothing is old enough yet
+        let mode = PruneMode::Age(1000);
+        assert_eq!(prune_target_block(&provider, mode, 10, segment).unwrap(), None);
+
+        // Minimum number of blocks are always left in the database
+        let mode = PruneMode::Age(0);
+        assert_eq!(prune_target_block(&provider, mode, 10, PruneSegment::Receipts).unwrap(), None);
+    }
+
+    #[test]
+    fn dry_run() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 1..=10, B256::ZERO, 2..3);
+        db.insert_blocks(blocks.iter(), None).expect("insert blocks");
+
+        let mut transaction_senders = Vec::new();
+        for block in &blocks {
+            for transaction in &block.body {
+                transaction_senders.push((
+                    transaction_senders.len() as u64,
+                    transaction.recover_signer().expect("recover signer"),
+                ));
+            }
+        }
+        db.insert_transaction_senders(transaction_senders.clone())
+            .expect("insert transaction senders");
+
+        let pruner = Pruner::new(
+            db.factory.clone(),
+            vec![Arc::new(SenderRecovery::new(PruneMode::Full))],
+            5,
+            0,
+            5,
+            watch::channel(None).1,
+        );
+
+        // All senders would be pruned
+        let estimates = pruner.dry_run(10).unwrap();
+        let estimate = estimates.get(&PruneSegment::SenderRecovery).unwrap();
+        assert_eq!(estimate.to_block, 10);
+        assert_eq!(estimate.rows, transaction_senders.len());
+
+        // Nothing is actually pruned
+        assert_eq!(db.table::<tables::TxSenders>().unwrap().len(), transaction_senders.len());
+        assert_eq!(
+            db.factory
+                .provider()
+                .unwrap()
+                .get_prune_checkpoint(PruneSegment::SenderRecovery)
+                .unwrap(),
+            None
+        );
+    }
 }
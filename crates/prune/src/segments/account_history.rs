@@ -1,22 +1,25 @@
 use crate::{
     segments::{
-        history::prune_history_indices, PruneInput, PruneOutput, PruneOutputCheckpoint, Segment,
+        history::{prune_history_indices, update_retained_addresses},
+        PruneInput, PruneOutput, PruneOutputCheckpoint, Segment,
     },
     PrunerError,
 };
 use reth_db::{database::Database, models::ShardedKey, tables};
-use reth_primitives::{PruneMode, PruneSegment};
+use reth_primitives::{HistoryAddressPruneConfig, PruneMode, PruneSegment};
 use reth_provider::DatabaseProviderRW;
 use tracing::{instrument, trace};
 
 #[derive(Debug)]
 pub struct AccountHistory {
     mode: PruneMode,
+    /// Addresses whose account history is retained in full.
+    retained_addresses: HistoryAddressPruneConfig,
 }
 
 impl AccountHistory {
-    pub fn new(mode: PruneMode) -> Self {
-        Self { mode }
+    pub fn new(mode: PruneMode, retained_addresses: HistoryAddressPruneConfig) -> Self {
+        Self { mode, retained_addresses }
     }
 }
 
@@ -35,6 +38,17 @@ impl<DB: Database> Segment<DB> for AccountHistory {
         provider: &DatabaseProviderRW<DB>,
         input: PruneInput,
     ) -> Result<PruneOutput, PrunerError> {
+        let first_unpruned_block = input
+            .previous_checkpoint
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map_or(0, |block_number| block_number + 1);
+        update_retained_addresses(
+            provider,
+            &self.retained_addresses,
+            first_unpruned_block,
+            |checkpoint| &mut checkpoint.account_history_from,
+        )?;
+
         let range = match input.get_next_block_range() {
             Some(range) => range,
             None => {
@@ -49,7 +63,7 @@ impl<DB: Database> Segment<DB> for AccountHistory {
             .prune_table_with_range::<tables::AccountChangeSet>(
                 range,
                 input.delete_limit / 2,
-                |_| false,
+                |(_, change)| self.retained_addresses.contains(&change.address),
                 |row| last_changeset_pruned_block = Some(row.0),
             )?;
         trace!(target: "pruner", pruned = %pruned_changesets, %done, "Pruned account history (changesets)");
@@ -65,6 +79,7 @@ impl<DB: Database> Segment<DB> for AccountHistory {
             last_changeset_pruned_block,
             |a, b| a.key == b.key,
             |key| ShardedKey::last(key.key),
+            |key| self.retained_addresses.contains(&key.key),
         )?;
         trace!(target: "pruner", %processed, pruned = %pruned_indices, %done, "Pruned account history (history)" );
 
@@ -88,7 +103,9 @@ mod tests {
         generators,
         generators::{random_block_range, random_changeset_range, random_eoa_account_range},
     };
-    use reth_primitives::{BlockNumber, PruneCheckpoint, PruneMode, PruneSegment, B256};
+    use reth_primitives::{
+        BlockNumber, PruneCheckpoint, PruneMode, PruneSegment, RetainedHistoryCheckpoint, B256,
+    };
     use reth_provider::PruneCheckpointReader;
     use reth_stages::test_utils::TestStageDB;
     use std::{collections::BTreeMap, ops::AddAssign};
@@ -142,7 +159,7 @@ mod tests {
                 to_block,
                 delete_limit: 2000,
             };
-            let segment = AccountHistory::new(prune_mode);
+            let segment = AccountHistory::new(prune_mode, Default::default());
 
             let provider = db.factory.provider_rw().unwrap();
             let result = segment.prune(&provider, input).unwrap();
@@ -240,4 +257,76 @@ mod tests {
         test_prune(998, 2, (true, 998));
         test_prune(1400, 3, (true, 804));
     }
+
+    #[test]
+    fn prune_with_retained_addresses() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 1..=100, B256::ZERO, 0..1);
+        db.insert_blocks(blocks.iter(), None).expect("insert blocks");
+
+        let accounts =
+            random_eoa_account_range(&mut rng, 0..2).into_iter().collect::<BTreeMap<_, _>>();
+        let retained_address = *accounts.keys().next().unwrap();
+
+        let (changesets, _) = random_changeset_range(
+            &mut rng,
+            blocks.iter(),
+            accounts.into_iter().map(|(addr, acc)| (addr, (acc, Vec::new()))),
+            0..0,
+            0..0,
+        );
+        db.insert_changesets(changesets.clone(), None).expect("insert changesets");
+        db.insert_history(changesets.clone(), None).expect("insert history");
+
+        let original_changesets = db.table::<tables::AccountChangeSet>().unwrap();
+        let original_shards = db.table::<tables::AccountHistory>().unwrap();
+
+        let to_block = 50;
+        let prune_mode = PruneMode::Before(to_block);
+        let input = PruneInput { previous_checkpoint: None, to_block, delete_limit: 1000 };
+        let segment = AccountHistory::new(prune_mode, [retained_address].into_iter().collect());
+
+        let provider = db.factory.provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        assert_matches!(result, PruneOutput { done: true, .. });
+        provider.commit().expect("commit");
+
+        // The address was retained before anything was pruned, so its history is complete
+        assert_eq!(
+            db.table::<tables::PruneRetainedAddresses>().unwrap(),
+            vec![(
+                retained_address,
+                RetainedHistoryCheckpoint {
+                    account_history_from: Some(0),
+                    storage_history_from: None
+                }
+            )]
+        );
+
+        // Changesets of the retained address are left untouched, the others are pruned
+        let expected_changesets = original_changesets
+            .into_iter()
+            .filter(|(block_number, change)| {
+                change.address == retained_address || *block_number > to_block
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(db.table::<tables::AccountChangeSet>().unwrap(), expected_changesets);
+
+        // Shards of the retained address are left untouched, the others are pruned
+        let actual_shards = db.table::<tables::AccountHistory>().unwrap();
+        assert_eq!(
+            actual_shards.iter().filter(|(key, _)| key.key == retained_address).collect::<Vec<_>>(),
+            original_shards
+                .iter()
+                .filter(|(key, _)| key.key == retained_address)
+                .collect::<Vec<_>>()
+        );
+        assert!(actual_shards
+            .iter()
+            .filter(|(key, _)| key.key != retained_address)
+            .flat_map(|(_, blocks)| blocks.iter(0))
+            .all(|block| block > to_block as usize));
+    }
 }
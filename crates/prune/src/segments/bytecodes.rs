@@ -0,0 +1,348 @@
+use crate::{
+    segments::{PruneInput, PruneOutput, PruneOutputCheckpoint, Segment},
+    PrunerError,
+};
+use parking_lot::Mutex;
+use reth_db::{
+    cursor::{DbCursorRO, DbCursorRW},
+    database::Database,
+    models::AccountBeforeTx,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_primitives::{BlockNumber, PruneMode, PruneSegment, SnapshotSegment, B256};
+use reth_provider::{
+    BlockHashReader, ChangeSetReader, DatabaseProvider, DatabaseProviderRW, PruneCheckpointReader,
+};
+use std::collections::HashSet;
+use tracing::{instrument, trace};
+
+/// Minimum number of blocks between two full passes over the `Bytecodes` table.
+///
+/// Every pass needs to walk the whole plain account state, so it's not worth running it on every
+/// pruner run.
+const BLOCKS_BETWEEN_PASSES: u64 = 100_000;
+
+/// Prunes the bytecodes that are not referenced anymore.
+///
+/// Bytecodes are keyed by their hash and not by the block they were deployed at, so instead of
+/// pruning a block range, the segment collects all code hashes referenced by the plain account
+/// state and by the account changesets that are still retained, and deletes the rest. This keeps
+/// the bytecodes needed to serve any historical state that hasn't been pruned yet.
+///
+/// A pass that hits the delete limit is resumed by the next run. The position of the sweep is
+/// saved as the first 8 bytes of the next code hash to check in the `tx_number` of the checkpoint,
+/// while the `block_number` stays at the end of the last complete pass. The referenced bytecodes
+/// are kept in memory until the pass completes, and a resumed run only adds the ones referenced by
+/// the blocks executed since.
+///
+/// The segment should run after [`AccountHistory`](crate::segments::AccountHistory) in the same
+/// transaction, so the changesets it has just pruned don't keep the bytecodes alive.
+#[derive(Debug)]
+pub struct Bytecodes {
+    mode: PruneMode,
+    marked: Mutex<Option<MarkedBytecodes>>,
+}
+
+impl Bytecodes {
+    pub fn new(mode: PruneMode) -> Self {
+        Self { mode, marked: Mutex::new(None) }
+    }
+}
+
+impl<DB: Database> Segment<DB> for Bytecodes {
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::Bytecodes
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        input: PruneInput,
+    ) -> Result<PruneOutput, PrunerError> {
+        let range = match input.get_next_block_range() {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No bytecodes to prune");
+                return Ok(PruneOutput::done())
+            }
+        };
+        let previous_pass_end =
+            input.previous_checkpoint.and_then(|checkpoint| checkpoint.block_number);
+        let resume_position = input.previous_checkpoint.and_then(|checkpoint| checkpoint.tx_number);
+        if resume_position.is_none() &&
+            input.previous_checkpoint.is_some() &&
+            range.end() - range.start() + 1 < BLOCKS_BETWEEN_PASSES
+        {
+            trace!(target: "pruner", ?range, "Not enough blocks since the last bytecodes pruning pass");
+            return Ok(PruneOutput::done())
+        }
+
+        // Mark
+        let mut marked = self.marked.lock();
+        let resumed = match marked.as_mut() {
+            Some(marked) if resume_position.is_some() => marked.update(provider)?,
+            _ => false,
+        };
+        if !resumed {
+            // The history below the `AccountHistory` checkpoint is gone, so its bytecodes don't
+            // need to be kept
+            let retained_from = provider
+                .get_prune_checkpoint(PruneSegment::AccountHistory)?
+                .and_then(|checkpoint| checkpoint.block_number)
+                .map_or(0, |block_number| block_number + 1);
+            *marked = Some(MarkedBytecodes::new(provider, retained_from)?);
+        }
+        let referenced = &marked.as_ref().expect("marked bytecodes are set").referenced;
+        trace!(target: "pruner", referenced = %referenced.len(), %resumed, "Collected referenced bytecodes");
+
+        // Sweep
+        let start = resume_position.map(|position| {
+            let mut hash = B256::ZERO;
+            hash[..8].copy_from_slice(&position.to_be_bytes());
+            hash
+        });
+        let mut cursor = provider.tx_ref().cursor_write::<tables::Bytecodes>()?;
+        let mut walker = cursor.walk(start)?;
+        let mut pruned = 0;
+        let mut next_position = None;
+        while let Some((hash, _)) = walker.next().transpose()? {
+            if referenced.contains(&hash) {
+                continue
+            }
+            if pruned == input.delete_limit {
+                next_position = Some(u64::from_be_bytes(hash[..8].try_into().expect("8 bytes")));
+                break
+            }
+
+            walker.delete_current()?;
+            pruned += 1;
+        }
+        let done = next_position.is_none();
+        if done {
+            *marked = None;
+        }
+        trace!(target: "pruner", %pruned, %done, ?resume_position, ?next_position, "Pruned bytecodes");
+
+        Ok(PruneOutput {
+            done,
+            pruned,
+            checkpoint: Some(if done {
+                PruneOutputCheckpoint { block_number: Some(*range.end()), tx_number: None }
+            } else {
+                PruneOutputCheckpoint { block_number: previous_pass_end, tx_number: next_position }
+            }),
+        })
+    }
+}
+
+/// Bytecodes referenced by the state, kept across the runs of a pass.
+#[derive(Debug)]
+struct MarkedBytecodes {
+    /// Last block with account changes that the referenced bytecodes account for.
+    block_number: BlockNumber,
+    /// Canonical hash of [`Self::block_number`] at the time of marking, to detect reorgs.
+    block_hash: Option<B256>,
+    /// Hashes of the referenced bytecodes.
+    referenced: HashSet<B256>,
+}
+
+impl MarkedBytecodes {
+    fn new<TX: DbTx>(
+        provider: &DatabaseProvider<TX>,
+        retained_from: BlockNumber,
+    ) -> Result<Self, PrunerError> {
+        let block_number = provider
+            .tx_ref()
+            .cursor_read::<tables::AccountChangeSet>()?
+            .last()?
+            .map_or(0, |(block_number, _)| block_number);
+        Ok(Self {
+            block_number,
+            block_hash: provider.block_hash(block_number)?,
+            referenced: referenced_bytecodes(provider, retained_from)?,
+        })
+    }
+
+    /// Adds the bytecodes of the accounts changed after [`Self::block_number`].
+    ///
+    /// Returns `false` if the marked block is not canonical anymore, and the bytecodes need to be
+    /// marked again.
+    fn update<TX: DbTx>(&mut self, provider: &DatabaseProvider<TX>) -> Result<bool, PrunerError> {
+        if provider.block_hash(self.block_number)? != self.block_hash {
+            return Ok(false)
+        }
+
+        let tx = provider.tx_ref();
+        let mut plain_accounts = tx.cursor_read::<tables::PlainAccountState>()?;
+        for entry in
+            tx.cursor_read::<tables::AccountChangeSet>()?.walk(Some(self.block_number + 1))?
+        {
+            let (block_number, AccountBeforeTx { address, info }) = entry?;
+            // Both the bytecode before the change, which is now in the history, and the current
+            // one are referenced
+            self.referenced.extend(info.and_then(|info| info.bytecode_hash));
+            if let Some((_, account)) = plain_accounts.seek_exact(address)? {
+                self.referenced.extend(account.bytecode_hash);
+            }
+            self.block_number = block_number;
+        }
+        self.block_hash = provider.block_hash(self.block_number)?;
+
+        Ok(true)
+    }
+}
+
+/// Collects the hashes of all bytecodes referenced by the plain account state, and by the account
+/// changesets from `retained_from` onwards, both in the database and in the snapshots.
+pub(crate) fn referenced_bytecodes<TX: DbTx>(
+    provider: &DatabaseProvider<TX>,
+    retained_from: BlockNumber,
+) -> Result<HashSet<B256>, PrunerError> {
+    let tx = provider.tx_ref();
+    let mut referenced = HashSet::new();
+    for entry in tx.cursor_read::<tables::PlainAccountState>()?.walk(None)? {
+        referenced.extend(entry?.1.bytecode_hash);
+    }
+    for entry in tx.cursor_read::<tables::AccountChangeSet>()?.walk(Some(retained_from))? {
+        referenced.extend(entry?.1.info.and_then(|info| info.bytecode_hash));
+    }
+
+    // The changesets that were moved to snapshots can still be served from there
+    if let Some(highest_snapshot_block) = provider.snapshot_provider().and_then(|snapshots| {
+        snapshots.get_highest_snapshot_block(SnapshotSegment::AccountChangeSets)
+    }) {
+        for block_number in retained_from..=highest_snapshot_block {
+            for change in provider.account_block_changeset(block_number)? {
+                referenced.extend(change.info.and_then(|info| info.bytecode_hash));
+            }
+        }
+    }
+
+    Ok(referenced)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{Bytecodes, PruneInput, PruneOutput, Segment};
+    use assert_matches::assert_matches;
+    use reth_db::{models::AccountBeforeTx, tables, transaction::DbTxMut};
+    use reth_primitives::{
+        keccak256, Account, Address, Bytecode, Bytes, PruneCheckpoint, PruneMode, PruneSegment,
+    };
+    use reth_provider::PruneCheckpointReader;
+    use reth_stages::test_utils::TestStageDB;
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+
+        let bytecodes = (0..10u8)
+            .map(|i| {
+                let code = Bytes::from(vec![0x60, i]);
+                (keccak256(&code), Bytecode::new_raw(code))
+            })
+            .collect::<Vec<_>>();
+        let account =
+            |i: usize| Account { bytecode_hash: Some(bytecodes[i].0), ..Default::default() };
+
+        db.commit(|tx| {
+            for (hash, bytecode) in &bytecodes {
+                tx.put::<tables::Bytecodes>(*hash, bytecode.clone())?;
+            }
+            // Referenced by the current state
+            tx.put::<tables::PlainAccountState>(Address::with_last_byte(0), account(0))?;
+            tx.put::<tables::PlainAccountState>(Address::with_last_byte(1), account(1))?;
+            // Referenced by the retained history
+            tx.put::<tables::AccountChangeSet>(
+                10,
+                AccountBeforeTx { address: Address::with_last_byte(2), info: Some(account(2)) },
+            )?;
+            Ok(())
+        })
+        .expect("insert state");
+
+        // The same segment is used for all runs, so it keeps the marked bytecodes between them
+        let segment = Bytecodes::new(PruneMode::Before(100));
+        let test_prune = |to_block, expected_result: (bool, usize), expected_bytecodes: usize| {
+            let prune_mode = PruneMode::Before(to_block);
+            let input = PruneInput {
+                previous_checkpoint: db
+                    .factory
+                    .provider()
+                    .unwrap()
+                    .get_prune_checkpoint(PruneSegment::Bytecodes)
+                    .unwrap(),
+                to_block,
+                delete_limit: 5,
+            };
+
+            let provider = db.factory.provider_rw().unwrap();
+            let result = segment.prune(&provider, input).unwrap();
+            assert_matches!(
+                result,
+                PruneOutput { done, pruned, .. } if (done, pruned) == expected_result
+            );
+            if let Some(checkpoint) = result.checkpoint {
+                segment
+                    .save_checkpoint(&provider, checkpoint.as_prune_checkpoint(prune_mode))
+                    .unwrap();
+            }
+            provider.commit().expect("commit");
+
+            assert_eq!(db.table::<tables::Bytecodes>().unwrap().len(), expected_bytecodes);
+        };
+
+        // Not done, the position of the sweep is saved
+        test_prune(100, (false, 5), 5);
+        assert_matches!(
+            db.factory.provider().unwrap().get_prune_checkpoint(PruneSegment::Bytecodes).unwrap(),
+            Some(PruneCheckpoint { block_number: None, tx_number: Some(_), .. })
+        );
+
+        // A bytecode that's not swept yet gets referenced by a block executed in the meantime
+        let remaining = || {
+            db.table::<tables::Bytecodes>()
+                .unwrap()
+                .into_iter()
+                .map(|(hash, _)| hash)
+                .collect::<Vec<_>>()
+        };
+        let deployed = (3..bytecodes.len())
+            .find(|&i| remaining().contains(&bytecodes[i].0))
+            .expect("unreferenced bytecode left");
+        db.commit(|tx| {
+            tx.put::<tables::PlainAccountState>(Address::with_last_byte(3), account(deployed))?;
+            tx.put::<tables::AccountChangeSet>(
+                11,
+                AccountBeforeTx { address: Address::with_last_byte(3), info: None },
+            )?;
+            Ok(())
+        })
+        .expect("insert deployment");
+
+        // Done, only the referenced bytecodes are left
+        test_prune(100, (true, 1), 4);
+        let remaining = remaining();
+        assert!((0..3).chain([deployed]).all(|i| remaining.contains(&bytecodes[i].0)));
+        assert_eq!(
+            db.factory.provider().unwrap().get_prune_checkpoint(PruneSegment::Bytecodes).unwrap(),
+            Some(PruneCheckpoint {
+                block_number: Some(100),
+                tx_number: None,
+                prune_mode: PruneMode::Before(100)
+            })
+        );
+
+        // Once the history is pruned, its bytecode is not referenced anymore, but the next pass
+        // only happens after enough blocks
+        db.commit(|tx| Ok(tx.clear::<tables::AccountChangeSet>()?)).expect("clear changesets");
+        test_prune(200, (true, 0), 4);
+        test_prune(100_100, (true, 1), 3);
+    }
+}
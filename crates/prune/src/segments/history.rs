@@ -3,21 +3,67 @@ use reth_db::{
     database::Database,
     models::ShardedKey,
     table::Table,
-    transaction::DbTxMut,
+    tables,
+    transaction::{DbTx, DbTxMut},
     BlockNumberList,
 };
 use reth_interfaces::db::DatabaseError;
-use reth_primitives::BlockNumber;
+use reth_primitives::{BlockNumber, HistoryAddressPruneConfig, RetainedHistoryCheckpoint};
 use reth_provider::DatabaseProviderRW;
 
+/// Saves the block from which the history of each retained address is complete to
+/// [tables::PruneRetainedAddresses], and forgets the addresses that are not retained anymore.
+///
+/// The history of a newly retained address may already be pruned, so it's only complete from
+/// `first_unpruned_block` on. `retained_from` selects the field of the segment in the checkpoint.
+pub(crate) fn update_retained_addresses<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+    retained_addresses: &HistoryAddressPruneConfig,
+    first_unpruned_block: BlockNumber,
+    retained_from: impl Fn(&mut RetainedHistoryCheckpoint) -> &mut Option<BlockNumber>,
+) -> Result<(), DatabaseError> {
+    let tx = provider.tx_ref();
+
+    let mut updates = Vec::new();
+    for entry in tx.cursor_read::<tables::PruneRetainedAddresses>()?.walk(None)? {
+        let (address, mut checkpoint) = entry?;
+        if !retained_addresses.contains(&address) && retained_from(&mut checkpoint).take().is_some()
+        {
+            updates.push((address, checkpoint));
+        }
+    }
+    for address in &retained_addresses.0 {
+        let mut checkpoint =
+            tx.get::<tables::PruneRetainedAddresses>(*address)?.unwrap_or_default();
+        if retained_from(&mut checkpoint).is_none() {
+            *retained_from(&mut checkpoint) = Some(first_unpruned_block);
+            updates.push((*address, checkpoint));
+        }
+    }
+
+    for (address, checkpoint) in updates {
+        if checkpoint == RetainedHistoryCheckpoint::default() {
+            tx.delete::<tables::PruneRetainedAddresses>(address, None)?;
+        } else {
+            tx.put::<tables::PruneRetainedAddresses>(address, checkpoint)?;
+        }
+    }
+
+    Ok(())
+}
+
 /// Prune history indices up to the provided block, inclusive.
 ///
+/// Shards of the keys for which `retain_key` returns `true` are left untouched, so their history
+/// stays available in full.
+///
 /// Returns total number of processed (walked) and deleted entities.
 pub(crate) fn prune_history_indices<DB, T, SK>(
     provider: &DatabaseProviderRW<DB>,
     to_block: BlockNumber,
     key_matches: impl Fn(&T::Key, &T::Key) -> bool,
     last_key: impl Fn(&T::Key) -> T::Key,
+    retain_key: impl Fn(&T::Key) -> bool,
 ) -> Result<(usize, usize), DatabaseError>
 where
     DB: Database,
@@ -37,6 +83,15 @@ where
     while let Some(result) = cursor.next()? {
         let (key, blocks): (T::Key, BlockNumberList) = result;
 
+        // If the history of this key is retained, skip to the last shard for this key.
+        if retain_key(&key) {
+            if key.as_ref().highest_block_number != u64::MAX {
+                cursor.seek_exact(last_key(&key))?;
+            }
+            processed += 1;
+            continue
+        }
+
         // If shard consists only of block numbers less than the target one, delete shard
         // completely.
         if key.as_ref().highest_block_number <= to_block {
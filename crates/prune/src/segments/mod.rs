@@ -1,4 +1,5 @@
 mod account_history;
+mod bytecodes;
 mod headers;
 mod history;
 mod receipts;
@@ -10,6 +11,8 @@ mod transaction_lookup;
 mod transactions;
 
 pub use account_history::AccountHistory;
+pub(crate) use bytecodes::referenced_bytecodes;
+pub use bytecodes::Bytecodes;
 pub use headers::Headers;
 pub use receipts::Receipts;
 pub use receipts_by_logs::ReceiptsByLogs;
@@ -1,5 +1,5 @@
 use crate::segments::{
-    AccountHistory, Receipts, ReceiptsByLogs, Segment, SenderRecovery, StorageHistory,
+    AccountHistory, Bytecodes, Receipts, ReceiptsByLogs, Segment, SenderRecovery, StorageHistory,
     TransactionLookup,
 };
 use reth_db::database::Database;
@@ -45,6 +45,8 @@ impl<DB: Database> SegmentSet<DB> {
             receipts,
             account_history,
             storage_history,
+            bytecodes,
+            history_address_filter,
             receipts_log_filter,
         } = prune_modes;
 
@@ -61,9 +63,16 @@ impl<DB: Database> SegmentSet<DB> {
             // Sender recovery
             .segment_opt(sender_recovery.map(SenderRecovery::new))
             // Account history
-            .segment_opt(account_history.map(AccountHistory::new))
+            .segment_opt(
+                account_history
+                    .map(|mode| AccountHistory::new(mode, history_address_filter.clone())),
+            )
+            // Bytecodes, after account history so that the pruned history is not retained anymore
+            .segment_opt(bytecodes.map(Bytecodes::new))
             // Storage history
-            .segment_opt(storage_history.map(StorageHistory::new))
+            .segment_opt(
+                storage_history.map(|mode| StorageHistory::new(mode, history_address_filter)),
+            )
     }
 }
 
@@ -1,6 +1,7 @@
 use crate::{
     segments::{
-        history::prune_history_indices, PruneInput, PruneOutput, PruneOutputCheckpoint, Segment,
+        history::{prune_history_indices, update_retained_addresses},
+        PruneInput, PruneOutput, PruneOutputCheckpoint, Segment,
     },
     PrunerError,
 };
@@ -9,18 +10,20 @@ use reth_db::{
     models::{storage_sharded_key::StorageShardedKey, BlockNumberAddress},
     tables,
 };
-use reth_primitives::{PruneMode, PruneSegment};
+use reth_primitives::{HistoryAddressPruneConfig, PruneMode, PruneSegment};
 use reth_provider::DatabaseProviderRW;
 use tracing::{instrument, trace};
 
 #[derive(Debug)]
 pub struct StorageHistory {
     mode: PruneMode,
+    /// Addresses whose storage history is retained in full.
+    retained_addresses: HistoryAddressPruneConfig,
 }
 
 impl StorageHistory {
-    pub fn new(mode: PruneMode) -> Self {
-        Self { mode }
+    pub fn new(mode: PruneMode, retained_addresses: HistoryAddressPruneConfig) -> Self {
+        Self { mode, retained_addresses }
     }
 }
 
@@ -39,6 +42,17 @@ impl<DB: Database> Segment<DB> for StorageHistory {
         provider: &DatabaseProviderRW<DB>,
         input: PruneInput,
     ) -> Result<PruneOutput, PrunerError> {
+        let first_unpruned_block = input
+            .previous_checkpoint
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map_or(0, |block_number| block_number + 1);
+        update_retained_addresses(
+            provider,
+            &self.retained_addresses,
+            first_unpruned_block,
+            |checkpoint| &mut checkpoint.storage_history_from,
+        )?;
+
         let range = match input.get_next_block_range() {
             Some(range) => range,
             None => {
@@ -53,7 +67,7 @@ impl<DB: Database> Segment<DB> for StorageHistory {
             .prune_table_with_range::<tables::StorageChangeSet>(
                 BlockNumberAddress::range(range),
                 input.delete_limit / 2,
-                |_| false,
+                |(key, _)| self.retained_addresses.contains(&key.address()),
                 |row| last_changeset_pruned_block = Some(row.0.block_number()),
             )?;
         trace!(target: "pruner", deleted = %pruned_changesets, %done, "Pruned storage history (changesets)");
@@ -69,6 +83,7 @@ impl<DB: Database> Segment<DB> for StorageHistory {
             last_changeset_pruned_block,
             |a, b| a.address == b.address && a.sharded_key.key == b.sharded_key.key,
             |key| StorageShardedKey::last(key.address, key.sharded_key.key),
+            |key| self.retained_addresses.contains(&key.address),
         )?;
         trace!(target: "pruner", %processed, deleted = %pruned_indices, %done, "Pruned storage history (history)" );
 
@@ -146,7 +161,7 @@ mod tests {
                 to_block,
                 delete_limit: 2000,
             };
-            let segment = StorageHistory::new(prune_mode);
+            let segment = StorageHistory::new(prune_mode, Default::default());
 
             let provider = db.factory.provider_rw().unwrap();
             let result = segment.prune(&provider, input).unwrap();
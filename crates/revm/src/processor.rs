@@ -593,14 +593,15 @@ mod tests {
         bytes,
         constants::{BEACON_ROOTS_ADDRESS, EIP1559_INITIAL_BASE_FEE, SYSTEM_ADDRESS},
         keccak256,
-        trie::AccountProof,
+        trie::{AccountProof, ExecutionWitness},
         Account, Bytecode, Bytes, ChainSpecBuilder, ForkCondition, Signature, StorageKey,
         Transaction, TransactionKind, TxEip1559, MAINNET,
     };
     use reth_provider::{
-        AccountReader, BlockHashReader, BundleStateWithReceipts, StateRootProvider,
+        AccountReader, BlockHashReader, BundleStateWithReceipts, HashedStateEntry,
+        StateRootProvider,
     };
-    use reth_trie::updates::TrieUpdates;
+    use reth_trie::{updates::TrieUpdates, HashedPostState, HashedStorage};
     use revm::{Database, TransitionState};
     use std::collections::HashMap;
 
@@ -667,6 +668,10 @@ mod tests {
         ) -> ProviderResult<(B256, TrieUpdates)> {
             unimplemented!("state root computation is not supported")
         }
+
+        fn hashed_state_root(&self, _hashed_state: &HashedPostState) -> ProviderResult<B256> {
+            unimplemented!("state root computation is not supported")
+        }
     }
 
     impl StateProvider for StateProviderTest {
@@ -688,6 +693,35 @@ mod tests {
         fn proof(&self, _address: Address, _keys: &[B256]) -> ProviderResult<AccountProof> {
             unimplemented!("proof generation is not supported")
         }
+
+        fn witness(
+            &self,
+            _targets: &HashMap<Address, Vec<B256>>,
+            _changes: &HashedPostState,
+        ) -> ProviderResult<ExecutionWitness> {
+            unimplemented!("witness generation is not supported")
+        }
+
+        fn storage_root(&self, _address: Address, _changes: HashedStorage) -> ProviderResult<B256> {
+            unimplemented!("storage root calculation is not supported")
+        }
+
+        fn account_range(
+            &self,
+            _start: B256,
+            _limit: usize,
+        ) -> ProviderResult<Vec<HashedStateEntry<Address, Account>>> {
+            unimplemented!("account range iteration is not supported")
+        }
+
+        fn storage_range(
+            &self,
+            _address: Address,
+            _start: B256,
+            _limit: usize,
+        ) -> ProviderResult<Vec<HashedStateEntry<StorageKey, U256>>> {
+            unimplemented!("storage range iteration is not supported")
+        }
     }
 
     #[test]
@@ -1,6 +1,9 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_primitives::Address;
-use reth_rpc_types::txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus};
+use reth_rpc_types::{
+    txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus},
+    TxpoolSubPools,
+};
 
 /// Txpool rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "txpool"))]
@@ -34,4 +37,22 @@ pub trait TxPoolApi {
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     #[method(name = "content")]
     async fn txpool_content(&self) -> RpcResult<TxpoolContent>;
+
+    /// Returns the details of all transactions currently in the txpool, grouped by the sub-pool
+    /// they're in: `pending`, `baseFee`, `queued` and `blob`.
+    ///
+    /// This is reth specific and not part of the `txpool` namespace of geth.
+    #[method(name = "subPools")]
+    async fn txpool_sub_pools(&self) -> RpcResult<TxpoolSubPools>;
+
+    /// Creates a subscription that streams the lifecycle events of all transactions in the txpool,
+    /// see [TxpoolEvent](reth_rpc_types::TxpoolEvent).
+    ///
+    /// This is reth specific and not part of the `txpool` namespace of geth.
+    #[subscription(
+        name = "subscribeEvents" => "events",
+        unsubscribe = "unsubscribeEvents",
+        item = reth_rpc_types::TxpoolEvent
+    )]
+    async fn txpool_subscribe_events(&self) -> jsonrpsee::core::SubscriptionResult;
 }
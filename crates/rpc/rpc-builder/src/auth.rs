@@ -22,7 +22,7 @@ use reth_rpc::{
         cache::EthStateCache, gas_oracle::GasPriceOracle, EthFilterConfig, FeeHistoryCache,
         FeeHistoryCacheConfig,
     },
-    AuthLayer, BlockingTaskPool, Claims, EngineEthApi, EthApi, EthFilter,
+    AuthLayer, BlockingTaskGuard, BlockingTaskPool, Claims, EngineEthApi, EthApi, EthFilter,
     EthSubscriptionIdProvider, JwtAuthValidator, JwtSecret,
 };
 use reth_rpc_api::{servers::*, EngineApiServer};
@@ -81,8 +81,10 @@ where
         eth_cache.clone(),
         gas_oracle,
         EthConfig::default().rpc_gas_cap,
+        EthConfig::default().eth_proof_window,
         Box::new(executor.clone()),
         BlockingTaskPool::build().expect("failed to build tracing pool"),
+        BlockingTaskGuard::new(EthConfig::default().max_tracing_requests),
         fee_history_cache,
         evm_config,
     );
@@ -17,11 +17,13 @@
 //! Configure only an http server with a selection of [RethRpcModule]s
 //!
 //! ```
+//! use reth_interfaces::blockchain_tree::BlockchainTreeEngine;
 //! use reth_network_api::{NetworkInfo, Peers};
 //! use reth_node_api::ConfigureEvmEnv;
 //! use reth_provider::{
-//!     AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-//!     ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+//!     AccountReader, BadBlockProvider, BlockReaderIdExt, BlockchainTreePendingStateProvider,
+//!     CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HistoryReader,
+//!     StateProviderFactory,
 //! };
 //! use reth_rpc_builder::{
 //!     RethRpcModule, RpcModuleBuilder, RpcServerConfig, ServerBuilder, TransportRpcModuleConfig,
@@ -39,6 +41,10 @@
 //!         + BlockReaderIdExt
 //!         + ChainSpecProvider
 //!         + ChangeSetReader
+//!         + HistoryReader
+//!         + BlockchainTreePendingStateProvider
+//!         + BadBlockProvider
+//!         + BlockchainTreeEngine
 //!         + StateProviderFactory
 //!         + EvmEnvProvider
 //!         + Clone
@@ -77,11 +83,13 @@
 //!
 //!
 //! ```
+//! use reth_interfaces::blockchain_tree::BlockchainTreeEngine;
 //! use reth_network_api::{NetworkInfo, Peers};
 //! use reth_node_api::{ConfigureEvmEnv, EngineTypes};
 //! use reth_provider::{
-//!     AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-//!     ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+//!     AccountReader, BadBlockProvider, BlockReaderIdExt, BlockchainTreePendingStateProvider,
+//!     CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HistoryReader,
+//!     StateProviderFactory,
 //! };
 //! use reth_rpc::JwtSecret;
 //! use reth_rpc_api::EngineApiServer;
@@ -104,6 +112,10 @@
 //!         + BlockReaderIdExt
 //!         + ChainSpecProvider
 //!         + ChangeSetReader
+//!         + HistoryReader
+//!         + BlockchainTreePendingStateProvider
+//!         + BadBlockProvider
+//!         + BlockchainTreeEngine
 //!         + StateProviderFactory
 //!         + EvmEnvProvider
 //!         + Clone
@@ -157,6 +169,7 @@ use std::{
     collections::{HashMap, HashSet},
     fmt,
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    path::PathBuf,
     str::FromStr,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
@@ -180,12 +193,14 @@ use crate::{
 };
 use constants::*;
 use error::{RpcError, ServerKind};
+use reth_interfaces::blockchain_tree::BlockchainTreeEngine;
 use reth_ipc::server::IpcServer;
 pub use reth_ipc::server::{Builder as IpcServerBuilder, Endpoint};
 use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
 use reth_provider::{
-    AccountReader, BlockReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-    ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+    AccountReader, BadBlockProvider, BlockReader, BlockReaderIdExt,
+    BlockchainTreePendingStateProvider, CanonStateSubscriptions, ChainSpecProvider,
+    ChangeSetReader, EvmEnvProvider, HistoryReader, StateProviderFactory,
 };
 use reth_rpc::{
     eth::{
@@ -241,6 +256,10 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + HistoryReader
+        + BlockchainTreePendingStateProvider
+        + BadBlockProvider
+        + BlockchainTreeEngine
         + Clone
         + Unpin
         + 'static,
@@ -432,6 +451,10 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + HistoryReader
+        + BlockchainTreePendingStateProvider
+        + BadBlockProvider
+        + BlockchainTreeEngine
         + Clone
         + Unpin
         + 'static,
@@ -563,6 +586,8 @@ impl Default for RpcModuleBuilder<(), (), (), (), (), ()> {
 pub struct RpcModuleConfig {
     /// `eth` namespace settings
     eth: EthConfig,
+    /// Directory the `debug` namespace writes trace files to, defaults to the temp directory.
+    trace_dir: Option<PathBuf>,
 }
 
 // === impl RpcModuleConfig ===
@@ -574,7 +599,12 @@ impl RpcModuleConfig {
     }
     /// Returns a new RPC module config given the eth namespace config
     pub fn new(eth: EthConfig) -> Self {
-        Self { eth }
+        Self { eth, trace_dir: None }
+    }
+
+    /// Returns the directory the `debug` namespace writes trace files to.
+    pub fn trace_dir(&self) -> PathBuf {
+        self.trace_dir.clone().unwrap_or_else(std::env::temp_dir)
     }
 }
 
@@ -582,6 +612,7 @@ impl RpcModuleConfig {
 #[derive(Clone, Debug, Default)]
 pub struct RpcModuleConfigBuilder {
     eth: Option<EthConfig>,
+    trace_dir: Option<PathBuf>,
 }
 
 // === impl RpcModuleConfigBuilder ===
@@ -593,10 +624,16 @@ impl RpcModuleConfigBuilder {
         self
     }
 
+    /// Configures the directory the `debug` namespace writes trace files to
+    pub fn trace_dir(mut self, trace_dir: impl Into<PathBuf>) -> Self {
+        self.trace_dir = Some(trace_dir.into());
+        self
+    }
+
     /// Consumes the type and creates the [RpcModuleConfig]
     pub fn build(self) -> RpcModuleConfig {
-        let RpcModuleConfigBuilder { eth } = self;
-        RpcModuleConfig { eth: eth.unwrap_or_default() }
+        let RpcModuleConfigBuilder { eth, trace_dir } = self;
+        RpcModuleConfig { eth: eth.unwrap_or_default(), trace_dir }
     }
 }
 
@@ -728,6 +765,10 @@ impl RpcModuleSelection {
             + EvmEnvProvider
             + ChainSpecProvider
             + ChangeSetReader
+            + HistoryReader
+            + BlockchainTreePendingStateProvider
+            + BadBlockProvider
+            + BlockchainTreeEngine
             + Clone
             + Unpin
             + 'static,
@@ -1050,6 +1091,10 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + HistoryReader
+        + BlockchainTreePendingStateProvider
+        + BadBlockProvider
+        + BlockchainTreeEngine
         + Clone
         + Unpin
         + 'static,
@@ -1205,6 +1250,8 @@ where
                             self.provider.clone(),
                             eth_api.clone(),
                             self.blocking_pool_guard.clone(),
+                            self.config.trace_dir(),
+                            Box::new(self.executor.clone()),
                         )
                         .into_rpc()
                         .into(),
@@ -1227,9 +1274,12 @@ where
                         .into_rpc()
                         .into(),
                         RethRpcModule::Web3 => Web3Api::new(self.network.clone()).into_rpc().into(),
-                        RethRpcModule::Txpool => {
-                            TxPoolApi::new(self.pool.clone()).into_rpc().into()
-                        }
+                        RethRpcModule::Txpool => TxPoolApi::with_spawner(
+                            self.pool.clone(),
+                            Box::new(self.executor.clone()),
+                        )
+                        .into_rpc()
+                        .into(),
                         RethRpcModule::Rpc => RPCApi::new(
                             namespaces
                                 .iter()
@@ -1238,7 +1288,11 @@ where
                         )
                         .into_rpc()
                         .into(),
-                        RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
+                        RethRpcModule::Ots => {
+                            OtterscanApi::new(self.provider.clone(), eth_api.clone())
+                                .into_rpc()
+                                .into()
+                        }
                         RethRpcModule::Reth => {
                             RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
                                 .into_rpc()
@@ -1317,8 +1371,10 @@ where
                 cache.clone(),
                 gas_oracle,
                 self.config.eth.rpc_gas_cap,
+                self.config.eth.eth_proof_window,
                 executor.clone(),
                 blocking_task_pool.clone(),
+                self.blocking_pool_guard.clone(),
                 fee_history_cache,
                 self.evm_config.clone(),
             );
@@ -1389,9 +1445,11 @@ where
     /// # Panics
     ///
     /// If called outside of the tokio runtime. See also [Self::eth_api]
-    pub fn otterscan_api(&mut self) -> OtterscanApi<EthApi<Provider, Pool, Network, EvmConfig>> {
+    pub fn otterscan_api(
+        &mut self,
+    ) -> OtterscanApi<Provider, EthApi<Provider, Pool, Network, EvmConfig>> {
         let eth_api = self.eth_api();
-        OtterscanApi::new(eth_api)
+        OtterscanApi::new(self.provider.clone(), eth_api)
     }
 
     /// Instantiates DebugApi
@@ -1401,7 +1459,13 @@ where
     /// If called outside of the tokio runtime. See also [Self::eth_api]
     pub fn debug_api(&mut self) -> DebugApi<Provider, EthApi<Provider, Pool, Network, EvmConfig>> {
         let eth_api = self.eth_api();
-        DebugApi::new(self.provider.clone(), eth_api, self.blocking_pool_guard.clone())
+        DebugApi::new(
+            self.provider.clone(),
+            eth_api,
+            self.blocking_pool_guard.clone(),
+            self.config.trace_dir(),
+            Box::new(self.executor.clone()),
+        )
     }
 
     /// Instantiates NetApi
@@ -229,7 +229,7 @@ where
     DebugApiClient::raw_block(client, block_id).await.unwrap();
     DebugApiClient::raw_transaction(client, B256::default()).await.unwrap();
     DebugApiClient::raw_receipts(client, block_id).await.unwrap();
-    assert!(is_unimplemented(DebugApiClient::bad_blocks(client).await.err().unwrap()));
+    DebugApiClient::bad_blocks(client).await.unwrap();
 }
 
 async fn test_basic_net_calls<C>(client: &C)
@@ -297,47 +297,35 @@ where
 
     OtterscanClient::get_api_level(client).await.unwrap();
 
-    assert!(is_unimplemented(
-        OtterscanClient::get_internal_operations(client, tx_hash).await.err().unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::get_transaction_error(client, tx_hash).await.err().unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::trace_transaction(client, tx_hash).await.err().unwrap()
-    ));
+    OtterscanClient::get_internal_operations(client, tx_hash).await.unwrap();
+
+    OtterscanClient::get_transaction_error(client, tx_hash).await.unwrap();
+
+    OtterscanClient::trace_transaction(client, tx_hash).await.unwrap();
 
     OtterscanClient::get_block_details(client, block_number).await.unwrap();
 
     OtterscanClient::get_block_details_by_hash(client, block_hash).await.unwrap();
 
-    assert!(is_unimplemented(
+    // the noop provider doesn't have any blocks
+    assert!(!is_unimplemented(
         OtterscanClient::get_block_transactions(client, block_number, page_number, page_size,)
             .await
             .err()
             .unwrap()
     ));
-    assert!(is_unimplemented(
-        OtterscanClient::search_transactions_before(client, address, block_number, page_size,)
-            .await
-            .err()
-            .unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::search_transactions_after(client, address, block_number, page_size,)
-            .await
-            .err()
-            .unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::get_transaction_by_sender_and_nonce(client, sender, nonce,)
-            .await
-            .err()
-            .unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::get_contract_creator(client, address).await.err().unwrap()
-    ));
+
+    OtterscanClient::search_transactions_before(client, address, block_number, page_size)
+        .await
+        .unwrap();
+
+    OtterscanClient::search_transactions_after(client, address, block_number, page_size)
+        .await
+        .unwrap();
+
+    OtterscanClient::get_transaction_by_sender_and_nonce(client, sender, nonce).await.unwrap();
+
+    OtterscanClient::get_contract_creator(client, address).await.unwrap();
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -0,0 +1,116 @@
+//! Types for the `debug` namespace.
+
+use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_rpc_trace_types::geth::GethDefaultTracingOptions;
+use alloy_rpc_types::RichBlock;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Result of `debug_storageRangeAt`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageRangeResult {
+    /// Storage slots of the page, keyed by the hashed storage key.
+    pub storage: BTreeMap<B256, StorageRangeEntry>,
+    /// Hashed storage key of the first slot of the next page, if any.
+    pub next_key: Option<B256>,
+}
+
+/// A single storage slot returned by `debug_storageRangeAt`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageRangeEntry {
+    /// Preimage of the hashed storage key, if known.
+    pub key: Option<B256>,
+    /// Value of the storage slot.
+    pub value: B256,
+}
+
+/// Result of `debug_accountRange`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountRangeResult {
+    /// State root of the block.
+    pub root: B256,
+    /// Accounts of the page, keyed by address or `pre(<hashed address>)` if the address is not
+    /// known.
+    pub accounts: BTreeMap<String, DumpAccount>,
+    /// Hashed address of the first account of the next page, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<B256>,
+}
+
+/// An account returned by `debug_accountRange`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DumpAccount {
+    /// Balance of the account.
+    pub balance: U256,
+    /// Nonce of the account.
+    pub nonce: u64,
+    /// Storage root of the account.
+    pub root: B256,
+    /// Hash of the account code.
+    pub code_hash: B256,
+    /// Code of the account, omitted if not requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// Storage of the account, omitted if not requested.
+    ///
+    /// Slots are keyed by the storage key, or by the hashed storage key if the preimage is not
+    /// known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<B256, B256>>,
+    /// Address of the account, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+    /// Hashed address of the account.
+    #[serde(rename = "key")]
+    pub hashed_address: B256,
+}
+
+/// A block that failed validation, as returned by `debug_getBadBlocks`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BadBlock {
+    /// Hash of the block.
+    pub hash: B256,
+    /// The block, with full transactions.
+    pub block: RichBlock,
+    /// RLP encoding of the block.
+    pub rlp: Bytes,
+    /// The error the block was rejected with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Options for the `debug_standardTrace*ToFile` methods.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StandardTraceConfig {
+    /// Options of the struct logger.
+    #[serde(flatten)]
+    pub config: GethDefaultTracingOptions,
+    /// Only trace the transaction with this hash, if set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<B256>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_storage_range_result() {
+        let s = r#"{"storage":{"0x290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563":{"key":"0x0000000000000000000000000000000000000000000000000000000000000000","value":"0x0000000000000000000000000000000000000000000000000000000000000001"}},"nextKey":null}"#;
+        let result: StorageRangeResult = serde_json::from_str(s).unwrap();
+        assert_eq!(result.storage.len(), 1);
+        assert_eq!(serde_json::to_string(&result).unwrap(), s);
+    }
+
+    #[test]
+    fn serde_standard_trace_config() {
+        let s = r#"{"disableStack":true,"txHash":"0x290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563"}"#;
+        let config: StandardTraceConfig = serde_json::from_str(s).unwrap();
+        assert_eq!(config.config.disable_stack, Some(true));
+        assert!(config.tx_hash.is_some());
+    }
+}
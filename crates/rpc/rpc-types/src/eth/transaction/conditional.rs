@@ -0,0 +1,59 @@
+//! Preconditions of transactions submitted via `eth_sendRawTransactionConditional`.
+
+use alloy_primitives::{Address, B256, U64};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Preconditions that must hold for a transaction to be included, see
+/// `eth_sendRawTransactionConditional`.
+///
+/// The block number and timestamp ranges are inclusive.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionConditional {
+    /// Expected storage of accounts, keyed by address.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub known_accounts: BTreeMap<Address, KnownAccount>,
+    /// Minimum block number.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number_min: Option<U64>,
+    /// Maximum block number.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number_max: Option<U64>,
+    /// Minimum block timestamp.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp_min: Option<U64>,
+    /// Maximum block timestamp.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp_max: Option<U64>,
+}
+
+/// The expected storage of an account, either its storage root or the values of storage slots.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KnownAccount {
+    /// The expected storage root of the account.
+    StorageRoot(B256),
+    /// The expected values of storage slots of the account.
+    StorageSlots(BTreeMap<B256, B256>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_transaction_conditional() {
+        let s = r#"{"knownAccounts":{"0x000000000000000000000000000000000000beef":{"0x0000000000000000000000000000000000000000000000000000000000000002":"0x0000000000000000000000000000000000000000000000000000000000000003"},"0x000000000000000000000000000000000000dead":"0x0000000000000000000000000000000000000000000000000000000000000001"},"blockNumberMin":"0x1","timestampMax":"0x64"}"#;
+        let conditional: TransactionConditional = serde_json::from_str(s).unwrap();
+        assert_eq!(conditional.block_number_min, Some(U64::from(1)));
+        assert_eq!(conditional.block_number_max, None);
+        assert_eq!(conditional.timestamp_max, Some(U64::from(100)));
+        assert_eq!(
+            conditional.known_accounts
+                [&"0x000000000000000000000000000000000000dead".parse::<Address>().unwrap()],
+            KnownAccount::StorageRoot(B256::with_last_byte(1))
+        );
+        assert_eq!(serde_json::to_string(&conditional).unwrap(), s);
+    }
+}
@@ -11,11 +11,13 @@
 
 mod admin;
 pub mod beacon;
+mod debug;
 mod eth;
 mod mev;
 mod net;
 mod otterscan;
 mod peer;
+mod pool;
 pub mod relay;
 mod rpc;
 
@@ -38,8 +40,10 @@ pub use eth::{
 };
 
 pub use admin::*;
+pub use debug::*;
 pub use mev::*;
 pub use net::*;
 pub use otterscan::*;
 pub use peer::*;
+pub use pool::*;
 pub use rpc::*;
@@ -1,9 +1,11 @@
 use crate::{Block, BlockTransactions, Rich, Transaction, TransactionReceipt};
 use alloy_primitives::{Address, Bytes, U256};
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 /// Operation type enum for `InternalOperation` struct
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+///
+/// Serialized as its numeric value, like the Erigon implementation of the Otterscan API.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum OperationType {
     /// Operation Transfer
     OpTransfer = 0,
@@ -15,67 +17,107 @@ pub enum OperationType {
     OpCreate2 = 3,
 }
 
+impl Serialize for OperationType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+
+impl<'de> Deserialize<'de> for OperationType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match u8::deserialize(deserializer)? {
+            0 => Ok(Self::OpTransfer),
+            1 => Ok(Self::OpSelfDestruct),
+            2 => Ok(Self::OpCreate),
+            3 => Ok(Self::OpCreate2),
+            ty => Err(de::Error::custom(format!("invalid operation type {ty}"))),
+        }
+    }
+}
+
 /// Custom struct for otterscan `getInternalOperations` RPC response
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct InternalOperation {
-    r#type: OperationType,
-    from: Address,
-    to: Address,
-    value: u128,
+    /// The type of the internal operation.
+    pub r#type: OperationType,
+    /// The address the operation originates from.
+    pub from: Address,
+    /// The address the operation targets.
+    pub to: Address,
+    /// The value transferred by the operation.
+    pub value: U256,
 }
 
 /// Custom struct for otterscan `traceTransaction` RPC response
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct TraceEntry {
-    r#type: String,
-    depth: u32,
-    from: Address,
-    to: Address,
-    value: u128,
-    input: Bytes,
+    /// The type of the call, e.g. `CALL`, `DELEGATECALL`, `CREATE2` or `SELFDESTRUCT`.
+    pub r#type: String,
+    /// The depth of the call.
+    pub depth: u32,
+    /// The caller.
+    pub from: Address,
+    /// The callee, the created contract or the beneficiary of a self-destruct.
+    pub to: Address,
+    /// The value transferred by the call, `None` for calls that can't transfer value.
+    pub value: Option<U256>,
+    /// The input of the call.
+    pub input: Bytes,
 }
 
 /// Internal issuance struct for `BlockDetails` struct
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct InternalIssuance {
-    block_reward: U256,
-    uncle_reward: U256,
-    issuance: U256,
+    /// The block reward.
+    pub block_reward: U256,
+    /// The uncle reward.
+    pub uncle_reward: U256,
+    /// The total issuance of the block.
+    pub issuance: U256,
 }
 
 /// Custom `Block` struct that includes transaction count for Otterscan responses
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OtsBlock {
+    /// The block.
     #[serde(flatten)]
-    block: Block,
-    transaction_count: usize,
+    pub block: Block,
+    /// The number of transactions in the block.
+    pub transaction_count: usize,
 }
 
 /// Custom struct for otterscan `getBlockDetails` RPC response
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BlockDetails {
-    block: OtsBlock,
-    issuance: InternalIssuance,
-    total_fees: U256,
+    /// The block.
+    pub block: OtsBlock,
+    /// The issuance of the block.
+    pub issuance: InternalIssuance,
+    /// The sum of all fees paid in the block.
+    pub total_fees: U256,
 }
 
 /// Custom transaction receipt struct for otterscan `OtsBlockTransactions` struct
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OtsTransactionReceipt {
+    /// The transaction receipt.
     #[serde(flatten)]
-    receipt: TransactionReceipt,
-    timestamp: u64,
+    pub receipt: TransactionReceipt,
+    /// The timestamp of the block the transaction was included in.
+    pub timestamp: u64,
 }
 
 /// Custom struct for otterscan `getBlockTransactions` RPC response
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct OtsBlockTransactions {
-    fullblock: OtsBlock,
-    receipts: Vec<OtsTransactionReceipt>,
+    /// The block with the transactions of the requested page.
+    pub fullblock: OtsBlock,
+    /// The receipts of the transactions of the requested page, without logs and bloom.
+    pub receipts: Vec<OtsTransactionReceipt>,
 }
 
 /// Custom struct for otterscan `searchTransactionsAfter`and `searchTransactionsBefore` RPC
@@ -83,17 +125,23 @@ pub struct OtsBlockTransactions {
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionsWithReceipts {
-    txs: Vec<Transaction>,
-    receipts: Vec<OtsTransactionReceipt>,
-    first_page: bool,
-    last_page: bool,
+    /// The transactions of the page.
+    pub txs: Vec<Transaction>,
+    /// The receipts of the transactions of the page.
+    pub receipts: Vec<OtsTransactionReceipt>,
+    /// Whether this is the first page, i.e. contains the most recent transactions.
+    pub first_page: bool,
+    /// Whether this is the last page, i.e. contains the oldest transactions.
+    pub last_page: bool,
 }
 
 /// Custom struct for otterscan `getContractCreator` RPC responses
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct ContractCreator {
-    tx: Transaction,
-    creator: Address,
+    /// The transaction the contract was created in.
+    pub tx: Transaction,
+    /// The address that created the contract.
+    pub creator: Address,
 }
 
 impl From<Block> for OtsBlock {
@@ -117,3 +165,17 @@ impl From<Rich<Block>> for BlockDetails {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_internal_operation() {
+        let s = r#"{"type":1,"from":"0x0000000000000000000000000000000000000001","to":"0x0000000000000000000000000000000000000002","value":"0x2a"}"#;
+        let op = serde_json::from_str::<InternalOperation>(s).unwrap();
+        assert_eq!(op.r#type, OperationType::OpSelfDestruct);
+        assert_eq!(op.value, U256::from(42));
+        assert_eq!(serde_json::to_string(&op).unwrap(), s);
+    }
+}
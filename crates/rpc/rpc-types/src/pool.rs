@@ -0,0 +1,142 @@
+//! Reth specific transaction pool types that expose the pool's sub-pools and transaction events.
+
+use alloy_primitives::{Address, B256};
+use alloy_rpc_types::Transaction;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A sub-pool of the transaction pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxpoolSubPool {
+    /// Transactions that are ready to be included in the next block.
+    Pending,
+    /// Transactions whose fee cap is below the current base fee.
+    BaseFee,
+    /// Transactions with a nonce gap or that the sender can't afford.
+    Queued,
+    /// Blob transactions that can't be included in the next block.
+    Blob,
+}
+
+/// Why a transaction was discarded from the transaction pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxpoolDiscardReason {
+    /// The transaction failed validation and was never added to the pool.
+    Invalid,
+    /// The nonce of the transaction is lower than the nonce of its sender on chain.
+    NonceTooLow,
+    /// The transaction was evicted to respect the size limits of the pool.
+    SizeLimit,
+    /// The transaction was explicitly removed from the pool.
+    Removed,
+}
+
+/// A lifecycle event of a transaction in the transaction pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum TxpoolEvent {
+    /// The transaction was added to the pending sub-pool.
+    Pending {
+        /// The hash of the transaction.
+        hash: B256,
+    },
+    /// The transaction was added to one of the parked sub-pools.
+    Queued {
+        /// The hash of the transaction.
+        hash: B256,
+    },
+    /// The transaction was replaced by another transaction with the same sender and nonce.
+    Replaced {
+        /// The hash of the replaced transaction.
+        hash: B256,
+        /// The hash of the replacement.
+        replaced_by: B256,
+    },
+    /// The transaction was moved from one sub-pool to another.
+    Moved {
+        /// The hash of the transaction.
+        hash: B256,
+        /// The sub-pool the transaction was moved from.
+        from: TxpoolSubPool,
+        /// The sub-pool the transaction was moved to.
+        to: TxpoolSubPool,
+    },
+    /// The transaction was discarded.
+    Discarded {
+        /// The hash of the transaction.
+        hash: B256,
+        /// Why the transaction was discarded.
+        reason: TxpoolDiscardReason,
+    },
+    /// The transaction became invalid.
+    Invalid {
+        /// The hash of the transaction.
+        hash: B256,
+    },
+    /// The transaction was included in a block.
+    Mined {
+        /// The hash of the transaction.
+        hash: B256,
+        /// The hash of the block.
+        block_hash: B256,
+    },
+}
+
+/// The transactions of every sub-pool of the transaction pool, grouped by sender and nonce.
+///
+/// Unlike [`TxpoolContent`](crate::txpool::TxpoolContent), which only distinguishes between
+/// pending and queued transactions, this shows each sub-pool separately.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolSubPools {
+    /// Transactions of the pending sub-pool.
+    pub pending: BTreeMap<Address, BTreeMap<String, Transaction>>,
+    /// Transactions of the base fee sub-pool.
+    pub base_fee: BTreeMap<Address, BTreeMap<String, Transaction>>,
+    /// Transactions of the queued sub-pool.
+    pub queued: BTreeMap<Address, BTreeMap<String, Transaction>>,
+    /// Transactions of the blob sub-pool.
+    pub blob: BTreeMap<Address, BTreeMap<String, Transaction>>,
+}
+
+impl TxpoolSubPools {
+    /// Returns the transactions of the given sub-pool.
+    pub fn get_mut(
+        &mut self,
+        subpool: TxpoolSubPool,
+    ) -> &mut BTreeMap<Address, BTreeMap<String, Transaction>> {
+        match subpool {
+            TxpoolSubPool::Pending => &mut self.pending,
+            TxpoolSubPool::BaseFee => &mut self.base_fee,
+            TxpoolSubPool::Queued => &mut self.queued,
+            TxpoolSubPool::Blob => &mut self.blob,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_txpool_event() {
+        let event = TxpoolEvent::Moved {
+            hash: B256::with_last_byte(1),
+            from: TxpoolSubPool::Pending,
+            to: TxpoolSubPool::BaseFee,
+        };
+        let s = r#"{"event":"moved","hash":"0x0000000000000000000000000000000000000000000000000000000000000001","from":"pending","to":"baseFee"}"#;
+        assert_eq!(serde_json::to_string(&event).unwrap(), s);
+        assert_eq!(serde_json::from_str::<TxpoolEvent>(s).unwrap(), event);
+
+        let event = TxpoolEvent::Discarded {
+            hash: B256::with_last_byte(1),
+            reason: TxpoolDiscardReason::NonceTooLow,
+        };
+        let s = r#"{"event":"discarded","hash":"0x0000000000000000000000000000000000000000000000000000000000000001","reason":"nonceTooLow"}"#;
+        assert_eq!(serde_json::to_string(&event).unwrap(), s);
+        assert_eq!(serde_json::from_str::<TxpoolEvent>(s).unwrap(), event);
+    }
+}
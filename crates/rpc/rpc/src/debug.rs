@@ -1,6 +1,8 @@
 use crate::{
+    eip3155,
     eth::{
         error::{EthApiError, EthResult},
+        pre_block_beacon_root_contract_call,
         revm_utils::{
             inspect, inspect_and_return_db, prepare_call_env, replay_transactions_until, transact,
             EvmOverrides,
@@ -14,12 +16,19 @@ use alloy_primitives::U256;
 use alloy_rlp::{Decodable, Encodable};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
+use reth_interfaces::{blockchain_tree::BlockchainTreeEngine, RethError};
 use reth_primitives::{
-    revm::env::tx_env_with_recovered, Address, Block, BlockId, BlockNumberOrTag, Bytes,
-    TransactionSignedEcRecovered, Withdrawals, B256,
+    constants::BEACON_ROOTS_ADDRESS,
+    hex, keccak256,
+    revm::{compat::into_reth_acc, env::tx_env_with_recovered},
+    trie::ExecutionWitness,
+    Address, Block, BlockId, BlockNumHash, BlockNumber, BlockNumberOrTag, BlockWithSenders, Bytes,
+    ChainSpec, TransactionSignedEcRecovered, Withdrawals, B256,
 };
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, HeaderProvider, StateProviderBox, TransactionVariant,
+    BadBlock, BadBlockProvider, BlockNumReader, BlockReaderIdExt, BlockSource,
+    BlockchainTreePendingStateProvider, ChainSpecProvider, ChangeSetReader, HeaderProvider,
+    StateProvider, StateProviderBox, StateRootProvider, TransactionVariant,
 };
 use reth_revm::database::{StateProviderDatabase, SubState};
 use reth_rpc_api::DebugApiServer;
@@ -28,18 +37,41 @@ use reth_rpc_types::{
         BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerType,
         GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, NoopFrame, TraceResult,
     },
-    BlockError, Bundle, RichBlock, StateContext, TransactionRequest,
+    AccountRangeResult, BadBlock as RpcBadBlock, BlockError, BlockTransactionsKind, Bundle,
+    DumpAccount, StandardTraceConfig, StateContext, StorageRangeEntry, StorageRangeResult,
+    TransactionRequest,
 };
+use reth_rpc_types_compat::block::{from_block, from_block_with_tx_hashes};
+use reth_tasks::TaskSpawner;
+use reth_trie::{HashedPostState, HashedStorage};
 use revm::{
-    db::CacheDB,
-    primitives::{db::DatabaseCommit, BlockEnv, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg},
+    db::{AccountState, CacheDB},
+    primitives::{
+        db::{DatabaseCommit, DatabaseRef},
+        BlockEnv, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg, SpecId, State as EvmState,
+    },
 };
 use revm_inspectors::tracing::{
     js::{JsInspector, TransactionContext},
     FourByteInspector, TracingInspector, TracingInspectorConfig,
 };
-use std::sync::Arc;
-use tokio::sync::{AcquireError, OwnedSemaphorePermit};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fs::File,
+    future::Future,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tokio::sync::{oneshot, AcquireError, OwnedSemaphorePermit};
+
+/// The maximum number of accounts returned by `debug_accountRange`.
+const ACCOUNT_RANGE_MAX_RESULTS: u64 = 256;
+
+/// The maximum number of blocks `debug_getModifiedAccountsByHash` and
+/// `debug_getModifiedAccountsByNumber` return the modified accounts of, since all account and
+/// storage changes of the blocks are loaded.
+const MODIFIED_ACCOUNTS_MAX_BLOCKS: u64 = 1024;
 
 /// `debug` API implementation.
 ///
@@ -52,8 +84,22 @@ pub struct DebugApi<Provider, Eth> {
 
 impl<Provider, Eth> DebugApi<Provider, Eth> {
     /// Create a new instance of the [DebugApi]
-    pub fn new(provider: Provider, eth: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
-        let inner = Arc::new(DebugApiInner { provider, eth_api: eth, blocking_task_guard });
+    ///
+    /// The `debug_standardTrace*` methods write their trace files into `trace_dir`.
+    pub fn new(
+        provider: Provider,
+        eth: Eth,
+        blocking_task_guard: BlockingTaskGuard,
+        trace_dir: PathBuf,
+        task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        let inner = Arc::new(DebugApiInner {
+            provider,
+            eth_api: eth,
+            blocking_task_guard,
+            trace_dir,
+            task_spawner,
+        });
         Self { inner }
     }
 }
@@ -62,7 +108,14 @@ impl<Provider, Eth> DebugApi<Provider, Eth> {
 
 impl<Provider, Eth> DebugApi<Provider, Eth>
 where
-    Provider: BlockReaderIdExt + HeaderProvider + ChainSpecProvider + 'static,
+    Provider: BlockReaderIdExt
+        + HeaderProvider
+        + ChainSpecProvider
+        + ChangeSetReader
+        + BlockchainTreePendingStateProvider
+        + BadBlockProvider
+        + BlockchainTreeEngine
+        + 'static,
     Eth: EthTransactions + 'static,
 {
     /// Acquires a permit to execute a tracing call.
@@ -139,10 +192,20 @@ where
     ) -> EthResult<Vec<TraceResult>> {
         let block =
             Block::decode(&mut rlp_block.as_ref()).map_err(BlockError::RlpDecodeRawBlock)?;
-
-        let (cfg, block_env) = self.inner.eth_api.evm_env_for_raw_block(&block.header).await?;
         // we trace on top the block's parent block
         let parent = block.parent_hash;
+        let (cfg, block_env, transactions) = self.prepare_block_replay(block).await?;
+
+        self.trace_block_with(parent.into(), transactions, cfg, block_env, opts).await
+    }
+
+    /// Returns the evm env of the given block, which is not necessarily known to the provider,
+    /// and its transactions with recovered senders.
+    async fn prepare_block_replay(
+        &self,
+        block: Block,
+    ) -> EthResult<(CfgEnvWithHandlerCfg, BlockEnv, Vec<TransactionSignedEcRecovered>)> {
+        let (cfg, block_env) = self.inner.eth_api.evm_env_for_raw_block(&block.header).await?;
 
         // Depending on EIP-2 we need to recover the transactions differently
         let transactions =
@@ -166,9 +229,318 @@ where
                     .collect::<EthResult<Vec<_>>>()?
             };
 
+        Ok((cfg, block_env, transactions))
+    }
+
+    /// Returns the bad blocks seen by the node, most recent first.
+    pub fn bad_blocks(&self) -> EthResult<Vec<RpcBadBlock>> {
+        self.inner
+            .provider
+            .bad_blocks()?
+            .into_iter()
+            .map(|BadBlock { block, error }| -> EthResult<_> {
+                let hash = block.hash();
+                let block = block.unseal();
+                let rlp = alloy_rlp::encode(&block).into();
+
+                // the block is not part of the chain, so its total difficulty is derived from the
+                // parent, if known
+                let total_difficulty = self
+                    .inner
+                    .provider
+                    .header_td(&block.parent_hash)?
+                    .map(|td| td + block.difficulty)
+                    .unwrap_or_default();
+
+                let block = match block.clone().with_recovered_senders() {
+                    Some(block) => from_block(
+                        block,
+                        total_difficulty,
+                        BlockTransactionsKind::Full,
+                        Some(hash),
+                    )?,
+                    // transactions with invalid signatures can only be returned as hashes
+                    None => from_block_with_tx_hashes(
+                        BlockWithSenders { block, senders: Vec::new() },
+                        total_difficulty,
+                        Some(hash),
+                    ),
+                };
+
+                Ok(RpcBadBlock { hash, block: block.into(), rlp, error: Some(error) })
+            })
+            .collect()
+    }
+
+    /// Replays the bad block with the given hash on top of its parent and returns the trace of
+    /// each transaction.
+    pub async fn debug_trace_bad_block(
+        &self,
+        block_hash: B256,
+        opts: GethDebugTracingOptions,
+    ) -> EthResult<Vec<TraceResult>> {
+        let block = self.bad_block(block_hash)?;
+        let parent = block.parent_hash;
+        let (cfg, block_env, transactions) = self.prepare_block_replay(block).await?;
+
         self.trace_block_with(parent.into(), transactions, cfg, block_env, opts).await
     }
 
+    /// Replays the bad block with the given hash on top of its parent and writes the EIP-3155
+    /// trace of each transaction to a file in the trace directory.
+    ///
+    /// Returns the paths of the written files.
+    pub async fn debug_standard_trace_bad_block_to_file(
+        &self,
+        block_hash: B256,
+        opts: StandardTraceConfig,
+    ) -> EthResult<Vec<String>> {
+        let block = self.bad_block(block_hash)?;
+        self.standard_trace_block_with(block_hash, block, opts).await
+    }
+
+    /// Replays the block with the given hash on top of its parent and writes the EIP-3155 trace
+    /// of each transaction to a file in the trace directory.
+    ///
+    /// Returns the paths of the written files.
+    pub async fn debug_standard_trace_block_to_file(
+        &self,
+        block_hash: B256,
+        opts: StandardTraceConfig,
+    ) -> EthResult<Vec<String>> {
+        let block = self
+            .inner
+            .provider
+            .find_block_by_hash(block_hash, BlockSource::Any)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        self.standard_trace_block_with(block_hash, block, opts).await
+    }
+
+    /// Replays the block with the given hash on top of its parent and returns the state root
+    /// after each transaction.
+    ///
+    /// The block can be canonical, part of a side chain or a bad block.
+    pub async fn debug_intermediate_roots(&self, block_hash: B256) -> EthResult<Vec<B256>> {
+        let block = match self.inner.provider.find_block_by_hash(block_hash, BlockSource::Any)? {
+            Some(block) => block,
+            None => self.bad_block(block_hash)?,
+        };
+        let parent = block.parent_hash;
+        let block_number = block.number;
+        let parent_beacon_block_root = block.parent_beacon_block_root;
+        let chain_spec = self.inner.provider.chain_spec();
+        let (cfg, block_env, transactions) = self.prepare_block_replay(block).await?;
+
+        // empty accounts are removed from the state since EIP-161
+        let state_clear = cfg.handler_cfg.spec_id >= SpecId::SPURIOUS_DRAGON;
+
+        self.inner
+            .eth_api
+            .spawn_with_state_at_block(parent.into(), move |state| {
+                let mut hashed_states = Vec::with_capacity(transactions.len());
+                let mut db = CacheDB::new(StateProviderDatabase::new(&state));
+                // the changes of the pre-block system calls are part of the first root
+                let mut pre_block_state = apply_pre_block_calls(
+                    &mut db,
+                    &chain_spec,
+                    &cfg,
+                    &block_env,
+                    block_number,
+                    parent_beacon_block_root,
+                )?;
+                for tx in transactions {
+                    let env = EnvWithHandlerCfg {
+                        env: Env::boxed(
+                            cfg.cfg_env.clone(),
+                            block_env.clone(),
+                            tx_env_with_recovered(&tx),
+                        ),
+                        handler_cfg: cfg.handler_cfg,
+                    };
+                    let (res, _) = transact(&mut db, env)?;
+                    let mut hashed_state = std::mem::take(&mut pre_block_state);
+                    hashed_state.extend(hashed_transaction_state(&res.state, state_clear));
+                    hashed_states.push(hashed_state);
+                    db.commit(res.state);
+                }
+                // the parent state is only rebuilt once for all roots
+                Ok(state.hashed_state_roots(&hashed_states)?)
+            })
+            .await
+    }
+
+    /// Executes the given block on top of its parent and returns the witness of the parent state
+    /// that is accessed by the block.
+    ///
+    /// The block is executed with the block executor, so the state accessed by the pre-block system
+    /// calls, the block reward and the withdrawals is included. The witness also contains the
+    /// nodes that are needed to compute the post-state root from it alone.
+    pub async fn debug_execution_witness(
+        &self,
+        block: BlockNumberOrTag,
+    ) -> EthResult<ExecutionWitness> {
+        let block = self
+            .inner
+            .provider
+            .block_by_number_or_tag(block)?
+            .ok_or(EthApiError::UnknownBlockNumber)?
+            .with_recovered_senders()
+            .ok_or(EthApiError::InvalidTransactionSignature)?;
+
+        self.inner
+            .eth_api
+            .spawn_with_executed_block(block, move |state, loaded, output| {
+                // every account and slot that was loaded or changed while executing the block
+                let mut targets = HashMap::<Address, Vec<B256>>::new();
+                for (address, account) in loaded.accounts {
+                    let slots = targets.entry(address).or_default();
+                    if let Some(account) = account.account {
+                        slots.extend(account.storage.into_keys().map(B256::from));
+                    }
+                }
+                for (address, account) in &output.state().state {
+                    let slots = targets.entry(*address).or_default();
+                    slots.extend(account.storage.keys().copied().map(B256::from));
+                    slots.sort_unstable();
+                    slots.dedup();
+                }
+
+                let changes = output.hash_state_slow();
+                Ok(state.witness(&targets, &changes)?)
+            })
+            .await
+    }
+
+    /// Replays the given block on top of its parent and writes the EIP-3155 trace of each
+    /// transaction, or only the one selected by the config, to a file in the trace directory.
+    ///
+    /// Returns the paths of the written files.
+    async fn standard_trace_block_with(
+        &self,
+        block_hash: B256,
+        block: Block,
+        opts: StandardTraceConfig,
+    ) -> EthResult<Vec<String>> {
+        let parent = block.parent_hash;
+        let (cfg, block_env, transactions) = self.prepare_block_replay(block).await?;
+
+        let StandardTraceConfig { config, tx_hash } = opts;
+        if let Some(tx_hash) = tx_hash {
+            if !transactions.iter().any(|tx| tx.hash == tx_hash) {
+                return Err(EthApiError::TransactionNotFound)
+            }
+        }
+
+        let dir = self.inner.trace_dir.clone();
+        self.inner
+            .eth_api
+            .spawn_with_state_at_block(parent.into(), move |state| {
+                std::fs::create_dir_all(&dir).map_err(|err| {
+                    EthApiError::Internal(RethError::Custom(format!(
+                        "failed to create trace directory {}: {err}",
+                        dir.display()
+                    )))
+                })?;
+
+                let mut files = Vec::new();
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                for (index, tx) in transactions.into_iter().enumerate() {
+                    let env = EnvWithHandlerCfg {
+                        env: Env::boxed(
+                            cfg.cfg_env.clone(),
+                            block_env.clone(),
+                            tx_env_with_recovered(&tx),
+                        ),
+                        handler_cfg: cfg.handler_cfg,
+                    };
+
+                    if tx_hash.is_some_and(|hash| hash != tx.hash) {
+                        let (res, _) = transact(&mut db, env)?;
+                        db.commit(res.state);
+                        continue
+                    }
+
+                    let mut inspector =
+                        TracingInspector::new(TracingInspectorConfig::from_geth_config(&config));
+                    let (res, _) = inspect(&mut db, env, &mut inspector)?;
+                    db.commit(res.state);
+
+                    let path = dir.join(format!(
+                        "block_{}-{index}-{}.jsonl",
+                        hex::encode(&block_hash[..4]),
+                        hex::encode(&tx.hash[..4])
+                    ));
+                    write_trace_file(&path, |writer| {
+                        eip3155::write_trace(
+                            writer,
+                            &inspector.into_traces(),
+                            res.result.gas_used(),
+                            &config,
+                        )
+                    })?;
+                    files.push(path.display().to_string());
+
+                    if tx_hash.is_some() {
+                        break
+                    }
+                }
+                Ok(files)
+            })
+            .await
+    }
+
+    /// Rewinds the canonical chain to the given block.
+    ///
+    /// This unwinds the blocks above the given block from the database and the stage checkpoints,
+    /// like `reth stage unwind` does, and moves them from the canonical chain of the blockchain
+    /// tree into a side chain.
+    ///
+    /// Finalized blocks can't be unwound.
+    pub async fn debug_set_head(&self, number: BlockNumber) -> EthResult<()> {
+        let tip = self.inner.provider.best_block_number()?;
+        if number > tip {
+            return Err(EthApiError::InvalidParams(format!(
+                "block number ({number}) must not be higher than the current head ({tip})"
+            )))
+        }
+        if let Some(finalized) = self.inner.provider.finalized_block_number()? {
+            if number < finalized {
+                return Err(EthApiError::InvalidParams(format!(
+                    "block number ({number}) must not be below the finalized block ({finalized})"
+                )))
+            }
+        }
+
+        // unwinding writes to the database and can take a while
+        self.on_blocking_task(|this| async move { Ok(this.inner.provider.unwind(number)?) }).await
+    }
+
+    /// Executes the future on a new blocking task.
+    async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
+    where
+        C: FnOnce(Self) -> F,
+        F: Future<Output = EthResult<R>> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let this = self.clone();
+        let f = c(this);
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            let res = f.await;
+            let _ = tx.send(res);
+        }));
+        rx.await.map_err(|_| EthApiError::InternalEthError)?
+    }
+
+    /// Returns the bad block with the given hash.
+    fn bad_block(&self, block_hash: B256) -> EthResult<Block> {
+        self.inner
+            .provider
+            .bad_block(block_hash)?
+            .map(|bad_block| bad_block.block.unseal())
+            .ok_or_else(|| EthApiError::InvalidParams(format!("bad block {block_hash} not found")))
+    }
+
     /// Replays a block and returns the trace of each transaction.
     pub async fn debug_trace_block(
         &self,
@@ -492,6 +864,201 @@ where
             .await
     }
 
+    /// Returns a page of the storage of the given contract at the state right before the
+    /// transaction at `tx_idx` in the given block is executed.
+    ///
+    /// Storage slots are ordered by their hashed key, the page starts at `key_start` and contains
+    /// at most `max_result` slots.
+    pub async fn debug_storage_range_at(
+        &self,
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> EthResult<StorageRangeResult> {
+        let ((cfg, block_env, _), block) = futures::try_join!(
+            self.inner.eth_api.evm_env_at(block_hash.into()),
+            self.inner.eth_api.block_by_id_with_senders(block_hash.into()),
+        )?;
+        let block = block.ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        if tx_idx > block.body.len() {
+            return Err(EthApiError::InvalidParams(format!(
+                "transaction index {tx_idx} out of range for block {block_hash}"
+            )))
+        }
+        let max_result = max_result as usize;
+
+        // we need the state at the beginning of the block, which is the state at the parent block
+        self.inner
+            .eth_api
+            .spawn_with_state_at_block(block.parent_hash.into(), move |state| {
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+                // replay all transactions prior to the targeted transaction
+                for tx in block.into_transactions_ecrecovered().take(tx_idx) {
+                    let env = EnvWithHandlerCfg {
+                        env: Env::boxed(
+                            cfg.cfg_env.clone(),
+                            block_env.clone(),
+                            tx_env_with_recovered(&tx),
+                        ),
+                        handler_cfg: cfg.handler_cfg,
+                    };
+                    let (res, _) = transact(&mut db, env)?;
+                    db.commit(res.state);
+                }
+
+                // slots touched by the replayed transactions, keyed by their hashed key
+                let (changes, storage_cleared) = db
+                    .accounts
+                    .get(&contract_address)
+                    .map(|account| {
+                        let changes = account
+                            .storage
+                            .iter()
+                            .map(|(slot, value)| {
+                                let slot = B256::from(*slot);
+                                (keccak256(slot), (Some(slot), *value))
+                            })
+                            .filter(|(hashed_slot, _)| *hashed_slot >= key_start)
+                            .collect::<BTreeMap<_, _>>();
+                        let storage_cleared = matches!(
+                            account.account_state,
+                            AccountState::StorageCleared | AccountState::NotExisting
+                        );
+                        (changes, storage_cleared)
+                    })
+                    .unwrap_or_default();
+
+                // every touched slot may remove at most one slot of the persisted storage, so this
+                // is enough to fill the page and determine the key of the next page
+                let mut storage = BTreeMap::new();
+                if !storage_cleared {
+                    let limit = max_result.saturating_add(changes.len()).saturating_add(1);
+                    for entry in db.db.storage_range(contract_address, key_start, limit)? {
+                        storage.insert(entry.hashed_key, (entry.key, entry.value));
+                    }
+                }
+                storage.extend(changes);
+
+                let mut storage = storage.into_iter().filter(|(_, (_, value))| !value.is_zero());
+                let page = storage
+                    .by_ref()
+                    .take(max_result)
+                    .map(|(hashed_key, (key, value))| {
+                        (hashed_key, StorageRangeEntry { key, value: value.into() })
+                    })
+                    .collect();
+                let next_key = storage.next().map(|(hashed_key, _)| hashed_key);
+
+                Ok(StorageRangeResult { storage: page, next_key })
+            })
+            .await
+    }
+
+    /// Returns a page of the accounts at the state of the given block.
+    ///
+    /// Accounts are ordered by their hashed address, the page starts at `start` and contains at
+    /// most `max_results` accounts. If `incompletes` is false, accounts whose address is not known
+    /// are skipped.
+    pub async fn debug_account_range(
+        &self,
+        block_id: BlockId,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
+        incompletes: bool,
+    ) -> EthResult<AccountRangeResult> {
+        if start.len() > B256::len_bytes() {
+            return Err(EthApiError::InvalidParams(format!(
+                "start key too long: {} > {}",
+                start.len(),
+                B256::len_bytes()
+            )))
+        }
+        let mut start_key = B256::ZERO;
+        start_key[..start.len()].copy_from_slice(&start);
+
+        let max_results = if max_results == 0 || max_results > ACCOUNT_RANGE_MAX_RESULTS {
+            ACCOUNT_RANGE_MAX_RESULTS
+        } else {
+            max_results
+        } as usize;
+
+        let root = self
+            .inner
+            .provider
+            .header_by_id(block_id)?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?
+            .state_root;
+
+        self.inner
+            .eth_api
+            .spawn_with_state_at_block(block_id, move |state| {
+                let mut entries = state.account_range(start_key, max_results + 1)?.into_iter();
+
+                let mut accounts = BTreeMap::new();
+                for entry in entries.by_ref().take(max_results) {
+                    let Some(address) = entry.key else {
+                        if incompletes {
+                            let account = DumpAccount {
+                                balance: entry.value.balance,
+                                nonce: entry.value.nonce,
+                                code_hash: entry.value.get_bytecode_hash(),
+                                hashed_address: entry.hashed_key,
+                                ..Default::default()
+                            };
+                            accounts.insert(format!("pre({})", entry.hashed_key), account);
+                        }
+                        continue
+                    };
+
+                    let code_hash = entry.value.get_bytecode_hash();
+                    let code = if nocode {
+                        None
+                    } else {
+                        Some(
+                            state
+                                .bytecode_by_hash(code_hash)?
+                                .map(|code| code.original_bytes())
+                                .unwrap_or_default(),
+                        )
+                    };
+                    let storage = if nostorage {
+                        None
+                    } else {
+                        Some(
+                            state
+                                .storage_range(address, B256::ZERO, usize::MAX)?
+                                .into_iter()
+                                .map(|slot| {
+                                    (slot.key.unwrap_or(slot.hashed_key), slot.value.into())
+                                })
+                                .collect(),
+                        )
+                    };
+
+                    let account = DumpAccount {
+                        balance: entry.value.balance,
+                        nonce: entry.value.nonce,
+                        root: state.storage_root(address, Default::default())?,
+                        code_hash,
+                        code,
+                        storage,
+                        address: Some(address),
+                        hashed_address: entry.hashed_key,
+                    };
+                    accounts.insert(address.to_string(), account);
+                }
+                let next = entries.next().map(|entry| entry.hashed_key);
+
+                Ok(AccountRangeResult { root, accounts, next })
+            })
+            .await
+    }
+
     /// Executes the configured transaction with the environment on the given database.
     ///
     /// Returns the trace frame and the state that got updated after executing the transaction.
@@ -587,12 +1154,75 @@ where
 
         Ok((frame.into(), res.state))
     }
+
+    /// Returns the addresses of all accounts that were modified in the blocks after the `start`
+    /// block, up to and including the `end` block.
+    ///
+    /// Fails if the range spans more than [MODIFIED_ACCOUNTS_MAX_BLOCKS] blocks.
+    ///
+    /// Persisted blocks are served from the account and storage changesets. If the `end` block
+    /// belongs to a side chain of the blockchain tree, the modified accounts of the blocks that
+    /// aren't persisted yet are taken from the block reverts of the tree's pending state.
+    fn modified_accounts(&self, start: BlockNumber, end: BlockNumHash) -> EthResult<Vec<Address>> {
+        if start >= end.number {
+            return Err(EthApiError::InvalidParams(format!(
+                "start block height ({start}) must be less than end block height ({})",
+                end.number
+            )))
+        }
+        if end.number - start > MODIFIED_ACCOUNTS_MAX_BLOCKS {
+            return Err(EthApiError::InvalidParams(format!(
+                "block range ({start}..{}) exceeds the maximum of {MODIFIED_ACCOUNTS_MAX_BLOCKS} \
+                 blocks",
+                end.number
+            )))
+        }
+
+        let mut accounts = BTreeSet::new();
+        let mut last_persisted = end.number;
+        if let Some(pending) = self.inner.provider.find_pending_state_provider(end.hash) {
+            // the pending state contains the changes of all side chain blocks after the fork
+            let state = pending.state();
+            for (block, reverts) in (state.first_block()..).zip(state.state().reverts.iter()) {
+                if block > start {
+                    accounts.extend(reverts.iter().map(|(address, _)| *address));
+                }
+            }
+            last_persisted = pending.canonical_fork().number;
+        }
+
+        if start < last_persisted {
+            accounts.extend(
+                self.inner.provider.modified_accounts_with_range(start + 1..=last_persisted)?,
+            );
+        }
+
+        Ok(accounts.into_iter().collect())
+    }
+
+    /// Returns the number and hash of the block with the given hash, including blocks that are
+    /// only part of the blockchain tree.
+    fn block_num_hash_by_hash(&self, hash: B256) -> EthResult<BlockNumHash> {
+        let block = self
+            .inner
+            .provider
+            .find_block_by_hash(hash, BlockSource::Any)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        Ok(BlockNumHash::new(block.number, hash))
+    }
 }
 
 #[async_trait]
 impl<Provider, Eth> DebugApiServer for DebugApi<Provider, Eth>
 where
-    Provider: BlockReaderIdExt + HeaderProvider + ChainSpecProvider + 'static,
+    Provider: BlockReaderIdExt
+        + HeaderProvider
+        + ChainSpecProvider
+        + ChangeSetReader
+        + BlockchainTreePendingStateProvider
+        + BadBlockProvider
+        + BlockchainTreeEngine
+        + 'static,
     Eth: EthApiSpec + 'static,
 {
     /// Handler for `debug_getRawHeader`
@@ -674,8 +1304,8 @@ where
     }
 
     /// Handler for `debug_getBadBlocks`
-    async fn bad_blocks(&self) -> RpcResult<Vec<RichBlock>> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn bad_blocks(&self) -> RpcResult<Vec<RpcBadBlock>> {
+        Ok(DebugApi::bad_blocks(self)?)
     }
 
     /// Handler for `debug_traceChain`
@@ -753,16 +1383,27 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_accountRange`
     async fn debug_account_range(
         &self,
-        _block_number: BlockNumberOrTag,
-        _start: Bytes,
-        _max_results: u64,
-        _nocode: bool,
-        _nostorage: bool,
-        _incompletes: bool,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_number: BlockNumberOrTag,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
+        incompletes: bool,
+    ) -> RpcResult<AccountRangeResult> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(DebugApi::debug_account_range(
+            self,
+            block_number.into(),
+            start,
+            max_results,
+            nocode,
+            nostorage,
+            incompletes,
+        )
+        .await?)
     }
 
     async fn debug_block_profile(&self, _file: String, _seconds: u64) -> RpcResult<()> {
@@ -797,6 +1438,15 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_executionWitness`
+    async fn debug_execution_witness(
+        &self,
+        block: BlockNumberOrTag,
+    ) -> RpcResult<ExecutionWitness> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(DebugApi::debug_execution_witness(self, block).await?)
+    }
+
     async fn debug_free_os_memory(&self) -> RpcResult<()> {
         Ok(())
     }
@@ -817,32 +1467,49 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_getModifiedAccountsByHash`
     async fn debug_get_modified_accounts_by_hash(
         &self,
-        _start_hash: B256,
-        _end_hash: B256,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_hash: B256,
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>> {
+        let start = self.block_num_hash_by_hash(start_hash)?;
+        let (start, end) = match end_hash {
+            Some(end_hash) => (start.number, self.block_num_hash_by_hash(end_hash)?),
+            // with a single block, return the accounts modified in that block
+            None => (start.number.saturating_sub(1), start),
+        };
+        Ok(self.modified_accounts(start, end)?)
     }
 
+    /// Handler for `debug_getModifiedAccountsByNumber`
     async fn debug_get_modified_accounts_by_number(
         &self,
-        _start_number: u64,
-        _end_number: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_number: u64,
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>> {
+        let (start_number, end_number) = match end_number {
+            Some(end_number) => (start_number, end_number),
+            // with a single block, return the accounts modified in that block
+            None => (start_number.saturating_sub(1), start_number),
+        };
+        let end_hash = self
+            .inner
+            .provider
+            .block_hash(end_number)
+            .to_rpc_result()?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        Ok(self.modified_accounts(start_number, BlockNumHash::new(end_number, end_hash))?)
     }
 
     async fn debug_go_trace(&self, _file: String, _seconds: u64) -> RpcResult<()> {
         Ok(())
     }
 
-    async fn debug_intermediate_roots(
-        &self,
-        _block_hash: B256,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_intermediateRoots`
+    async fn debug_intermediate_roots(&self, block_hash: B256) -> RpcResult<Vec<B256>> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(DebugApi::debug_intermediate_roots(self, block_hash).await?)
     }
 
     async fn debug_mem_stats(&self) -> RpcResult<()> {
@@ -873,8 +1540,9 @@ where
         Ok(())
     }
 
-    async fn debug_set_head(&self, _number: u64) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_setHead`
+    async fn debug_set_head(&self, number: u64) -> RpcResult<()> {
+        Ok(DebugApi::debug_set_head(self, number).await?)
     }
 
     async fn debug_set_mutex_profile_fraction(&self, _rate: i32) -> RpcResult<()> {
@@ -889,20 +1557,30 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_standardTraceBadBlockToFile`
     async fn debug_standard_trace_bad_block_to_file(
         &self,
-        _block: BlockNumberOrTag,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<StandardTraceConfig>,
+    ) -> RpcResult<Vec<String>> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(DebugApi::debug_standard_trace_bad_block_to_file(
+            self,
+            block_hash,
+            opts.unwrap_or_default(),
+        )
+        .await?)
     }
 
+    /// Handler for `debug_standardTraceBlockToFile`
     async fn debug_standard_trace_block_to_file(
         &self,
-        _block: BlockNumberOrTag,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<StandardTraceConfig>,
+    ) -> RpcResult<Vec<String>> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(DebugApi::debug_standard_trace_block_to_file(self, block_hash, opts.unwrap_or_default())
+            .await?)
     }
 
     async fn debug_start_cpu_profile(&self, _file: String) -> RpcResult<()> {
@@ -921,23 +1599,35 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_storageRangeAt`
     async fn debug_storage_range_at(
         &self,
-        _block_hash: B256,
-        _tx_idx: usize,
-        _contract_address: Address,
-        _key_start: B256,
-        _max_result: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> RpcResult<StorageRangeResult> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(DebugApi::debug_storage_range_at(
+            self,
+            block_hash,
+            tx_idx,
+            contract_address,
+            key_start,
+            max_result,
+        )
+        .await?)
     }
 
+    /// Handler for `debug_traceBadBlock`
     async fn debug_trace_bad_block(
         &self,
-        _block_hash: B256,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<TraceResult>> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(DebugApi::debug_trace_bad_block(self, block_hash, opts.unwrap_or_default()).await?)
     }
 
     async fn debug_verbosity(&self, _level: usize) -> RpcResult<()> {
@@ -980,4 +1670,170 @@ struct DebugApiInner<Provider, Eth> {
     eth_api: Eth,
     // restrict the number of concurrent calls to blocking calls
     blocking_task_guard: BlockingTaskGuard,
+    /// Directory the trace files are written to.
+    trace_dir: PathBuf,
+    /// The type that can spawn blocking tasks, like unwinding the chain.
+    task_spawner: Box<dyn TaskSpawner>,
+}
+
+/// Creates the file at the given path and writes its content with the given closure.
+fn write_trace_file(
+    path: &Path,
+    f: impl FnOnce(&mut BufWriter<File>) -> io::Result<()>,
+) -> EthResult<()> {
+    let write = || {
+        let mut writer = BufWriter::new(File::create(path)?);
+        f(&mut writer)?;
+        writer.flush()
+    };
+    write().map_err(|err| {
+        EthApiError::Internal(RethError::Custom(format!(
+            "failed to write trace file {}: {err}",
+            path.display()
+        )))
+    })
+}
+
+/// Applies the pre-block system calls of a block to the given database, which must not have
+/// been used yet, and returns the hashed state of their changes.
+///
+/// The only pre-block system call is the EIP-4788 beacon root contract call, which only changes
+/// the storage of the beacon roots contract.
+fn apply_pre_block_calls<DB>(
+    db: &mut CacheDB<DB>,
+    chain_spec: &ChainSpec,
+    cfg: &CfgEnvWithHandlerCfg,
+    block_env: &BlockEnv,
+    block_number: BlockNumber,
+    parent_beacon_block_root: Option<B256>,
+) -> EthResult<HashedPostState>
+where
+    DB: DatabaseRef,
+    DB::Error: std::fmt::Display,
+{
+    pre_block_beacon_root_contract_call(
+        db,
+        chain_spec,
+        block_number,
+        cfg,
+        block_env,
+        parent_beacon_block_root,
+    )?;
+
+    // the database is unused, so the slots of the contract were all loaded by the call
+    let mut hashed_state = HashedPostState::default();
+    let contract = db
+        .accounts
+        .get(&BEACON_ROOTS_ADDRESS)
+        .filter(|account| account.account_state != AccountState::NotExisting);
+    if let Some(contract) = contract {
+        let hashed_address = keccak256(BEACON_ROOTS_ADDRESS);
+        hashed_state.accounts.insert(hashed_address, Some(into_reth_acc(contract.info.clone())));
+        hashed_state.storages.insert(
+            hashed_address,
+            HashedStorage::from_iter(
+                false,
+                contract
+                    .storage
+                    .iter()
+                    .map(|(key, value)| (keccak256(B256::new(key.to_be_bytes())), *value)),
+            ),
+        );
+    }
+    Ok(hashed_state)
+}
+
+/// Returns the hashed state changes of a single transaction.
+///
+/// Accounts that self-destructed, or were touched and left empty once the state clear rules
+/// apply, are removed along with their storage.
+fn hashed_transaction_state(state: &EvmState, state_clear: bool) -> HashedPostState {
+    let mut hashed_state = HashedPostState::default();
+    for (address, account) in state.iter().filter(|(_, account)| account.is_touched()) {
+        let hashed_address = keccak256(address);
+        let destroyed = account.is_selfdestructed() || (state_clear && account.is_empty());
+        hashed_state
+            .accounts
+            .insert(hashed_address, (!destroyed).then(|| into_reth_acc(account.info.clone())));
+
+        let storage = account
+            .storage
+            .iter()
+            .filter(|(_, slot)| !destroyed && slot.is_changed())
+            .map(|(key, slot)| (keccak256(B256::new(key.to_be_bytes())), slot.present_value));
+        hashed_state.storages.insert(
+            hashed_address,
+            HashedStorage::from_iter(destroyed || account.is_created(), storage),
+        );
+    }
+    hashed_state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{
+        bytes, revm_primitives::AccountInfo, ChainSpecBuilder, ForkCondition, Hardfork, MAINNET,
+    };
+    use revm::{db::EmptyDB, primitives::Bytecode};
+
+    #[test]
+    fn pre_block_calls_of_cancun_block() {
+        let code = bytes!("3373fffffffffffffffffffffffffffffffffffffffe14604d57602036146024575f5ffd5b5f35801560495762001fff810690815414603c575f5ffd5b62001fff01545f5260205ff35b5f5ffd5b62001fff42064281555f359062001fff015500");
+        let contract = AccountInfo {
+            balance: U256::ZERO,
+            nonce: 1,
+            code_hash: keccak256(&code),
+            code: Some(Bytecode::new_raw(code)),
+        };
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(BEACON_ROOTS_ADDRESS, contract.clone());
+
+        let chain_spec = ChainSpecBuilder::from(&*MAINNET)
+            .shanghai_activated()
+            .with_fork(Hardfork::Cancun, ForkCondition::Timestamp(1))
+            .build();
+        let cfg = CfgEnvWithHandlerCfg::new_with_spec_id(Default::default(), SpecId::CANCUN);
+        let timestamp = 12;
+        let block_env = BlockEnv {
+            number: U256::from(1),
+            timestamp: U256::from(timestamp),
+            ..Default::default()
+        };
+        let parent_beacon_block_root = B256::with_last_byte(0x69);
+
+        let hashed_state = apply_pre_block_calls(
+            &mut db,
+            &chain_spec,
+            &cfg,
+            &block_env,
+            1,
+            Some(parent_beacon_block_root),
+        )
+        .unwrap();
+
+        // the timestamp and the root are stored in the ring buffers of the contract
+        let history_buffer_length = 8191;
+        let slot = |index: u64| keccak256(B256::new(U256::from(index).to_be_bytes()));
+        let hashed_address = keccak256(BEACON_ROOTS_ADDRESS);
+        assert_eq!(
+            hashed_state.accounts.get(&hashed_address),
+            Some(&Some(into_reth_acc(contract.clone())))
+        );
+        let storage = &hashed_state.storages[&hashed_address];
+        assert_eq!(storage.storage.get(&slot(timestamp)), Some(&U256::from(timestamp)));
+        assert_eq!(
+            storage.storage.get(&slot(timestamp + history_buffer_length)),
+            Some(&U256::from_be_bytes(parent_beacon_block_root.0))
+        );
+
+        // before cancun, no pre-block calls are applied
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(BEACON_ROOTS_ADDRESS, contract);
+        let block_env = BlockEnv { timestamp: U256::ZERO, ..block_env };
+        assert_eq!(
+            apply_pre_block_calls(&mut db, &chain_spec, &cfg, &block_env, 1, None).unwrap(),
+            HashedPostState::default()
+        );
+    }
 }
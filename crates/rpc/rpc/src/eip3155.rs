@@ -0,0 +1,128 @@
+//! Writer for [EIP-3155](https://eips.ethereum.org/EIPS/eip-3155) traces.
+//!
+//! The traces are built from the steps recorded by a
+//! [TracingInspector](revm_inspectors::tracing::TracingInspector) and written as one JSON object
+//! per line, followed by a summary line of the transaction.
+
+use reth_primitives::{Bytes, U256, U64};
+use reth_rpc_types::trace::geth::GethDefaultTracingOptions;
+use revm::interpreter::{opcode, InstructionResult};
+use revm_inspectors::tracing::{
+    types::{CallTraceNode, CallTraceStep},
+    CallTraceArena,
+};
+use serde::Serialize;
+use std::io::{self, Write};
+
+/// A single step of an EIP-3155 trace.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Eip3155Step<'a> {
+    pc: u64,
+    op: u8,
+    gas: U64,
+    gas_cost: U64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    memory: Option<&'a Bytes>,
+    mem_size: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stack: Option<&'a [U256]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    return_data: Option<&'a Bytes>,
+    depth: u64,
+    refund: u64,
+    op_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// The summary line that ends the EIP-3155 trace of a transaction.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Eip3155Summary<'a> {
+    output: &'a Bytes,
+    gas_used: U64,
+    pass: bool,
+}
+
+/// Writes the EIP-3155 trace of a transaction from the recorded call traces.
+///
+/// The steps of nested calls are written in execution order, i.e. right after the step of the
+/// call opcode that entered them.
+pub(crate) fn write_trace<W: Write>(
+    writer: &mut W,
+    traces: &CallTraceArena,
+    gas_used: u64,
+    opts: &GethDefaultTracingOptions,
+) -> io::Result<()> {
+    let nodes = traces.nodes();
+    let Some(root) = nodes.first() else { return Ok(()) };
+    write_node(writer, nodes, root, opts)?;
+
+    let summary = Eip3155Summary {
+        output: &root.trace.output,
+        gas_used: U64::from(gas_used),
+        pass: root.trace.success,
+    };
+    serde_json::to_writer(&mut *writer, &summary)?;
+    writeln!(writer)
+}
+
+/// Writes the steps of the given node, descending into the child calls as they are entered.
+fn write_node<W: Write>(
+    writer: &mut W,
+    nodes: &[CallTraceNode],
+    node: &CallTraceNode,
+    opts: &GethDefaultTracingOptions,
+) -> io::Result<()> {
+    let mut children = node.children.iter();
+    for step in &node.trace.steps {
+        write_step(writer, node, step, opts)?;
+
+        // a call opcode that failed early did not enter a child call
+        if is_call_like(step) {
+            if let Some(child) = children.next() {
+                write_node(writer, nodes, &nodes[*child], opts)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_step<W: Write>(
+    writer: &mut W,
+    node: &CallTraceNode,
+    step: &CallTraceStep,
+    opts: &GethDefaultTracingOptions,
+) -> io::Result<()> {
+    let entry = Eip3155Step {
+        pc: step.pc as u64,
+        op: step.op.get(),
+        gas: U64::from(step.gas_remaining),
+        gas_cost: U64::from(step.gas_cost),
+        memory: opts.is_memory_enabled().then(|| step.memory.as_bytes()),
+        mem_size: step.memory_size as u64,
+        stack: step.stack.as_deref().filter(|_| opts.is_stack_enabled()),
+        return_data: opts.is_return_data_enabled().then_some(&node.trace.output),
+        depth: step.depth,
+        refund: step.gas_refund_counter,
+        op_name: step.op.to_string(),
+        error: (step.status as u8 >= InstructionResult::Revert as u8)
+            .then(|| format!("{:?}", step.status)),
+    };
+    serde_json::to_writer(&mut *writer, &entry)?;
+    writeln!(writer)
+}
+
+/// Returns true if the step is one of the opcodes that enter a new call frame.
+fn is_call_like(step: &CallTraceStep) -> bool {
+    matches!(
+        step.op.get(),
+        opcode::CALL |
+            opcode::CALLCODE |
+            opcode::DELEGATECALL |
+            opcode::STATICCALL |
+            opcode::CREATE |
+            opcode::CREATE2
+    )
+}
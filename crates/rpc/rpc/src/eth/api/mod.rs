@@ -50,7 +50,8 @@ mod sign;
 mod state;
 mod transactions;
 
-use crate::BlockingTaskPool;
+use crate::{BlockingTaskGuard, BlockingTaskPool};
+pub(crate) use pending_block::pre_block_beacon_root_contract_call;
 pub use transactions::{EthTransactions, TransactionSource};
 
 /// `Eth` API trait.
@@ -103,7 +104,9 @@ where
         eth_cache: EthStateCache,
         gas_oracle: GasPriceOracle<Provider>,
         gas_cap: impl Into<GasCap>,
+        eth_proof_window: u64,
         blocking_task_pool: BlockingTaskPool,
+        blocking_task_guard: BlockingTaskGuard,
         fee_history_cache: FeeHistoryCache,
         evm_config: EvmConfig,
     ) -> Self {
@@ -114,8 +117,10 @@ where
             eth_cache,
             gas_oracle,
             gas_cap.into().into(),
+            eth_proof_window,
             Box::<TokioTaskExecutor>::default(),
             blocking_task_pool,
+            blocking_task_guard,
             fee_history_cache,
             evm_config,
         )
@@ -130,8 +135,10 @@ where
         eth_cache: EthStateCache,
         gas_oracle: GasPriceOracle<Provider>,
         gas_cap: u64,
+        eth_proof_window: u64,
         task_spawner: Box<dyn TaskSpawner>,
         blocking_task_pool: BlockingTaskPool,
+        blocking_task_guard: BlockingTaskGuard,
         fee_history_cache: FeeHistoryCache,
         evm_config: EvmConfig,
     ) -> Self {
@@ -151,10 +158,12 @@ where
             eth_cache,
             gas_oracle,
             gas_cap,
+            eth_proof_window,
             starting_block: U256::from(latest_block),
             task_spawner,
             pending_block: Default::default(),
             blocking_task_pool,
+            blocking_task_guard,
             fee_history_cache,
             evm_config,
             #[cfg(feature = "optimism")]
@@ -199,6 +208,11 @@ where
         self.inner.gas_cap
     }
 
+    /// Returns the number of blocks before the latest block for which `eth_getProof` is served
+    pub fn eth_proof_window(&self) -> u64 {
+        self.inner.eth_proof_window
+    }
+
     /// Returns the inner `Provider`
     pub fn provider(&self) -> &Provider {
         &self.inner.provider
@@ -451,6 +465,15 @@ impl Default for GasCap {
     }
 }
 
+/// The default number of blocks before the latest block for which `eth_getProof` is served.
+///
+/// By default, proofs are only served for the latest block.
+pub const DEFAULT_ETH_PROOF_WINDOW: u64 = 0;
+
+/// The maximum number of blocks before the latest block for which `eth_getProof` can be served,
+/// roughly 4 weeks of mainnet blocks.
+pub const MAX_ETH_PROOF_WINDOW: u64 = 28 * 24 * 60 * 60 / 12;
+
 impl From<u64> for GasCap {
     fn from(gas_cap: u64) -> Self {
         Self(gas_cap)
@@ -479,6 +502,8 @@ struct EthApiInner<Provider, Pool, Network, EvmConfig> {
     gas_oracle: GasPriceOracle<Provider>,
     /// Maximum gas limit for `eth_call` and call tracing RPC methods.
     gas_cap: u64,
+    /// The number of blocks before the latest block for which `eth_getProof` is served.
+    eth_proof_window: u64,
     /// The block number at which the node started
     starting_block: U256,
     /// The type that can spawn tasks which would otherwise block.
@@ -487,6 +512,8 @@ struct EthApiInner<Provider, Pool, Network, EvmConfig> {
     pending_block: Mutex<Option<PendingBlock>>,
     /// A pool dedicated to blocking tasks.
     blocking_task_pool: BlockingTaskPool,
+    /// Guard for the number of concurrent proof requests.
+    blocking_task_guard: BlockingTaskGuard,
     /// Cache for block fees history
     fee_history_cache: FeeHistoryCache,
     /// The type that defines how to configure the EVM
@@ -7,7 +7,7 @@ use crate::{
             inspect, inspect_and_return_db, prepare_call_env, replay_transactions_until, transact,
             EvmOverrides,
         },
-        utils::recover_raw_transaction,
+        utils::{into_pool_conditional, recover_raw_transaction},
     },
     EthApi, EthApiSpec,
 };
@@ -17,30 +17,32 @@ use reth_node_api::ConfigureEvmEnv;
 use reth_primitives::{
     eip4844::calc_blob_gasprice,
     revm::env::{fill_block_env_with_coinbase, tx_env_with_recovered},
-    Address, BlockId, BlockNumberOrTag, Bytes, FromRecoveredPooledTransaction, Header,
-    IntoRecoveredTransaction, Receipt, SealedBlock, SealedBlockWithSenders,
+    Address, BlockId, BlockNumberOrTag, BlockWithSenders, Bytes, FromRecoveredPooledTransaction,
+    Header, IntoRecoveredTransaction, Receipt, SealedBlock, SealedBlockWithSenders,
     TransactionKind::{Call, Create},
     TransactionMeta, TransactionSigned, TransactionSignedEcRecovered, B256, U128, U256, U64,
 };
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderBox, StateProviderFactory,
+    BlockExecutor, BlockReaderIdExt, BundleStateWithReceipts, ChainSpecProvider, EvmEnvProvider,
+    HeaderProvider, StateProviderBox, StateProviderFactory,
 };
 use reth_revm::{
     database::StateProviderDatabase,
+    processor::EVMProcessor,
     tracing::{TracingInspector, TracingInspectorConfig},
 };
 use reth_rpc_types::{
     transaction::{
         EIP1559TransactionRequest, EIP2930TransactionRequest, EIP4844TransactionRequest,
-        LegacyTransactionRequest,
+        LegacyTransactionRequest, TransactionConditional,
     },
     Index, Log, Transaction, TransactionInfo, TransactionKind as RpcTransactionKind,
     TransactionReceipt, TransactionRequest, TypedTransactionRequest,
 };
 use reth_rpc_types_compat::transaction::from_recovered_with_block_context;
-use reth_transaction_pool::{TransactionOrigin, TransactionPool};
+use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
 use revm::{
-    db::CacheDB,
+    db::{CacheDB, CacheState},
     primitives::{
         db::DatabaseCommit, BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, ExecutionResult,
         ResultAndState, SpecId, State,
@@ -102,6 +104,18 @@ pub trait EthTransactions: Send + Sync {
         F: FnOnce(StateProviderBox) -> EthResult<T> + Send + 'static,
         T: Send + 'static;
 
+    /// Executes the block with the block executor on top of the state of its parent on a new task,
+    /// including the pre-block system calls and the post-block state changes.
+    ///
+    /// The closure is called with the state of the parent, the state that was loaded from it
+    /// while executing the block and the output of the execution.
+    async fn spawn_with_executed_block<F, T>(&self, block: BlockWithSenders, f: F) -> EthResult<T>
+    where
+        F: FnOnce(StateProviderBox, CacheState, BundleStateWithReceipts) -> EthResult<T>
+            + Send
+            + 'static,
+        T: Send + 'static;
+
     /// Returns the revm evm env for the requested [BlockId]
     ///
     /// If the [BlockId] this will return the [BlockId] of the block the env was configured
@@ -178,6 +192,19 @@ pub trait EthTransactions: Send + Sync {
     /// Returns the hash of the transaction.
     async fn send_raw_transaction(&self, tx: Bytes) -> EthResult<B256>;
 
+    /// Decodes and recovers the transaction and submits it to the pool with the given
+    /// preconditions.
+    ///
+    /// The transaction is rejected if the preconditions don't hold for the latest block, and
+    /// removed from the pool once they no longer hold.
+    ///
+    /// Returns the hash of the transaction.
+    async fn send_raw_transaction_conditional(
+        &self,
+        tx: Bytes,
+        conditional: TransactionConditional,
+    ) -> EthResult<B256>;
+
     /// Signs transaction with a matching signer, if any and submits the transaction to the pool.
     /// Returns the hash of the signed transaction.
     async fn send_transaction(&self, request: TransactionRequest) -> EthResult<B256>;
@@ -280,6 +307,22 @@ pub trait EthTransactions: Send + Sync {
             + 'static,
         R: Send + 'static;
 
+    /// Retrieves the transaction if it exists and executes it with the given inspector.
+    ///
+    /// Same as [Self::spawn_trace_transaction_in_block], but with an arbitrary [Inspector].
+    async fn spawn_inspect_transaction_in_block<I, F, R>(
+        &self,
+        hash: B256,
+        inspector: I,
+        f: F,
+    ) -> EthResult<Option<R>>
+    where
+        I: Inspector<StateCacheDB> + Send + 'static,
+        F: FnOnce(TransactionInfo, I, ResultAndState, StateCacheDB) -> EthResult<R>
+            + Send
+            + 'static,
+        R: Send + 'static;
+
     /// Executes all transactions of a block and returns a list of callback results invoked for each
     /// transaction in the block.
     ///
@@ -372,6 +415,36 @@ where
         .await
     }
 
+    async fn spawn_with_executed_block<F, T>(&self, block: BlockWithSenders, f: F) -> EthResult<T>
+    where
+        F: FnOnce(StateProviderBox, CacheState, BundleStateWithReceipts) -> EthResult<T>
+            + Send
+            + 'static,
+        T: Send + 'static,
+    {
+        let total_difficulty = self
+            .provider()
+            .header_td_by_number(block.number)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        let chain_spec = self.provider().chain_spec();
+        let evm_config = self.inner.evm_config.clone();
+        self.spawn_with_state_at_block(block.parent_hash.into(), move |state| {
+            let mut executor = EVMProcessor::new_with_db(
+                chain_spec,
+                StateProviderDatabase::new(&state),
+                evm_config,
+            );
+            executor
+                .execute(&block, total_difficulty)
+                .map_err(|err| EthApiError::Internal(err.into()))?;
+            let output = executor.take_output_state();
+            let loaded = std::mem::take(&mut executor.db_mut().cache);
+            drop(executor);
+            f(state, loaded, output)
+        })
+        .await
+    }
+
     async fn evm_env_at(
         &self,
         at: BlockId,
@@ -551,6 +624,26 @@ where
         Ok(hash)
     }
 
+    async fn send_raw_transaction_conditional(
+        &self,
+        tx: Bytes,
+        conditional: TransactionConditional,
+    ) -> EthResult<B256> {
+        let recovered = recover_raw_transaction(tx)?;
+        let mut pool_transaction =
+            <Pool::Transaction>::from_recovered_pooled_transaction(recovered);
+        pool_transaction
+            .set_conditional(into_pool_conditional(conditional))
+            .map_err(|_| EthApiError::Unsupported("conditional transactions are not supported"))?;
+
+        // submit the transaction to the pool with a `Private` origin, so that it's not propagated
+        // to peers that would include it without checking its conditions
+        let hash =
+            self.pool().add_transaction(TransactionOrigin::Private, pool_transaction).await?;
+
+        Ok(hash)
+    }
+
     async fn send_transaction(&self, mut request: TransactionRequest) -> EthResult<B256> {
         let from = match request.from {
             Some(from) => from,
@@ -882,6 +975,22 @@ where
             + Send
             + 'static,
         R: Send + 'static,
+    {
+        self.spawn_inspect_transaction_in_block(hash, TracingInspector::new(config), f).await
+    }
+
+    async fn spawn_inspect_transaction_in_block<I, F, R>(
+        &self,
+        hash: B256,
+        mut inspector: I,
+        f: F,
+    ) -> EthResult<Option<R>>
+    where
+        I: Inspector<StateCacheDB> + Send + 'static,
+        F: FnOnce(TransactionInfo, I, ResultAndState, StateCacheDB) -> EthResult<R>
+            + Send
+            + 'static,
+        R: Send + 'static,
     {
         let (transaction, block) = match self.transaction_and_block(hash).await? {
             None => return Ok(None),
@@ -905,7 +1014,6 @@ where
             let env =
                 EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, tx_env_with_recovered(&tx));
 
-            let mut inspector = TracingInspector::new(config);
             let (res, _, db) = inspect_and_return_db(db, env, &mut inspector)?;
             f(tx_info, inspector, res, db)
         })
@@ -1416,9 +1524,9 @@ mod tests {
     use crate::{
         eth::{
             cache::EthStateCache, gas_oracle::GasPriceOracle, FeeHistoryCache,
-            FeeHistoryCacheConfig,
+            FeeHistoryCacheConfig, DEFAULT_ETH_PROOF_WINDOW,
         },
-        BlockingTaskPool, EthApi,
+        BlockingTaskGuard, BlockingTaskPool, EthApi,
     };
     use reth_network_api::noop::NoopNetwork;
     use reth_node_ethereum::EthEvmConfig;
@@ -1444,7 +1552,9 @@ mod tests {
             cache.clone(),
             GasPriceOracle::new(noop_provider, Default::default(), cache.clone()),
             ETHEREUM_BLOCK_GAS_LIMIT,
+            DEFAULT_ETH_PROOF_WINDOW,
             BlockingTaskPool::build().expect("failed to build tracing pool"),
+            BlockingTaskGuard::new(1),
             fee_history_cache,
             evm_config,
         );
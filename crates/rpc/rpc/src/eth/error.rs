@@ -10,9 +10,12 @@ use reth_interfaces::RethError;
 use reth_primitives::{revm_primitives::InvalidHeader, Address, Bytes, U256};
 use reth_revm::tracing::js::JsInspectorError;
 use reth_rpc_types::{error::EthRpcErrorCode, request::TransactionInputError, BlockError};
-use reth_transaction_pool::error::{
-    Eip4844PoolTransactionError, InvalidPoolTransactionError, PoolError, PoolErrorKind,
-    PoolTransactionError,
+use reth_transaction_pool::{
+    error::{
+        Eip4844PoolTransactionError, InvalidPoolTransactionError, PoolError, PoolErrorKind,
+        PoolTransactionError,
+    },
+    TransactionConditionalError,
 };
 use revm::primitives::{EVMError, ExecutionResult, HaltReason, OutOfGasError};
 use std::time::Duration;
@@ -48,6 +51,9 @@ pub enum EthApiError {
     /// When an invalid block range is provided
     #[error("invalid block range")]
     InvalidBlockRange,
+    /// Thrown when the target block for proof computation exceeds the configured proof window
+    #[error("distance to target block exceeds maximum proof window")]
+    ExceedsMaxProofWindow,
     /// An internal error where prevrandao is not set in the evm's environment
     #[error("prevrandao not in the EVM's environment after merge")]
     PrevrandaoNotSet,
@@ -143,6 +149,7 @@ impl From<EthApiError> for ErrorObject<'static> {
             EthApiError::InvalidTransactionSignature |
             EthApiError::EmptyRawTransactionData |
             EthApiError::InvalidBlockRange |
+            EthApiError::ExceedsMaxProofWindow |
             EthApiError::ConflictingFeeFieldsInRequest |
             EthApiError::Signing(_) |
             EthApiError::BothStateAndStateDiffInOverride(_) |
@@ -617,6 +624,12 @@ pub enum RpcPoolError {
     /// constraint (blob vs normal tx)
     #[error("address already reserved")]
     AddressAlreadyReserved,
+    /// Thrown if the transaction was rejected by the pool's admission policy
+    #[error("rejected by admission policy: {0}")]
+    AdmissionPolicy(InvalidPoolTransactionError),
+    /// Thrown if the preconditions of a conditional transaction don't hold
+    #[error("transaction conditional failed: {0}")]
+    Conditional(#[from] TransactionConditionalError),
     /// Other unspecified error
     #[error(transparent)]
     Other(Box<dyn std::error::Error + Send + Sync>),
@@ -666,6 +679,16 @@ impl From<InvalidPoolTransactionError> for RpcPoolError {
             InvalidPoolTransactionError::Overdraft => {
                 RpcPoolError::Invalid(RpcInvalidTransactionError::InsufficientFunds)
             }
+            InvalidPoolTransactionError::PriorityFeeBelowMinimum { .. } => {
+                RpcPoolError::Underpriced
+            }
+            InvalidPoolTransactionError::ExceedsMaxCalldataSize(_, _) => {
+                RpcPoolError::OversizedData
+            }
+            err @ (InvalidPoolTransactionError::SenderNotAllowed(_) |
+            InvalidPoolTransactionError::RecipientNotAllowed(_) |
+            InvalidPoolTransactionError::BlockedSelector(_)) => RpcPoolError::AdmissionPolicy(err),
+            InvalidPoolTransactionError::Conditional(err) => RpcPoolError::Conditional(err),
         }
     }
 }
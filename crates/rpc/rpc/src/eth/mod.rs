@@ -15,10 +15,13 @@ pub(crate) mod utils;
 
 pub use api::{
     fee_history::{fee_history_cache_new_blocks_task, FeeHistoryCache, FeeHistoryCacheConfig},
-    EthApi, EthApiSpec, EthTransactions, TransactionSource, RPC_DEFAULT_GAS_CAP,
+    EthApi, EthApiSpec, EthTransactions, TransactionSource, DEFAULT_ETH_PROOF_WINDOW,
+    MAX_ETH_PROOF_WINDOW, RPC_DEFAULT_GAS_CAP,
 };
 
+pub(crate) use api::pre_block_beacon_root_contract_call;
 pub use bundle::EthBundle;
 pub use filter::{EthFilter, EthFilterConfig};
 pub use id_provider::EthSubscriptionIdProvider;
+pub(crate) use pubsub::pipe_from_stream;
 pub use pubsub::EthPubSub;
@@ -2,6 +2,8 @@
 
 use crate::eth::error::{EthApiError, EthResult};
 use reth_primitives::{Bytes, PooledTransactionsElement, PooledTransactionsElementEcRecovered};
+use reth_rpc_types::transaction::{KnownAccount, TransactionConditional};
+use reth_transaction_pool::AccountStorageCondition;
 
 /// Recovers a [PooledTransactionsElementEcRecovered] from an enveloped encoded byte stream.
 ///
@@ -18,3 +20,26 @@ pub(crate) fn recover_raw_transaction(
 
     transaction.try_into_ecrecovered().or(Err(EthApiError::InvalidTransactionSignature))
 }
+
+/// Converts the RPC [TransactionConditional] into the preconditions checked by the pool.
+pub(crate) fn into_pool_conditional(
+    conditional: TransactionConditional,
+) -> reth_transaction_pool::TransactionConditional {
+    reth_transaction_pool::TransactionConditional {
+        block_number_min: conditional.block_number_min.map(|n| n.to()),
+        block_number_max: conditional.block_number_max.map(|n| n.to()),
+        timestamp_min: conditional.timestamp_min.map(|t| t.to()),
+        timestamp_max: conditional.timestamp_max.map(|t| t.to()),
+        known_accounts: conditional
+            .known_accounts
+            .into_iter()
+            .map(|(address, account)| {
+                let condition = match account {
+                    KnownAccount::StorageRoot(root) => AccountStorageCondition::RootHash(root),
+                    KnownAccount::StorageSlots(slots) => AccountStorageCondition::Slots(slots),
+                };
+                (address, condition)
+            })
+            .collect(),
+    }
+}
@@ -26,6 +26,7 @@
 
 mod admin;
 mod debug;
+mod eip3155;
 mod engine;
 pub mod eth;
 mod layers;
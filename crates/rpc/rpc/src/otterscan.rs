@@ -1,32 +1,195 @@
-use crate::result::internal_rpc_err;
+use crate::{
+    eth::{error::EthApiError, EthTransactions},
+    result::{internal_rpc_err, ToRpcResult},
+};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
-use reth_primitives::{Address, BlockId, BlockNumberOrTag, TxHash, B256};
+use reth_primitives::{
+    Account, Address, BlockId, BlockNumber, BlockNumberOrTag, Bloom, Bytes, TxHash, B256,
+    KECCAK_EMPTY, U256, U64,
+};
+use reth_provider::{BlockNumReader, ChangeSetReader, HistoryReader};
+use reth_revm::{
+    primitives::ExecutionResult,
+    tracing::{
+        types::{CallKind, CallTraceNode},
+        TracingInspector, TracingInspectorConfig,
+    },
+};
 use reth_rpc_api::{EthApiServer, OtterscanServer};
 use reth_rpc_types::{
-    BlockDetails, ContractCreator, InternalOperation, OtsBlockTransactions, TraceEntry,
-    Transaction, TransactionsWithReceipts,
+    BlockDetails, BlockTransactions, ContractCreator, InternalOperation, OperationType, OtsBlock,
+    OtsBlockTransactions, OtsTransactionReceipt, TraceEntry, Transaction, TransactionsWithReceipts,
+};
+use revm::{
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter},
+    primitives::Log,
+    Database, EvmContext, Inspector,
 };
 
 const API_LEVEL: u64 = 8;
 
+/// The number of account history entries that are loaded at once when paging through the history
+/// of an address.
+const HISTORY_BATCH_SIZE: usize = 128;
+
 /// Otterscan API.
 #[derive(Debug)]
-pub struct OtterscanApi<Eth> {
+pub struct OtterscanApi<Provider, Eth> {
+    provider: Provider,
     eth: Eth,
 }
 
-impl<Eth> OtterscanApi<Eth> {
+impl<Provider, Eth> OtterscanApi<Provider, Eth> {
     /// Creates a new instance of `Otterscan`.
-    pub fn new(eth: Eth) -> Self {
-        Self { eth }
+    pub fn new(provider: Provider, eth: Eth) -> Self {
+        Self { provider, eth }
+    }
+}
+
+impl<Provider, Eth> OtterscanApi<Provider, Eth>
+where
+    Provider: BlockNumReader + ChangeSetReader + HistoryReader + 'static,
+    Eth: EthApiServer + EthTransactions + 'static,
+{
+    /// Returns the transactions of the given block that touched the given address together with
+    /// their receipts, in the order they were included in the block.
+    ///
+    /// The account history index only records that an account changed in a block. Transactions
+    /// that reference the address directly, as sender, recipient, created contract or log emitter,
+    /// are matched without re-execution. If any transaction doesn't, the block is traced to find
+    /// the remaining transactions that touched the address in an internal call.
+    async fn block_transactions_touching(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+    ) -> RpcResult<Vec<(Transaction, OtsTransactionReceipt)>> {
+        let block_id = BlockId::Number(block_number.into());
+        let (block, receipts) = futures::try_join!(
+            self.eth.block_by_number(block_number.into(), true),
+            self.eth.block_receipts(block_id),
+        )?;
+        let (Some(block), Some(receipts)) = (block, receipts) else { return Ok(Vec::new()) };
+
+        let timestamp = block.header.timestamp.saturating_to::<u64>();
+        let BlockTransactions::Full(transactions) = block.inner.transactions else {
+            return Ok(Vec::new())
+        };
+
+        let mut touched = transactions
+            .iter()
+            .zip(&receipts)
+            .map(|(tx, receipt)| {
+                tx.from == address ||
+                    tx.to == Some(address) ||
+                    receipt.contract_address == Some(address) ||
+                    receipt.logs.iter().any(|log| log.address == address)
+            })
+            .collect::<Vec<_>>();
+
+        // transactions can also reach the address through internal calls, so trace the ones that
+        // don't touch it directly
+        if touched.contains(&false) {
+            let direct = touched.clone();
+            let traced = self
+                .eth
+                .trace_block_with(
+                    block_id,
+                    TracingInspectorConfig::default_parity(),
+                    move |tx_info, inspector, _, _, _| {
+                        if tx_info.index.is_some_and(|index| direct[index as usize]) {
+                            return Ok(true)
+                        }
+                        Ok(inspector.into_traces().nodes().iter().any(|node| {
+                            node.trace.caller == address ||
+                                node.trace.address == address ||
+                                node.trace.selfdestruct_refund_target == Some(address)
+                        }))
+                    },
+                )
+                .await?;
+            if let Some(traced) = traced {
+                touched = traced;
+            }
+        }
+
+        Ok(transactions
+            .into_iter()
+            .zip(receipts)
+            .zip(touched)
+            .filter(|(_, touched)| *touched)
+            .map(|((tx, receipt), _)| (tx, OtsTransactionReceipt { receipt, timestamp }))
+            .collect())
+    }
+
+    /// Replays the given transaction and returns its call trace, or `None` if the transaction
+    /// doesn't exist.
+    async fn trace_transaction_calls(
+        &self,
+        tx_hash: TxHash,
+    ) -> RpcResult<Option<TransactionTrace>> {
+        let trace = self
+            .eth
+            .spawn_inspect_transaction_in_block(
+                tx_hash,
+                SelfdestructInspector::new(TracingInspectorConfig::default_parity()),
+                move |_, inspector, _, _| Ok(inspector.into_transaction_trace()),
+            )
+            .await?;
+        Ok(trace)
+    }
+
+    /// Returns the block in which the account state changed from not satisfying the predicate to
+    /// satisfying it, or `None` if it already satisfied the predicate before the earliest
+    /// available history.
+    ///
+    /// The predicate must be monotonic over the account's history: `false` for all blocks up to
+    /// some point and `true` for all following blocks, including the latest state.
+    ///
+    /// This binary searches the block numbers, so that every step only reads the history shard
+    /// with the account's first change at or after the block.
+    fn history_partition_point<F>(
+        &self,
+        address: Address,
+        mut predicate: F,
+    ) -> RpcResult<Option<BlockNumber>>
+    where
+        F: FnMut(Option<Account>) -> bool,
+    {
+        let (mut low, mut high) = (0, self.provider.best_block_number().to_rpc_result()? + 1);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            // The account state at the start of a block is the state before the first change at
+            // or after the block, or the latest state if there's no such change.
+            let changed =
+                self.provider.account_history_blocks_after(address, mid, 1).to_rpc_result()?;
+            let satisfied = match changed.first() {
+                Some(block) => predicate(
+                    self.provider
+                        .account_changeset_entry(*block, address)
+                        .to_rpc_result()?
+                        .and_then(|entry| entry.info),
+                ),
+                None => true,
+            };
+            if satisfied {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+
+        // The state at the start of `low` is the first that satisfies the predicate, hence the
+        // account changed in the block before.
+        Ok(low.checked_sub(1))
     }
 }
 
 #[async_trait]
-impl<Eth> OtterscanServer for OtterscanApi<Eth>
+impl<Provider, Eth> OtterscanServer for OtterscanApi<Provider, Eth>
 where
-    Eth: EthApiServer,
+    Provider: BlockNumReader + ChangeSetReader + HistoryReader + 'static,
+    Eth: EthApiServer + EthTransactions + 'static,
 {
     /// Handler for `ots_hasCode`
     async fn has_code(&self, address: Address, block_number: Option<BlockId>) -> RpcResult<bool> {
@@ -39,18 +202,34 @@ where
     }
 
     /// Handler for `ots_getInternalOperations`
-    async fn get_internal_operations(&self, _tx_hash: TxHash) -> RpcResult<Vec<InternalOperation>> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn get_internal_operations(
+        &self,
+        tx_hash: TxHash,
+    ) -> RpcResult<Option<Vec<InternalOperation>>> {
+        let trace = self.trace_transaction_calls(tx_hash).await?;
+        Ok(trace.map(TransactionTrace::into_internal_operations))
     }
 
     /// Handler for `ots_getTransactionError`
-    async fn get_transaction_error(&self, _tx_hash: TxHash) -> RpcResult<String> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn get_transaction_error(&self, tx_hash: TxHash) -> RpcResult<Option<Bytes>> {
+        let output = self
+            .eth
+            .spawn_trace_transaction_in_block(
+                tx_hash,
+                TracingInspectorConfig::none(),
+                move |_, _, res, _| match res.result {
+                    ExecutionResult::Revert { output, .. } => Ok(output),
+                    _ => Ok(Bytes::new()),
+                },
+            )
+            .await?;
+        Ok(output)
     }
 
     /// Handler for `ots_traceTransaction`
-    async fn trace_transaction(&self, _tx_hash: TxHash) -> RpcResult<TraceEntry> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn trace_transaction(&self, tx_hash: TxHash) -> RpcResult<Option<Vec<TraceEntry>>> {
+        let trace = self.trace_transaction_calls(tx_hash).await?;
+        Ok(trace.map(TransactionTrace::into_trace_entries))
     }
 
     /// Handler for `ots_getBlockDetails`
@@ -69,46 +248,510 @@ where
     }
 
     /// Handler for `getBlockTransactions`
+    ///
+    /// Pages are counted from the end of the block, so the first page contains the last
+    /// transactions of the block. Like in Erigon, the input of the transactions is cropped to the
+    /// 4 byte method selector and the logs and bloom of the receipts are cleared.
     async fn get_block_transactions(
         &self,
-        _block_number: BlockNumberOrTag,
-        _page_number: usize,
-        _page_size: usize,
+        block_number: BlockNumberOrTag,
+        page_number: usize,
+        page_size: usize,
     ) -> RpcResult<OtsBlockTransactions> {
-        Err(internal_rpc_err("unimplemented"))
+        let (block, receipts) = futures::try_join!(
+            self.eth.block_by_number(block_number, true),
+            self.eth.block_receipts(BlockId::Number(block_number)),
+        )?;
+        let (Some(block), Some(mut receipts)) = (block, receipts) else {
+            return Err(EthApiError::UnknownBlockNumber.into())
+        };
+
+        let timestamp = block.header.timestamp.saturating_to::<u64>();
+        let mut fullblock = OtsBlock::from(block.inner);
+        let BlockTransactions::Full(transactions) = &mut fullblock.block.transactions else {
+            return Err(internal_rpc_err("block transactions are not full"))
+        };
+        if transactions.len() != receipts.len() {
+            return Err(internal_rpc_err("number of transactions and receipts doesn't match"))
+        }
+
+        let page_end = transactions.len().saturating_sub(page_number.saturating_mul(page_size));
+        let page_start = page_end.saturating_sub(page_size);
+
+        *transactions = transactions.drain(page_start..page_end).collect();
+        for tx in transactions.iter_mut() {
+            if tx.input.len() > 4 {
+                tx.input = tx.input.slice(..4);
+            }
+        }
+
+        let receipts = receipts
+            .drain(page_start..page_end)
+            .map(|mut receipt| {
+                receipt.logs = Vec::new();
+                receipt.logs_bloom = Bloom::default();
+                OtsTransactionReceipt { receipt, timestamp }
+            })
+            .collect();
+
+        Ok(OtsBlockTransactions { fullblock, receipts })
     }
 
     /// Handler for `searchTransactionsBefore`
+    ///
+    /// Pages backwards through the account history of the address, starting right before the
+    /// given block, or at the latest block if the block number is `0`. Blocks are always included
+    /// as a whole, so a page can contain more than `page_size` transactions.
     async fn search_transactions_before(
         &self,
-        _address: Address,
-        _block_number: BlockNumberOrTag,
-        _page_size: usize,
+        address: Address,
+        block_number: BlockNumberOrTag,
+        page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        let (first_page, to) = match block_number.as_number() {
+            None | Some(0) => (true, self.provider.best_block_number().to_rpc_result()?),
+            Some(number) => (false, number - 1),
+        };
+
+        let mut txs = Vec::new();
+        let mut receipts = Vec::new();
+        let mut next = Some(to);
+        while let Some(to) = next.filter(|_| txs.len() < page_size) {
+            let blocks = self
+                .provider
+                .account_history_blocks_before(address, to, HISTORY_BATCH_SIZE)
+                .to_rpc_result()?;
+            next = if blocks.len() < HISTORY_BATCH_SIZE {
+                None
+            } else {
+                blocks.last().and_then(|block| block.checked_sub(1))
+            };
+
+            for block in blocks {
+                // most recent transactions first
+                for (tx, receipt) in
+                    self.block_transactions_touching(address, block).await?.into_iter().rev()
+                {
+                    txs.push(tx);
+                    receipts.push(receipt);
+                }
+                if txs.len() >= page_size {
+                    next = block.checked_sub(1);
+                    break
+                }
+            }
+        }
+
+        let last_page = match next {
+            Some(to) => self
+                .provider
+                .account_history_blocks_before(address, to, 1)
+                .to_rpc_result()?
+                .is_empty(),
+            None => true,
+        };
+
+        Ok(TransactionsWithReceipts { txs, receipts, first_page, last_page })
     }
 
     /// Handler for `searchTransactionsAfter`
+    ///
+    /// Pages forwards through the account history of the address, starting right after the given
+    /// block, or at genesis if the block number is `0`. Like `searchTransactionsBefore`, the
+    /// transactions of the page are returned with the most recent transactions first.
     async fn search_transactions_after(
         &self,
-        _address: Address,
-        _block_number: BlockNumberOrTag,
-        _page_size: usize,
+        address: Address,
+        block_number: BlockNumberOrTag,
+        page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        let (last_page, from) = match block_number.as_number() {
+            None | Some(0) => (true, 0),
+            Some(number) => (false, number + 1),
+        };
+
+        let mut txs = Vec::new();
+        let mut receipts = Vec::new();
+        let mut next = Some(from);
+        while let Some(from) = next.filter(|_| txs.len() < page_size) {
+            let blocks = self
+                .provider
+                .account_history_blocks_after(address, from, HISTORY_BATCH_SIZE)
+                .to_rpc_result()?;
+            next = if blocks.len() < HISTORY_BATCH_SIZE {
+                None
+            } else {
+                blocks.last().map(|block| block + 1)
+            };
+
+            for block in blocks {
+                for (tx, receipt) in self.block_transactions_touching(address, block).await? {
+                    txs.push(tx);
+                    receipts.push(receipt);
+                }
+                if txs.len() >= page_size {
+                    next = Some(block + 1);
+                    break
+                }
+            }
+        }
+
+        let first_page = match next {
+            Some(from) => self
+                .provider
+                .account_history_blocks_after(address, from, 1)
+                .to_rpc_result()?
+                .is_empty(),
+            None => true,
+        };
+
+        txs.reverse();
+        receipts.reverse();
+
+        Ok(TransactionsWithReceipts { txs, receipts, first_page, last_page })
     }
 
     /// Handler for `getTransactionBySenderAndNonce`
     async fn get_transaction_by_sender_and_nonce(
         &self,
-        _sender: Address,
-        _nonce: u64,
+        sender: Address,
+        nonce: u64,
     ) -> RpcResult<Option<Transaction>> {
-        Err(internal_rpc_err("unimplemented"))
+        // the nonce hasn't been used yet
+        if self.eth.transaction_count(sender, None).await? <= U256::from(nonce) {
+            return Ok(None)
+        }
+
+        // The nonce of an account only ever increases, hence the transaction was included in the
+        // block right before the first block at which the nonce was already used.
+        let block_number = self.history_partition_point(sender, |account| {
+            account.map(|account| account.nonce).unwrap_or_default() > nonce
+        })?;
+        let Some(block_number) = block_number else {
+            // the transaction predates the available history
+            return Ok(None)
+        };
+
+        let Some(block) = self.eth.block_by_number(block_number.into(), true).await? else {
+            return Ok(None)
+        };
+        let BlockTransactions::Full(transactions) = block.inner.transactions else {
+            return Ok(None)
+        };
+
+        Ok(transactions.into_iter().find(|tx| tx.from == sender && tx.nonce == U64::from(nonce)))
     }
 
     /// Handler for `getContractCreator`
-    async fn get_contract_creator(&self, _address: Address) -> RpcResult<Option<ContractCreator>> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn get_contract_creator(&self, address: Address) -> RpcResult<Option<ContractCreator>> {
+        if !self.has_code(address, None).await? {
+            return Ok(None)
+        }
+
+        // Once deployed, the code of an account doesn't change, hence the contract was created in
+        // the block right before the first block at which the account already had code.
+        let block_number = self.history_partition_point(address, |account| {
+            account.is_some_and(|account| account.get_bytecode_hash() != KECCAK_EMPTY)
+        })?;
+        let Some(block_number) = block_number else {
+            // the contract predates the available history
+            return Ok(None)
+        };
+
+        // the contract may have been created by an internal call, so trace the block to find the
+        // create call and its caller
+        let creations = self
+            .eth
+            .trace_block_with(
+                block_number.into(),
+                TracingInspectorConfig::default_parity(),
+                move |tx_info, inspector, _, _, _| {
+                    Ok(inspector
+                        .into_traces()
+                        .nodes()
+                        .iter()
+                        .find(|node| {
+                            node.trace.kind.is_any_create() &&
+                                node.trace.success &&
+                                node.trace.address == address
+                        })
+                        .and_then(|node| tx_info.hash.map(|hash| (hash, node.trace.caller))))
+                },
+            )
+            .await?
+            .unwrap_or_default();
+        let Some((tx_hash, creator)) = creations.into_iter().flatten().last() else {
+            return Ok(None)
+        };
+
+        let Some(tx) = EthApiServer::transaction_by_hash(&self.eth, tx_hash).await? else {
+            return Ok(None)
+        };
+
+        Ok(Some(ContractCreator { tx, creator }))
+    }
+}
+
+/// The call trace of a transaction, with the balances that self-destructing calls sent to their
+/// refund targets.
+#[derive(Debug, Default)]
+struct TransactionTrace {
+    /// The call trace nodes in the order the calls were made.
+    nodes: Vec<CallTraceNode>,
+    /// The balance sent by the self-destruct of each call, indexed like the nodes.
+    selfdestructs: Vec<Option<U256>>,
+}
+
+impl TransactionTrace {
+    /// Returns the refund target and the balance sent to it if the call of the node
+    /// self-destructed.
+    fn selfdestruct(&self, node: &CallTraceNode) -> Option<(Address, U256)> {
+        let refund_target = node.trace.selfdestruct_refund_target?;
+        let value = self.selfdestructs.get(node.idx).copied().flatten().unwrap_or_default();
+        Some((refund_target, value))
+    }
+
+    /// Converts the trace into `ots_traceTransaction` entries.
+    ///
+    /// Every call reports the value it was made with, self-destructs are reported as a separate
+    /// entry below the call with the balance sent to the refund target.
+    fn into_trace_entries(self) -> Vec<TraceEntry> {
+        let mut entries = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let trace = &node.trace;
+            let value = match trace.kind {
+                CallKind::StaticCall | CallKind::DelegateCall => None,
+                _ => Some(trace.value),
+            };
+            entries.push(TraceEntry {
+                r#type: trace.kind.to_string(),
+                depth: trace.depth as u32,
+                from: trace.caller,
+                to: trace.address,
+                value,
+                input: trace.data.clone(),
+            });
+
+            if let Some((refund_target, value)) = self.selfdestruct(node) {
+                entries.push(TraceEntry {
+                    r#type: "SELFDESTRUCT".to_string(),
+                    depth: trace.depth as u32 + 1,
+                    from: trace.address,
+                    to: refund_target,
+                    value: Some(value),
+                    input: Bytes::new(),
+                });
+            }
+        }
+        entries
+    }
+
+    /// Converts the trace into the value transfers, creations and self-destructs of
+    /// `ots_getInternalOperations`.
+    fn into_internal_operations(self) -> Vec<InternalOperation> {
+        let mut operations = Vec::new();
+        for node in &self.nodes {
+            let trace = &node.trace;
+
+            // the top level call is the transaction itself
+            if trace.depth > 0 {
+                let r#type = match trace.kind {
+                    CallKind::Call if trace.value > U256::ZERO => Some(OperationType::OpTransfer),
+                    CallKind::Create => Some(OperationType::OpCreate),
+                    CallKind::Create2 => Some(OperationType::OpCreate2),
+                    _ => None,
+                };
+                if let Some(r#type) = r#type {
+                    operations.push(InternalOperation {
+                        r#type,
+                        from: trace.caller,
+                        to: trace.address,
+                        value: trace.value,
+                    });
+                }
+            }
+
+            if let Some((refund_target, value)) = self.selfdestruct(node) {
+                operations.push(InternalOperation {
+                    r#type: OperationType::OpSelfDestruct,
+                    from: trace.address,
+                    to: refund_target,
+                    value,
+                });
+            }
+        }
+        operations
+    }
+}
+
+/// A [TracingInspector] that also records the balance each self-destructing call sends to its
+/// refund target, which the call trace doesn't include.
+#[derive(Debug)]
+struct SelfdestructInspector {
+    tracer: TracingInspector,
+    /// The indices of the calls that are currently executing, innermost last.
+    calls: Vec<usize>,
+    /// The balance sent by the self-destruct of each call, in the order the calls were made.
+    selfdestructs: Vec<Option<U256>>,
+}
+
+impl SelfdestructInspector {
+    fn new(config: TracingInspectorConfig) -> Self {
+        Self { tracer: TracingInspector::new(config), calls: Vec::new(), selfdestructs: Vec::new() }
+    }
+
+    fn into_transaction_trace(self) -> TransactionTrace {
+        TransactionTrace {
+            nodes: self.tracer.into_traces().into_nodes(),
+            selfdestructs: self.selfdestructs,
+        }
+    }
+
+    /// Tracks a call that is about to be executed.
+    ///
+    /// Calls are numbered in the order they are made, like the nodes of the call trace.
+    fn start_call(&mut self) {
+        self.calls.push(self.selfdestructs.len());
+        self.selfdestructs.push(None);
+    }
+}
+
+impl<DB: Database> Inspector<DB> for SelfdestructInspector {
+    fn initialize_interp(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        self.tracer.initialize_interp(interp, context)
+    }
+
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        self.tracer.step(interp, context)
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        self.tracer.step_end(interp, context)
+    }
+
+    fn log(&mut self, context: &mut EvmContext<DB>, log: &Log) {
+        self.tracer.log(context, log)
+    }
+
+    fn call(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.start_call();
+        self.tracer.call(context, inputs)
+    }
+
+    fn call_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.calls.pop();
+        self.tracer.call_end(context, inputs, outcome)
+    }
+
+    fn create(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.start_call();
+        self.tracer.create(context, inputs)
+    }
+
+    fn create_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.calls.pop();
+        self.tracer.create_end(context, inputs, outcome)
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        if let Some(call) = self.calls.last() {
+            self.selfdestructs[*call] = Some(value);
+        }
+        Inspector::<DB>::selfdestruct(&mut self.tracer, contract, target, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_revm::tracing::types::CallTrace;
+
+    /// A transaction that calls a contract with value, which self-destructs and sends its whole
+    /// balance to a refund target.
+    fn selfdestructing_callee() -> (TransactionTrace, [Address; 4]) {
+        let [sender, caller, callee, refund_target] = [1, 2, 3, 4].map(Address::repeat_byte);
+        let nodes = vec![
+            CallTraceNode {
+                idx: 0,
+                trace: CallTrace {
+                    depth: 0,
+                    kind: CallKind::Call,
+                    caller: sender,
+                    address: caller,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            CallTraceNode {
+                idx: 1,
+                trace: CallTrace {
+                    depth: 1,
+                    kind: CallKind::Call,
+                    caller,
+                    address: callee,
+                    value: U256::from(10),
+                    selfdestruct_refund_target: Some(refund_target),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ];
+        let trace = TransactionTrace { nodes, selfdestructs: vec![None, Some(U256::from(25))] };
+        (trace, [sender, caller, callee, refund_target])
+    }
+
+    #[test]
+    fn trace_entries_of_selfdestructing_callee() {
+        let (trace, [sender, caller, callee, refund_target]) = selfdestructing_callee();
+        let entries = trace.into_trace_entries();
+
+        let summary = entries
+            .iter()
+            .map(|entry| (entry.r#type.as_str(), entry.depth, entry.from, entry.to, entry.value))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                ("CALL", 0, sender, caller, Some(U256::ZERO)),
+                ("CALL", 1, caller, callee, Some(U256::from(10))),
+                ("SELFDESTRUCT", 2, callee, refund_target, Some(U256::from(25))),
+            ]
+        );
+    }
+
+    #[test]
+    fn internal_operations_of_selfdestructing_callee() {
+        let (trace, [_, caller, callee, refund_target]) = selfdestructing_callee();
+        let operations = trace.into_internal_operations();
+
+        let summary = operations
+            .iter()
+            .map(|operation| (operation.r#type, operation.from, operation.to, operation.value))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (OperationType::OpTransfer, caller, callee, U256::from(10)),
+                (OperationType::OpSelfDestruct, callee, refund_target, U256::from(25)),
+            ]
+        );
     }
 }
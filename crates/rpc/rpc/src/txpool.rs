@@ -1,12 +1,18 @@
+use crate::eth::pipe_from_stream;
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult as Result;
+use futures::StreamExt;
+use jsonrpsee::{core::RpcResult as Result, PendingSubscriptionSink};
 use reth_primitives::{Address, U256, U64};
 use reth_rpc_api::TxPoolApiServer;
 use reth_rpc_types::{
     txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus},
-    Transaction,
+    Transaction, TxpoolDiscardReason, TxpoolEvent, TxpoolSubPool, TxpoolSubPools,
+};
+use reth_tasks::{TaskSpawner, TokioTaskExecutor};
+use reth_transaction_pool::{
+    AllPoolTransactions, DiscardReason, FullTransactionEvent, PoolTransaction, SubPool,
+    TransactionPool,
 };
-use reth_transaction_pool::{AllPoolTransactions, PoolTransaction, TransactionPool};
 use std::collections::BTreeMap;
 use tracing::trace;
 
@@ -17,12 +23,22 @@ use tracing::trace;
 pub struct TxPoolApi<Pool> {
     /// An interface to interact with the pool
     pool: Pool,
+    /// The type that's used to spawn subscription tasks.
+    subscription_task_spawner: Box<dyn TaskSpawner>,
 }
 
 impl<Pool> TxPoolApi<Pool> {
     /// Creates a new instance of `TxpoolApi`.
+    ///
+    /// Subscription tasks are spawned via [tokio::task::spawn]
     pub fn new(pool: Pool) -> Self {
-        TxPoolApi { pool }
+        Self::with_spawner(pool, Box::<TokioTaskExecutor>::default())
+    }
+
+    /// Creates a new instance of `TxpoolApi` that spawns subscription tasks with the given
+    /// spawner.
+    pub fn with_spawner(pool: Pool, subscription_task_spawner: Box<dyn TaskSpawner>) -> Self {
+        TxPoolApi { pool, subscription_task_spawner }
     }
 }
 
@@ -31,17 +47,6 @@ where
     Pool: TransactionPool + 'static,
 {
     fn content(&self) -> TxpoolContent {
-        #[inline]
-        fn insert<T: PoolTransaction>(
-            tx: &T,
-            content: &mut BTreeMap<Address, BTreeMap<String, Transaction>>,
-        ) {
-            content.entry(tx.sender()).or_default().insert(
-                tx.nonce().to_string(),
-                reth_rpc_types_compat::transaction::from_recovered(tx.to_recovered_transaction()),
-            );
-        }
-
         let AllPoolTransactions { pending, queued } = self.pool.all_transactions();
 
         let mut content = TxpoolContent::default();
@@ -54,6 +59,71 @@ where
 
         content
     }
+
+    fn sub_pools(&self) -> TxpoolSubPools {
+        let mut sub_pools = TxpoolSubPools::default();
+        for subpool in [SubPool::Pending, SubPool::BaseFee, SubPool::Queued, SubPool::Blob] {
+            let content = sub_pools.get_mut(to_rpc_subpool(subpool));
+            for tx in self.pool.subpool_transactions(subpool) {
+                insert(&tx.transaction, content);
+            }
+        }
+        sub_pools
+    }
+}
+
+/// Inserts the transaction into the content, grouped by sender and nonce.
+#[inline]
+fn insert<T: PoolTransaction>(
+    tx: &T,
+    content: &mut BTreeMap<Address, BTreeMap<String, Transaction>>,
+) {
+    content.entry(tx.sender()).or_default().insert(
+        tx.nonce().to_string(),
+        reth_rpc_types_compat::transaction::from_recovered(tx.to_recovered_transaction()),
+    );
+}
+
+/// Converts the [SubPool] into its RPC representation.
+const fn to_rpc_subpool(subpool: SubPool) -> TxpoolSubPool {
+    match subpool {
+        SubPool::Pending => TxpoolSubPool::Pending,
+        SubPool::BaseFee => TxpoolSubPool::BaseFee,
+        SubPool::Queued => TxpoolSubPool::Queued,
+        SubPool::Blob => TxpoolSubPool::Blob,
+    }
+}
+
+/// Converts the [FullTransactionEvent] into its RPC representation.
+///
+/// Returns `None` for propagation events, and for [FullTransactionEvent::Discarded] which is
+/// followed by the [FullTransactionEvent::DiscardedWithReason] event.
+fn to_rpc_event<T: PoolTransaction>(event: FullTransactionEvent<T>) -> Option<TxpoolEvent> {
+    let event = match event {
+        FullTransactionEvent::Pending(hash) => TxpoolEvent::Pending { hash },
+        FullTransactionEvent::Queued(hash) => TxpoolEvent::Queued { hash },
+        FullTransactionEvent::Mined { tx_hash, block_hash } => {
+            TxpoolEvent::Mined { hash: tx_hash, block_hash }
+        }
+        FullTransactionEvent::Replaced { transaction, replaced_by } => {
+            TxpoolEvent::Replaced { hash: *transaction.hash(), replaced_by }
+        }
+        FullTransactionEvent::Moved { tx_hash, from, to } => {
+            TxpoolEvent::Moved { hash: tx_hash, from: to_rpc_subpool(from), to: to_rpc_subpool(to) }
+        }
+        FullTransactionEvent::DiscardedWithReason { tx_hash, reason } => {
+            let reason = match reason {
+                DiscardReason::Invalid => TxpoolDiscardReason::Invalid,
+                DiscardReason::NonceTooLow => TxpoolDiscardReason::NonceTooLow,
+                DiscardReason::SizeLimit => TxpoolDiscardReason::SizeLimit,
+                DiscardReason::Removed => TxpoolDiscardReason::Removed,
+            };
+            TxpoolEvent::Discarded { hash: tx_hash, reason }
+        }
+        FullTransactionEvent::Invalid(hash) => TxpoolEvent::Invalid { hash },
+        FullTransactionEvent::Discarded(_) | FullTransactionEvent::Propagated(_) => return None,
+    };
+    Some(event)
 }
 
 #[async_trait]
@@ -135,6 +205,33 @@ where
         trace!(target: "rpc::eth", "Serving txpool_inspect");
         Ok(self.content())
     }
+
+    /// Returns the details of all transactions currently in the pool, grouped by sub-pool.
+    ///
+    /// Handler for `txpool_subPools`
+    async fn txpool_sub_pools(&self) -> Result<TxpoolSubPools> {
+        trace!(target: "rpc::eth", "Serving txpool_subPools");
+        Ok(self.sub_pools())
+    }
+
+    /// Streams the lifecycle events of all transactions in the pool.
+    ///
+    /// Handler for `txpool_subscribeEvents`
+    async fn txpool_subscribe_events(
+        &self,
+        pending: PendingSubscriptionSink,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        trace!(target: "rpc::eth", "Serving txpool_subscribeEvents");
+        let sink = pending.accept().await?;
+        let stream = self
+            .pool
+            .all_transactions_event_listener()
+            .filter_map(|event| futures::future::ready(to_rpc_event(event)));
+        self.subscription_task_spawner.spawn(Box::pin(async move {
+            let _ = pipe_from_stream(sink, stream).await;
+        }));
+        Ok(())
+    }
 }
 
 impl<Pool> std::fmt::Debug for TxPoolApi<Pool> {
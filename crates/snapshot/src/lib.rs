@@ -8,10 +8,12 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 mod error;
+mod reencode;
 pub mod segments;
 mod snapshotter;
 
 pub use error::SnapshotterError;
+pub use reencode::{finish_reencoded_snapshots, reencode_snapshot};
 pub use snapshotter::{
     HighestSnapshotsTracker, SnapshotTargets, Snapshotter, SnapshotterResult, SnapshotterWithResult,
 };
@@ -0,0 +1,208 @@
+//! Re-encoding of existing snapshots.
+
+use crate::segments::configure_jar;
+use reth_db::{database::Database, table::Decompress};
+use reth_interfaces::provider::{ProviderError, ProviderResult};
+use reth_nippy_jar::{jar_file_paths, ColumnResult, NippyJar, NippyJarCursor};
+use reth_primitives::{
+    snapshot::{Filters, SegmentConfig, SegmentHeader},
+    SnapshotSegment, TransactionSignedNoHash, B256,
+};
+use reth_provider::{DatabaseProviderRO, TransactionsProviderExt};
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
+
+/// Extension of the directory a snapshot is re-encoded into.
+const REENCODING_EXTENSION: &str = "reencoding";
+
+/// Extension the re-encoding directory is renamed to once the new snapshot is complete. From then
+/// on, the snapshot is replaced by its contents even if the process is interrupted.
+const REENCODED_EXTENSION: &str = "reencoded";
+
+/// Number of rows at the end of each column used to train the compression dictionaries.
+const DICTIONARY_SAMPLE_ROWS: usize = 1000;
+
+/// Re-encodes the snapshot at `path` with the provided [`SegmentConfig`], replacing the existing
+/// files once the new snapshot is complete.
+///
+/// All rows are streamed from the existing snapshot. The keys for the filters & PHF are taken from
+/// the snapshot as well, except for [`SnapshotSegment::Receipts`] and
+/// [`SnapshotSegment::TxSenders`], whose transaction hashes are fetched through the provider.
+///
+/// The new snapshot is written into a separate directory, which is renamed once complete. An
+/// interrupted re-encoding is either discarded or finished by [`finish_reencoded_snapshots`].
+pub fn reencode_snapshot<DB: Database>(
+    provider: &DatabaseProviderRO<DB>,
+    path: &Path,
+    segment_config: SegmentConfig,
+) -> ProviderResult<()> {
+    let (snapshots_path, file_name) = match (path.parent(), path.file_name()) {
+        (Some(parent), Some(file_name)) => (parent, file_name),
+        _ => {
+            return Err(ProviderError::FsPathError(format!(
+                "invalid snapshot path: {}",
+                path.display()
+            )))
+        }
+    };
+    finish_reencoded_snapshots(snapshots_path)?;
+
+    let jar = NippyJar::<SegmentHeader>::load(path)?;
+    let segment = jar.user_header().segment();
+    let block_range = jar.user_header().block_range().clone();
+    let tx_range = jar.user_header().tx_range().clone();
+    let total_rows = jar.rows();
+
+    let filters = match segment {
+        // Changesets are only queried by block number
+        SnapshotSegment::AccountChangeSets | SnapshotSegment::StorageChangeSets => {
+            Filters::WithoutFilters
+        }
+        _ => segment_config.filters,
+    };
+
+    let staging = staging_path(snapshots_path, file_name, REENCODING_EXTENSION);
+    if staging.exists() {
+        reth_primitives::fs::remove_dir_all(&staging)?;
+    }
+    reth_primitives::fs::create_dir_all(&staging)?;
+
+    let mut new_jar = configure_jar(
+        NippyJar::new(
+            jar.columns(),
+            &staging.join(file_name),
+            SegmentHeader::new(block_range, tx_range.clone(), segment),
+        ),
+        SegmentConfig { filters, compression: segment_config.compression },
+        total_rows,
+        // Train the dictionary with the most recent rows
+        || {
+            let mut samples = vec![Vec::new(); jar.columns()];
+            let mut cursor = NippyJarCursor::new(&jar)?;
+            for row in total_rows.saturating_sub(DICTIONARY_SAMPLE_ROWS)..total_rows {
+                let Some(values) = cursor.row_by_number(row)? else { break };
+                for (column, value) in samples.iter_mut().zip(values) {
+                    column.push(value.to_vec());
+                }
+            }
+            Ok(samples)
+        },
+    )?;
+
+    if filters.has_filters() {
+        let keys: Box<dyn Iterator<Item = ColumnResult<B256>> + '_> = match segment {
+            SnapshotSegment::Headers => Box::new(
+                column_values(&jar, 2)?.map(|hash| hash.map(|hash| B256::from_slice(&hash))),
+            ),
+            SnapshotSegment::Bytecodes => Box::new(
+                column_values(&jar, 1)?.map(|hash| hash.map(|hash| B256::from_slice(&hash))),
+            ),
+            SnapshotSegment::Transactions => {
+                Box::new(column_values(&jar, 0)?.map(|tx| -> ColumnResult<B256> {
+                    Ok(TransactionSignedNoHash::decompress(tx?)?.hash())
+                }))
+            }
+            SnapshotSegment::Receipts | SnapshotSegment::TxSenders => Box::new(
+                provider
+                    .transaction_hashes_by_range(*tx_range.start()..(*tx_range.end() + 1))?
+                    .into_iter()
+                    .map(|(hash, _)| Ok(hash)),
+            ),
+            SnapshotSegment::AccountChangeSets | SnapshotSegment::StorageChangeSets => {
+                unreachable!("changesets have no filters")
+            }
+        };
+        new_jar.prepare_index(keys, total_rows)?;
+    }
+
+    new_jar.freeze(
+        (0..jar.columns()).map(|column| column_values(&jar, column)).collect::<Result<_, _>>()?,
+        total_rows as u64,
+    )?;
+
+    // The new snapshot is complete, commit to replacing the existing one
+    let reencoded = staging_path(snapshots_path, file_name, REENCODED_EXTENSION);
+    reth_primitives::fs::rename(&staging, &reencoded)?;
+    replace_snapshot(&reencoded, path)
+}
+
+/// Finishes or discards any interrupted [`reencode_snapshot`] in `snapshots_path`.
+///
+/// Snapshots whose re-encoding was completed are replaced by the new files, incomplete
+/// re-encodings are removed. Should be called before any snapshot in the directory is opened.
+pub fn finish_reencoded_snapshots(snapshots_path: &Path) -> ProviderResult<()> {
+    if !snapshots_path.exists() {
+        return Ok(())
+    }
+
+    for entry in reth_primitives::fs::read_dir(snapshots_path)? {
+        let path = entry.map_err(|err| ProviderError::FsPathError(err.to_string()))?.path();
+        if !path.is_dir() {
+            continue
+        }
+
+        match path.extension().and_then(OsStr::to_str) {
+            Some(REENCODING_EXTENSION) => reth_primitives::fs::remove_dir_all(&path)?,
+            Some(REENCODED_EXTENSION) => {
+                if let Some(file_name) = path.file_stem() {
+                    replace_snapshot(&path, &snapshots_path.join(file_name))?;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Moves the snapshot files from the `reencoded` directory over the snapshot at `path` and removes
+/// the directory.
+///
+/// The index file is moved last, so that a missing index file in a non-empty directory means the
+/// new snapshot has none. This allows resuming the replacement if it was interrupted.
+fn replace_snapshot(reencoded: &Path, path: &Path) -> ProviderResult<()> {
+    let [data, index, offsets, config] = jar_file_paths(path);
+    let moved = |target: &Path| -> ProviderResult<PathBuf> {
+        let file_name = target.file_name().ok_or_else(|| {
+            ProviderError::FsPathError(format!("invalid snapshot path: {}", target.display()))
+        })?;
+        Ok(reencoded.join(file_name))
+    };
+
+    let remaining = reth_primitives::fs::read_dir(reencoded)?.count();
+    if remaining > 0 && !moved(&index)?.exists() && index.exists() {
+        reth_primitives::fs::remove_file(&index)?;
+    }
+
+    for target in [data, offsets, config, index] {
+        let source = moved(&target)?;
+        if source.exists() {
+            reth_primitives::fs::rename(source, target)?;
+        }
+    }
+
+    reth_primitives::fs::remove_dir_all(reencoded)?;
+    Ok(())
+}
+
+/// Returns the path of the directory the snapshot `file_name` is re-encoded into.
+fn staging_path(snapshots_path: &Path, file_name: &OsStr, extension: &str) -> PathBuf {
+    let mut name = file_name.to_os_string();
+    name.push(".");
+    name.push(extension);
+    snapshots_path.join(name)
+}
+
+/// Returns an iterator streaming the values of `column` from the snapshot.
+fn column_values(
+    jar: &NippyJar<SegmentHeader>,
+    column: usize,
+) -> ProviderResult<impl Iterator<Item = ColumnResult<Vec<u8>>> + '_> {
+    let mut cursor = NippyJarCursor::new(jar)?;
+    Ok(std::iter::from_fn(move || match cursor.next_row_with_cols(1 << column) {
+        Ok(row) => row.map(|row| Ok(row[0].to_vec())),
+        Err(err) => Some(Err(err.into())),
+    }))
+}
@@ -0,0 +1,91 @@
+use crate::segments::{prepare_jar, Segment};
+use reth_db::{
+    cursor::DbCursorRO, database::Database, models::StoredAccountChangeSet, table::Compress,
+    tables, transaction::DbTx,
+};
+use reth_interfaces::provider::ProviderResult;
+use reth_nippy_jar::ColumnResult;
+use reth_primitives::{
+    snapshot::{Compression, Filters, SegmentConfig},
+    BlockNumber, SnapshotSegment,
+};
+use reth_provider::DatabaseProviderRO;
+use std::{ops::RangeInclusive, path::Path};
+
+/// Snapshot segment responsible for [SnapshotSegment::AccountChangeSets] part of data.
+///
+/// Each row holds the whole account changeset of a block, so rows are indexed by block number.
+#[derive(Debug)]
+pub struct AccountChangeSets {
+    config: SegmentConfig,
+}
+
+impl AccountChangeSets {
+    /// Creates new instance of [AccountChangeSets] snapshot segment.
+    ///
+    /// Rows are only looked up by block number, so the segment never has any filters.
+    pub fn new(compression: Compression) -> Self {
+        Self { config: SegmentConfig { compression, filters: Filters::WithoutFilters } }
+    }
+}
+
+impl Default for AccountChangeSets {
+    fn default() -> Self {
+        Self { config: SnapshotSegment::AccountChangeSets.config() }
+    }
+}
+
+impl Segment for AccountChangeSets {
+    fn from_config(config: SegmentConfig) -> Self {
+        Self::new(config.compression)
+    }
+
+    fn segment(&self) -> SnapshotSegment {
+        SnapshotSegment::AccountChangeSets
+    }
+
+    fn snapshot<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRO<DB>,
+        directory: impl AsRef<Path>,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        let range_len = range.clone().count();
+        let mut jar = prepare_jar::<DB, 1>(
+            provider,
+            directory,
+            self.segment(),
+            self.config,
+            range.clone(),
+            range_len,
+            || {
+                // Train the dictionary with the most recent blocks (at most 1000).
+                let start = range.end().saturating_sub(999).max(*range.start());
+                Ok([(start..=*range.end())
+                    .map(|block_number| account_changeset_row(provider, block_number))
+                    .collect::<ProviderResult<Vec<_>>>()?])
+            },
+        )?;
+
+        let rows = range.map(|block_number| -> ColumnResult<Vec<u8>> {
+            Ok(account_changeset_row(provider, block_number)?)
+        });
+        jar.freeze(vec![rows], range_len as u64)?;
+
+        Ok(())
+    }
+}
+
+/// Returns the compressed account changeset of the block, which is empty if no account changed.
+fn account_changeset_row<DB: Database>(
+    provider: &DatabaseProviderRO<DB>,
+    block_number: BlockNumber,
+) -> ProviderResult<Vec<u8>> {
+    let changes = provider
+        .tx_ref()
+        .cursor_read::<tables::AccountChangeSet>()?
+        .walk_range(block_number..=block_number)?
+        .map(|entry| entry.map(|(_, account_before)| account_before))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(StoredAccountChangeSet { changes }.compress())
+}
@@ -0,0 +1,110 @@
+use crate::segments::{prepare_jar, Segment};
+use reth_db::{
+    cursor::DbCursorRO, database::Database, snapshot::create_snapshot_T1, tables,
+    transaction::DbTx, RawTable,
+};
+use reth_interfaces::provider::ProviderResult;
+use reth_nippy_jar::ColumnResult;
+use reth_primitives::{
+    snapshot::{Compression, Filters, SegmentConfig, SegmentHeader},
+    BlockNumber, SnapshotSegment, B256,
+};
+use reth_provider::DatabaseProviderRO;
+use std::{ops::RangeInclusive, path::Path};
+
+/// Snapshot segment responsible for [SnapshotSegment::Bytecodes] part of data.
+///
+/// Bytecodes are not indexed by block, and the table only ever grows. Therefore, the segment is
+/// cumulative: its snapshot holds every bytecode known at the time it is taken, keyed by the code
+/// hash, and replaces the previous bytecode snapshot. Every snapshot rewrites the whole table and
+/// collects all code hashes in memory, which is why the
+/// [Snapshotter](crate::Snapshotter::with_bytecodes) only takes it if explicitly enabled.
+///
+/// Bytecodes are only queried by their hash, so the snapshot always has filters.
+#[derive(Debug)]
+pub struct Bytecodes {
+    config: SegmentConfig,
+}
+
+impl Bytecodes {
+    /// Creates new instance of [Bytecodes] snapshot segment.
+    pub fn new(compression: Compression, filters: Filters) -> Self {
+        Self::from_config(SegmentConfig { compression, filters })
+    }
+}
+
+impl Default for Bytecodes {
+    fn default() -> Self {
+        Self { config: SnapshotSegment::Bytecodes.config() }
+    }
+}
+
+impl Segment for Bytecodes {
+    fn from_config(config: SegmentConfig) -> Self {
+        let filters = if config.filters.has_filters() {
+            config.filters
+        } else {
+            SnapshotSegment::Bytecodes.config().filters
+        };
+        Self { config: SegmentConfig { filters, ..config } }
+    }
+
+    fn segment(&self) -> SnapshotSegment {
+        SnapshotSegment::Bytecodes
+    }
+
+    fn snapshot<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRO<DB>,
+        directory: impl AsRef<Path>,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        // The code hashes are both the second column and the keys for filters & PHF
+        let hashes = provider
+            .tx_ref()
+            .cursor_read::<RawTable<tables::Bytecodes>>()?
+            .walk(None)?
+            .map(|row| row.map(|(key, _)| key.into_key()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let row_count = hashes.len();
+
+        let mut jar = prepare_jar::<DB, 2>(
+            provider,
+            directory,
+            self.segment(),
+            self.config,
+            range,
+            row_count,
+            || {
+                let mut cursor = provider.tx_ref().cursor_read::<RawTable<tables::Bytecodes>>()?;
+                let bytecodes = cursor
+                    .walk_back(None)?
+                    .take(row_count.min(1000))
+                    .map(|row| row.map(|(_, value)| value.into_value()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok([bytecodes, hashes.iter().rev().take(row_count.min(1000)).cloned().collect()])
+            },
+        )?;
+
+        let mut keys = None;
+        if self.config.filters.has_filters() {
+            keys = Some(hashes.iter().map(|hash| Ok(B256::from_slice(hash))));
+        }
+
+        let hash_column: Box<dyn Iterator<Item = ColumnResult<Vec<u8>>>> =
+            Box::new(hashes.clone().into_iter().map(Ok));
+
+        create_snapshot_T1::<tables::Bytecodes, B256, SegmentHeader>(
+            provider.tx_ref(),
+            B256::ZERO..=B256::repeat_byte(0xff),
+            Some(vec![hash_column]),
+            // We already prepared the dictionary beforehand
+            None::<Vec<std::vec::IntoIter<Vec<u8>>>>,
+            keys,
+            row_count,
+            &mut jar,
+        )?;
+
+        Ok(())
+    }
+}
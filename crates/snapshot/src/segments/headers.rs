@@ -31,6 +31,10 @@ impl Default for Headers {
 }
 
 impl Segment for Headers {
+    fn from_config(config: SegmentConfig) -> Self {
+        Self { config }
+    }
+
     fn segment(&self) -> SnapshotSegment {
         SnapshotSegment::Headers
     }
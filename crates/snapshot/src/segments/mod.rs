@@ -9,6 +9,18 @@ pub use headers::Headers;
 mod receipts;
 pub use receipts::Receipts;
 
+mod senders;
+pub use senders::TxSenders;
+
+mod account_changesets;
+pub use account_changesets::AccountChangeSets;
+
+mod storage_changesets;
+pub use storage_changesets::StorageChangeSets;
+
+mod bytecodes;
+pub use bytecodes::Bytecodes;
+
 use reth_db::{
     cursor::DbCursorRO, database::Database, table::Table, transaction::DbTx, RawKey, RawTable,
 };
@@ -27,6 +39,9 @@ pub(crate) type Rows<const COLUMNS: usize> = [Vec<Vec<u8>>; COLUMNS];
 
 /// A segment represents a snapshotting of some portion of the data.
 pub trait Segment: Default {
+    /// Creates the segment with the given [`SegmentConfig`].
+    fn from_config(config: SegmentConfig) -> Self;
+
     /// Snapshot data using the provided range. The `directory` parameter determines the snapshot
     /// file's save location.
     fn snapshot<DB: Database>(
@@ -67,12 +82,24 @@ pub(crate) fn prepare_jar<DB: Database, const COLUMNS: usize>(
     prepare_compression: impl Fn() -> ProviderResult<Rows<COLUMNS>>,
 ) -> ProviderResult<NippyJar<SegmentHeader>> {
     let tx_range = provider.transaction_range_by_block_range(block_range.clone())?;
-    let mut nippy_jar = NippyJar::new(
+    let nippy_jar = NippyJar::new(
         COLUMNS,
         &directory.as_ref().join(segment.filename(&block_range, &tx_range).as_str()),
         SegmentHeader::new(block_range, tx_range, segment),
     );
 
+    configure_jar(nippy_jar, segment_config, total_rows, || Ok(Vec::from(prepare_compression()?)))
+}
+
+/// Sets up the compression, inclusion filter and PHF of the [`NippyJar`] according to the
+/// [`SegmentConfig`]. The dataset returned by `prepare_compression` is only requested when a zstd
+/// dictionary has to be trained.
+pub(crate) fn configure_jar(
+    mut nippy_jar: NippyJar<SegmentHeader>,
+    segment_config: SegmentConfig,
+    total_rows: usize,
+    prepare_compression: impl FnOnce() -> ProviderResult<Vec<Vec<Vec<u8>>>>,
+) -> ProviderResult<NippyJar<SegmentHeader>> {
     nippy_jar = match segment_config.compression {
         Compression::Lz4 => nippy_jar.with_lz4(),
         Compression::Zstd => nippy_jar.with_zstd(false, 0),
@@ -80,7 +107,7 @@ pub(crate) fn prepare_jar<DB: Database, const COLUMNS: usize>(
             let dataset = prepare_compression()?;
 
             nippy_jar = nippy_jar.with_zstd(true, 5_000_000);
-            nippy_jar.prepare_compression(dataset.to_vec())?;
+            nippy_jar.prepare_compression(dataset)?;
             nippy_jar
         }
         Compression::Uncompressed => nippy_jar,
@@ -28,6 +28,10 @@ impl Default for Receipts {
 }
 
 impl Segment for Receipts {
+    fn from_config(config: SegmentConfig) -> Self {
+        Self { config }
+    }
+
     fn segment(&self) -> SnapshotSegment {
         SnapshotSegment::Receipts
     }
@@ -28,6 +28,10 @@ impl Default for Transactions {
 }
 
 impl Segment for Transactions {
+    fn from_config(config: SegmentConfig) -> Self {
+        Self { config }
+    }
+
     fn segment(&self) -> SnapshotSegment {
         SnapshotSegment::Transactions
     }
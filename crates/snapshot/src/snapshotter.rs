@@ -1,9 +1,13 @@
 //! Support for snapshotting.
 
-use crate::{segments, segments::Segment, SnapshotterError};
+use crate::{finish_reencoded_snapshots, segments, segments::Segment, SnapshotterError};
 use reth_db::{database::Database, snapshot::iter_snapshots};
 use reth_interfaces::{RethError, RethResult};
-use reth_primitives::{snapshot::HighestSnapshots, BlockNumber, TxNumber};
+use reth_nippy_jar::jar_file_paths;
+use reth_primitives::{
+    snapshot::{HighestSnapshots, SegmentConfig},
+    BlockNumber, SnapshotSegment, TxNumber,
+};
 use reth_provider::{BlockReader, DatabaseProviderRO, ProviderFactory, TransactionsProviderExt};
 use std::{
     collections::HashMap,
@@ -38,6 +42,10 @@ pub struct Snapshotter<DB> {
     highest_snapshots_tracker: HighestSnapshotsTracker,
     /// Block interval after which the snapshot is taken.
     block_interval: u64,
+    /// Configuration of the segments. Segments without an entry use their default configuration.
+    segment_configs: HashMap<SnapshotSegment, SegmentConfig>,
+    /// Whether [SnapshotSegment::Bytecodes] is snapshotted, see [Snapshotter::with_bytecodes].
+    snapshot_bytecodes: bool,
 }
 
 /// Tracker for the latest [`HighestSnapshots`] value.
@@ -49,12 +57,22 @@ pub struct SnapshotTargets {
     headers: Option<RangeInclusive<BlockNumber>>,
     receipts: Option<(RangeInclusive<BlockNumber>, RangeInclusive<TxNumber>)>,
     transactions: Option<(RangeInclusive<BlockNumber>, RangeInclusive<TxNumber>)>,
+    account_changesets: Option<RangeInclusive<BlockNumber>>,
+    storage_changesets: Option<RangeInclusive<BlockNumber>>,
+    senders: Option<(RangeInclusive<BlockNumber>, RangeInclusive<TxNumber>)>,
+    bytecodes: Option<RangeInclusive<BlockNumber>>,
 }
 
 impl SnapshotTargets {
     /// Returns `true` if any of the targets are [Some].
     pub fn any(&self) -> bool {
-        self.headers.is_some() || self.receipts.is_some() || self.transactions.is_some()
+        self.headers.is_some() ||
+            self.receipts.is_some() ||
+            self.transactions.is_some() ||
+            self.account_changesets.is_some() ||
+            self.storage_changesets.is_some() ||
+            self.senders.is_some() ||
+            self.bytecodes.is_some()
     }
 
     /// Returns `true` if all targets are either [None] or multiple of `block_interval`.
@@ -63,6 +81,10 @@ impl SnapshotTargets {
             self.headers.as_ref(),
             self.receipts.as_ref().map(|(blocks, _)| blocks),
             self.transactions.as_ref().map(|(blocks, _)| blocks),
+            self.account_changesets.as_ref(),
+            self.storage_changesets.as_ref(),
+            self.senders.as_ref().map(|(blocks, _)| blocks),
+            self.bytecodes.as_ref(),
         ]
         .iter()
         .all(|blocks| blocks.map_or(true, |blocks| (blocks.end() + 1) % block_interval == 0))
@@ -75,6 +97,10 @@ impl SnapshotTargets {
             (self.headers.as_ref(), snapshots.headers),
             (self.receipts.as_ref().map(|(blocks, _)| blocks), snapshots.receipts),
             (self.transactions.as_ref().map(|(blocks, _)| blocks), snapshots.transactions),
+            (self.account_changesets.as_ref(), snapshots.account_changesets),
+            (self.storage_changesets.as_ref(), snapshots.storage_changesets),
+            (self.senders.as_ref().map(|(blocks, _)| blocks), snapshots.senders),
+            (self.bytecodes.as_ref(), snapshots.bytecodes),
         ]
         .iter()
         .all(|(target, highest)| {
@@ -103,6 +129,8 @@ impl<DB: Database> Snapshotter<DB> {
             highest_snapshots_notifier,
             highest_snapshots_tracker,
             block_interval,
+            segment_configs: HashMap::default(),
+            snapshot_bytecodes: false,
         };
 
         snapshotter.create_directory()?;
@@ -111,6 +139,25 @@ impl<DB: Database> Snapshotter<DB> {
         Ok(snapshotter)
     }
 
+    /// Sets the configuration used to create new snapshots of the given segments.
+    pub fn with_segment_configs(
+        mut self,
+        segment_configs: impl IntoIterator<Item = (SnapshotSegment, SegmentConfig)>,
+    ) -> Self {
+        self.segment_configs.extend(segment_configs);
+        self
+    }
+
+    /// Sets whether bytecodes are snapshotted. Disabled by default.
+    ///
+    /// Bytecodes aren't indexed by block, so every bytecode snapshot holds all bytecodes of the
+    /// database and replaces the previous one. Each run that snapshots them rewrites the whole
+    /// table and keeps all code hashes in memory.
+    pub fn with_bytecodes(mut self, enabled: bool) -> Self {
+        self.snapshot_bytecodes = enabled;
+        self
+    }
+
     /// Ensures the snapshots directory and its temporary subdirectory are properly set up.
     ///
     /// This function performs the following actions:
@@ -128,6 +175,8 @@ impl<DB: Database> Snapshotter<DB> {
             reth_primitives::fs::remove_dir_all(&temporary_path)?;
         }
 
+        finish_reencoded_snapshots(&self.snapshots_path)?;
+
         reth_primitives::fs::create_dir_all(temporary_path)?;
 
         Ok(())
@@ -144,6 +193,18 @@ impl<DB: Database> Snapshotter<DB> {
         if let Some((block_number, _)) = &targets.transactions {
             self.highest_snapshots.transactions = Some(*block_number.end());
         }
+        if let Some(block_number) = &targets.account_changesets {
+            self.highest_snapshots.account_changesets = Some(*block_number.end());
+        }
+        if let Some(block_number) = &targets.storage_changesets {
+            self.highest_snapshots.storage_changesets = Some(*block_number.end());
+        }
+        if let Some((block_number, _)) = &targets.senders {
+            self.highest_snapshots.senders = Some(*block_number.end());
+        }
+        if let Some(block_number) = &targets.bytecodes {
+            self.highest_snapshots.bytecodes = Some(*block_number.end());
+        }
     }
 
     /// Looks into the snapshot directory to find the highest snapshotted block of each segment, and
@@ -188,8 +249,18 @@ impl<DB: Database> Snapshotter<DB> {
 
         self.run_segment::<segments::Headers>(targets.headers.clone())?;
 
+        self.run_segment::<segments::AccountChangeSets>(targets.account_changesets.clone())?;
+
+        self.run_segment::<segments::StorageChangeSets>(targets.storage_changesets.clone())?;
+
+        self.run_segment::<segments::TxSenders>(targets.senders.clone().map(|(range, _)| range))?;
+
+        self.run_segment::<segments::Bytecodes>(targets.bytecodes.clone())?;
+
         self.update_highest_snapshots_tracker()?;
 
+        self.remove_replaced_snapshots()?;
+
         Ok(targets)
     }
 
@@ -200,7 +271,11 @@ impl<DB: Database> Snapshotter<DB> {
     /// up on boot (on [`Snapshotter::new`]) and the snapshot process restarted from scratch for
     /// this block range and segment.
     ///
-    /// If it succeeds, then we move the snapshot file from the temporary directory to its main one.
+    /// If it succeeds, then we move the snapshot files from the temporary directory to its main
+    /// one.
+    ///
+    /// Snapshots of cumulative segments always start at genesis, see
+    /// [`SnapshotSegment::is_cumulative`].
     fn run_segment<S: Segment>(
         &self,
         block_range: Option<RangeInclusive<BlockNumber>>,
@@ -208,13 +283,53 @@ impl<DB: Database> Snapshotter<DB> {
         if let Some(block_range) = block_range {
             let temp = self.snapshots_path.join(TEMPORARY_SUBDIRECTORY);
             let provider = self.provider_factory.provider()?;
+            let mut segment = S::default();
+            if let Some(config) = self.segment_configs.get(&segment.segment()) {
+                segment = S::from_config(*config);
+            }
+            let block_range = if segment.segment().is_cumulative() {
+                0..=*block_range.end()
+            } else {
+                block_range
+            };
             let tx_range = provider.transaction_range_by_block_range(block_range.clone())?;
-            let segment = S::default();
             let filename = segment.segment().filename(&block_range, &tx_range);
 
             segment.snapshot::<DB>(&provider, temp.clone(), block_range)?;
 
-            reth_primitives::fs::rename(temp.join(&filename), self.snapshots_path.join(filename))?;
+            let temp_paths = jar_file_paths(&temp.join(&filename));
+            let paths = jar_file_paths(&self.snapshots_path.join(filename));
+            for (temp_path, path) in temp_paths.into_iter().zip(paths) {
+                if temp_path.exists() {
+                    reth_primitives::fs::rename(temp_path, path)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes the snapshots of cumulative segments that are replaced by the highest snapshot of
+    /// their segment.
+    ///
+    /// It runs after the trackers are notified of the highest snapshots, so that the snapshot
+    /// providers don't look up removed snapshots.
+    fn remove_replaced_snapshots(&self) -> RethResult<()> {
+        for (segment, ranges) in
+            iter_snapshots(&self.snapshots_path).map_err(|err| RethError::Provider(err.into()))?
+        {
+            if !segment.is_cumulative() {
+                continue
+            }
+
+            // The ranges are sorted by their last block, the highest one is kept.
+            for (block_range, tx_range) in ranges.iter().rev().skip(1) {
+                let path = self.snapshots_path.join(segment.filename(block_range, tx_range));
+                for path in jar_file_paths(&path) {
+                    if path.exists() {
+                        reth_primitives::fs::remove_file(path)?;
+                    }
+                }
+            }
         }
         Ok(())
     }
@@ -240,6 +355,18 @@ impl<DB: Database> Snapshotter<DB> {
             self.get_snapshot_target_block_range(to_block_number, self.highest_snapshots.receipts);
         let transactions_block_range = self
             .get_snapshot_target_block_range(to_block_number, self.highest_snapshots.transactions);
+        let account_changesets_block_range = self.get_snapshot_target_block_range(
+            to_block_number,
+            self.highest_snapshots.account_changesets,
+        );
+        let storage_changesets_block_range = self.get_snapshot_target_block_range(
+            to_block_number,
+            self.highest_snapshots.storage_changesets,
+        );
+        let senders_block_range =
+            self.get_snapshot_target_block_range(to_block_number, self.highest_snapshots.senders);
+        let bytecodes_block_range =
+            self.get_snapshot_target_block_range(to_block_number, self.highest_snapshots.bytecodes);
 
         // Calculate transaction ranges to snapshot
         let mut block_to_tx_number_cache = HashMap::default();
@@ -255,6 +382,12 @@ impl<DB: Database> Snapshotter<DB> {
             self.highest_snapshots.transactions,
             &transactions_block_range,
         )?;
+        let senders_tx_range = self.get_snapshot_target_tx_range(
+            &provider,
+            &mut block_to_tx_number_cache,
+            self.highest_snapshots.senders,
+            &senders_block_range,
+        )?;
 
         Ok(SnapshotTargets {
             headers: headers_block_range
@@ -275,6 +408,31 @@ impl<DB: Database> Snapshotter<DB> {
                 .expect("finalized block should be >= last transactions snapshot")
                 .ge(&(self.block_interval as usize))
                 .then_some((transactions_block_range, transactions_tx_range)),
+            account_changesets: account_changesets_block_range
+                .size_hint()
+                .1
+                .expect("finalized block should be >= last account changesets snapshot")
+                .ge(&(self.block_interval as usize))
+                .then_some(account_changesets_block_range),
+            storage_changesets: storage_changesets_block_range
+                .size_hint()
+                .1
+                .expect("finalized block should be >= last storage changesets snapshot")
+                .ge(&(self.block_interval as usize))
+                .then_some(storage_changesets_block_range),
+            senders: senders_block_range
+                .size_hint()
+                .1
+                .expect("finalized block should be >= last senders snapshot")
+                .ge(&(self.block_interval as usize))
+                .then_some((senders_block_range, senders_tx_range)),
+            bytecodes: bytecodes_block_range
+                .size_hint()
+                .1
+                .expect("finalized block should be >= last bytecodes snapshot")
+                .ge(&(self.block_interval as usize))
+                .then_some(bytecodes_block_range)
+                .filter(|_| self.snapshot_bytecodes),
         })
     }
 
@@ -323,11 +481,15 @@ impl<DB: Database> Snapshotter<DB> {
 mod tests {
     use crate::{snapshotter::SnapshotTargets, Snapshotter};
     use assert_matches::assert_matches;
+    use reth_db::{database::Database, snapshot::iter_snapshots, tables, transaction::DbTxMut};
     use reth_interfaces::{
         test_utils::{generators, generators::random_block_range},
         RethError,
     };
-    use reth_primitives::{snapshot::HighestSnapshots, B256};
+    use reth_primitives::{
+        keccak256, snapshot::HighestSnapshots, Bytecode, Bytes, SnapshotSegment, B256,
+    };
+    use reth_provider::{HistoricalStateProviderRef, SnapshotProvider, StateProvider};
     use reth_stages::test_utils::TestStageDB;
 
     #[test]
@@ -351,7 +513,13 @@ mod tests {
         let blocks = random_block_range(&mut rng, 0..=3, B256::ZERO, 2..3);
         db.insert_blocks(blocks.iter(), None).expect("insert blocks");
 
-        let mut snapshotter = Snapshotter::new(db.factory, snapshots_dir.into_path(), 2).unwrap();
+        // Bytecodes are only snapshotted if enabled
+        let snapshotter = Snapshotter::new(db.factory.clone(), snapshots_dir.path(), 2).unwrap();
+        assert_eq!(snapshotter.get_snapshot_targets(1).map(|targets| targets.bytecodes), Ok(None));
+
+        let mut snapshotter = Snapshotter::new(db.factory, snapshots_dir.into_path(), 2)
+            .unwrap()
+            .with_bytecodes(true);
 
         // Snapshot targets has data per part up to the passed finalized block number,
         // respecting the block interval
@@ -361,7 +529,11 @@ mod tests {
             SnapshotTargets {
                 headers: Some(0..=1),
                 receipts: Some((0..=1, 0..=3)),
-                transactions: Some((0..=1, 0..=3))
+                transactions: Some((0..=1, 0..=3)),
+                account_changesets: Some(0..=1),
+                storage_changesets: Some(0..=1),
+                senders: Some((0..=1, 0..=3)),
+                bytecodes: Some(0..=1),
             }
         );
         assert!(targets.is_multiple_of_block_interval(snapshotter.block_interval));
@@ -372,7 +544,15 @@ mod tests {
         // Nothing to snapshot, last snapshots state of snapshotter doesn't pass the thresholds
         assert_eq!(
             snapshotter.get_snapshot_targets(2),
-            Ok(SnapshotTargets { headers: None, receipts: None, transactions: None })
+            Ok(SnapshotTargets {
+                headers: None,
+                receipts: None,
+                transactions: None,
+                account_changesets: None,
+                storage_changesets: None,
+                senders: None,
+                bytecodes: None,
+            })
         );
 
         // Snapshot targets has data per part up to the passed finalized block number,
@@ -383,7 +563,11 @@ mod tests {
             SnapshotTargets {
                 headers: Some(2..=3),
                 receipts: Some((2..=3, 4..=7)),
-                transactions: Some((2..=3, 4..=7))
+                transactions: Some((2..=3, 4..=7)),
+                account_changesets: Some(2..=3),
+                storage_changesets: Some(2..=3),
+                senders: Some((2..=3, 4..=7)),
+                bytecodes: Some(2..=3),
             }
         );
         assert!(targets.is_multiple_of_block_interval(snapshotter.block_interval));
@@ -394,4 +578,58 @@ mod tests {
         // Block body indices not found
         assert_matches!(snapshotter.get_snapshot_targets(5), Err(RethError::Custom(_)));
     }
+
+    #[test]
+    fn bytecodes_snapshot_replaces_previous() {
+        let db = TestStageDB::default();
+        let snapshots_dir = tempfile::TempDir::new().unwrap();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=3, B256::ZERO, 2..3);
+        db.insert_blocks(blocks.iter(), None).expect("insert blocks");
+
+        let codes = [0x00, 0x01].map(|op| Bytes::from(vec![0x60, op]));
+        let insert_code = |code: &Bytes| {
+            db.commit(|tx| {
+                Ok(tx.put::<tables::Bytecodes>(keccak256(code), Bytecode::new_raw(code.clone()))?)
+            })
+            .unwrap()
+        };
+        let targets = |bytecodes| SnapshotTargets {
+            headers: None,
+            receipts: None,
+            transactions: None,
+            account_changesets: None,
+            storage_changesets: None,
+            senders: None,
+            bytecodes: Some(bytecodes),
+        };
+
+        let mut snapshotter =
+            Snapshotter::new(db.factory.clone(), snapshots_dir.path(), 2).unwrap();
+        insert_code(&codes[0]);
+        snapshotter.run(targets(0..=1)).unwrap();
+        insert_code(&codes[1]);
+        snapshotter.run(targets(2..=3)).unwrap();
+
+        // Only the second snapshot is left, and it starts at genesis.
+        let snapshots = iter_snapshots(snapshots_dir.path()).unwrap();
+        let bytecode_ranges = snapshots[&SnapshotSegment::Bytecodes]
+            .iter()
+            .map(|(block_range, _)| block_range.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(bytecode_ranges, vec![0..=3]);
+
+        // The bytecodes are read from the snapshot once they're removed from the database.
+        db.commit(|tx| Ok(tx.clear::<tables::Bytecodes>()?)).unwrap();
+        let snapshot_provider = SnapshotProvider::new(snapshots_dir.path()).unwrap();
+        let tx = db.factory.db_ref().tx().unwrap();
+        let state =
+            HistoricalStateProviderRef::new(&tx, 1).with_snapshot_provider(&snapshot_provider);
+        for code in &codes {
+            let bytecode = state.bytecode_by_hash(keccak256(code)).unwrap();
+            assert_eq!(bytecode.map(|bytecode| bytecode.original_bytes()), Some(code.clone()));
+        }
+        assert_eq!(state.bytecode_by_hash(keccak256([0x60, 0x02])).unwrap(), None);
+    }
 }
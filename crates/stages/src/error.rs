@@ -3,7 +3,7 @@ use reth_interfaces::{
     consensus, db::DatabaseError as DbError, executor, p2p::error::DownloadError,
     provider::ProviderError, RethError,
 };
-use reth_primitives::SealedHeader;
+use reth_primitives::{BlockNumber, SealedHeader};
 use thiserror::Error;
 use tokio::sync::mpsc::error::SendError;
 
@@ -124,6 +124,15 @@ pub enum PipelineError {
     /// The pipeline encountered an error while trying to send an event.
     #[error("pipeline encountered an error while trying to send an event")]
     Channel(#[from] Box<SendError<PipelineEvent>>),
+    /// The unwind target is below the pivot of a snap sync, whose state before the pivot is
+    /// missing.
+    #[error("unable to unwind to block #{to} below the snap sync pivot #{pivot}")]
+    UnwindBelowSnapPivot {
+        /// The unwind target.
+        to: BlockNumber,
+        /// The snap sync pivot.
+        pivot: BlockNumber,
+    },
     /// The stage encountered an internal error.
     #[error(transparent)]
     Internal(Box<dyn std::error::Error + Send + Sync>),
@@ -226,6 +226,13 @@ where
 
         let mut provider_rw = self.provider_factory.provider_rw()?;
 
+        // The state before the pivot of a snap sync was never downloaded.
+        let pivot =
+            provider_rw.get_stage_checkpoint(StageId::SNAP_SYNC)?.unwrap_or_default().block_number;
+        if to < pivot {
+            return Err(PipelineError::UnwindBelowSnapPivot { to, pivot })
+        }
+
         for stage in unwind_pipeline {
             let stage_id = stage.id();
             let span = info_span!("Unwinding", stage = %stage_id);
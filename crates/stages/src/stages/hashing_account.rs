@@ -16,7 +16,7 @@ use reth_primitives::{
         StageId,
     },
 };
-use reth_provider::{AccountExtReader, DatabaseProviderRW, HashingWriter};
+use reth_provider::{AccountExtReader, DatabaseProviderRW, HashingWriter, StageCheckpointReader};
 use std::{
     cmp::max,
     fmt::Debug,
@@ -147,7 +147,13 @@ impl<DB: Database> Stage<DB> for AccountHashingStage {
         // account otherwise take changesets aggregate the sets and apply hashing to
         // AccountHashing table. Also, if we start from genesis, we need to hash from scratch, as
         // genesis accounts are not in changeset.
-        if to_block - from_block > self.clean_threshold || from_block == 1 {
+        //
+        // A snap synced database has no complete plain state to hash from, so the hashed state is
+        // always updated from the changesets after the pivot.
+        let snap_synced = provider
+            .get_stage_checkpoint(StageId::SNAP_SYNC)?
+            .is_some_and(|checkpoint| checkpoint.block_number > 0);
+        if !snap_synced && (to_block - from_block > self.clean_threshold || from_block == 1) {
             let tx = provider.tx_ref();
             let stage_checkpoint = input
                 .checkpoint
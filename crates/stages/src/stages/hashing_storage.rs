@@ -16,7 +16,7 @@ use reth_primitives::{
     },
     StorageEntry,
 };
-use reth_provider::{DatabaseProviderRW, HashingWriter, StorageReader};
+use reth_provider::{DatabaseProviderRW, HashingWriter, StageCheckpointReader, StorageReader};
 use std::{collections::BTreeMap, fmt::Debug};
 use tracing::*;
 
@@ -67,7 +67,13 @@ impl<DB: Database> Stage<DB> for StorageHashingStage {
         // account otherwise take changesets aggregate the sets and apply hashing to
         // AccountHashing table. Also, if we start from genesis, we need to hash from scratch, as
         // genesis accounts are not in changeset, along with their storages.
-        if to_block - from_block > self.clean_threshold || from_block == 1 {
+        //
+        // A snap synced database has no complete plain state to hash from, so the hashed state is
+        // always updated from the changesets after the pivot.
+        let snap_synced = provider
+            .get_stage_checkpoint(StageId::SNAP_SYNC)?
+            .is_some_and(|checkpoint| checkpoint.block_number > 0);
+        if !snap_synced && (to_block - from_block > self.clean_threshold || from_block == 1) {
             let stage_checkpoint = input
                 .checkpoint
                 .and_then(|checkpoint| checkpoint.storage_hashing_stage_checkpoint());
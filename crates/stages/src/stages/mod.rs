@@ -16,6 +16,8 @@ mod index_account_history;
 mod index_storage_history;
 /// Stage for computing state root.
 mod merkle;
+/// The receipts stage.
+mod receipts;
 /// The sender recovery stage.
 mod sender_recovery;
 /// The total difficulty stage
@@ -32,6 +34,7 @@ pub use headers::*;
 pub use index_account_history::*;
 pub use index_storage_history::*;
 pub use merkle::*;
+pub use receipts::*;
 pub use sender_recovery::*;
 pub use total_difficulty::*;
 pub use tx_lookup::*;
@@ -0,0 +1,434 @@
+use crate::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
+use futures_util::TryStreamExt;
+use reth_db::{database::Database, tables, transaction::DbTxMut};
+use reth_interfaces::p2p::{
+    error::DownloadError,
+    receipts::{downloader::ReceiptDownloader, response::BlockReceipts},
+};
+use reth_primitives::{
+    stage::{StageCheckpoint, StageId},
+    BlockNumber, GotExpected, PruneCheckpoint, PruneMode, PruneSegment,
+};
+use reth_provider::{
+    BlockReader, DatabaseProviderRW, ProviderError, PruneCheckpointReader, PruneCheckpointWriter,
+    StageCheckpointReader,
+};
+use std::{
+    ops::RangeInclusive,
+    task::{ready, Context, Poll},
+};
+use tracing::*;
+
+/// The receipts stage downloads the receipts of blocks from peers instead of executing them.
+///
+/// Receipts are only downloaded for the blocks up to the snap sync pivot, whose state was synced
+/// instead of executed. The receipts of the blocks after it are written by the execution, the
+/// blocks outside of the range are skipped. The receipts are verified against the receipts root
+/// of their header only.
+///
+/// The range is resolved from the pivot on the first execution of the stage, which then returns
+/// without progress so the receipts are downloaded on the next run. The receipts of blocks before
+/// the configured first block or pruned by the configured prune mode at the pivot are not
+/// downloaded. Once the receipts of the range are written, the prune checkpoint that the snap sync
+/// set for the receipts is moved back to the first block of the range.
+///
+/// # Tables
+///
+/// The receipts are inserted into the [`Receipts`][reth_db::tables::Receipts] table, keyed by the
+/// transaction numbers of the [`BlockBodyIndices`][reth_db::tables::BlockBodyIndices] table. The
+/// stage therefore has to run after the [`BodyStage`][crate::stages::BodyStage].
+#[derive(Debug)]
+pub struct ReceiptsStage<D: ReceiptDownloader> {
+    /// The receipts downloader.
+    downloader: D,
+    /// The first block whose receipts can be downloaded.
+    first_block: BlockNumber,
+    /// The prune mode of the receipts.
+    prune_mode: Option<PruneMode>,
+    /// The range of blocks to download the receipts for, once it's resolved.
+    range: Option<RangeInclusive<BlockNumber>>,
+    /// Block receipts buffer.
+    buffer: Option<Vec<BlockReceipts>>,
+}
+
+impl<D: ReceiptDownloader> ReceiptsStage<D> {
+    /// Create new receipts stage that downloads the receipts of the snap synced blocks, starting
+    /// at the given block.
+    pub fn new(downloader: D, first_block: BlockNumber) -> Self {
+        Self { downloader, first_block, prune_mode: None, range: None, buffer: None }
+    }
+
+    /// Set the prune mode of the receipts, the receipts of the pruned blocks are not downloaded.
+    pub fn with_prune_mode(mut self, prune_mode: Option<PruneMode>) -> Self {
+        self.prune_mode = prune_mode;
+        self
+    }
+
+    /// Returns the range of blocks of the input to download the receipts for, if any.
+    fn download_range(&self, input: &ExecInput) -> Option<RangeInclusive<BlockNumber>> {
+        let range = self.range.as_ref()?;
+        let (from, to) = input.next_block_range().into_inner();
+        let range = from.max(*range.start())..=to.min(*range.end());
+        (!range.is_empty()).then_some(range)
+    }
+
+    /// Resolves the range of blocks to download the receipts for from the snap sync pivot.
+    fn resolve_range<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+    ) -> Result<RangeInclusive<BlockNumber>, StageError> {
+        let pivot =
+            provider.get_stage_checkpoint(StageId::SNAP_SYNC)?.unwrap_or_default().block_number;
+
+        let mut start = self.first_block.max(1);
+        if let Some(mode) = self.prune_mode {
+            if let Some((pruned_block, _)) =
+                mode.prune_target_block(pivot, PruneSegment::Receipts)?
+            {
+                start = start.max(pruned_block + 1);
+            }
+        }
+
+        debug!(target: "sync::stages::receipts", start, pivot, "Resolved receipts range");
+        Ok(start..=pivot)
+    }
+
+    /// Moves the prune checkpoint that the snap sync set for the receipts of the blocks up to the
+    /// pivot back to the first block of the range, now that the receipts of the range are written.
+    fn save_prune_checkpoint<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        range: &RangeInclusive<BlockNumber>,
+    ) -> Result<(), StageError> {
+        let (start, pivot) = (*range.start(), *range.end());
+        let Some(checkpoint) = provider.get_prune_checkpoint(PruneSegment::Receipts)? else {
+            return Ok(())
+        };
+        if checkpoint.block_number != Some(pivot) ||
+            checkpoint.prune_mode != PruneMode::Before(pivot + 1)
+        {
+            return Ok(())
+        }
+
+        let pruned_block = start - 1;
+        let tx_number = provider
+            .block_body_indices(pruned_block)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(pruned_block))?
+            .last_tx_num();
+        provider.save_prune_checkpoint(
+            PruneSegment::Receipts,
+            PruneCheckpoint {
+                block_number: Some(pruned_block),
+                tx_number: Some(tx_number),
+                prune_mode: PruneMode::Before(start),
+            },
+        )?;
+        Ok(())
+    }
+}
+
+impl<DB: Database, D: ReceiptDownloader> Stage<DB> for ReceiptsStage<D> {
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::Other("Receipts")
+    }
+
+    fn poll_execute_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+        input: ExecInput,
+    ) -> Poll<Result<(), StageError>> {
+        if input.target_reached() || self.buffer.is_some() {
+            return Poll::Ready(Ok(()))
+        }
+
+        // Nothing to download, the blocks are skipped on execution or the range is resolved first.
+        let Some(range) = self.download_range(&input) else { return Poll::Ready(Ok(())) };
+
+        // Update the block range on the downloader
+        self.downloader.set_download_range(range)?;
+
+        // Poll next downloader item.
+        let maybe_next_result = ready!(self.downloader.try_poll_next_unpin(cx));
+
+        // Downloader can return `None` only if the response relaying channel was closed.
+        let response = match maybe_next_result {
+            Some(Ok(downloaded)) => {
+                self.buffer = Some(downloaded);
+                Ok(())
+            }
+            Some(Err(err)) => Err(err.into()),
+            None => Err(StageError::ChannelClosed),
+        };
+        Poll::Ready(response)
+    }
+
+    /// Write the downloaded receipts of the blocks from the last checkpoint of this stage up
+    /// until the latest synced body.
+    fn execute(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        if self.range.is_none() {
+            self.range = Some(self.resolve_range(provider)?);
+            // The receipts are downloaded on the next run, now that the range is known.
+            if self.download_range(&input).is_some() {
+                return Ok(ExecOutput { checkpoint: input.checkpoint(), done: false })
+            }
+        }
+
+        let Some(range) = self.download_range(&input) else {
+            debug!(target: "sync::stages::receipts", target = input.target(), "Skipping blocks outside of receipts range");
+            return Ok(ExecOutput::done(StageCheckpoint::new(input.target())))
+        };
+        let (from_block, to_block) = range.into_inner();
+
+        let buffer = self.buffer.take().ok_or(StageError::MissingDownloadBuffer)?;
+        trace!(target: "sync::stages::receipts", stage_progress = from_block, target = to_block, blocks_len = buffer.len(), "Writing receipts");
+
+        let tx = provider.tx_ref();
+        let mut highest_block = input.checkpoint().block_number;
+        for block in buffer {
+            let block_number = block.block_number();
+            let indices = provider
+                .block_body_indices(block_number)?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(block_number))?;
+            if indices.tx_count as usize != block.receipts.len() {
+                return Err(DownloadError::ReceiptsCountMismatch {
+                    block_number,
+                    count: GotExpected::new(block.receipts.len(), indices.tx_count as usize),
+                }
+                .into())
+            }
+            for (tx_num, receipt) in indices.tx_num_range().zip(block.receipts) {
+                tx.put::<tables::Receipts>(tx_num, receipt)?;
+            }
+            highest_block = block_number;
+        }
+
+        // The blocks past the end of the range are skipped.
+        let range = self.range.clone().expect("is resolved");
+        if highest_block == *range.end() {
+            self.save_prune_checkpoint(provider, &range)?;
+            highest_block = input.target();
+        }
+
+        let done = highest_block == input.target();
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(highest_block), done })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        self.buffer.take();
+
+        let last_tx_num = provider
+            .block_body_indices(input.unwind_to)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(input.unwind_to))?
+            .last_tx_num();
+        provider.unwind_table_by_num::<tables::Receipts>(last_tx_num)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::TestStageDB, StageExt};
+    use futures_util::Stream;
+    use reth_db::transaction::DbTx;
+    use reth_interfaces::{
+        p2p::{error::DownloadResult, receipts::downloader::ReceiptDownloaderResult},
+        test_utils::generators::{self, random_block_range, random_receipt},
+    };
+    use reth_primitives::B256;
+    use reth_provider::StageCheckpointWriter;
+    use std::{collections::VecDeque, pin::Pin};
+
+    /// A downloader that yields the receipts of the blocks in the requested range.
+    #[derive(Debug, Default)]
+    struct TestReceiptDownloader {
+        blocks: Vec<BlockReceipts>,
+        queue: VecDeque<BlockReceipts>,
+    }
+
+    impl ReceiptDownloader for TestReceiptDownloader {
+        fn set_download_range(&mut self, range: RangeInclusive<BlockNumber>) -> DownloadResult<()> {
+            self.queue =
+                self.blocks.iter().filter(|b| range.contains(&b.block_number())).cloned().collect();
+            Ok(())
+        }
+    }
+
+    impl Stream for TestReceiptDownloader {
+        type Item = ReceiptDownloaderResult;
+
+        fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            let this = self.get_mut();
+            Poll::Ready(Some(Ok(this.queue.drain(..).collect())))
+        }
+    }
+
+    #[tokio::test]
+    async fn execute_writes_receipts_in_range() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=9, B256::ZERO, 1..3);
+        db.insert_blocks(blocks.iter(), None).unwrap();
+
+        let receipts = blocks
+            .iter()
+            .map(|block| BlockReceipts {
+                header: block.header.clone(),
+                receipts: block
+                    .body
+                    .iter()
+                    .map(|tx| random_receipt(&mut rng, tx, Some(0)))
+                    .collect(),
+            })
+            .collect();
+        let downloader = TestReceiptDownloader { blocks: receipts, queue: VecDeque::new() };
+        let mut stage = ReceiptsStage::new(downloader, 3);
+
+        // The snap sync synced the state of the pivot and marked the receipts as pruned.
+        let provider = db.factory.provider_rw().unwrap();
+        provider.save_stage_checkpoint(StageId::SNAP_SYNC, StageCheckpoint::new(6)).unwrap();
+        let snap_checkpoint = PruneCheckpoint {
+            block_number: Some(6),
+            tx_number: None,
+            prune_mode: PruneMode::Before(7),
+        };
+        provider.save_prune_checkpoint(PruneSegment::Receipts, snap_checkpoint).unwrap();
+        provider.commit().unwrap();
+
+        // The first run resolves the range from the pivot.
+        let input = ExecInput { target: Some(9), checkpoint: Some(StageCheckpoint::new(0)) };
+        stage.execute_ready(input).await.unwrap();
+        let provider = db.factory.provider_rw().unwrap();
+        let output = stage.execute(&provider, input).unwrap();
+        provider.commit().unwrap();
+        assert_eq!(output, ExecOutput { checkpoint: StageCheckpoint::new(0), done: false });
+        assert_eq!(stage.range, Some(3..=6));
+
+        stage.execute_ready(input).await.unwrap();
+        let provider = db.factory.provider_rw().unwrap();
+        let output = stage.execute(&provider, input).unwrap();
+        provider.commit().unwrap();
+        assert_eq!(output, ExecOutput { checkpoint: StageCheckpoint::new(9), done: true });
+
+        // The receipts are only marked as pruned before the range.
+        let checkpoint =
+            db.factory.provider().unwrap().get_prune_checkpoint(PruneSegment::Receipts);
+        let last_tx_num = blocks[..=2].iter().map(|b| b.body.len() as u64).sum::<u64>() - 1;
+        assert_eq!(
+            checkpoint.unwrap(),
+            Some(PruneCheckpoint {
+                block_number: Some(2),
+                tx_number: Some(last_tx_num),
+                prune_mode: PruneMode::Before(3),
+            })
+        );
+
+        // Only the receipts of the blocks within the range are written.
+        let provider = db.factory.provider().unwrap();
+        for block in &blocks {
+            let indices = provider.block_body_indices(block.number).unwrap().unwrap();
+            for tx_num in indices.tx_num_range() {
+                let receipt = provider.tx_ref().get::<tables::Receipts>(tx_num).unwrap();
+                assert_eq!(receipt.is_some(), (3..=6).contains(&block.number));
+            }
+        }
+        drop(provider);
+
+        // Unwinding removes the receipts of the unwound blocks.
+        let provider = db.factory.provider_rw().unwrap();
+        let output = stage
+            .unwind(
+                &provider,
+                UnwindInput { checkpoint: output.checkpoint, unwind_to: 4, bad_block: None },
+            )
+            .unwrap();
+        provider.commit().unwrap();
+        assert_eq!(output, UnwindOutput { checkpoint: StageCheckpoint::new(4) });
+
+        let last_tx_num = blocks[..=4].iter().map(|b| b.body.len() as u64).sum::<u64>() - 1;
+        let remaining = db.table::<tables::Receipts>().unwrap();
+        assert!(remaining.iter().all(|(tx_num, _)| *tx_num <= last_tx_num));
+        assert!(!remaining.is_empty());
+    }
+
+    #[tokio::test]
+    async fn execute_skips_pruned_receipts() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=9, B256::ZERO, 1..3);
+        db.insert_blocks(blocks.iter(), None).unwrap();
+        let provider = db.factory.provider_rw().unwrap();
+        provider.save_stage_checkpoint(StageId::SNAP_SYNC, StageCheckpoint::new(200)).unwrap();
+        provider.commit().unwrap();
+
+        let mut stage = ReceiptsStage::new(TestReceiptDownloader::default(), 0)
+            .with_prune_mode(Some(PruneMode::Distance(64)));
+        let input = ExecInput { target: Some(9), checkpoint: Some(StageCheckpoint::new(0)) };
+        stage.execute_ready(input).await.unwrap();
+        let provider = db.factory.provider_rw().unwrap();
+        let output = stage.execute(&provider, input).unwrap();
+        assert_eq!(output, ExecOutput { checkpoint: StageCheckpoint::new(9), done: true });
+        assert_eq!(stage.range, Some(137..=200));
+    }
+
+    #[tokio::test]
+    async fn execute_rejects_receipts_count_mismatch() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=3, B256::ZERO, 1..3);
+        db.insert_blocks(blocks.iter(), None).unwrap();
+        let provider = db.factory.provider_rw().unwrap();
+        provider.save_stage_checkpoint(StageId::SNAP_SYNC, StageCheckpoint::new(3)).unwrap();
+        provider.commit().unwrap();
+
+        // One receipt of every block is missing.
+        let receipts = blocks
+            .iter()
+            .map(|block| BlockReceipts {
+                header: block.header.clone(),
+                receipts: block.body[1..]
+                    .iter()
+                    .map(|tx| random_receipt(&mut rng, tx, Some(0)))
+                    .collect(),
+            })
+            .collect();
+        let downloader = TestReceiptDownloader { blocks: receipts, queue: VecDeque::new() };
+        let mut stage = ReceiptsStage::new(downloader, 0);
+
+        let input = ExecInput { target: Some(3), checkpoint: Some(StageCheckpoint::new(0)) };
+        for _ in 0..2 {
+            stage.execute_ready(input).await.unwrap();
+            let provider = db.factory.provider_rw().unwrap();
+            if let Err(err) = stage.execute(&provider, input) {
+                assert!(matches!(
+                    err,
+                    StageError::Download(DownloadError::ReceiptsCountMismatch {
+                        block_number: 1,
+                        ..
+                    })
+                ));
+                return
+            }
+            provider.commit().unwrap();
+        }
+        panic!("receipts count mismatch was not detected");
+    }
+}
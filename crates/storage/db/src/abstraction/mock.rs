@@ -67,6 +67,10 @@ impl DbTx for TxMock {
         Ok(self._table.len())
     }
 
+    fn table_size<T: Table>(&self) -> Result<usize, DatabaseError> {
+        Ok(0)
+    }
+
     fn disable_long_read_transaction_safety(&mut self) {}
 }
 
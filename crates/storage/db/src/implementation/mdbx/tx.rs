@@ -293,6 +293,16 @@ impl<K: TransactionKind> DbTx for Tx<K> {
             .entries())
     }
 
+    /// Returns the size of the table in bytes using cheap DB stats invocation.
+    fn table_size<T: Table>(&self) -> Result<usize, DatabaseError> {
+        let stats = self
+            .inner
+            .db_stat_with_dbi(self.get_dbi::<T>()?)
+            .map_err(|e| DatabaseError::Stats(e.into()))?;
+        Ok(stats.page_size() as usize *
+            (stats.leaf_pages() + stats.branch_pages() + stats.overflow_pages()))
+    }
+
     /// Disables long-lived read transaction safety guarantees, such as backtrace recording and
     /// timeout.
     fn disable_long_read_transaction_safety(&mut self) {
@@ -1,11 +1,15 @@
-use super::{ReceiptMask, TransactionMask};
+use super::{
+    AccountChangeSetMask, BytecodeMask, ReceiptMask, StorageChangeSetMask, TransactionMask,
+    TxSenderMask,
+};
 use crate::{
     add_snapshot_mask,
+    models::{StoredAccountChangeSet, StoredStorageChangeSet},
     snapshot::mask::{ColumnSelectorOne, ColumnSelectorTwo, HeaderMask},
     table::Table,
-    CanonicalHeaders, HeaderTD, Receipts, Transactions,
+    CanonicalHeaders, HeaderTD, Receipts, Transactions, TxSenders,
 };
-use reth_primitives::{BlockHash, Header};
+use reth_primitives::{BlockHash, Bytecode, Header, B256};
 
 // HEADER MASKS
 
@@ -26,3 +30,17 @@ add_snapshot_mask!(ReceiptMask, <Receipts as Table>::Value, 0b1);
 
 // TRANSACTION MASKS
 add_snapshot_mask!(TransactionMask, <Transactions as Table>::Value, 0b1);
+
+// ACCOUNT CHANGESET MASKS
+add_snapshot_mask!(AccountChangeSetMask, StoredAccountChangeSet, 0b1);
+
+// STORAGE CHANGESET MASKS
+add_snapshot_mask!(StorageChangeSetMask, StoredStorageChangeSet, 0b1);
+
+// SENDER MASKS
+add_snapshot_mask!(TxSenderMask, <TxSenders as Table>::Value, 0b1);
+
+// BYTECODE MASKS
+add_snapshot_mask!(BytecodeMask, Bytecode, 0b01);
+add_snapshot_mask!(BytecodeMask, B256, 0b10);
+add_snapshot_mask!(BytecodeMask, Bytecode, B256, 0b11);
@@ -42,12 +42,16 @@ impl_compression_for_compact!(
     StoredBlockBodyIndices,
     StoredBlockOmmers,
     StoredBlockWithdrawals,
+    StoredAccountChangeSet,
+    StoredStorageChangeSet,
     Bytecode,
     AccountBeforeTx,
     TransactionSignedNoHash,
     CompactU256,
     StageCheckpoint,
-    PruneCheckpoint
+    PruneCheckpoint,
+    RetainedHistoryCheckpoint,
+    StoredBadBlock
 );
 
 macro_rules! impl_compression_fixed_compact {
@@ -27,7 +27,7 @@ use crate::{
         codecs::CompactU256,
         models::{
             accounts::{AccountBeforeTx, BlockNumberAddress},
-            blocks::{HeaderHash, StoredBlockOmmers},
+            blocks::{HeaderHash, StoredBadBlock, StoredBlockOmmers},
             storage_sharded_key::StorageShardedKey,
             ShardedKey, StoredBlockBodyIndices, StoredBlockWithdrawals,
         },
@@ -37,7 +37,8 @@ use reth_primitives::{
     stage::StageCheckpoint,
     trie::{StorageTrieEntry, StoredBranchNode, StoredNibbles, StoredNibblesSubKey},
     Account, Address, BlockHash, BlockNumber, Bytecode, Header, IntegerList, PruneCheckpoint,
-    PruneSegment, Receipt, StorageEntry, TransactionSignedNoHash, TxHash, TxNumber, B256,
+    PruneSegment, Receipt, RetainedHistoryCheckpoint, StorageEntry, TransactionSignedNoHash,
+    TxHash, TxNumber, B256,
 };
 use std::fmt;
 
@@ -369,6 +370,14 @@ tables! {
 
     /// Stores the highest pruned block number and prune mode of each prune segment.
     table PruneCheckpoints<Key = PruneSegment, Value = PruneCheckpoint>;
+
+    /// Stores the blocks from which the history of each address retained by the history address
+    /// filter of the pruner is complete.
+    table PruneRetainedAddresses<Key = Address, Value = RetainedHistoryCheckpoint>;
+
+    /// Stores the most recent blocks that failed validation, keyed by the order in which they
+    /// were rejected.
+    table BadBlocks<Key = u64, Value = StoredBadBlock>;
 }
 
 // Alias types.
@@ -7,15 +7,15 @@ use crate::{
     table::{Decode, Encode},
     DatabaseError,
 };
-use reth_codecs::{derive_arbitrary, Compact};
-use reth_primitives::{Account, Address, BlockNumber, Buf};
+use reth_codecs::{derive_arbitrary, main_codec, Compact};
+use reth_primitives::{Account, Address, BlockNumber, Buf, B256, U256};
 use serde::{Deserialize, Serialize};
 
 /// Account as it is saved inside [`AccountChangeSet`][crate::tables::AccountChangeSet].
 ///
 /// [`Address`] is the subkey.
 #[derive_arbitrary(compact)]
-#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize)]
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct AccountBeforeTx {
     /// Address for the account. Acts as `DupSort::SubKey`.
     pub address: Address,
@@ -56,6 +56,39 @@ impl Compact for AccountBeforeTx {
     }
 }
 
+/// Storage slot as it is saved inside [`StorageChangeSet`](crate::tables::StorageChangeSet),
+/// together with the address of its account.
+#[main_codec]
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct StorageBeforeTx {
+    /// Address of the account the storage slot belongs to.
+    pub address: Address,
+    /// Storage key.
+    pub key: B256,
+    /// Value on storage key before the transaction.
+    pub value: U256,
+}
+
+/// The account changeset of a single block, as it is stored in a row of the
+/// [`SnapshotSegment::AccountChangeSets`](reth_primitives::SnapshotSegment::AccountChangeSets)
+/// snapshot.
+#[main_codec]
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct StoredAccountChangeSet {
+    /// The accounts as they were before the block, ordered by address.
+    pub changes: Vec<AccountBeforeTx>,
+}
+
+/// The storage changeset of a single block, as it is stored in a row of the
+/// [`SnapshotSegment::StorageChangeSets`](reth_primitives::SnapshotSegment::StorageChangeSets)
+/// snapshot.
+#[main_codec]
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct StoredStorageChangeSet {
+    /// The storage slots as they were before the block, ordered by address and key.
+    pub changes: Vec<StorageBeforeTx>,
+}
+
 /// [`BlockNumber`] concatenated with [`Address`]. Used as the key for
 /// [`StorageChangeSet`](crate::tables::StorageChangeSet)
 ///
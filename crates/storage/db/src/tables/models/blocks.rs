@@ -1,7 +1,7 @@
 //! Block related models and types.
 
 use reth_codecs::{main_codec, Compact};
-use reth_primitives::{Header, TxNumber, Withdrawals, B256};
+use reth_primitives::{Header, TransactionSignedNoHash, TxNumber, Withdrawals, B256};
 use std::ops::Range;
 
 /// Total number of transactions.
@@ -83,6 +83,27 @@ pub struct StoredBlockWithdrawals {
     pub withdrawals: Withdrawals,
 }
 
+/// The storage representation of a block that failed validation.
+///
+/// The block hash is stored separately, since the block may have been rejected because its hash
+/// doesn't match its header.
+#[main_codec]
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+pub struct StoredBadBlock {
+    /// The hash of the block.
+    pub hash: B256,
+    /// The transactions of the block.
+    pub body: Vec<TransactionSignedNoHash>,
+    /// The block headers of this block's uncles.
+    pub ommers: Vec<Header>,
+    /// The block withdrawals.
+    pub withdrawals: Option<Withdrawals>,
+    /// The UTF-8 encoded reason why the block is invalid.
+    pub error: Vec<u8>,
+    /// The block header.
+    pub header: Header,
+}
+
 /// Hash of the block header. Value for [`CanonicalHeaders`][crate::tables::CanonicalHeaders]
 pub type HeaderHash = B256;
 
@@ -45,6 +45,17 @@ const INDEX_FILE_EXTENSION: &str = "idx";
 const OFFSETS_FILE_EXTENSION: &str = "off";
 const CONFIG_FILE_EXTENSION: &str = "conf";
 
+/// Returns the path of the data file of the jar at the given path, followed by the paths of its
+/// index, offsets and config files.
+pub fn jar_file_paths(path: &Path) -> [PathBuf; 4] {
+    [
+        path.to_path_buf(),
+        path.with_extension(INDEX_FILE_EXTENSION),
+        path.with_extension(OFFSETS_FILE_EXTENSION),
+        path.with_extension(CONFIG_FILE_EXTENSION),
+    ]
+}
+
 /// A [`RefRow`] is a list of column value slices pointing to either an internal buffer or a
 /// memory-mapped file.
 type RefRow<'a> = Vec<&'a [u8]>;
@@ -207,6 +218,16 @@ impl<H: NippyJarHeader> NippyJar<H> {
         &self.user_header
     }
 
+    /// Gets total columns in jar.
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Gets total rows in jar.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
     /// Returns the size of inclusion filter
     pub fn filter_size(&self) -> usize {
         self.size()
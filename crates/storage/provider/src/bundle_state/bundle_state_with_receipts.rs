@@ -1,4 +1,6 @@
-use crate::{StateChanges, StateReverts};
+use crate::{
+    bundle_state::HashedStateChanges, providers::is_snap_synced, StateChanges, StateReverts,
+};
 use reth_db::{
     cursor::{DbCursorRO, DbCursorRW},
     tables,
@@ -294,6 +296,9 @@ impl BundleStateWithReceipts {
         tx: &TX,
         is_value_known: OriginalValuesKnown,
     ) -> Result<(), DatabaseError> {
+        // The hashed state of a snap synced database is the only complete copy of the state, it's
+        // updated along with the plain state.
+        let hashed_state = is_snap_synced(tx)?.then(|| self.hash_state_slow());
         let (plain_state, reverts) = self.bundle.into_plain_state_and_reverts(is_value_known);
 
         StateReverts(reverts).write_to_db(tx, self.first_block)?;
@@ -321,6 +326,9 @@ impl BundleStateWithReceipts {
         }
 
         StateChanges(plain_state).write_to_db(tx)?;
+        if let Some(hashed_state) = hashed_state {
+            HashedStateChanges(hashed_state).write_to_db(tx)?;
+        }
 
         Ok(())
     }
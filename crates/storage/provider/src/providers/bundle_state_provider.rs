@@ -1,10 +1,14 @@
 use crate::{
     bundle_state::BundleStateWithReceipts, AccountReader, BlockHashReader, BundleStateDataProvider,
-    StateProvider, StateRootProvider,
+    HashedStateEntry, StateProvider, StateRootProvider,
 };
 use reth_interfaces::provider::{ProviderError, ProviderResult};
-use reth_primitives::{trie::AccountProof, Account, Address, BlockNumber, Bytecode, B256};
-use reth_trie::updates::TrieUpdates;
+use reth_primitives::{
+    trie::{AccountProof, ExecutionWitness},
+    Account, Address, BlockNumber, Bytecode, StorageKey, StorageValue, B256,
+};
+use reth_trie::{updates::TrieUpdates, HashedPostState, HashedStorage};
+use std::collections::HashMap;
 
 /// A state provider that either resolves to data in a wrapped [`crate::BundleStateWithReceipts`],
 /// or an underlying state provider.
@@ -74,6 +78,22 @@ impl<SP: StateProvider, BSDP: BundleStateDataProvider> StateRootProvider
         state.extend(bundle_state.clone());
         self.state_provider.state_root_with_updates(&state)
     }
+
+    fn hashed_state_root(&self, hashed_state: &HashedPostState) -> ProviderResult<B256> {
+        let mut state = self.bundle_state_data_provider.state().hash_state_slow();
+        state.extend(hashed_state.clone());
+        self.state_provider.hashed_state_root(&state)
+    }
+
+    fn hashed_state_roots(&self, hashed_states: &[HashedPostState]) -> ProviderResult<Vec<B256>> {
+        let mut hashed_states = hashed_states.to_vec();
+        if let Some(first) = hashed_states.first_mut() {
+            let mut state = self.bundle_state_data_provider.state().hash_state_slow();
+            state.extend(std::mem::take(first));
+            *first = state;
+        }
+        self.state_provider.hashed_state_roots(&hashed_states)
+    }
 }
 
 impl<SP: StateProvider, BSDP: BundleStateDataProvider> StateProvider
@@ -105,4 +125,33 @@ impl<SP: StateProvider, BSDP: BundleStateDataProvider> StateProvider
     fn proof(&self, _address: Address, _keys: &[B256]) -> ProviderResult<AccountProof> {
         Err(ProviderError::StateRootNotAvailableForHistoricalBlock)
     }
+
+    fn witness(
+        &self,
+        _targets: &HashMap<Address, Vec<B256>>,
+        _changes: &HashedPostState,
+    ) -> ProviderResult<ExecutionWitness> {
+        Err(ProviderError::StateRootNotAvailableForHistoricalBlock)
+    }
+
+    fn storage_root(&self, _address: Address, _changes: HashedStorage) -> ProviderResult<B256> {
+        Err(ProviderError::StateRootNotAvailableForHistoricalBlock)
+    }
+
+    fn account_range(
+        &self,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<Vec<HashedStateEntry<Address, Account>>> {
+        Err(ProviderError::StateRootNotAvailableForHistoricalBlock)
+    }
+
+    fn storage_range(
+        &self,
+        _address: Address,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<Vec<HashedStateEntry<StorageKey, StorageValue>>> {
+        Err(ProviderError::StateRootNotAvailableForHistoricalBlock)
+    }
 }
@@ -4,10 +4,10 @@ use crate::{
         SnapshotProvider,
     },
     traits::{BlockSource, ReceiptProvider},
-    BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, EvmEnvProvider,
-    HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, HeaderSyncMode, ProviderError,
-    PruneCheckpointReader, StageCheckpointReader, StateProviderBox, TransactionVariant,
-    TransactionsProvider, WithdrawalsProvider,
+    BadBlock, BadBlockProvider, BadBlockWriter, BlockHashReader, BlockNumReader, BlockReader,
+    ChainSpecProvider, EvmEnvProvider, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider,
+    HeaderSyncMode, NodeDataProvider, ProviderError, PruneCheckpointReader, StageCheckpointReader,
+    StateProviderBox, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
 use reth_db::{database::Database, init_db, models::StoredBlockBodyIndices, DatabaseEnv};
 use reth_interfaces::{provider::ProviderResult, RethError, RethResult};
@@ -15,7 +15,7 @@ use reth_node_api::ConfigureEvmEnv;
 use reth_primitives::{
     snapshot::HighestSnapshots,
     stage::{StageCheckpoint, StageId},
-    Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, ChainInfo,
+    Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, Bytes, ChainInfo,
     ChainSpec, Header, PruneCheckpoint, PruneSegment, Receipt, SealedBlock, SealedBlockWithSenders,
     SealedHeader, TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber,
     Withdrawal, Withdrawals, B256, U256,
@@ -156,6 +156,9 @@ impl<DB: Database> ProviderFactory<DB> {
             provider.get_prune_checkpoint(PruneSegment::StorageHistory)?;
 
         let mut state_provider = HistoricalStateProvider::new(provider.into_tx(), block_number);
+        if let Some(snapshot_provider) = &self.snapshot_provider {
+            state_provider = state_provider.with_snapshot_provider(snapshot_provider.clone());
+        }
 
         // If we pruned account or storage history, we can't return state on every historical block.
         // Instead, we should cap it at the latest prune checkpoint for corresponding prune segment.
@@ -523,17 +526,45 @@ impl<DB: Database> PruneCheckpointReader for ProviderFactory<DB> {
     }
 }
 
+impl<DB: Database> BadBlockProvider for ProviderFactory<DB> {
+    fn bad_blocks(&self) -> ProviderResult<Vec<BadBlock>> {
+        self.provider()?.bad_blocks()
+    }
+}
+
+impl<DB: Database> BadBlockWriter for ProviderFactory<DB> {
+    fn insert_bad_block(&self, bad_block: BadBlock) -> ProviderResult<()> {
+        let provider = self.provider_rw()?;
+        provider.insert_bad_block(bad_block)?;
+        provider.commit()?;
+        Ok(())
+    }
+}
+
+impl<DB: Database> NodeDataProvider for ProviderFactory<DB> {
+    fn node_data_by_hash(&self, hash: B256) -> ProviderResult<Option<Bytes>> {
+        self.provider()?.node_data_by_hash(hash)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::ProviderFactory;
     use crate::{
-        test_utils::create_test_provider_factory, BlockHashReader, BlockNumReader, BlockWriter,
-        HeaderSyncGapProvider, HeaderSyncMode, TransactionsProvider,
+        test_utils::create_test_provider_factory, BadBlock, BadBlockProvider, BadBlockWriter,
+        BlockHashReader, BlockNumReader, BlockWriter, ChangeSetReader, HeaderSyncGapProvider,
+        HeaderSyncMode, HistoryReader, TransactionsProvider, MAX_BAD_BLOCKS,
     };
     use alloy_rlp::Decodable;
     use assert_matches::assert_matches;
     use rand::Rng;
-    use reth_db::{tables, test_utils::ERROR_TEMPDIR, transaction::DbTxMut, DatabaseEnv};
+    use reth_db::{
+        models::{AccountBeforeTx, BlockNumberAddress, ShardedKey},
+        tables,
+        test_utils::ERROR_TEMPDIR,
+        transaction::DbTxMut,
+        BlockNumberList, DatabaseEnv,
+    };
     use reth_interfaces::{
         provider::ProviderError,
         test_utils::{
@@ -543,9 +574,10 @@ mod tests {
         RethError,
     };
     use reth_primitives::{
-        hex_literal::hex, ChainSpecBuilder, PruneMode, PruneModes, SealedBlock, TxNumber, B256,
+        hex_literal::hex, Address, ChainSpecBuilder, Header, PruneMode, PruneModes, SealedBlock,
+        StorageEntry, TxNumber, B256, U256,
     };
-    use std::{ops::RangeInclusive, sync::Arc};
+    use std::{collections::BTreeSet, ops::RangeInclusive, sync::Arc};
     use tokio::sync::watch;
 
     #[test]
@@ -564,6 +596,35 @@ mod tests {
         assert_eq!(chain_info.best_hash, B256::ZERO);
     }
 
+    #[test]
+    fn bad_blocks_eviction() {
+        let factory = create_test_provider_factory();
+        let bad_block = |number: u64| BadBlock {
+            block: SealedBlock {
+                header: Header { number, ..Default::default() }.seal_slow(),
+                ..Default::default()
+            },
+            error: number.to_string(),
+        };
+
+        factory.insert_bad_block(bad_block(0)).unwrap();
+        factory.insert_bad_block(bad_block(1)).unwrap();
+        // already known, ignored
+        factory.insert_bad_block(bad_block(0)).unwrap();
+        assert_eq!(factory.bad_blocks().unwrap(), vec![bad_block(1), bad_block(0)]);
+        assert_eq!(factory.bad_block(bad_block(0).block.hash()).unwrap(), Some(bad_block(0)));
+
+        // evicts the oldest blocks
+        for number in 2..=MAX_BAD_BLOCKS as u64 + 1 {
+            factory.insert_bad_block(bad_block(number)).unwrap();
+        }
+        let bad_blocks = factory.bad_blocks().unwrap();
+        assert_eq!(bad_blocks.len(), MAX_BAD_BLOCKS);
+        assert_eq!(bad_blocks.first(), Some(&bad_block(MAX_BAD_BLOCKS as u64 + 1)));
+        assert_eq!(bad_blocks.last(), Some(&bad_block(2)));
+        assert_eq!(factory.bad_block(bad_block(0).block.hash()).unwrap(), None);
+    }
+
     #[test]
     fn provider_flow() {
         let factory = create_test_provider_factory();
@@ -737,4 +798,89 @@ mod tests {
             Err(RethError::Provider(ProviderError::InconsistentHeaderGap))
         );
     }
+
+    #[test]
+    fn account_history_blocks() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let address = Address::with_last_byte(1);
+        let higher_address = Address::with_last_byte(2);
+        provider
+            .tx_ref()
+            .put::<tables::AccountHistory>(
+                ShardedKey::new(address, 7),
+                BlockNumberList::new([1, 3, 7]).unwrap(),
+            )
+            .unwrap();
+        provider
+            .tx_ref()
+            .put::<tables::AccountHistory>(
+                ShardedKey::new(address, u64::MAX),
+                BlockNumberList::new([10, 15]).unwrap(),
+            )
+            .unwrap();
+        provider
+            .tx_ref()
+            .put::<tables::AccountHistory>(
+                ShardedKey::new(higher_address, u64::MAX),
+                BlockNumberList::new([4]).unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            provider.account_history_blocks_after(address, 0, usize::MAX),
+            Ok(vec![1, 3, 7, 10, 15])
+        );
+        assert_eq!(provider.account_history_blocks_after(address, 4, 2), Ok(vec![7, 10]));
+        assert_eq!(provider.account_history_blocks_after(address, 16, usize::MAX), Ok(vec![]));
+        assert_eq!(
+            provider.account_history_blocks_before(address, u64::MAX, usize::MAX),
+            Ok(vec![15, 10, 7, 3, 1])
+        );
+        assert_eq!(provider.account_history_blocks_before(address, 9, 2), Ok(vec![7, 3]));
+        assert_eq!(provider.account_history_blocks_before(address, 0, usize::MAX), Ok(vec![]));
+        assert_eq!(
+            provider.account_history_blocks_before(higher_address, 10, usize::MAX),
+            Ok(vec![4])
+        );
+        assert_eq!(
+            provider.account_history_blocks_after(Address::with_last_byte(3), 0, usize::MAX),
+            Ok(vec![])
+        );
+    }
+
+    #[test]
+    fn modified_accounts_with_range() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let account_change = |block, address| {
+            provider
+                .tx_ref()
+                .put::<tables::AccountChangeSet>(block, AccountBeforeTx { address, info: None })
+                .unwrap()
+        };
+        account_change(1, Address::with_last_byte(1));
+        account_change(2, Address::with_last_byte(2));
+        account_change(3, Address::with_last_byte(3));
+        // storage only change
+        provider
+            .tx_ref()
+            .put::<tables::StorageChangeSet>(
+                BlockNumberAddress((2, Address::with_last_byte(4))),
+                StorageEntry { key: B256::with_last_byte(1), value: U256::from(1) },
+            )
+            .unwrap();
+
+        assert_eq!(
+            provider.modified_accounts_with_range(2..=3),
+            Ok(BTreeSet::from([2, 3, 4].map(Address::with_last_byte)))
+        );
+        assert_eq!(
+            provider.modified_accounts_with_range(1..=1),
+            Ok(BTreeSet::from([Address::with_last_byte(1)]))
+        );
+        assert_eq!(provider.modified_accounts_with_range(4..=10), Ok(BTreeSet::new()));
+    }
 }
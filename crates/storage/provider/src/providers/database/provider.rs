@@ -5,11 +5,13 @@ use crate::{
     traits::{
         AccountExtReader, BlockSource, ChangeSetReader, ReceiptProvider, StageCheckpointWriter,
     },
-    AccountReader, BlockExecutionWriter, BlockHashReader, BlockNumReader, BlockReader, BlockWriter,
-    Chain, EvmEnvProvider, HashingWriter, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider,
-    HeaderSyncMode, HistoryWriter, OriginalValuesKnown, ProviderError, PruneCheckpointReader,
-    PruneCheckpointWriter, StageCheckpointReader, StorageReader, TransactionVariant,
-    TransactionsProvider, TransactionsProviderExt, WithdrawalsProvider,
+    AccountReader, BadBlock, BadBlockProvider, BadBlockWriter, BlockExecutionWriter,
+    BlockHashReader, BlockNumReader, BlockReader, BlockWriter, Chain, EvmEnvProvider,
+    HashingWriter, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, HeaderSyncMode,
+    HistoryReader, HistoryWriter, NodeDataProvider, OriginalValuesKnown, ProviderError,
+    PruneCheckpointReader, PruneCheckpointWriter, StageCheckpointReader, StorageReader,
+    TransactionVariant, TransactionsProvider, TransactionsProviderExt, WithdrawalsProvider,
+    MAX_BAD_BLOCKS,
 };
 use itertools::{izip, Itertools};
 use reth_db::{
@@ -36,7 +38,7 @@ use reth_primitives::{
     revm::{config::revm_spec, env::fill_block_env},
     stage::{StageCheckpoint, StageId},
     trie::Nibbles,
-    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders,
+    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, Bytes,
     ChainInfo, ChainSpec, GotExpected, Hardfork, Head, Header, PruneCheckpoint, PruneModes,
     PruneSegment, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, SnapshotSegment,
     StorageEntry, TransactionMeta, TransactionSigned, TransactionSignedEcRecovered,
@@ -221,6 +223,11 @@ impl<TX: DbTx> DatabaseProvider<TX> {
         self
     }
 
+    /// Returns the [`SnapshotProvider`], if the provider has access to one.
+    pub fn snapshot_provider(&self) -> Option<&Arc<SnapshotProvider>> {
+        self.snapshot_provider.as_ref()
+    }
+
     /// Consume `DbTx` or `DbTxMut`.
     pub fn into_tx(self) -> TX {
         self.tx
@@ -286,10 +293,13 @@ impl<TX: DbTx> DatabaseProvider<TX> {
         if let Some(snapshot_provider) = &self.snapshot_provider {
             // If there is, check the maximum block or transaction number of the segment.
             if let Some(snapshot_upper_bound) = match segment {
-                SnapshotSegment::Headers => snapshot_provider.get_highest_snapshot_block(segment),
-                SnapshotSegment::Transactions | SnapshotSegment::Receipts => {
-                    snapshot_provider.get_highest_snapshot_tx(segment)
-                }
+                SnapshotSegment::Headers |
+                SnapshotSegment::AccountChangeSets |
+                SnapshotSegment::StorageChangeSets |
+                SnapshotSegment::Bytecodes => snapshot_provider.get_highest_snapshot_block(segment),
+                SnapshotSegment::Transactions |
+                SnapshotSegment::Receipts |
+                SnapshotSegment::TxSenders => snapshot_provider.get_highest_snapshot_tx(segment),
             } {
                 if block_or_tx_range.start <= snapshot_upper_bound {
                     let end = block_or_tx_range.end.min(snapshot_upper_bound + 1);
@@ -333,10 +343,13 @@ impl<TX: DbTx> DatabaseProvider<TX> {
         if let Some(provider) = &self.snapshot_provider {
             // If there is, check the maximum block or transaction number of the segment.
             let snapshot_upper_bound = match segment {
-                SnapshotSegment::Headers => provider.get_highest_snapshot_block(segment),
-                SnapshotSegment::Transactions | SnapshotSegment::Receipts => {
-                    provider.get_highest_snapshot_tx(segment)
-                }
+                SnapshotSegment::Headers |
+                SnapshotSegment::AccountChangeSets |
+                SnapshotSegment::StorageChangeSets |
+                SnapshotSegment::Bytecodes => provider.get_highest_snapshot_block(segment),
+                SnapshotSegment::Transactions |
+                SnapshotSegment::Receipts |
+                SnapshotSegment::TxSenders => provider.get_highest_snapshot_tx(segment),
             };
 
             if snapshot_upper_bound
@@ -1027,15 +1040,121 @@ impl<TX: DbTx> ChangeSetReader for DatabaseProvider<TX> {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
-        let range = block_number..=block_number;
-        self.tx
-            .cursor_read::<tables::AccountChangeSet>()?
-            .walk_range(range)?
-            .map(|result| -> ProviderResult<_> {
-                let (_, account_before) = result?;
-                Ok(account_before)
-            })
-            .collect()
+        let changeset = self.get_with_snapshot(
+            SnapshotSegment::AccountChangeSets,
+            block_number,
+            |snapshot| snapshot.account_block_changeset(block_number).map(Some),
+            || {
+                let range = block_number..=block_number;
+                self.tx
+                    .cursor_read::<tables::AccountChangeSet>()?
+                    .walk_range(range)?
+                    .map(|result| -> ProviderResult<_> {
+                        let (_, account_before) = result?;
+                        Ok(account_before)
+                    })
+                    .collect::<ProviderResult<_>>()
+                    .map(Some)
+            },
+        )?;
+        Ok(changeset.unwrap_or_default())
+    }
+
+    fn account_changeset_entry(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+    ) -> ProviderResult<Option<AccountBeforeTx>> {
+        self.get_with_snapshot(
+            SnapshotSegment::AccountChangeSets,
+            block_number,
+            |snapshot| snapshot.account_changeset_entry(block_number, address),
+            || {
+                Ok(self
+                    .tx
+                    .cursor_dup_read::<tables::AccountChangeSet>()?
+                    .seek_by_key_subkey(block_number, address)?
+                    .filter(|acc| acc.address == address))
+            },
+        )
+    }
+
+    fn modified_accounts_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        // accounts with only storage changes don't necessarily have an account changeset entry
+        let mut accounts = self.changed_accounts_with_range(range.clone())?;
+        accounts.extend(self.changed_storages_with_range(range)?.into_keys());
+        Ok(accounts)
+    }
+}
+
+impl<TX: DbTx> HistoryReader for DatabaseProvider<TX> {
+    fn account_history_blocks_after(
+        &self,
+        address: Address,
+        from: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        let mut cursor = self.tx.cursor_read::<tables::AccountHistory>()?;
+        let mut blocks = Vec::new();
+
+        // The first shard with a highest block number at or after `from` is the one that may
+        // contain `from`, all following shards of this address only contain later blocks.
+        let mut shard = cursor.seek(ShardedKey::new(address, from))?;
+        while let Some((key, list)) = shard {
+            if key.key != address {
+                break
+            }
+
+            for block in list.iter(0).map(|block| block as BlockNumber).filter(|b| *b >= from) {
+                if blocks.len() >= limit {
+                    return Ok(blocks)
+                }
+                blocks.push(block);
+            }
+
+            shard = cursor.next()?;
+        }
+
+        Ok(blocks)
+    }
+
+    fn account_history_blocks_before(
+        &self,
+        address: Address,
+        to: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        let mut cursor = self.tx.cursor_read::<tables::AccountHistory>()?;
+        let mut blocks = Vec::new();
+
+        // The last shard of an address is always keyed with `u64::MAX`, so this lands on the shard
+        // that may contain `to` if the address has any history, all previous shards of this
+        // address only contain earlier blocks.
+        let mut shard = cursor.seek(ShardedKey::new(address, to))?;
+        while let Some((key, list)) = shard {
+            if key.key != address {
+                break
+            }
+
+            let shard_blocks = list
+                .iter(0)
+                .map(|block| block as BlockNumber)
+                .take_while(|block| *block <= to)
+                .collect::<Vec<_>>();
+            for block in shard_blocks.into_iter().rev() {
+                if blocks.len() >= limit {
+                    return Ok(blocks)
+                }
+                blocks.push(block);
+            }
+
+            shard = cursor.prev()?;
+        }
+
+        Ok(blocks)
     }
 }
 
@@ -1645,11 +1764,24 @@ impl<TX: DbTx> TransactionsProvider for DatabaseProvider<TX> {
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Address>> {
-        self.cursor_read_collect::<tables::TxSenders, _>(range, Ok).map_err(Into::into)
+        self.get_range_with_snapshot(
+            SnapshotSegment::TxSenders,
+            to_range(range),
+            |snapshot, range, _| snapshot.senders_by_tx_range(range),
+            |range, _| {
+                self.cursor_read_collect::<tables::TxSenders, _>(range, Ok).map_err(Into::into)
+            },
+            |_| true,
+        )
     }
 
     fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>> {
-        Ok(self.tx.get::<tables::TxSenders>(id)?)
+        self.get_with_snapshot(
+            SnapshotSegment::TxSenders,
+            id,
+            |snapshot| snapshot.transaction_sender(id),
+            || Ok(self.tx.get::<tables::TxSenders>(id)?),
+        )
     }
 }
 
@@ -2555,6 +2687,48 @@ impl<TX: DbTx> PruneCheckpointReader for DatabaseProvider<TX> {
     }
 }
 
+impl<TX: DbTx> NodeDataProvider for DatabaseProvider<TX> {
+    fn node_data_by_hash(&self, hash: B256) -> ProviderResult<Option<Bytes>> {
+        Ok(self.tx.get::<tables::Bytecodes>(hash)?.map(|bytecode| bytecode.original_bytes()))
+    }
+}
+
+impl<TX: DbTx> BadBlockProvider for DatabaseProvider<TX> {
+    fn bad_blocks(&self) -> ProviderResult<Vec<BadBlock>> {
+        self.tx
+            .cursor_read::<tables::BadBlocks>()?
+            .walk_back(None)?
+            .map(|entry| Ok(entry?.1.into()))
+            .collect()
+    }
+}
+
+impl<TX: DbTxMut + DbTx> BadBlockWriter for DatabaseProvider<TX> {
+    fn insert_bad_block(&self, bad_block: BadBlock) -> ProviderResult<()> {
+        let hash = bad_block.block.hash();
+        let mut cursor = self.tx.cursor_write::<tables::BadBlocks>()?;
+        for entry in cursor.walk(None)? {
+            if entry?.1.hash == hash {
+                return Ok(())
+            }
+        }
+
+        let next_id = cursor.last()?.map_or(0, |(id, _)| id + 1);
+        cursor.append(next_id, bad_block.into())?;
+
+        let mut entries = self.tx.entries::<tables::BadBlocks>()?;
+        while entries > MAX_BAD_BLOCKS {
+            if cursor.first()?.is_none() {
+                break
+            }
+            cursor.delete_current()?;
+            entries -= 1;
+        }
+
+        Ok(())
+    }
+}
+
 impl<TX: DbTxMut> PruneCheckpointWriter for DatabaseProvider<TX> {
     fn save_prune_checkpoint(
         &self,
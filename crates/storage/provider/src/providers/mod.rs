@@ -1,10 +1,11 @@
 use crate::{
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    BlockchainTreePendingStateProvider, BundleStateDataProvider, CanonChainTracker,
-    CanonStateNotifications, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    DatabaseProviderFactory, EvmEnvProvider, HeaderProvider, ProviderError, PruneCheckpointReader,
-    ReceiptProvider, ReceiptProviderIdExt, StageCheckpointReader, StateProviderBox,
-    StateProviderFactory, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    AccountReader, BadBlock, BadBlockProvider, BadBlockWriter, BlockHashReader, BlockIdReader,
+    BlockNumReader, BlockReader, BlockReaderIdExt, BlockchainTreePendingStateProvider,
+    BundleStateDataProvider, CanonChainTracker, CanonStateNotifications, CanonStateSubscriptions,
+    ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory, EvmEnvProvider, HeaderProvider,
+    HistoryReader, NodeDataProvider, ProviderError, PruneCheckpointReader, ReceiptProvider,
+    ReceiptProviderIdExt, StageCheckpointReader, StateProviderBox, StateProviderFactory,
+    TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
 use reth_db::{database::Database, models::StoredBlockBodyIndices};
 use reth_interfaces::{
@@ -17,20 +18,21 @@ use reth_node_api::ConfigureEvmEnv;
 use reth_primitives::{
     stage::{StageCheckpoint, StageId},
     Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumHash, BlockNumber,
-    BlockNumberOrTag, BlockWithSenders, ChainInfo, ChainSpec, Header, PruneCheckpoint,
+    BlockNumberOrTag, BlockWithSenders, Bytes, ChainInfo, ChainSpec, Header, PruneCheckpoint,
     PruneSegment, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, TransactionMeta,
     TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
     U256,
 };
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
-    collections::{BTreeMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashSet},
     ops::{RangeBounds, RangeInclusive},
     sync::Arc,
     time::Instant,
 };
 use tracing::trace;
 
+pub(crate) use state::is_snap_synced;
 pub use state::{
     historical::{HistoricalStateProvider, HistoricalStateProviderRef},
     latest::{LatestStateProvider, LatestStateProviderRef},
@@ -538,6 +540,16 @@ where
     }
 }
 
+impl<DB, Tree> NodeDataProvider for BlockchainProvider<DB, Tree>
+where
+    DB: Database,
+    Tree: Send + Sync,
+{
+    fn node_data_by_hash(&self, hash: B256) -> ProviderResult<Option<Bytes>> {
+        self.database.provider()?.node_data_by_hash(hash)
+    }
+}
+
 impl<DB, Tree> ChainSpecProvider for BlockchainProvider<DB, Tree>
 where
     DB: Send + Sync,
@@ -627,7 +639,7 @@ where
 
 impl<DB, Tree> BlockchainTreeEngine for BlockchainProvider<DB, Tree>
 where
-    DB: Send + Sync,
+    DB: Database,
     Tree: BlockchainTreeEngine,
 {
     fn buffer_block(&self, block: SealedBlockWithSenders) -> Result<(), InsertBlockError> {
@@ -662,7 +674,25 @@ where
     }
 
     fn unwind(&self, unwind_to: BlockNumber) -> RethResult<()> {
-        self.tree.unwind(unwind_to)
+        self.tree.unwind(unwind_to)?;
+
+        // the unwound blocks are no longer canonical, so the tracked heads can't be above the new
+        // canonical head
+        if let Some(head) = self.database.sealed_header(unwind_to)? {
+            if self.chain_info.get_safe_num_hash().is_some_and(|safe| safe.number > unwind_to) {
+                self.chain_info.set_safe(head.clone());
+            }
+            if self
+                .chain_info
+                .get_finalized_num_hash()
+                .is_some_and(|finalized| finalized.number > unwind_to)
+            {
+                self.chain_info.set_finalized(head.clone());
+            }
+            self.chain_info.set_canonical_head(head);
+        }
+
+        Ok(())
     }
 }
 
@@ -861,6 +891,26 @@ where
     }
 }
 
+impl<DB, Tree> BadBlockProvider for BlockchainProvider<DB, Tree>
+where
+    DB: Database,
+    Tree: Send + Sync,
+{
+    fn bad_blocks(&self) -> ProviderResult<Vec<BadBlock>> {
+        self.database.bad_blocks()
+    }
+}
+
+impl<DB, Tree> BadBlockWriter for BlockchainProvider<DB, Tree>
+where
+    DB: Database,
+    Tree: Send + Sync,
+{
+    fn insert_bad_block(&self, bad_block: BadBlock) -> ProviderResult<()> {
+        self.database.insert_bad_block(bad_block)
+    }
+}
+
 impl<DB, Tree> CanonStateSubscriptions for BlockchainProvider<DB, Tree>
 where
     DB: Send + Sync,
@@ -882,6 +932,45 @@ where
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
         self.database.provider()?.account_block_changeset(block_number)
     }
+
+    fn account_changeset_entry(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+    ) -> ProviderResult<Option<AccountBeforeTx>> {
+        self.database.provider()?.account_changeset_entry(block_number, address)
+    }
+
+    fn modified_accounts_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        self.database.provider()?.modified_accounts_with_range(range)
+    }
+}
+
+impl<DB, Tree> HistoryReader for BlockchainProvider<DB, Tree>
+where
+    DB: Database,
+    Tree: Sync + Send,
+{
+    fn account_history_blocks_after(
+        &self,
+        address: Address,
+        from: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.provider()?.account_history_blocks_after(address, from, limit)
+    }
+
+    fn account_history_blocks_before(
+        &self,
+        address: Address,
+        to: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.provider()?.account_history_blocks_before(address, to, limit)
+    }
 }
 
 impl<DB, Tree> AccountReader for BlockchainProvider<DB, Tree>
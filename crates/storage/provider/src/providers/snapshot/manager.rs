@@ -1,26 +1,32 @@
 use super::{LoadedJar, SnapshotJarProvider};
 use crate::{
-    to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, HeaderProvider,
-    ReceiptProvider, TransactionVariant, TransactionsProvider, TransactionsProviderExt,
-    WithdrawalsProvider,
+    to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, ChangeSetReader,
+    HeaderProvider, ReceiptProvider, TransactionVariant, TransactionsProvider,
+    TransactionsProviderExt, WithdrawalsProvider,
 };
 use dashmap::DashMap;
 use parking_lot::RwLock;
 use reth_db::{
     codecs::CompactU256,
-    models::StoredBlockBodyIndices,
-    snapshot::{iter_snapshots, HeaderMask, ReceiptMask, SnapshotCursor, TransactionMask},
+    models::{
+        AccountBeforeTx, StorageBeforeTx, StoredAccountChangeSet, StoredBlockBodyIndices,
+        StoredStorageChangeSet,
+    },
+    snapshot::{
+        iter_snapshots, AccountChangeSetMask, BytecodeMask, HeaderMask, ReceiptMask,
+        SnapshotCursor, StorageChangeSetMask, TransactionMask, TxSenderMask,
+    },
 };
 use reth_interfaces::provider::{ProviderError, ProviderResult};
 use reth_nippy_jar::NippyJar;
 use reth_primitives::{
     snapshot::HighestSnapshots, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber,
-    BlockWithSenders, ChainInfo, Header, Receipt, SealedBlock, SealedBlockWithSenders,
-    SealedHeader, SnapshotSegment, TransactionMeta, TransactionSigned, TransactionSignedNoHash,
-    TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
+    BlockWithSenders, Bytecode, ChainInfo, Header, Receipt, SealedBlock, SealedBlockWithSenders,
+    SealedHeader, SnapshotSegment, StorageEntry, StorageKey, TransactionMeta, TransactionSigned,
+    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
 };
 use std::{
-    collections::{hash_map::Entry, BTreeMap, HashMap},
+    collections::{hash_map::Entry, BTreeMap, BTreeSet, HashMap},
     ops::{Range, RangeBounds, RangeInclusive},
     path::{Path, PathBuf},
 };
@@ -161,7 +167,8 @@ impl SnapshotProvider {
         } else {
             let jar = NippyJar::load(&self.path.join(segment.filename(block_range, tx_range)))
                 .map(|jar| {
-                if self.load_filters {
+                // Cumulative segments are only queried by key.
+                if self.load_filters || segment.is_cumulative() {
                     return jar.load_filters()
                 }
                 Ok(jar)
@@ -229,10 +236,12 @@ impl SnapshotProvider {
         None
     }
 
-    /// Updates the inner transaction and block index
+    /// Rebuilds the inner transaction and block index from the snapshots on disk.
     pub fn update_index(&self) -> ProviderResult<()> {
         let mut block_index = self.snapshots_block_index.write();
         let mut tx_index = self.snapshots_tx_index.write();
+        block_index.clear();
+        tx_index.clear();
 
         for (segment, ranges) in iter_snapshots(&self.path)? {
             for (block_range, tx_range) in ranges {
@@ -327,8 +336,15 @@ impl SnapshotProvider {
         P: FnMut(&T) -> bool,
     {
         let get_provider = |start: u64| match segment {
-            SnapshotSegment::Headers => self.get_segment_provider_from_block(segment, start, None),
-            SnapshotSegment::Transactions | SnapshotSegment::Receipts => {
+            SnapshotSegment::Headers |
+            SnapshotSegment::AccountChangeSets |
+            SnapshotSegment::StorageChangeSets |
+            SnapshotSegment::Bytecodes => {
+                self.get_segment_provider_from_block(segment, start, None)
+            }
+            SnapshotSegment::Transactions |
+            SnapshotSegment::Receipts |
+            SnapshotSegment::TxSenders => {
                 self.get_segment_provider_from_transaction(segment, start, None)
             }
         };
@@ -359,6 +375,71 @@ impl SnapshotProvider {
 
         Ok(result)
     }
+
+    /// Returns the storage changeset of the given block, ordered by address and storage key.
+    pub fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<StorageBeforeTx>> {
+        Ok(self
+            .get_segment_provider_from_block(
+                SnapshotSegment::StorageChangeSets,
+                block_number,
+                None,
+            )?
+            .cursor()?
+            .get_one::<StorageChangeSetMask<StoredStorageChangeSet>>(block_number.into())?
+            .map(|changeset| changeset.changes)
+            .unwrap_or_default())
+    }
+
+    /// Returns the storage slot of the account from before the given block, if the slot changed
+    /// in this block.
+    pub fn storage_changeset_entry(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageEntry>> {
+        let changes = self.storage_block_changeset(block_number)?;
+        Ok(changes
+            .binary_search_by(|entry| (entry.address, entry.key).cmp(&(address, storage_key)))
+            .ok()
+            .map(|index| StorageEntry::new(changes[index].key, changes[index].value)))
+    }
+
+    /// Returns the bytecode with the given hash.
+    ///
+    /// Every bytecode snapshot holds all bytecodes known at the time it was taken and replaces the
+    /// previous ones, so only the highest one is queried. The index is rebuilt first if the
+    /// snapshotter has taken a newer one.
+    pub fn bytecode_by_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
+        let segment = SnapshotSegment::Bytecodes;
+        let tracked = self
+            .highest_tracker
+            .as_ref()
+            .and_then(|tracker| tracker.borrow().and_then(|highest| highest.bytecodes));
+        if tracked > self.get_highest_snapshot_block(segment) {
+            self.update_index()?;
+        }
+
+        let Some((block_end, tx_range)) = self
+            .snapshots_block_index
+            .read()
+            .get(&segment)
+            .and_then(|index| index.last_key_value())
+            .map(|(block_end, tx_range)| (*block_end, tx_range.clone()))
+        else {
+            return Ok(None)
+        };
+
+        Ok(self
+            .get_or_create_jar_provider(segment, &(0..=block_end), &tx_range)?
+            .cursor()?
+            .get_two::<BytecodeMask<Bytecode, B256>>((&code_hash).into())?
+            .filter(|(_, hash)| hash == &code_hash)
+            .map(|(bytecode, _)| bytecode))
+    }
 }
 
 impl HeaderProvider for SnapshotProvider {
@@ -566,9 +647,12 @@ impl TransactionsProvider for SnapshotProvider {
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Address>> {
-        let txes = self.transactions_by_tx_range(range)?;
-        TransactionSignedNoHash::recover_signers(&txes, txes.len())
-            .ok_or(ProviderError::SenderRecoveryError)
+        self.fetch_range(
+            SnapshotSegment::TxSenders,
+            to_range(range),
+            |cursor, number| cursor.get_one::<TxSenderMask<Address>>(number.into()),
+            |_| true,
+        )
     }
 
     fn transactions_by_tx_range(
@@ -586,7 +670,55 @@ impl TransactionsProvider for SnapshotProvider {
     }
 
     fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>> {
-        Ok(self.transaction_by_id_no_hash(id)?.and_then(|tx| tx.recover_signer()))
+        self.get_segment_provider_from_transaction(SnapshotSegment::TxSenders, id, None)?
+            .cursor()?
+            .get_one::<TxSenderMask<Address>>(id.into())
+    }
+}
+
+impl ChangeSetReader for SnapshotProvider {
+    fn account_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<AccountBeforeTx>> {
+        Ok(self
+            .get_segment_provider_from_block(
+                SnapshotSegment::AccountChangeSets,
+                block_number,
+                None,
+            )?
+            .cursor()?
+            .get_one::<AccountChangeSetMask<StoredAccountChangeSet>>(block_number.into())?
+            .map(|changeset| changeset.changes)
+            .unwrap_or_default())
+    }
+
+    fn account_changeset_entry(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+    ) -> ProviderResult<Option<AccountBeforeTx>> {
+        let mut changes = self.account_block_changeset(block_number)?;
+        Ok(changes
+            .binary_search_by(|account| account.address.cmp(&address))
+            .ok()
+            .map(|index| changes.swap_remove(index)))
+    }
+
+    fn modified_accounts_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        let mut addresses = BTreeSet::new();
+        for block_number in range {
+            addresses.extend(
+                self.account_block_changeset(block_number)?.into_iter().map(|acc| acc.address),
+            );
+            addresses.extend(
+                self.storage_block_changeset(block_number)?.into_iter().map(|slot| slot.address),
+            );
+        }
+        Ok(addresses)
     }
 }
 
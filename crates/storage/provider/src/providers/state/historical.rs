@@ -1,10 +1,14 @@
 use crate::{
-    providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
-    BundleStateWithReceipts, ProviderError, StateProvider, StateRootProvider,
+    providers::{
+        state::{self, macros::delegate_provider_impls, range, SnapSynced},
+        SnapshotProvider,
+    },
+    AccountReader, BlockHashReader, BundleStateWithReceipts, ChangeSetReader, HashedStateEntry,
+    ProviderError, StateProvider, StateRootProvider,
 };
 use reth_db::{
     cursor::{DbCursorRO, DbDupCursorRO},
-    models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+    models::{storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey},
     table::Table,
     tables,
     transaction::DbTx,
@@ -12,10 +16,17 @@ use reth_db::{
 };
 use reth_interfaces::provider::ProviderResult;
 use reth_primitives::{
-    constants::EPOCH_SLOTS, trie::AccountProof, Account, Address, BlockNumber, Bytecode,
-    StorageKey, StorageValue, B256,
+    constants::EPOCH_SLOTS,
+    keccak256,
+    trie::{AccountProof, ExecutionWitness},
+    Account, Address, BlockNumber, Bytecode, RetainedHistoryCheckpoint, SnapshotSegment,
+    StorageEntry, StorageKey, StorageValue, B256,
+};
+use reth_trie::{updates::TrieUpdates, HashedPostState, HashedStorage};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
 };
-use reth_trie::{updates::TrieUpdates, HashedPostState};
 
 /// State provider for a given block number which takes a tx reference.
 ///
@@ -28,6 +39,13 @@ use reth_trie::{updates::TrieUpdates, HashedPostState};
 /// - [tables::StorageHistory]
 /// - [tables::AccountChangeSet]
 /// - [tables::StorageChangeSet]
+///
+/// If a [SnapshotProvider] is set, changesets of snapshotted blocks are read from the snapshots
+/// and bytecodes missing from the database are looked up in them.
+///
+/// The account and storage history of the addresses retained by the history address filter of the
+/// pruner is available from the blocks saved in [tables::PruneRetainedAddresses], even if the
+/// history of other addresses is pruned.
 #[derive(Debug)]
 pub struct HistoricalStateProviderRef<'b, TX: DbTx> {
     /// Transaction
@@ -36,6 +54,10 @@ pub struct HistoricalStateProviderRef<'b, TX: DbTx> {
     block_number: BlockNumber,
     /// Lowest blocks at which different parts of the state are available.
     lowest_available_blocks: LowestAvailableBlocks,
+    /// Snapshot provider
+    snapshot_provider: Option<&'b SnapshotProvider>,
+    /// Whether the database was snap synced
+    snap_synced: SnapSynced,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -49,7 +71,13 @@ pub enum HistoryInfo {
 impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
     /// Create new StateProvider for historical block number
     pub fn new(tx: &'b TX, block_number: BlockNumber) -> Self {
-        Self { tx, block_number, lowest_available_blocks: Default::default() }
+        Self {
+            tx,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            snapshot_provider: None,
+            snap_synced: Default::default(),
+        }
     }
 
     /// Create new StateProvider for historical block number and lowest block numbers at which
@@ -59,21 +87,96 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
         block_number: BlockNumber,
         lowest_available_blocks: LowestAvailableBlocks,
     ) -> Self {
-        Self { tx, block_number, lowest_available_blocks }
+        Self {
+            tx,
+            block_number,
+            lowest_available_blocks,
+            snapshot_provider: None,
+            snap_synced: Default::default(),
+        }
+    }
+
+    /// Sets the snapshot provider to read snapshotted changesets and bytecodes from.
+    pub fn with_snapshot_provider(mut self, snapshot_provider: &'b SnapshotProvider) -> Self {
+        self.snapshot_provider = Some(snapshot_provider);
+        self
+    }
+
+    /// Returns the error for the state of the address at the provider block being pruned.
+    fn history_pruned_error(&self, address: Address) -> ProviderResult<ProviderError> {
+        Ok(if self.tx.entries::<tables::PruneRetainedAddresses>()? > 0 {
+            ProviderError::AddressHistoryAtBlockPruned { address, block_number: self.block_number }
+        } else {
+            ProviderError::StateAtBlockPruned(self.block_number)
+        })
+    }
+
+    /// Returns the lowest block number at which the history of the address is available, or an
+    /// error if it's pruned at the provider block.
+    ///
+    /// If the history of the address is retained by the pruner, it's available from the block it
+    /// was retained from.
+    fn lowest_available_history_block_number(
+        &self,
+        address: Address,
+        lowest_available_block_number: Option<BlockNumber>,
+        retained_from: impl FnOnce(RetainedHistoryCheckpoint) -> Option<BlockNumber>,
+    ) -> ProviderResult<Option<BlockNumber>> {
+        let Some(mut lowest_available_block_number) = lowest_available_block_number else {
+            return Ok(None)
+        };
+        if let Some(retained_from) =
+            self.tx.get::<tables::PruneRetainedAddresses>(address)?.and_then(retained_from)
+        {
+            if retained_from == 0 {
+                return Ok(None)
+            }
+            lowest_available_block_number = lowest_available_block_number.min(retained_from);
+        }
+
+        if lowest_available_block_number > self.block_number {
+            return Err(self.history_pruned_error(address)?)
+        }
+        Ok(Some(lowest_available_block_number))
+    }
+
+    /// Returns the lowest block number at which the account history of the address is available,
+    /// or an error if it's pruned at the provider block.
+    fn lowest_available_account_history_block_number(
+        &self,
+        address: Address,
+    ) -> ProviderResult<Option<BlockNumber>> {
+        self.lowest_available_history_block_number(
+            address,
+            self.lowest_available_blocks.account_history_block_number,
+            |checkpoint| checkpoint.account_history_from,
+        )
+    }
+
+    /// Returns the lowest block number at which the storage history of the address is available,
+    /// or an error if it's pruned at the provider block.
+    fn lowest_available_storage_history_block_number(
+        &self,
+        address: Address,
+    ) -> ProviderResult<Option<BlockNumber>> {
+        self.lowest_available_history_block_number(
+            address,
+            self.lowest_available_blocks.storage_history_block_number,
+            |checkpoint| checkpoint.storage_history_from,
+        )
     }
 
     /// Lookup an account in the AccountHistory table
     pub fn account_history_lookup(&self, address: Address) -> ProviderResult<HistoryInfo> {
-        if !self.lowest_available_blocks.is_account_history_available(self.block_number) {
-            return Err(ProviderError::StateAtBlockPruned(self.block_number))
-        }
+        let lowest_available_block_number =
+            self.lowest_available_account_history_block_number(address)?;
 
         // history key to search IntegerList of block number changesets.
         let history_key = ShardedKey::new(address, self.block_number);
         self.history_info::<tables::AccountHistory, _>(
             history_key,
             |key| key.key == address,
-            self.lowest_available_blocks.account_history_block_number,
+            lowest_available_block_number,
         )
     }
 
@@ -83,16 +186,15 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
         address: Address,
         storage_key: StorageKey,
     ) -> ProviderResult<HistoryInfo> {
-        if !self.lowest_available_blocks.is_storage_history_available(self.block_number) {
-            return Err(ProviderError::StateAtBlockPruned(self.block_number))
-        }
+        let lowest_available_block_number =
+            self.lowest_available_storage_history_block_number(address)?;
 
         // history key to search IntegerList of block number changesets.
         let history_key = StorageShardedKey::new(address, storage_key, self.block_number);
         self.history_info::<tables::StorageHistory, _>(
             history_key,
             |key| key.address == address && key.sharded_key.key == storage_key,
-            self.lowest_available_blocks.storage_history_block_number,
+            lowest_available_block_number,
         )
     }
 
@@ -121,6 +223,125 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
         Ok(HashedPostState::from_revert_range(self.tx, self.block_number..=tip)?)
     }
 
+    /// Retrieve the hashed addresses of the accounts changed by this and all subsequent blocks,
+    /// from the given hashed address on.
+    ///
+    /// The addresses are read from the account history index, so the walk is bounded by the
+    /// number of indexed accounts instead of the number of changes since the block.
+    fn changed_accounts(&self, start: B256) -> ProviderResult<BTreeMap<B256, Address>> {
+        if !self.lowest_available_blocks.is_account_history_available(self.block_number) {
+            return Err(ProviderError::StateAtBlockPruned(self.block_number))
+        }
+
+        let mut accounts = BTreeMap::default();
+        for entry in self.tx.cursor_read::<tables::AccountHistory>()?.walk(None)? {
+            let (key, chunk) = entry?;
+            // the highest block of the shard is its last entry
+            let chunk = chunk.0;
+            if chunk
+                .len()
+                .checked_sub(1)
+                .is_some_and(|last| chunk.select(last) as u64 >= self.block_number)
+            {
+                let hashed_address = keccak256(key.key);
+                if hashed_address >= start {
+                    accounts.insert(hashed_address, key.key);
+                }
+            }
+        }
+        Ok(accounts)
+    }
+
+    /// Retrieve the hashed storage keys of the account changed by this and all subsequent
+    /// blocks.
+    ///
+    /// The keys are read from the storage history index of the account, so only the history of
+    /// this account is walked.
+    fn changed_storage_keys(&self, address: Address) -> ProviderResult<BTreeMap<B256, StorageKey>> {
+        self.lowest_available_storage_history_block_number(address)?;
+
+        let mut keys = BTreeMap::default();
+        let mut cursor = self.tx.cursor_read::<tables::StorageHistory>()?;
+        for entry in cursor.walk(Some(StorageShardedKey::new(address, B256::ZERO, 0)))? {
+            let (key, chunk) = entry?;
+            if key.address != address {
+                break
+            }
+            // the highest block of the shard is its last entry
+            let chunk = chunk.0;
+            if chunk
+                .len()
+                .checked_sub(1)
+                .is_some_and(|last| chunk.select(last) as u64 >= self.block_number)
+            {
+                keys.insert(keccak256(key.sharded_key.key), key.sharded_key.key);
+            }
+        }
+        Ok(keys)
+    }
+
+    /// Retrieve the hashed storage reverts of the account.
+    fn hashed_storage_reverts(&self, address: Address) -> ProviderResult<HashedPostState> {
+        let mut storage = HashedStorage::new(false);
+        for (hashed_key, key) in self.changed_storage_keys(address)? {
+            storage.storage.insert(hashed_key, self.storage(address, key)?.unwrap_or_default());
+        }
+        Ok(HashedPostState::default().with_storages([(keccak256(address), storage)]))
+    }
+
+    /// Returns the snapshot provider if the given block of the segment is snapshotted.
+    fn snapshot_provider_at(
+        &self,
+        segment: SnapshotSegment,
+        block_number: BlockNumber,
+    ) -> Option<&'b SnapshotProvider> {
+        self.snapshot_provider.filter(|provider| {
+            provider
+                .get_highest_snapshot_block(segment)
+                .is_some_and(|highest| highest >= block_number)
+        })
+    }
+
+    /// Returns the account state from before the given block, if the account changed in this
+    /// block.
+    fn account_changeset_entry(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+    ) -> ProviderResult<Option<AccountBeforeTx>> {
+        if let Some(provider) =
+            self.snapshot_provider_at(SnapshotSegment::AccountChangeSets, block_number)
+        {
+            return provider.account_changeset_entry(block_number, address)
+        }
+
+        Ok(self
+            .tx
+            .cursor_dup_read::<tables::AccountChangeSet>()?
+            .seek_by_key_subkey(block_number, address)?
+            .filter(|acc| acc.address == address))
+    }
+
+    /// Returns the storage slot from before the given block, if the slot changed in this block.
+    fn storage_changeset_entry(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageEntry>> {
+        if let Some(provider) =
+            self.snapshot_provider_at(SnapshotSegment::StorageChangeSets, block_number)
+        {
+            return provider.storage_changeset_entry(block_number, address, storage_key)
+        }
+
+        Ok(self
+            .tx
+            .cursor_dup_read::<tables::StorageChangeSet>()?
+            .seek_by_key_subkey((block_number, address).into(), storage_key)?
+            .filter(|entry| entry.key == storage_key))
+    }
+
     fn history_info<T, K>(
         &self,
         key: K,
@@ -184,17 +405,14 @@ impl<'b, TX: DbTx> AccountReader for HistoricalStateProviderRef<'b, TX> {
         match self.account_history_lookup(address)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(self
-                .tx
-                .cursor_dup_read::<tables::AccountChangeSet>()?
-                .seek_by_key_subkey(changeset_block_number, address)?
-                .filter(|acc| acc.address == address)
+                .account_changeset_entry(changeset_block_number, address)?
                 .ok_or(ProviderError::AccountChangesetNotFound {
                     block_number: changeset_block_number,
                     address,
                 })?
                 .info),
             HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => {
-                Ok(self.tx.get::<tables::PlainAccountState>(address)?)
+                Ok(state::plain_account(self.tx, &self.snap_synced, address)?)
             }
         }
     }
@@ -241,6 +459,23 @@ impl<'b, TX: DbTx> StateRootProvider for HistoricalStateProviderRef<'b, TX> {
             .state_root_with_updates(self.tx)
             .map_err(|err| ProviderError::Database(err.into()))
     }
+
+    fn hashed_state_root(&self, hashed_state: &HashedPostState) -> ProviderResult<B256> {
+        let mut revert_state = self.revert_state()?;
+        revert_state.extend(hashed_state.clone());
+        revert_state.state_root(self.tx).map_err(|err| ProviderError::Database(err.into()))
+    }
+
+    fn hashed_state_roots(&self, hashed_states: &[HashedPostState]) -> ProviderResult<Vec<B256>> {
+        let mut state = self.revert_state()?;
+        hashed_states
+            .iter()
+            .map(|hashed_state| {
+                state.extend(hashed_state.clone());
+                state.state_root(self.tx).map_err(|err| ProviderError::Database(err.into()))
+            })
+            .collect()
+    }
 }
 
 impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
@@ -253,10 +488,7 @@ impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
         match self.storage_history_lookup(address, storage_key)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(Some(
-                self.tx
-                    .cursor_dup_read::<tables::StorageChangeSet>()?
-                    .seek_by_key_subkey((changeset_block_number, address).into(), storage_key)?
-                    .filter(|entry| entry.key == storage_key)
+                self.storage_changeset_entry(changeset_block_number, address, storage_key)?
                     .ok_or_else(|| ProviderError::StorageChangesetNotFound {
                         block_number: changeset_block_number,
                         address,
@@ -264,24 +496,67 @@ impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
                     })?
                     .value,
             )),
-            HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => Ok(self
-                .tx
-                .cursor_dup_read::<tables::PlainStorageState>()?
-                .seek_by_key_subkey(address, storage_key)?
-                .filter(|entry| entry.key == storage_key)
-                .map(|entry| entry.value)
-                .or(Some(StorageValue::ZERO))),
+            HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => Ok(Some(
+                state::plain_storage(self.tx, &self.snap_synced, address, storage_key)?
+                    .unwrap_or(StorageValue::ZERO),
+            )),
         }
     }
 
     /// Get account code by its hash
     fn bytecode_by_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
-        self.tx.get::<tables::Bytecodes>(code_hash).map_err(Into::into)
+        if let Some(bytecode) = self.tx.get::<tables::Bytecodes>(code_hash)? {
+            return Ok(Some(bytecode))
+        }
+
+        match self.snapshot_provider {
+            Some(provider) => provider.bytecode_by_hash(code_hash),
+            None => Ok(None),
+        }
     }
 
     /// Get account and storage proofs.
-    fn proof(&self, _address: Address, _keys: &[B256]) -> ProviderResult<AccountProof> {
-        Err(ProviderError::StateRootNotAvailableForHistoricalBlock)
+    fn proof(&self, address: Address, keys: &[B256]) -> ProviderResult<AccountProof> {
+        self.revert_state()?
+            .account_proof(self.tx, address, keys)
+            .map_err(|err| ProviderError::Database(err.into()))
+    }
+
+    fn witness(
+        &self,
+        targets: &HashMap<Address, Vec<B256>>,
+        changes: &HashedPostState,
+    ) -> ProviderResult<ExecutionWitness> {
+        self.revert_state()?
+            .witness(self.tx, targets, changes)
+            .map_err(|err| ProviderError::Database(err.into()))
+    }
+
+    fn storage_root(&self, address: Address, changes: HashedStorage) -> ProviderResult<B256> {
+        let mut state = self.hashed_storage_reverts(address)?;
+        state.extend(HashedPostState::default().with_storages([(keccak256(address), changes)]));
+        range::storage_root(self.tx, state, address)
+    }
+
+    fn account_range(
+        &self,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<HashedStateEntry<Address, Account>>> {
+        let changed = self.changed_accounts(start)?;
+        range::account_range(self.tx, start, limit, &changed, |address| self.basic_account(address))
+    }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<HashedStateEntry<StorageKey, StorageValue>>> {
+        let changed = self.changed_storage_keys(address)?;
+        range::storage_range(self.tx, address, start, limit, &changed, |key| {
+            self.storage(address, key)
+        })
     }
 }
 
@@ -295,12 +570,28 @@ pub struct HistoricalStateProvider<TX: DbTx> {
     block_number: BlockNumber,
     /// Lowest blocks at which different parts of the state are available.
     lowest_available_blocks: LowestAvailableBlocks,
+    /// Snapshot provider
+    snapshot_provider: Option<Arc<SnapshotProvider>>,
+    /// Whether the database was snap synced
+    snap_synced: SnapSynced,
 }
 
 impl<TX: DbTx> HistoricalStateProvider<TX> {
     /// Create new StateProvider for historical block number
     pub fn new(tx: TX, block_number: BlockNumber) -> Self {
-        Self { tx, block_number, lowest_available_blocks: Default::default() }
+        Self {
+            tx,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            snapshot_provider: None,
+            snap_synced: Default::default(),
+        }
+    }
+
+    /// Sets the snapshot provider to read snapshotted changesets and bytecodes from.
+    pub fn with_snapshot_provider(mut self, snapshot_provider: Arc<SnapshotProvider>) -> Self {
+        self.snapshot_provider = Some(snapshot_provider);
+        self
     }
 
     /// Set the lowest block number at which the account history is available.
@@ -324,11 +615,16 @@ impl<TX: DbTx> HistoricalStateProvider<TX> {
     /// Returns a new provider that takes the `TX` as reference
     #[inline(always)]
     fn as_ref(&self) -> HistoricalStateProviderRef<'_, TX> {
-        HistoricalStateProviderRef::new_with_lowest_available_blocks(
+        let mut provider = HistoricalStateProviderRef::new_with_lowest_available_blocks(
             &self.tx,
             self.block_number,
             self.lowest_available_blocks,
-        )
+        );
+        provider.snap_synced = self.snap_synced.clone();
+        if let Some(snapshot_provider) = &self.snapshot_provider {
+            provider = provider.with_snapshot_provider(snapshot_provider);
+        }
+        provider
     }
 }
 
@@ -367,7 +663,8 @@ impl LowestAvailableBlocks {
 mod tests {
     use crate::{
         providers::state::historical::{HistoryInfo, LowestAvailableBlocks},
-        AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, StateProvider,
+        AccountReader, HashedStateEntry, HistoricalStateProvider, HistoricalStateProviderRef,
+        StateProvider,
     };
     use reth_db::{
         database::Database,
@@ -378,7 +675,11 @@ mod tests {
         BlockNumberList,
     };
     use reth_interfaces::provider::ProviderError;
-    use reth_primitives::{address, b256, Account, Address, StorageEntry, B256, U256};
+    use reth_primitives::{
+        address, b256, keccak256, Account, Address, RetainedHistoryCheckpoint, StorageEntry, B256,
+        U256,
+    };
+    use reth_trie::{test_utils::storage_root, HashedStorage};
 
     const ADDRESS: Address = address!("0000000000000000000000000000000000000001");
     const HIGHER_ADDRESS: Address = address!("0000000000000000000000000000000000000005");
@@ -589,6 +890,149 @@ mod tests {
         );
     }
 
+    #[test]
+    fn history_provider_storage_range() {
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().unwrap();
+
+        let other_storage = B256::with_last_byte(2);
+        let entry_plain = StorageEntry { key: STORAGE, value: U256::from(100) };
+        let other_entry_plain = StorageEntry { key: other_storage, value: U256::from(200) };
+        let entry_at5 = StorageEntry { key: STORAGE, value: U256::from(5) };
+        let other_entry_at5 = StorageEntry { key: other_storage, value: U256::ZERO };
+
+        // setup
+        tx.put::<tables::CanonicalHeaders>(5, B256::random()).unwrap();
+        tx.put::<tables::StorageChangeSet>((5, ADDRESS).into(), entry_at5).unwrap();
+        tx.put::<tables::StorageChangeSet>((5, ADDRESS).into(), other_entry_at5).unwrap();
+        for key in [STORAGE, other_storage] {
+            tx.put::<tables::StorageHistory>(
+                StorageShardedKey::new(ADDRESS, key, u64::MAX),
+                BlockNumberList::new([5]).unwrap(),
+            )
+            .unwrap();
+        }
+
+        // setup plain and hashed state
+        for entry in [entry_plain, other_entry_plain] {
+            tx.put::<tables::PlainStorageState>(ADDRESS, entry).unwrap();
+            tx.put::<tables::HashedStorage>(
+                keccak256(ADDRESS),
+                StorageEntry { key: keccak256(entry.key), value: entry.value },
+            )
+            .unwrap();
+        }
+        tx.commit().unwrap();
+
+        let tx = db.tx().unwrap();
+
+        let mut expected = [entry_plain, other_entry_plain]
+            .map(|entry| HashedStateEntry {
+                hashed_key: keccak256(entry.key),
+                key: Some(entry.key),
+                value: entry.value,
+            })
+            .to_vec();
+        expected.sort_by_key(|entry| entry.hashed_key);
+
+        // run
+        assert_eq!(
+            HistoricalStateProviderRef::new(&tx, 5).storage_range(ADDRESS, B256::ZERO, 10),
+            Ok(vec![HashedStateEntry {
+                hashed_key: keccak256(STORAGE),
+                key: Some(STORAGE),
+                value: entry_at5.value,
+            }])
+        );
+        assert_eq!(
+            HistoricalStateProviderRef::new(&tx, 6).storage_range(ADDRESS, B256::ZERO, 10),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            HistoricalStateProviderRef::new(&tx, 6).storage_range(ADDRESS, B256::ZERO, 1),
+            Ok(expected[..1].to_vec())
+        );
+        assert_eq!(
+            HistoricalStateProviderRef::new(&tx, 6).storage_range(
+                ADDRESS,
+                expected[1].hashed_key,
+                10
+            ),
+            Ok(expected[1..].to_vec())
+        );
+        assert_eq!(
+            HistoricalStateProviderRef::new(&tx, 6).storage_range(HIGHER_ADDRESS, B256::ZERO, 10),
+            Ok(vec![])
+        );
+
+        // The changes are applied on top of the historical storage
+        let changes = HashedStorage::from_iter(false, [(keccak256(other_storage), U256::from(7))]);
+        assert_eq!(
+            HistoricalStateProviderRef::new(&tx, 5).storage_root(ADDRESS, changes),
+            Ok(storage_root(
+                [(STORAGE, entry_at5.value), (other_storage, U256::from(7))].into_iter()
+            ))
+        );
+    }
+
+    #[test]
+    fn history_provider_account_range() {
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().unwrap();
+
+        let acc_plain = Account { nonce: 100, balance: U256::ZERO, bytecode_hash: None };
+        let acc_at3 = Account { nonce: 3, balance: U256::ZERO, bytecode_hash: None };
+        let higher_acc_plain = Account { nonce: 4, balance: U256::ZERO, bytecode_hash: None };
+
+        // setup
+        tx.put::<tables::AccountHistory>(
+            ShardedKey { key: ADDRESS, highest_block_number: u64::MAX },
+            BlockNumberList::new([3]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::AccountChangeSet>(
+            3,
+            AccountBeforeTx { address: ADDRESS, info: Some(acc_at3) },
+        )
+        .unwrap();
+
+        // setup plain and hashed state
+        for (address, account) in [(ADDRESS, acc_plain), (HIGHER_ADDRESS, higher_acc_plain)] {
+            tx.put::<tables::PlainAccountState>(address, account).unwrap();
+            tx.put::<tables::HashedAccount>(keccak256(address), account).unwrap();
+        }
+        tx.commit().unwrap();
+
+        let tx = db.tx().unwrap();
+
+        let entries = |accounts: [(Address, Account); 2]| {
+            let mut entries = accounts
+                .map(|(address, account)| HashedStateEntry {
+                    hashed_key: keccak256(address),
+                    key: Some(address),
+                    value: account,
+                })
+                .to_vec();
+            entries.sort_by_key(|entry| entry.hashed_key);
+            entries
+        };
+
+        // run
+        assert_eq!(
+            HistoricalStateProviderRef::new(&tx, 3).account_range(B256::ZERO, 10),
+            Ok(entries([(ADDRESS, acc_at3), (HIGHER_ADDRESS, higher_acc_plain)]))
+        );
+        let expected = entries([(ADDRESS, acc_plain), (HIGHER_ADDRESS, higher_acc_plain)]);
+        assert_eq!(
+            HistoricalStateProviderRef::new(&tx, 4).account_range(B256::ZERO, 10),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            HistoricalStateProviderRef::new(&tx, 4).account_range(expected[1].hashed_key, 10),
+            Ok(expected[1..].to_vec())
+        );
+    }
+
     #[test]
     fn history_provider_unavailable() {
         let db = create_test_rw_db();
@@ -645,4 +1089,80 @@ mod tests {
             Ok(HistoryInfo::MaybeInPlainState)
         );
     }
+
+    #[test]
+    fn history_provider_unavailable_with_address_filter() {
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().unwrap();
+
+        // the history of `ADDRESS` is retained from genesis, the history of `HIGHER_ADDRESS` was
+        // only retained after block 2 was pruned
+        tx.put::<tables::PruneRetainedAddresses>(
+            ADDRESS,
+            RetainedHistoryCheckpoint {
+                account_history_from: Some(0),
+                storage_history_from: Some(0),
+            },
+        )
+        .unwrap();
+        tx.put::<tables::PruneRetainedAddresses>(
+            HIGHER_ADDRESS,
+            RetainedHistoryCheckpoint {
+                account_history_from: Some(3),
+                storage_history_from: Some(3),
+            },
+        )
+        .unwrap();
+
+        // provider block_number < lowest available block number
+        let provider = HistoricalStateProviderRef::new_with_lowest_available_blocks(
+            &tx,
+            2,
+            LowestAvailableBlocks {
+                account_history_block_number: Some(4),
+                storage_history_block_number: Some(4),
+            },
+        );
+
+        assert_eq!(provider.account_history_lookup(ADDRESS), Ok(HistoryInfo::NotYetWritten));
+        assert_eq!(
+            provider.storage_history_lookup(ADDRESS, STORAGE),
+            Ok(HistoryInfo::NotYetWritten)
+        );
+
+        for address in [HIGHER_ADDRESS, Address::with_last_byte(6)] {
+            assert_eq!(
+                provider.account_history_lookup(address),
+                Err(ProviderError::AddressHistoryAtBlockPruned {
+                    address,
+                    block_number: provider.block_number
+                })
+            );
+            assert_eq!(
+                provider.storage_history_lookup(address, STORAGE),
+                Err(ProviderError::AddressHistoryAtBlockPruned {
+                    address,
+                    block_number: provider.block_number
+                })
+            );
+        }
+
+        // the history of `HIGHER_ADDRESS` is available from the block it was retained from
+        let provider = HistoricalStateProviderRef::new_with_lowest_available_blocks(
+            &tx,
+            3,
+            LowestAvailableBlocks {
+                account_history_block_number: Some(4),
+                storage_history_block_number: Some(4),
+            },
+        );
+        assert_eq!(
+            provider.account_history_lookup(HIGHER_ADDRESS),
+            Ok(HistoryInfo::MaybeInPlainState)
+        );
+        assert_eq!(
+            provider.storage_history_lookup(HIGHER_ADDRESS, STORAGE),
+            Ok(HistoryInfo::MaybeInPlainState)
+        );
+    }
 }
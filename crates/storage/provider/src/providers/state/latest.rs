@@ -1,36 +1,38 @@
 use crate::{
-    providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
-    BundleStateWithReceipts, StateProvider, StateRootProvider,
-};
-use reth_db::{
-    cursor::{DbCursorRO, DbDupCursorRO},
-    tables,
-    transaction::DbTx,
+    providers::state::{self, macros::delegate_provider_impls, range, SnapSynced},
+    AccountReader, BlockHashReader, BundleStateWithReceipts, HashedStateEntry, StateProvider,
+    StateRootProvider,
 };
+use reth_db::{cursor::DbCursorRO, tables, transaction::DbTx};
 use reth_interfaces::provider::{ProviderError, ProviderResult};
 use reth_primitives::{
-    trie::AccountProof, Account, Address, BlockNumber, Bytecode, StorageKey, StorageValue, B256,
+    keccak256,
+    trie::{AccountProof, ExecutionWitness},
+    Account, Address, BlockNumber, Bytecode, StorageKey, StorageValue, B256,
 };
-use reth_trie::{proof::Proof, updates::TrieUpdates};
+use reth_trie::{proof::Proof, updates::TrieUpdates, HashedPostState, HashedStorage};
+use std::collections::{BTreeMap, HashMap};
 
 /// State provider over latest state that takes tx reference.
 #[derive(Debug)]
 pub struct LatestStateProviderRef<'b, TX: DbTx> {
     /// database transaction
     db: &'b TX,
+    /// Whether the database was snap synced
+    snap_synced: SnapSynced,
 }
 
 impl<'b, TX: DbTx> LatestStateProviderRef<'b, TX> {
     /// Create new state provider
     pub fn new(db: &'b TX) -> Self {
-        Self { db }
+        Self { db, snap_synced: Default::default() }
     }
 }
 
 impl<'b, TX: DbTx> AccountReader for LatestStateProviderRef<'b, TX> {
     /// Get basic account information.
     fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
-        self.db.get::<tables::PlainAccountState>(address).map_err(Into::into)
+        Ok(state::plain_account(self.db, &self.snap_synced, address)?)
     }
 }
 
@@ -75,6 +77,10 @@ impl<'b, TX: DbTx> StateRootProvider for LatestStateProviderRef<'b, TX> {
             .state_root_with_updates(self.db)
             .map_err(|err| ProviderError::Database(err.into()))
     }
+
+    fn hashed_state_root(&self, hashed_state: &HashedPostState) -> ProviderResult<B256> {
+        hashed_state.state_root(self.db).map_err(|err| ProviderError::Database(err.into()))
+    }
 }
 
 impl<'b, TX: DbTx> StateProvider for LatestStateProviderRef<'b, TX> {
@@ -84,13 +90,7 @@ impl<'b, TX: DbTx> StateProvider for LatestStateProviderRef<'b, TX> {
         account: Address,
         storage_key: StorageKey,
     ) -> ProviderResult<Option<StorageValue>> {
-        let mut cursor = self.db.cursor_dup_read::<tables::PlainStorageState>()?;
-        if let Some(entry) = cursor.seek_by_key_subkey(account, storage_key)? {
-            if entry.key == storage_key {
-                return Ok(Some(entry.value))
-            }
-        }
-        Ok(None)
+        Ok(state::plain_storage(self.db, &self.snap_synced, account, storage_key)?)
     }
 
     /// Get account code by its hash
@@ -103,6 +103,38 @@ impl<'b, TX: DbTx> StateProvider for LatestStateProviderRef<'b, TX> {
             .account_proof(address, slots)
             .map_err(Into::<reth_db::DatabaseError>::into)?)
     }
+
+    fn witness(
+        &self,
+        targets: &HashMap<Address, Vec<B256>>,
+        changes: &HashedPostState,
+    ) -> ProviderResult<ExecutionWitness> {
+        Ok(Proof::new(self.db)
+            .witness(targets, changes)
+            .map_err(Into::<reth_db::DatabaseError>::into)?)
+    }
+
+    fn storage_root(&self, address: Address, changes: HashedStorage) -> ProviderResult<B256> {
+        let state = HashedPostState::default().with_storages([(keccak256(address), changes)]);
+        range::storage_root(self.db, state, address)
+    }
+
+    fn account_range(
+        &self,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<HashedStateEntry<Address, Account>>> {
+        range::account_range(self.db, start, limit, &BTreeMap::new(), |_| Ok(None))
+    }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<HashedStateEntry<StorageKey, StorageValue>>> {
+        range::storage_range(self.db, address, start, limit, &BTreeMap::new(), |_| Ok(None))
+    }
 }
 
 /// State provider for the latest state.
@@ -110,18 +142,20 @@ impl<'b, TX: DbTx> StateProvider for LatestStateProviderRef<'b, TX> {
 pub struct LatestStateProvider<TX: DbTx> {
     /// database transaction
     db: TX,
+    /// Whether the database was snap synced
+    snap_synced: SnapSynced,
 }
 
 impl<TX: DbTx> LatestStateProvider<TX> {
     /// Create new state provider
     pub fn new(db: TX) -> Self {
-        Self { db }
+        Self { db, snap_synced: Default::default() }
     }
 
     /// Returns a new provider that takes the `TX` as reference
     #[inline(always)]
     fn as_ref(&self) -> LatestStateProviderRef<'_, TX> {
-        LatestStateProviderRef::new(&self.db)
+        LatestStateProviderRef { db: &self.db, snap_synced: self.snap_synced.clone() }
     }
 }
 
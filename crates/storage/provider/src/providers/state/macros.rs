@@ -33,6 +33,8 @@ macro_rules! delegate_provider_impls {
             StateRootProvider $(where [$($generics)*])? {
                 fn state_root(&self, state: &crate::BundleStateWithReceipts) -> reth_interfaces::provider::ProviderResult<reth_primitives::B256>;
                 fn state_root_with_updates(&self, state: &crate::BundleStateWithReceipts) -> reth_interfaces::provider::ProviderResult<(reth_primitives::B256, reth_trie::updates::TrieUpdates)>;
+                fn hashed_state_root(&self, hashed_state: &reth_trie::HashedPostState) -> reth_interfaces::provider::ProviderResult<reth_primitives::B256>;
+                fn hashed_state_roots(&self, hashed_states: &[reth_trie::HashedPostState]) -> reth_interfaces::provider::ProviderResult<Vec<reth_primitives::B256>>;
             }
             AccountReader $(where [$($generics)*])? {
                 fn basic_account(&self, address: reth_primitives::Address) -> reth_interfaces::provider::ProviderResult<Option<reth_primitives::Account>>;
@@ -44,7 +46,11 @@ macro_rules! delegate_provider_impls {
             StateProvider $(where [$($generics)*])?{
                 fn storage(&self, account: reth_primitives::Address, storage_key: reth_primitives::StorageKey) -> reth_interfaces::provider::ProviderResult<Option<reth_primitives::StorageValue>>;
                 fn proof(&self, address: reth_primitives::Address, keys: &[reth_primitives::B256]) -> reth_interfaces::provider::ProviderResult<reth_primitives::trie::AccountProof>;
+                fn witness(&self, targets: &std::collections::HashMap<reth_primitives::Address, Vec<reth_primitives::B256>>, changes: &reth_trie::HashedPostState) -> reth_interfaces::provider::ProviderResult<reth_primitives::trie::ExecutionWitness>;
                 fn bytecode_by_hash(&self, code_hash: reth_primitives::B256) -> reth_interfaces::provider::ProviderResult<Option<reth_primitives::Bytecode>>;
+                fn storage_root(&self, address: reth_primitives::Address, changes: reth_trie::HashedStorage) -> reth_interfaces::provider::ProviderResult<reth_primitives::B256>;
+                fn account_range(&self, start: reth_primitives::B256, limit: usize) -> reth_interfaces::provider::ProviderResult<Vec<crate::HashedStateEntry<reth_primitives::Address, reth_primitives::Account>>>;
+                fn storage_range(&self, address: reth_primitives::Address, start: reth_primitives::B256, limit: usize) -> reth_interfaces::provider::ProviderResult<Vec<crate::HashedStateEntry<reth_primitives::StorageKey, reth_primitives::StorageValue>>>;
             }
         );
     }
@@ -2,3 +2,78 @@
 pub(crate) mod historical;
 pub(crate) mod latest;
 pub(crate) mod macros;
+pub(crate) mod range;
+
+use reth_db::{cursor::DbDupCursorRO, tables, transaction::DbTx, DatabaseError};
+use reth_primitives::{keccak256, stage::StageId, Account, Address, StorageKey, StorageValue};
+use std::sync::{Arc, OnceLock};
+
+/// Returns true if the state of the database was downloaded by snap sync.
+///
+/// The downloaded state is only available in the hashed state tables, as there are no preimages
+/// of its hashed keys. The plain state only contains the accounts and storage slots that changed
+/// since, the hashed state tables are kept up to date with every write to the plain state.
+pub(crate) fn is_snap_synced<TX: DbTx>(tx: &TX) -> Result<bool, DatabaseError> {
+    Ok(tx
+        .get::<tables::SyncStage>(StageId::SNAP_SYNC.to_string())?
+        .is_some_and(|checkpoint| checkpoint.block_number > 0))
+}
+
+/// Whether the database was snap synced, see [is_snap_synced].
+///
+/// It's looked up on first use and then shared by all providers over the same transaction.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SnapSynced(Arc<OnceLock<bool>>);
+
+impl SnapSynced {
+    /// Returns true if the state of the database was downloaded by snap sync.
+    pub(crate) fn get<TX: DbTx>(&self, tx: &TX) -> Result<bool, DatabaseError> {
+        if let Some(snap_synced) = self.0.get() {
+            return Ok(*snap_synced)
+        }
+        let snap_synced = is_snap_synced(tx)?;
+        Ok(*self.0.get_or_init(|| snap_synced))
+    }
+}
+
+/// Returns the account from the plain state, falling back to the hashed state on a snap synced
+/// database.
+pub(crate) fn plain_account<TX: DbTx>(
+    tx: &TX,
+    snap_synced: &SnapSynced,
+    address: Address,
+) -> Result<Option<Account>, DatabaseError> {
+    if let Some(account) = tx.get::<tables::PlainAccountState>(address)? {
+        return Ok(Some(account))
+    }
+    if snap_synced.get(tx)? {
+        return tx.get::<tables::HashedAccount>(keccak256(address))
+    }
+    Ok(None)
+}
+
+/// Returns the storage slot from the plain state, falling back to the hashed state on a snap
+/// synced database.
+pub(crate) fn plain_storage<TX: DbTx>(
+    tx: &TX,
+    snap_synced: &SnapSynced,
+    address: Address,
+    storage_key: StorageKey,
+) -> Result<Option<StorageValue>, DatabaseError> {
+    let entry = tx
+        .cursor_dup_read::<tables::PlainStorageState>()?
+        .seek_by_key_subkey(address, storage_key)?
+        .filter(|entry| entry.key == storage_key);
+    if let Some(entry) = entry {
+        return Ok(Some(entry.value))
+    }
+    if snap_synced.get(tx)? {
+        let hashed_slot = keccak256(storage_key);
+        return Ok(tx
+            .cursor_dup_read::<tables::HashedStorage>()?
+            .seek_by_key_subkey(keccak256(address), hashed_slot)?
+            .filter(|entry| entry.key == hashed_slot)
+            .map(|entry| entry.value))
+    }
+    Ok(None)
+}
@@ -0,0 +1,167 @@
+//! Helpers for iterating over ranges of the hashed state.
+
+use crate::HashedStateEntry;
+use reth_db::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    tables,
+    transaction::DbTx,
+};
+use reth_interfaces::provider::{ProviderError, ProviderResult};
+use reth_primitives::{keccak256, Account, Address, StorageEntry, StorageKey, StorageValue, B256};
+use reth_trie::{hashed_cursor::HashedPostStateCursorFactory, HashedPostState, StorageRoot};
+use std::collections::{BTreeMap, HashSet};
+
+/// Returns up to `limit` accounts of the hashed state, starting at the given hashed address.
+///
+/// The accounts of `changed`, keyed by their hashed address, replace the accounts of the hashed
+/// state. Their values are only looked up with `account` for the returned page. The addresses of
+/// the remaining accounts are looked up by hashing the addresses of the plain state.
+pub(crate) fn account_range<TX: DbTx>(
+    tx: &TX,
+    start: B256,
+    limit: usize,
+    changed: &BTreeMap<B256, Address>,
+    account: impl FnMut(Address) -> ProviderResult<Option<Account>>,
+) -> ProviderResult<Vec<HashedStateEntry<Address, Account>>> {
+    let mut cursor = tx.cursor_read::<tables::HashedAccount>()?;
+    let first = cursor.seek(start)?;
+    let mut page = merge_page(first, || Ok(cursor.next()?), start, limit, changed, account)?;
+
+    let mut unknown = unknown_keys(&page);
+    if !unknown.is_empty() {
+        let mut preimages = BTreeMap::new();
+        for entry in tx.cursor_read::<tables::PlainAccountState>()?.walk(None)? {
+            let (address, _) = entry?;
+            let hashed_address = keccak256(address);
+            if unknown.remove(&hashed_address) {
+                preimages.insert(hashed_address, address);
+                if unknown.is_empty() {
+                    break
+                }
+            }
+        }
+        set_keys(&mut page, &preimages);
+    }
+    Ok(page)
+}
+
+/// Returns up to `limit` storage slots of the given account in the hashed state, starting at the
+/// given hashed storage key.
+///
+/// The slots of `changed`, keyed by their hashed storage key, replace the slots of the hashed
+/// state. Their values are only looked up with `value` for the returned page. The keys of the
+/// remaining slots are looked up by hashing the storage keys of the account in the plain state.
+pub(crate) fn storage_range<TX: DbTx>(
+    tx: &TX,
+    address: Address,
+    start: B256,
+    limit: usize,
+    changed: &BTreeMap<B256, StorageKey>,
+    mut value: impl FnMut(StorageKey) -> ProviderResult<Option<StorageValue>>,
+) -> ProviderResult<Vec<HashedStateEntry<StorageKey, StorageValue>>> {
+    let hashed_address = keccak256(address);
+    let mut cursor = tx.cursor_dup_read::<tables::HashedStorage>()?;
+    let to_entry = |entry: Option<StorageEntry>| entry.map(|entry| (entry.key, entry.value));
+    let first = to_entry(
+        cursor.seek_by_key_subkey(hashed_address, start)?.filter(|entry| entry.key >= start),
+    );
+    let mut page = merge_page(
+        first,
+        || Ok(to_entry(cursor.next_dup_val()?)),
+        start,
+        limit,
+        changed,
+        |key| Ok(value(key)?.filter(|value| !value.is_zero())),
+    )?;
+
+    let mut unknown = unknown_keys(&page);
+    if !unknown.is_empty() {
+        let mut preimages = BTreeMap::new();
+        let mut cursor = tx.cursor_dup_read::<tables::PlainStorageState>()?;
+        for entry in cursor.walk_dup(Some(address), None)? {
+            let (_, StorageEntry { key, .. }) = entry?;
+            let hashed_key = keccak256(key);
+            if unknown.remove(&hashed_key) {
+                preimages.insert(hashed_key, key);
+                if unknown.is_empty() {
+                    break
+                }
+            }
+        }
+        set_keys(&mut page, &preimages);
+    }
+    Ok(page)
+}
+
+/// Returns the hashed keys of the entries of the page whose keys are unknown.
+fn unknown_keys<K, V>(page: &[HashedStateEntry<K, V>]) -> HashSet<B256> {
+    page.iter().filter(|entry| entry.key.is_none()).map(|entry| entry.hashed_key).collect()
+}
+
+/// Sets the keys of the entries of the page from the given preimages of their hashed keys.
+fn set_keys<K: Copy, V>(page: &mut [HashedStateEntry<K, V>], preimages: &BTreeMap<B256, K>) {
+    for entry in page.iter_mut().filter(|entry| entry.key.is_none()) {
+        entry.key = preimages.get(&entry.hashed_key).copied();
+    }
+}
+
+/// Merges the entries of a hashed table, which are yielded in order by `next` after the `first`
+/// one, with the `changed` entries from `start` on, and returns up to `limit` of them.
+///
+/// The values of the changed entries are looked up with `value` as they are added to the page,
+/// changed entries without a value are skipped.
+fn merge_page<K: Copy, V>(
+    first: Option<(B256, V)>,
+    mut next: impl FnMut() -> ProviderResult<Option<(B256, V)>>,
+    start: B256,
+    limit: usize,
+    changed: &BTreeMap<B256, K>,
+    mut value: impl FnMut(K) -> ProviderResult<Option<V>>,
+) -> ProviderResult<Vec<HashedStateEntry<K, V>>> {
+    let mut entry = first;
+    let mut changed = changed.range(start..).peekable();
+    let mut page = Vec::new();
+    while page.len() < limit {
+        let take_changed = match (&entry, changed.peek()) {
+            (None, None) => break,
+            (Some((hashed_key, _)), Some((changed_key, _))) => *changed_key <= hashed_key,
+            (None, Some(_)) => true,
+            (Some(_), None) => false,
+        };
+
+        if take_changed {
+            let (hashed_key, key) = changed.next().expect("is peeked");
+            // the changed value replaces the value of the table
+            if entry.as_ref().is_some_and(|(entry_key, _)| entry_key == hashed_key) {
+                entry = next()?;
+            }
+            if let Some(value) = value(*key)? {
+                page.push(HashedStateEntry { hashed_key: *hashed_key, key: Some(*key), value });
+            }
+        } else {
+            let (hashed_key, value) = entry.take().expect("is some");
+            page.push(HashedStateEntry { hashed_key, key: None, value });
+            entry = next()?;
+        }
+    }
+    Ok(page)
+}
+
+/// Returns the storage root of the given account with the hashed state applied on top of the
+/// database.
+pub(crate) fn storage_root<TX: DbTx>(
+    tx: &TX,
+    state: HashedPostState,
+    address: Address,
+) -> ProviderResult<B256> {
+    let hashed_address = keccak256(address);
+    let mut prefix_sets = state.construct_prefix_sets();
+    let state = state.into_sorted();
+    StorageRoot::from_tx_hashed(tx, hashed_address)
+        .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(tx, &state))
+        .with_prefix_set(
+            prefix_sets.storage_prefix_sets.remove(&hashed_address).unwrap_or_default(),
+        )
+        .root()
+        .map_err(|err| ProviderError::Database(err.into()))
+}
@@ -23,6 +23,13 @@ impl TestCanonStateSubscriptions {
         let event = CanonStateNotification::Reorg { old, new };
         self.canon_notif_tx.lock().as_mut().unwrap().retain(|tx| tx.send(event.clone()).is_ok())
     }
+
+    /// Adds revert to the queue that can be consumed with
+    /// [`TestCanonStateSubscriptions::subscribe_to_canonical_state`]
+    pub fn add_next_revert(&mut self, old: Arc<Chain>) {
+        let event = CanonStateNotification::Revert { old };
+        self.canon_notif_tx.lock().as_mut().unwrap().retain(|tx| tx.send(event.clone()).is_ok())
+    }
 }
 
 impl CanonStateSubscriptions for TestCanonStateSubscriptions {
@@ -1,26 +1,30 @@
 use crate::{
     bundle_state::BundleStateWithReceipts,
     traits::{BlockSource, ReceiptProvider},
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    BundleStateDataProvider, ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider,
-    ReceiptProviderIdExt, StateProvider, StateProviderBox, StateProviderFactory, StateRootProvider,
-    TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    AccountReader, BadBlock, BadBlockProvider, BlockHashReader, BlockIdReader, BlockNumReader,
+    BlockReader, BlockReaderIdExt, BlockchainTreePendingStateProvider, BundleStateDataProvider,
+    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HashedStateEntry, HeaderProvider,
+    HistoryReader, NodeDataProvider, ReceiptProviderIdExt, StateProvider, StateProviderBox,
+    StateProviderFactory, StateRootProvider, TransactionVariant, TransactionsProvider,
+    WithdrawalsProvider,
 };
 use parking_lot::Mutex;
 use reth_db::models::{AccountBeforeTx, StoredBlockBodyIndices};
 use reth_interfaces::provider::{ProviderError, ProviderResult};
 use reth_node_api::ConfigureEvmEnv;
 use reth_primitives::{
-    keccak256, trie::AccountProof, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId,
-    BlockNumber, BlockWithSenders, Bytecode, Bytes, ChainInfo, ChainSpec, Header, Receipt,
-    SealedBlock, SealedBlockWithSenders, SealedHeader, StorageKey, StorageValue, TransactionMeta,
-    TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
-    U256,
+    constants::EMPTY_ROOT_HASH,
+    keccak256,
+    trie::{AccountProof, ExecutionWitness},
+    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumber, BlockWithSenders,
+    Bytecode, Bytes, ChainInfo, ChainSpec, Header, Receipt, SealedBlock, SealedBlockWithSenders,
+    SealedHeader, StorageKey, StorageValue, TransactionMeta, TransactionSigned,
+    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
 };
-use reth_trie::updates::TrieUpdates;
+use reth_trie::{updates::TrieUpdates, HashedPostState, HashedStorage};
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap},
     ops::{RangeBounds, RangeInclusive},
     sync::Arc,
 };
@@ -526,6 +530,16 @@ impl StateRootProvider for MockEthProvider {
     ) -> ProviderResult<(B256, TrieUpdates)> {
         Ok((B256::default(), Default::default()))
     }
+
+    fn hashed_state_root(&self, _hashed_state: &HashedPostState) -> ProviderResult<B256> {
+        Ok(B256::default())
+    }
+}
+
+impl NodeDataProvider for MockEthProvider {
+    fn node_data_by_hash(&self, hash: B256) -> ProviderResult<Option<Bytes>> {
+        Ok(self.bytecode_by_hash(hash)?.map(|bytecode| bytecode.original_bytes()))
+    }
 }
 
 impl StateProvider for MockEthProvider {
@@ -553,6 +567,35 @@ impl StateProvider for MockEthProvider {
     fn proof(&self, _address: Address, _keys: &[B256]) -> ProviderResult<AccountProof> {
         Ok(AccountProof::default())
     }
+
+    fn witness(
+        &self,
+        _targets: &HashMap<Address, Vec<B256>>,
+        _changes: &HashedPostState,
+    ) -> ProviderResult<ExecutionWitness> {
+        Ok(ExecutionWitness::default())
+    }
+
+    fn storage_root(&self, _address: Address, _changes: HashedStorage) -> ProviderResult<B256> {
+        Ok(EMPTY_ROOT_HASH)
+    }
+
+    fn account_range(
+        &self,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<Vec<HashedStateEntry<Address, Account>>> {
+        Ok(Vec::new())
+    }
+
+    fn storage_range(
+        &self,
+        _address: Address,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<Vec<HashedStateEntry<StorageKey, StorageValue>>> {
+        Ok(Vec::new())
+    }
 }
 
 impl EvmEnvProvider for MockEthProvider {
@@ -709,4 +752,54 @@ impl ChangeSetReader for MockEthProvider {
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
         Ok(Vec::default())
     }
+
+    fn account_changeset_entry(
+        &self,
+        _block_number: BlockNumber,
+        _address: Address,
+    ) -> ProviderResult<Option<AccountBeforeTx>> {
+        Ok(None)
+    }
+
+    fn modified_accounts_with_range(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        Ok(BTreeSet::default())
+    }
+}
+
+impl HistoryReader for MockEthProvider {
+    fn account_history_blocks_after(
+        &self,
+        _address: Address,
+        _from: BlockNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+
+    fn account_history_blocks_before(
+        &self,
+        _address: Address,
+        _to: BlockNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+}
+
+impl BadBlockProvider for MockEthProvider {
+    fn bad_blocks(&self) -> ProviderResult<Vec<BadBlock>> {
+        Ok(Vec::new())
+    }
+}
+
+impl BlockchainTreePendingStateProvider for MockEthProvider {
+    fn find_pending_state_provider(
+        &self,
+        _block_hash: BlockHash,
+    ) -> Option<Box<dyn BundleStateDataProvider>> {
+        None
+    }
 }
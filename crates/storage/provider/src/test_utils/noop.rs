@@ -1,27 +1,37 @@
 use crate::{
     bundle_state::BundleStateWithReceipts,
     traits::{BlockSource, ReceiptProvider},
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider, PruneCheckpointReader,
-    ReceiptProviderIdExt, StageCheckpointReader, StateProvider, StateProviderBox,
-    StateProviderFactory, StateRootProvider, TransactionVariant, TransactionsProvider,
-    WithdrawalsProvider,
+    AccountReader, BadBlock, BadBlockProvider, BlockHashReader, BlockIdReader, BlockNumReader,
+    BlockReader, BlockReaderIdExt, BlockchainTreePendingStateProvider, BundleStateDataProvider,
+    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HashedStateEntry, HeaderProvider,
+    HistoryReader, NodeDataProvider, PruneCheckpointReader, ReceiptProviderIdExt,
+    StageCheckpointReader, StateProvider, StateProviderBox, StateProviderFactory,
+    StateRootProvider, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
 use reth_db::models::{AccountBeforeTx, StoredBlockBodyIndices};
-use reth_interfaces::provider::ProviderResult;
+use reth_interfaces::{
+    blockchain_tree::{
+        error::{BlockchainTreeError, InsertBlockError},
+        BlockValidationKind, BlockchainTreeEngine, CanonicalOutcome, InsertPayloadOk,
+    },
+    provider::ProviderResult,
+    RethResult,
+};
 use reth_node_api::ConfigureEvmEnv;
 use reth_primitives::{
+    constants::EMPTY_ROOT_HASH,
     stage::{StageCheckpoint, StageId},
-    trie::AccountProof,
-    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumber, Bytecode,
+    trie::{AccountProof, ExecutionWitness},
+    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumber, Bytecode, Bytes,
     ChainInfo, ChainSpec, Header, PruneCheckpoint, PruneSegment, Receipt, SealedBlock,
     SealedBlockWithSenders, SealedHeader, StorageKey, StorageValue, TransactionMeta,
     TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
     MAINNET, U256,
 };
-use reth_trie::updates::TrieUpdates;
+use reth_trie::{updates::TrieUpdates, HashedPostState, HashedStorage};
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
+    collections::{BTreeSet, HashMap},
     ops::{RangeBounds, RangeInclusive},
     sync::Arc,
 };
@@ -282,6 +292,94 @@ impl ChangeSetReader for NoopProvider {
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
         Ok(Vec::default())
     }
+
+    fn account_changeset_entry(
+        &self,
+        _block_number: BlockNumber,
+        _address: Address,
+    ) -> ProviderResult<Option<AccountBeforeTx>> {
+        Ok(None)
+    }
+
+    fn modified_accounts_with_range(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        Ok(BTreeSet::default())
+    }
+}
+
+impl HistoryReader for NoopProvider {
+    fn account_history_blocks_after(
+        &self,
+        _address: Address,
+        _from: BlockNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+
+    fn account_history_blocks_before(
+        &self,
+        _address: Address,
+        _to: BlockNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+}
+
+impl BadBlockProvider for NoopProvider {
+    fn bad_blocks(&self) -> ProviderResult<Vec<BadBlock>> {
+        Ok(Vec::new())
+    }
+}
+
+impl BlockchainTreePendingStateProvider for NoopProvider {
+    fn find_pending_state_provider(
+        &self,
+        _block_hash: BlockHash,
+    ) -> Option<Box<dyn BundleStateDataProvider>> {
+        None
+    }
+}
+
+impl BlockchainTreeEngine for NoopProvider {
+    fn buffer_block(&self, _block: SealedBlockWithSenders) -> Result<(), InsertBlockError> {
+        Ok(())
+    }
+
+    fn insert_block(
+        &self,
+        block: SealedBlockWithSenders,
+        _validation_kind: BlockValidationKind,
+    ) -> Result<InsertPayloadOk, InsertBlockError> {
+        Err(InsertBlockError::tree_error(
+            BlockchainTreeError::BlockHashNotFoundInChain { block_hash: block.hash() },
+            block.block,
+        ))
+    }
+
+    fn finalize_block(&self, _finalized_block: BlockNumber) {}
+
+    fn connect_buffered_blocks_to_canonical_hashes_and_finalize(
+        &self,
+        _last_finalized_block: BlockNumber,
+    ) -> RethResult<()> {
+        Ok(())
+    }
+
+    fn connect_buffered_blocks_to_canonical_hashes(&self) -> RethResult<()> {
+        Ok(())
+    }
+
+    fn make_canonical(&self, block_hash: &BlockHash) -> RethResult<CanonicalOutcome> {
+        Err(BlockchainTreeError::BlockHashNotFoundInChain { block_hash: *block_hash }.into())
+    }
+
+    fn unwind(&self, _unwind_to: BlockNumber) -> RethResult<()> {
+        Ok(())
+    }
 }
 
 impl StateRootProvider for NoopProvider {
@@ -295,6 +393,10 @@ impl StateRootProvider for NoopProvider {
     ) -> ProviderResult<(B256, TrieUpdates)> {
         Ok((B256::default(), TrieUpdates::default()))
     }
+
+    fn hashed_state_root(&self, _hashed_state: &HashedPostState) -> ProviderResult<B256> {
+        Ok(B256::default())
+    }
 }
 
 impl StateProvider for NoopProvider {
@@ -313,6 +415,35 @@ impl StateProvider for NoopProvider {
     fn proof(&self, _address: Address, _keys: &[B256]) -> ProviderResult<AccountProof> {
         Ok(AccountProof::default())
     }
+
+    fn witness(
+        &self,
+        _targets: &HashMap<Address, Vec<B256>>,
+        _changes: &HashedPostState,
+    ) -> ProviderResult<ExecutionWitness> {
+        Ok(ExecutionWitness::default())
+    }
+
+    fn storage_root(&self, _address: Address, _changes: HashedStorage) -> ProviderResult<B256> {
+        Ok(EMPTY_ROOT_HASH)
+    }
+
+    fn account_range(
+        &self,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<Vec<HashedStateEntry<Address, Account>>> {
+        Ok(Vec::new())
+    }
+
+    fn storage_range(
+        &self,
+        _address: Address,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<Vec<HashedStateEntry<StorageKey, StorageValue>>> {
+        Ok(Vec::new())
+    }
 }
 
 impl EvmEnvProvider for NoopProvider {
@@ -447,3 +578,9 @@ impl PruneCheckpointReader for NoopProvider {
         Ok(None)
     }
 }
+
+impl NodeDataProvider for NoopProvider {
+    fn node_data_by_hash(&self, _hash: B256) -> ProviderResult<Option<Bytes>> {
+        Ok(None)
+    }
+}
@@ -52,4 +52,21 @@ pub trait ChangeSetReader: Send + Sync {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>>;
+
+    /// Returns the account state from before the given block, if the account changed in this
+    /// block.
+    ///
+    /// Returns `None` if the account did not change in this block.
+    fn account_changeset_entry(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+    ) -> ProviderResult<Option<AccountBeforeTx>>;
+
+    /// Iterate over account and storage changesets and return all addresses of accounts whose
+    /// info or storage changed in the given range of blocks.
+    fn modified_accounts_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>>;
 }
@@ -0,0 +1,69 @@
+use auto_impl::auto_impl;
+use reth_db::models::StoredBadBlock;
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{BlockHash, SealedBlock};
+
+/// The maximum number of bad blocks that are kept in the database.
+pub const MAX_BAD_BLOCKS: usize = 10;
+
+/// A block that failed validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BadBlock {
+    /// The invalid block.
+    pub block: SealedBlock,
+    /// The reason why the block is invalid.
+    pub error: String,
+}
+
+impl From<StoredBadBlock> for BadBlock {
+    fn from(stored: StoredBadBlock) -> Self {
+        let StoredBadBlock { hash, body, ommers, withdrawals, error, header } = stored;
+        Self {
+            block: SealedBlock {
+                header: header.seal(hash),
+                body: body.into_iter().map(Into::into).collect(),
+                ommers,
+                withdrawals,
+            },
+            error: String::from_utf8_lossy(&error).into_owned(),
+        }
+    }
+}
+
+impl From<BadBlock> for StoredBadBlock {
+    fn from(bad_block: BadBlock) -> Self {
+        let BadBlock { block: SealedBlock { header, body, ommers, withdrawals }, error } =
+            bad_block;
+        let (header, hash) = header.split();
+        Self {
+            hash,
+            body: body.into_iter().map(Into::into).collect(),
+            ommers,
+            withdrawals,
+            error: error.into_bytes(),
+            header,
+        }
+    }
+}
+
+/// Provides access to the most recent blocks that failed validation.
+#[auto_impl(&, Arc, Box)]
+pub trait BadBlockProvider: Send + Sync {
+    /// Returns the most recent blocks that failed validation, most recent first.
+    fn bad_blocks(&self) -> ProviderResult<Vec<BadBlock>>;
+
+    /// Returns the bad block with the given hash, if it's still known.
+    fn bad_block(&self, hash: BlockHash) -> ProviderResult<Option<BadBlock>> {
+        Ok(self.bad_blocks()?.into_iter().find(|bad_block| bad_block.block.hash() == hash))
+    }
+}
+
+/// Records blocks that failed validation.
+#[auto_impl(&, Arc, Box)]
+pub trait BadBlockWriter: Send + Sync {
+    /// Records a block that failed validation.
+    ///
+    /// Blocks that are already known are ignored. Only the [`MAX_BAD_BLOCKS`] most recent blocks
+    /// are kept, older ones are evicted.
+    fn insert_bad_block(&self, bad_block: BadBlock) -> ProviderResult<()>;
+}
@@ -7,6 +7,34 @@ use std::{
     ops::{Range, RangeInclusive},
 };
 
+/// History reader
+#[auto_impl(&, Arc, Box)]
+pub trait HistoryReader: Send + Sync {
+    /// Returns up to `limit` numbers of blocks at or after `from` in which the given account
+    /// changed, in ascending order.
+    ///
+    /// NOTE: This reads the account history index, blocks that are not yet indexed or that have
+    /// been pruned are not included.
+    fn account_history_blocks_after(
+        &self,
+        address: Address,
+        from: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+
+    /// Returns up to `limit` numbers of blocks at or before `to` in which the given account
+    /// changed, in descending order.
+    ///
+    /// NOTE: This reads the account history index, blocks that are not yet indexed or that have
+    /// been pruned are not included.
+    fn account_history_blocks_before(
+        &self,
+        address: Address,
+        to: BlockNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+}
+
 /// History Writer
 #[auto_impl(&, Arc, Box)]
 pub trait HistoryWriter: Send + Sync {
@@ -6,6 +6,9 @@ pub use account::{AccountExtReader, AccountReader, ChangeSetReader};
 mod storage;
 pub use storage::StorageReader;
 
+mod bad_block;
+pub use bad_block::{BadBlock, BadBlockProvider, BadBlockWriter, MAX_BAD_BLOCKS};
+
 mod block;
 pub use block::{
     BlockExecutionWriter, BlockReader, BlockReaderIdExt, BlockSource, BlockWriter,
@@ -35,8 +38,8 @@ pub use receipts::{ReceiptProvider, ReceiptProviderIdExt};
 
 mod state;
 pub use state::{
-    BlockchainTreePendingStateProvider, BundleStateDataProvider, StateProvider, StateProviderBox,
-    StateProviderFactory, StateRootProvider,
+    BlockchainTreePendingStateProvider, BundleStateDataProvider, HashedStateEntry, StateProvider,
+    StateProviderBox, StateProviderFactory, StateRootProvider,
 };
 
 mod transactions;
@@ -64,10 +67,13 @@ mod hashing;
 pub use hashing::HashingWriter;
 
 mod history;
-pub use history::HistoryWriter;
+pub use history::{HistoryReader, HistoryWriter};
 
 mod prune_checkpoint;
 pub use prune_checkpoint::{PruneCheckpointReader, PruneCheckpointWriter};
 
+mod node_data;
+pub use node_data::NodeDataProvider;
+
 mod database_provider;
 pub use database_provider::DatabaseProviderFactory;
@@ -0,0 +1,12 @@
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{Bytes, B256};
+
+/// The trait for fetching `eth` node data by hash.
+///
+/// The trie tables are keyed by the path of the nodes, trie nodes can't be looked up by their
+/// hash. Only contract bytecode is available as node data.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait NodeDataProvider: Send + Sync {
+    /// Returns the node data with the given hash, if it exists.
+    fn node_data_by_hash(&self, hash: B256) -> ProviderResult<Option<Bytes>>;
+}
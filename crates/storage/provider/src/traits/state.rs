@@ -3,10 +3,12 @@ use crate::{BlockHashReader, BlockIdReader, BundleStateWithReceipts};
 use auto_impl::auto_impl;
 use reth_interfaces::provider::{ProviderError, ProviderResult};
 use reth_primitives::{
-    trie::AccountProof, Address, BlockHash, BlockId, BlockNumHash, BlockNumber, BlockNumberOrTag,
-    Bytecode, StorageKey, StorageValue, B256, KECCAK_EMPTY, U256,
+    trie::{AccountProof, ExecutionWitness},
+    Account, Address, BlockHash, BlockId, BlockNumHash, BlockNumber, BlockNumberOrTag, Bytecode,
+    StorageKey, StorageValue, B256, KECCAK_EMPTY, U256,
 };
-use reth_trie::updates::TrieUpdates;
+use reth_trie::{updates::TrieUpdates, HashedPostState, HashedStorage};
+use std::collections::HashMap;
 
 /// Type alias of boxed [StateProvider].
 pub type StateProviderBox = Box<dyn StateProvider>;
@@ -27,6 +29,41 @@ pub trait StateProvider: BlockHashReader + AccountReader + StateRootProvider + S
     /// Get account and storage proofs.
     fn proof(&self, address: Address, keys: &[B256]) -> ProviderResult<AccountProof>;
 
+    /// Get the execution witness of the given accounts and storage slots: the trie nodes of their
+    /// proofs and the bytecodes of the accounts.
+    ///
+    /// The witness also contains the trie nodes that are needed to compute the state root after
+    /// applying the given changes.
+    fn witness(
+        &self,
+        targets: &HashMap<Address, Vec<B256>>,
+        changes: &HashedPostState,
+    ) -> ProviderResult<ExecutionWitness>;
+
+    /// Get the storage root of the given account with the given storage changes applied on top.
+    fn storage_root(&self, address: Address, changes: HashedStorage) -> ProviderResult<B256>;
+
+    /// Get up to `limit` accounts, ordered by their hashed address and starting at the given
+    /// hashed address.
+    ///
+    /// The address of an account is only included if it's known.
+    fn account_range(
+        &self,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<HashedStateEntry<Address, Account>>>;
+
+    /// Get up to `limit` storage slots of the given account, ordered by their hashed storage key
+    /// and starting at the given hashed storage key.
+    ///
+    /// The plain storage key of a slot is only included if it's known.
+    fn storage_range(
+        &self,
+        address: Address,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<HashedStateEntry<StorageKey, StorageValue>>>;
+
     /// Get account code by its address.
     ///
     /// Returns `None` if the account doesn't exist or account is not a contract
@@ -75,6 +112,18 @@ pub trait StateProvider: BlockHashReader + AccountReader + StateRootProvider + S
     }
 }
 
+/// An entry of the hashed state, see [StateProvider::account_range] and
+/// [StateProvider::storage_range].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HashedStateEntry<K, V> {
+    /// The hashed key of the entry.
+    pub hashed_key: B256,
+    /// The preimage of the hashed key, if it's known.
+    pub key: Option<K>,
+    /// The value of the entry.
+    pub value: V,
+}
+
 /// Light wrapper that returns `StateProvider` implementations that correspond to the given
 /// `BlockNumber`, the latest state, or the pending state.
 ///
@@ -243,4 +292,23 @@ pub trait StateRootProvider: Send + Sync {
         &self,
         bundle_state: &BundleStateWithReceipts,
     ) -> ProviderResult<(B256, TrieUpdates)>;
+
+    /// Returns the state root of the [HashedPostState] on top of the current state.
+    fn hashed_state_root(&self, hashed_state: &HashedPostState) -> ProviderResult<B256>;
+
+    /// Returns the state roots after applying each of the [HashedPostState]s in turn on top of
+    /// the current state.
+    ///
+    /// Providers that need to rebuild the current state for every root should override this to
+    /// build it only once.
+    fn hashed_state_roots(&self, hashed_states: &[HashedPostState]) -> ProviderResult<Vec<B256>> {
+        let mut state = HashedPostState::default();
+        hashed_states
+            .iter()
+            .map(|hashed_state| {
+                state.extend(hashed_state.clone());
+                self.hashed_state_root(&state)
+            })
+            .collect()
+    }
 }
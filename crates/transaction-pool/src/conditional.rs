@@ -0,0 +1,395 @@
+//! Preconditions of transactions that were submitted via `eth_sendRawTransactionConditional`.
+
+use alloy_rlp::{BufMut, Decodable, Encodable, RlpDecodable, RlpEncodable};
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{Address, BlockNumber, Header, B256, U256};
+use reth_provider::StateProvider;
+use std::{
+    collections::BTreeMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The maximum cost of the [TransactionConditional::known_accounts] of a transaction.
+///
+/// Every expected storage root and every expected storage slot value costs one.
+pub const MAX_TRANSACTION_CONDITIONAL_COST: usize = 1000;
+
+/// Preconditions that must hold for a transaction to be accepted into and kept in the pool.
+///
+/// The block number and timestamp ranges are inclusive and checked against the block the
+/// transaction would be included in. The known accounts are checked against the state it would
+/// be executed on.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransactionConditional {
+    /// Minimum block number.
+    pub block_number_min: Option<BlockNumber>,
+    /// Maximum block number.
+    pub block_number_max: Option<BlockNumber>,
+    /// Minimum block timestamp.
+    pub timestamp_min: Option<u64>,
+    /// Maximum block timestamp.
+    pub timestamp_max: Option<u64>,
+    /// Expected storage of accounts.
+    pub known_accounts: BTreeMap<Address, AccountStorageCondition>,
+}
+
+/// The expected storage of an account, see [TransactionConditional::known_accounts].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountStorageCondition {
+    /// The expected storage root of the account.
+    RootHash(B256),
+    /// The expected values of storage slots of the account.
+    Slots(BTreeMap<B256, B256>),
+}
+
+impl TransactionConditional {
+    /// Returns the cost of the known accounts, see [MAX_TRANSACTION_CONDITIONAL_COST].
+    pub fn cost(&self) -> usize {
+        self.known_accounts
+            .values()
+            .map(|condition| match condition {
+                AccountStorageCondition::RootHash(_) => 1,
+                AccountStorageCondition::Slots(slots) => slots.len(),
+            })
+            .sum()
+    }
+
+    /// Checks whether the block number and timestamp of a block are within the expected ranges.
+    pub fn check_block(
+        &self,
+        number: BlockNumber,
+        timestamp: u64,
+    ) -> Result<(), TransactionConditionalError> {
+        if self.block_number_min.is_some_and(|min| number < min) ||
+            self.block_number_max.is_some_and(|max| number > max)
+        {
+            return Err(TransactionConditionalError::BlockNumberOutOfRange(number))
+        }
+        if self.timestamp_min.is_some_and(|min| timestamp < min) ||
+            self.timestamp_max.is_some_and(|max| timestamp > max)
+        {
+            return Err(TransactionConditionalError::TimestampOutOfRange(timestamp))
+        }
+        Ok(())
+    }
+
+    /// Checks whether the storage of the known accounts matches the given state.
+    pub fn check_state<S: ConditionalState>(
+        &self,
+        mut state: S,
+    ) -> ProviderResult<Result<(), TransactionConditionalError>> {
+        for (address, condition) in &self.known_accounts {
+            match condition {
+                AccountStorageCondition::RootHash(expected) => {
+                    if state.storage_root(*address)? != *expected {
+                        return Ok(Err(TransactionConditionalError::StorageRootMismatch(*address)))
+                    }
+                }
+                AccountStorageCondition::Slots(slots) => {
+                    for (slot, expected) in slots {
+                        let value = state.storage(*address, *slot)?;
+                        if value != U256::from_be_bytes(expected.0) {
+                            return Ok(Err(TransactionConditionalError::StorageSlotMismatch(
+                                *address, *slot,
+                            )))
+                        }
+                    }
+                }
+            }
+        }
+        Ok(Ok(()))
+    }
+
+    /// Checks all preconditions against the next block after the given parent and the state of the
+    /// parent.
+    ///
+    /// The timestamp of the next block isn't known yet, so it's assumed to be the current time, but
+    /// at least one second after the parent.
+    pub fn check_next_block<S: ConditionalState>(
+        &self,
+        parent: &Header,
+        state: S,
+    ) -> ProviderResult<Result<(), TransactionConditionalError>> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        self.check(parent.number + 1, now.max(parent.timestamp + 1), state)
+    }
+
+    /// Checks all preconditions against the given block number and timestamp and the given state.
+    pub fn check<S: ConditionalState>(
+        &self,
+        number: BlockNumber,
+        timestamp: u64,
+        state: S,
+    ) -> ProviderResult<Result<(), TransactionConditionalError>> {
+        let cost = self.cost();
+        if cost > MAX_TRANSACTION_CONDITIONAL_COST {
+            return Ok(Err(TransactionConditionalError::CostExceeded(
+                cost,
+                MAX_TRANSACTION_CONDITIONAL_COST,
+            )))
+        }
+        if let Err(err) = self.check_block(number, timestamp) {
+            return Ok(Err(err))
+        }
+        self.check_state(state)
+    }
+}
+
+/// The state that the [TransactionConditional::known_accounts] are checked against.
+///
+/// Implemented for [StateProvider]s, which are checked as is. Block builders provide the state of
+/// the block built so far instead.
+pub trait ConditionalState {
+    /// Returns the storage root of the account.
+    fn storage_root(&mut self, address: Address) -> ProviderResult<B256>;
+
+    /// Returns the value of the storage slot of the account.
+    fn storage(&mut self, address: Address, slot: B256) -> ProviderResult<U256>;
+}
+
+impl<S: StateProvider + ?Sized> ConditionalState for &S {
+    fn storage_root(&mut self, address: Address) -> ProviderResult<B256> {
+        StateProvider::storage_root(*self, address, Default::default())
+    }
+
+    fn storage(&mut self, address: Address, slot: B256) -> ProviderResult<U256> {
+        Ok(StateProvider::storage(*self, address, slot)?.unwrap_or_default())
+    }
+}
+
+/// The RLP encoding of a [TransactionConditional], which is stored in the transactions backup.
+///
+/// Unset minimums are encoded as zero and unset maximums as [u64::MAX], which are equivalent.
+#[derive(RlpEncodable, RlpDecodable)]
+struct TransactionConditionalRlp {
+    block_number_min: u64,
+    block_number_max: u64,
+    timestamp_min: u64,
+    timestamp_max: u64,
+    known_accounts: Vec<KnownAccountRlp>,
+}
+
+/// The RLP encoding of an entry of [TransactionConditional::known_accounts].
+#[derive(RlpEncodable, RlpDecodable)]
+struct KnownAccountRlp {
+    address: Address,
+    condition: AccountStorageCondition,
+}
+
+/// The RLP encoding of an expected storage slot value of [AccountStorageCondition::Slots].
+#[derive(RlpEncodable, RlpDecodable)]
+struct SlotRlp {
+    slot: B256,
+    value: B256,
+}
+
+impl From<&TransactionConditional> for TransactionConditionalRlp {
+    fn from(conditional: &TransactionConditional) -> Self {
+        Self {
+            block_number_min: conditional.block_number_min.unwrap_or_default(),
+            block_number_max: conditional.block_number_max.unwrap_or(u64::MAX),
+            timestamp_min: conditional.timestamp_min.unwrap_or_default(),
+            timestamp_max: conditional.timestamp_max.unwrap_or(u64::MAX),
+            known_accounts: conditional
+                .known_accounts
+                .iter()
+                .map(|(address, condition)| KnownAccountRlp {
+                    address: *address,
+                    condition: condition.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<TransactionConditionalRlp> for TransactionConditional {
+    fn from(conditional: TransactionConditionalRlp) -> Self {
+        Self {
+            block_number_min: (conditional.block_number_min != 0)
+                .then_some(conditional.block_number_min),
+            block_number_max: (conditional.block_number_max != u64::MAX)
+                .then_some(conditional.block_number_max),
+            timestamp_min: (conditional.timestamp_min != 0).then_some(conditional.timestamp_min),
+            timestamp_max: (conditional.timestamp_max != u64::MAX)
+                .then_some(conditional.timestamp_max),
+            known_accounts: conditional
+                .known_accounts
+                .into_iter()
+                .map(|account| (account.address, account.condition))
+                .collect(),
+        }
+    }
+}
+
+impl Encodable for TransactionConditional {
+    fn encode(&self, out: &mut dyn BufMut) {
+        TransactionConditionalRlp::from(self).encode(out)
+    }
+
+    fn length(&self) -> usize {
+        TransactionConditionalRlp::from(self).length()
+    }
+}
+
+impl Decodable for TransactionConditional {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        TransactionConditionalRlp::decode(buf).map(Into::into)
+    }
+}
+
+impl AccountStorageCondition {
+    fn slots_rlp(slots: &BTreeMap<B256, B256>) -> Vec<SlotRlp> {
+        slots.iter().map(|(slot, value)| SlotRlp { slot: *slot, value: *value }).collect()
+    }
+}
+
+/// A storage root is encoded as a string and the storage slots as a list.
+impl Encodable for AccountStorageCondition {
+    fn encode(&self, out: &mut dyn BufMut) {
+        match self {
+            Self::RootHash(root) => root.encode(out),
+            Self::Slots(slots) => Self::slots_rlp(slots).encode(out),
+        }
+    }
+
+    fn length(&self) -> usize {
+        match self {
+            Self::RootHash(root) => root.length(),
+            Self::Slots(slots) => Self::slots_rlp(slots).length(),
+        }
+    }
+}
+
+impl Decodable for AccountStorageCondition {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        if alloy_rlp::Header::decode(&mut &**buf)?.list {
+            let slots = Vec::<SlotRlp>::decode(buf)?;
+            Ok(Self::Slots(slots.into_iter().map(|slot| (slot.slot, slot.value)).collect()))
+        } else {
+            Ok(Self::RootHash(B256::decode(buf)?))
+        }
+    }
+}
+
+/// Errors of a [TransactionConditional] that doesn't hold.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TransactionConditionalError {
+    /// The cost of the known accounts exceeds the maximum.
+    #[error("conditional cost {0} exceeds max {1}")]
+    CostExceeded(usize, usize),
+    /// The block number is out of the expected range.
+    #[error("block number {0} out of conditional range")]
+    BlockNumberOutOfRange(BlockNumber),
+    /// The block timestamp is out of the expected range.
+    #[error("timestamp {0} out of conditional range")]
+    TimestampOutOfRange(u64),
+    /// The storage root of the account doesn't match.
+    #[error("storage root of {0} doesn't match conditional")]
+    StorageRootMismatch(Address),
+    /// The value of the storage slot of the account doesn't match.
+    #[error("storage slot {1} of {0} doesn't match conditional")]
+    StorageSlotMismatch(Address, B256),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_provider::{
+        test_utils::{ExtendedAccount, MockEthProvider},
+        StateProviderFactory,
+    };
+
+    #[test]
+    fn check_conditional() {
+        let address = Address::random();
+        let slot = B256::with_last_byte(1);
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            address,
+            ExtendedAccount::new(0, U256::ZERO).extend_storage([(slot, U256::from(42))]),
+        );
+        let state = provider.latest().unwrap();
+
+        let conditional = TransactionConditional {
+            block_number_min: Some(10),
+            block_number_max: Some(20),
+            timestamp_max: Some(1000),
+            known_accounts: BTreeMap::from([(
+                address,
+                AccountStorageCondition::Slots(BTreeMap::from([(
+                    slot,
+                    B256::from(U256::from(42)),
+                )])),
+            )]),
+            ..Default::default()
+        };
+        assert_eq!(conditional.check(10, 1000, &*state).unwrap(), Ok(()));
+        assert_eq!(
+            conditional.check(9, 1000, &*state).unwrap(),
+            Err(TransactionConditionalError::BlockNumberOutOfRange(9))
+        );
+        assert_eq!(
+            conditional.check(21, 1000, &*state).unwrap(),
+            Err(TransactionConditionalError::BlockNumberOutOfRange(21))
+        );
+        assert_eq!(
+            conditional.check(20, 1001, &*state).unwrap(),
+            Err(TransactionConditionalError::TimestampOutOfRange(1001))
+        );
+
+        let conditional = TransactionConditional {
+            known_accounts: BTreeMap::from([(
+                address,
+                AccountStorageCondition::Slots(BTreeMap::from([(slot, B256::ZERO)])),
+            )]),
+            ..Default::default()
+        };
+        assert_eq!(
+            conditional.check(0, 0, &*state).unwrap(),
+            Err(TransactionConditionalError::StorageSlotMismatch(address, slot))
+        );
+
+        let conditional = TransactionConditional {
+            known_accounts: BTreeMap::from([(
+                address,
+                AccountStorageCondition::Slots(
+                    (0..=MAX_TRANSACTION_CONDITIONAL_COST as u64)
+                        .map(|i| (B256::from(U256::from(i)), B256::ZERO))
+                        .collect(),
+                ),
+            )]),
+            ..Default::default()
+        };
+        assert_eq!(
+            conditional.check(0, 0, &*state).unwrap(),
+            Err(TransactionConditionalError::CostExceeded(
+                MAX_TRANSACTION_CONDITIONAL_COST + 1,
+                MAX_TRANSACTION_CONDITIONAL_COST
+            ))
+        );
+    }
+
+    #[test]
+    fn conditional_rlp_roundtrip() {
+        let conditional = TransactionConditional {
+            block_number_min: Some(10),
+            timestamp_max: Some(0),
+            known_accounts: BTreeMap::from([
+                (Address::random(), AccountStorageCondition::RootHash(B256::random())),
+                (
+                    Address::random(),
+                    AccountStorageCondition::Slots(BTreeMap::from([
+                        (B256::random(), B256::ZERO),
+                        (B256::random(), B256::random()),
+                    ])),
+                ),
+            ]),
+            ..Default::default()
+        };
+
+        let encoded = alloy_rlp::encode(&conditional);
+        assert_eq!(encoded.len(), conditional.length());
+        let decoded = TransactionConditional::decode(&mut encoded.as_slice()).unwrap();
+        assert_eq!(decoded, conditional);
+    }
+}
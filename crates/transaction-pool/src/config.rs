@@ -10,6 +10,13 @@ pub const TXPOOL_SUBPOOL_MAX_TXS_DEFAULT: usize = 10_000;
 /// The default maximum allowed size of the given subpool.
 pub const TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT: usize = 20;
 
+/// The default maximum share (in %) of a subpool's transaction limit a single sender may occupy.
+pub const TXPOOL_MAX_SENDER_SHARE_DEFAULT: usize = 1;
+
+/// The default maximum share (in %) of a subpool's transaction limit a single local sender may
+/// occupy.
+pub const TXPOOL_MAX_LOCAL_SENDER_SHARE_DEFAULT: usize = 10;
+
 /// Default price bump (in %) for the transaction pool underpriced check.
 pub const DEFAULT_PRICE_BUMP: u128 = 10;
 
@@ -31,6 +38,9 @@ pub struct PoolConfig {
     pub blob_limit: SubPoolLimit,
     /// Max number of executable transaction slots guaranteed per account
     pub max_account_slots: usize,
+    /// Max share of a subpool a single sender may occupy before its transactions are evicted
+    /// first.
+    pub sender_share: SenderShareConfig,
     /// Price bump (in %) for the transaction pool underpriced check.
     pub price_bumps: PriceBumpConfig,
     /// How to handle locally received transactions:
@@ -57,6 +67,7 @@ impl Default for PoolConfig {
             queued_limit: Default::default(),
             blob_limit: Default::default(),
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+            sender_share: Default::default(),
             price_bumps: Default::default(),
             local_transactions_config: Default::default(),
         }
@@ -95,6 +106,46 @@ impl Default for SubPoolLimit {
     }
 }
 
+/// Max share (in %) of a subpool's transaction limit a single sender may occupy.
+///
+/// If a subpool exceeds its [SubPoolLimit], transactions of senders that occupy more than their
+/// share are evicted first, starting with the sender that exceeds its share the most.
+///
+/// Local senders, see [LocalTransactionConfig::is_local], can occupy a larger share.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SenderShareConfig {
+    /// Max share (in %) of a subpool's transaction limit a single sender may occupy.
+    pub max_share: usize,
+    /// Max share (in %) of a subpool's transaction limit a single local sender may occupy.
+    pub max_local_share: usize,
+}
+
+impl SenderShareConfig {
+    /// Returns the max number of transactions a sender may have in a subpool with the given
+    /// [SubPoolLimit].
+    ///
+    /// This is always at least one transaction.
+    #[inline]
+    pub const fn max_sender_txs(&self, limit: &SubPoolLimit, is_local: bool) -> usize {
+        let share = if is_local { self.max_local_share } else { self.max_share };
+        let max_txs = limit.max_txs.saturating_mul(share) / 100;
+        if max_txs == 0 {
+            1
+        } else {
+            max_txs
+        }
+    }
+}
+
+impl Default for SenderShareConfig {
+    fn default() -> Self {
+        Self {
+            max_share: TXPOOL_MAX_SENDER_SHARE_DEFAULT,
+            max_local_share: TXPOOL_MAX_LOCAL_SENDER_SHARE_DEFAULT,
+        }
+    }
+}
+
 /// Price bump config (in %) for the transaction pool underpriced check.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub struct PriceBumpConfig {
@@ -225,4 +276,16 @@ mod tests {
         // now this should be above the limits
         assert!(config.is_exceeded(pool_size));
     }
+
+    #[test]
+    fn test_max_sender_txs() {
+        let config = SenderShareConfig { max_share: 1, max_local_share: 10 };
+        let limit = SubPoolLimit::new(1000, usize::MAX);
+        assert_eq!(config.max_sender_txs(&limit, false), 10);
+        assert_eq!(config.max_sender_txs(&limit, true), 100);
+
+        // at least one transaction
+        let limit = SubPoolLimit::new(10, usize::MAX);
+        assert_eq!(config.max_sender_txs(&limit, false), 1);
+    }
 }
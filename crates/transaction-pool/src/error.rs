@@ -1,6 +1,9 @@
 //! Transaction pool errors
 
-use reth_primitives::{Address, BlobTransactionValidationError, InvalidTransactionError, TxHash};
+use crate::TransactionConditionalError;
+use reth_primitives::{
+    Address, BlobTransactionValidationError, InvalidTransactionError, Selector, TxHash,
+};
 
 /// Transaction pool result type.
 pub type PoolResult<T> = Result<T, PoolError>;
@@ -204,6 +207,31 @@ pub enum InvalidPoolTransactionError {
     /// invocation.
     #[error("intrinsic gas too low")]
     IntrinsicGasTooLow,
+    /// Thrown if the sender is not allowed by the pool's admission policy.
+    #[error("sender {0} not allowed")]
+    SenderNotAllowed(Address),
+    /// Thrown if the recipient is not allowed by the pool's admission policy.
+    #[error("recipient {0} not allowed")]
+    RecipientNotAllowed(Address),
+    /// Thrown if the priority fee is below the minimum of the pool's admission policy.
+    #[error("priority fee {fee} below minimum {minimum}")]
+    PriorityFeeBelowMinimum {
+        /// The priority fee, or gas price for legacy transactions, of the transaction
+        fee: u128,
+        /// The minimum priority fee
+        minimum: u128,
+    },
+    /// Thrown if the input data of a transaction is larger than allowed by the pool's admission
+    /// policy.
+    #[error("calldata size {0} exceeds max calldata size {1}")]
+    ExceedsMaxCalldataSize(usize, usize),
+    /// Thrown if the transaction calls a function selector that is blocked by the pool's admission
+    /// policy.
+    #[error("function selector {0} is blocked")]
+    BlockedSelector(Selector),
+    /// Thrown if the preconditions of a conditional transaction don't hold.
+    #[error(transparent)]
+    Conditional(#[from] TransactionConditionalError),
 }
 
 // === impl InvalidPoolTransactionError ===
@@ -259,6 +287,18 @@ impl InvalidPoolTransactionError {
             }
             InvalidPoolTransactionError::IntrinsicGasTooLow => true,
             InvalidPoolTransactionError::Overdraft => false,
+            InvalidPoolTransactionError::SenderNotAllowed(_) |
+            InvalidPoolTransactionError::RecipientNotAllowed(_) |
+            InvalidPoolTransactionError::PriorityFeeBelowMinimum { .. } |
+            InvalidPoolTransactionError::ExceedsMaxCalldataSize(_, _) |
+            InvalidPoolTransactionError::BlockedSelector(_) => {
+                // local admission policy
+                false
+            }
+            InvalidPoolTransactionError::Conditional(_) => {
+                // depends on the current block and state
+                false
+            }
             InvalidPoolTransactionError::Other(err) => err.is_bad_transaction(),
             InvalidPoolTransactionError::Eip4844(eip4844_err) => {
                 match eip4844_err {
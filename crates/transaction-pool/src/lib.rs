@@ -160,16 +160,21 @@ use tracing::{instrument, trace};
 
 pub use crate::{
     blobstore::{BlobStore, BlobStoreError},
+    conditional::{
+        AccountStorageCondition, ConditionalState, TransactionConditional,
+        TransactionConditionalError, MAX_TRANSACTION_CONDITIONAL_COST,
+    },
     config::{
-        LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-        REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+        LocalTransactionConfig, PoolConfig, PriceBumpConfig, SenderShareConfig, SubPoolLimit,
+        DEFAULT_PRICE_BUMP, REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+        TXPOOL_MAX_LOCAL_SENDER_SHARE_DEFAULT, TXPOOL_MAX_SENDER_SHARE_DEFAULT,
         TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
     },
     error::PoolResult,
     ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
     pool::{
-        blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, FullTransactionEvent,
-        TransactionEvent, TransactionEvents,
+        blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, DiscardReason,
+        FullTransactionEvent, TransactionEvent, TransactionEvents,
     },
     traits::*,
     validate::{
@@ -186,6 +191,7 @@ pub mod pool;
 pub mod validate;
 
 pub mod blobstore;
+mod conditional;
 mod config;
 mod identifier;
 mod ordering;
@@ -448,6 +454,13 @@ where
         self.pool.all_transactions()
     }
 
+    fn subpool_transactions(
+        &self,
+        subpool: SubPool,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        self.pool.subpool_transactions(subpool)
+    }
+
     fn remove_transactions(
         &self,
         hashes: Vec<TxHash>,
@@ -2,19 +2,25 @@
 
 use crate::{
     blobstore::{BlobStoreCanonTracker, BlobStoreUpdates},
+    conditional::TransactionConditional,
     error::PoolError,
     metrics::MaintainPoolMetrics,
-    traits::{CanonicalStateUpdate, ChangedAccount, TransactionPool, TransactionPoolExt},
+    traits::{
+        AllPoolTransactions, CanonicalStateUpdate, ChangedAccount, GetPooledTransactionLimit,
+        PoolTransaction, TransactionOrigin, TransactionPool, TransactionPoolExt,
+    },
     BlockInfo,
 };
+use alloy_rlp::{RlpDecodable, RlpEncodable};
 use futures_util::{
     future::{BoxFuture, Fuse, FusedFuture},
     FutureExt, Stream, StreamExt,
 };
 use reth_primitives::{
     fs::FsPathError, Address, BlockHash, BlockNumber, BlockNumberOrTag,
-    FromRecoveredPooledTransaction, FromRecoveredTransaction, IntoRecoveredTransaction,
-    PooledTransactionsElementEcRecovered, TransactionSigned,
+    FromRecoveredPooledTransaction, FromRecoveredTransaction, PooledTransactionsElement,
+    PooledTransactionsElementEcRecovered, SealedBlock, TransactionSigned,
+    TransactionSignedEcRecovered, TxHash,
 };
 use reth_provider::{
     BlockReaderIdExt, BundleStateWithReceipts, CanonStateNotification, ChainSpecProvider,
@@ -23,7 +29,7 @@ use reth_provider::{
 use reth_tasks::TaskSpawner;
 use std::{
     borrow::Borrow,
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     hash::{Hash, Hasher},
     path::{Path, PathBuf},
 };
@@ -55,12 +61,22 @@ impl Default for MaintainPoolConfig {
 pub struct LocalTransactionBackupConfig {
     /// Path to transactions backup file
     pub transactions_path: Option<PathBuf>,
+    /// Whether to back up all transactions in the pool instead of only the local ones.
+    ///
+    /// This includes the transactions of all subpools and the blob sidecars of blob transactions.
+    pub all_transactions: bool,
 }
 
 impl LocalTransactionBackupConfig {
     /// Receive path to transactions backup and return initialized config
     pub const fn with_local_txs_backup(transactions_path: PathBuf) -> Self {
-        Self { transactions_path: Some(transactions_path) }
+        Self { transactions_path: Some(transactions_path), all_transactions: false }
+    }
+
+    /// Sets whether all transactions should be backed up instead of only the local ones.
+    pub const fn with_all_transactions(mut self, all_transactions: bool) -> Self {
+        self.all_transactions = all_transactions;
+        self
     }
 }
 
@@ -123,6 +139,9 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
     let mut last_finalized_block =
         FinalizedBlockTracker::new(client.finalized_block_number().ok().flatten());
 
+    // keeps track of the conditionals of mined transactions, so they can be restored on reorgs
+    let mut mined_conditionals = MinedConditionals::default();
+
     // keeps track of any dirty accounts that we know of are out of sync with the pool
     let mut dirty_addresses = HashSet::new();
 
@@ -186,6 +205,7 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
         if let Some(finalized) =
             last_finalized_block.update(client.finalized_block_number().ok().flatten())
         {
+            mined_conditionals.on_finalized_block(finalized);
             match blob_store_tracker.on_finalized_block(finalized) {
                 BlobStoreUpdates::None => {}
                 BlobStoreUpdates::Finalized(blobs) => {
@@ -310,35 +330,16 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
 
                 // update the pool then re-inject the pruned transactions
                 // find all transactions that were mined in the old chain but not in the new chain
-                let pruned_old_transactions = old_blocks
-                    .transactions_ecrecovered()
-                    .filter(|tx| !new_mined_transactions.contains(&tx.hash))
-                    .filter_map(|tx| {
-                        if tx.is_eip4844() {
-                            // reorged blobs no longer include the blob, which is necessary for
-                            // validating the transaction. Even though the transaction could have
-                            // been validated previously, we still need the blob in order to
-                            // accurately set the transaction's
-                            // encoded-length which is propagated over the network.
-                            pool.get_blob(tx.hash)
-                                .ok()
-                                .flatten()
-                                .and_then(|sidecar| {
-                                    PooledTransactionsElementEcRecovered::try_from_blob_transaction(
-                                        tx, sidecar,
-                                    )
-                                    .ok()
-                                })
-                                .map(
-                                    <P as TransactionPool>::Transaction::from_recovered_pooled_transaction,
-                                )
-                        } else {
-                            Some(<P as TransactionPool>::Transaction::from_recovered_transaction(
-                                tx,
-                            ))
-                        }
-                    })
-                    .collect::<Vec<_>>();
+                let (pruned_conditional_transactions, pruned_old_transactions) =
+                    pruned_transactions(
+                        &pool,
+                        old_blocks
+                            .transactions_ecrecovered()
+                            .filter(|tx| !new_mined_transactions.contains(&tx.hash)),
+                        &mut mined_conditionals,
+                    );
+
+                mined_conditionals.insert_mined(&pool, new_tip.number, &new_mined_transactions);
 
                 // update the pool first
                 let update = CanonicalStateUpdate {
@@ -351,18 +352,108 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                 };
                 pool.on_canonical_state_change(update);
 
+                // the preconditions of conditional transactions may no longer hold on the new tip
+                remove_failed_conditionals(&client, &pool, &new_tip.block);
+
                 // all transactions that were mined in the old chain but not in the new chain need
                 // to be re-injected
                 //
                 // Note: we no longer know if the tx was local or external
                 // Because the transactions are not finalized, the corresponding blobs are still in
                 // blob store (if we previously received them from the network)
-                metrics.inc_reinserted_transactions(pruned_old_transactions.len());
+                metrics.inc_reinserted_transactions(
+                    pruned_old_transactions.len() + pruned_conditional_transactions.len(),
+                );
                 let _ = pool.add_external_transactions(pruned_old_transactions).await;
+                let _ = pool
+                    .add_transactions(TransactionOrigin::Private, pruned_conditional_transactions)
+                    .await;
 
                 // keep track of new mined blob transactions
                 blob_store_tracker.add_new_chain_blocks(&new_blocks);
             }
+            CanonStateNotification::Revert { old } => {
+                let (old_blocks, old_state) = old.inner();
+                let fork_block = old.fork_block();
+
+                if old_blocks.tip().hash() != pool_info.last_seen_block_hash {
+                    // the reverted chain is not the chain the pool is tracking
+                    maintained_state = MaintainedPoolState::Drifted;
+                }
+
+                // the fork block is the new tip
+                let new_tip = match client.block_by_hash(fork_block.hash) {
+                    Ok(Some(block)) => block.seal(fork_block.hash),
+                    Ok(None) => {
+                        debug!(target: "txpool", ?fork_block, "reverted to unknown block");
+                        maintained_state = MaintainedPoolState::Drifted;
+                        continue
+                    }
+                    Err(err) => {
+                        debug!(target: "txpool", %err, ?fork_block, "failed to load new tip");
+                        maintained_state = MaintainedPoolState::Drifted;
+                        continue
+                    }
+                };
+
+                let chain_spec = client.chain_spec();
+
+                // fees for the next block: `new_tip+1`
+                let pending_block_base_fee = new_tip
+                    .next_block_base_fee(chain_spec.base_fee_params(new_tip.timestamp + 12))
+                    .unwrap_or_default();
+                let pending_block_blob_fee = new_tip.next_block_blob_fee();
+
+                // all accounts changed in the reverted chain need to be reloaded at the new tip
+                let changed_accounts = match load_accounts(
+                    client.clone(),
+                    new_tip.hash(),
+                    old_state.accounts_iter().map(|(a, _)| a),
+                ) {
+                    Ok(LoadedAccounts { accounts, failed_to_load }) => {
+                        dirty_addresses.extend(failed_to_load);
+                        accounts
+                    }
+                    Err(err) => {
+                        let (addresses, err) = *err;
+                        debug!(
+                            target: "txpool",
+                            ?err,
+                            "failed to load reverted changed accounts at new tip: {:?}",
+                            new_tip.hash()
+                        );
+                        dirty_addresses.extend(addresses);
+                        vec![]
+                    }
+                };
+
+                // all transactions of the reverted chain are no longer mined
+                let (pruned_conditional_transactions, pruned_old_transactions) =
+                    pruned_transactions(
+                        &pool,
+                        old_blocks.transactions_ecrecovered(),
+                        &mut mined_conditionals,
+                    );
+
+                let update = CanonicalStateUpdate {
+                    new_tip: &new_tip,
+                    pending_block_base_fee,
+                    pending_block_blob_fee,
+                    changed_accounts,
+                    mined_transactions: Vec::new(),
+                };
+                pool.on_canonical_state_change(update);
+
+                remove_failed_conditionals(&client, &pool, &new_tip);
+
+                metrics.inc_reinserted_transactions(
+                    pruned_old_transactions.len() + pruned_conditional_transactions.len(),
+                );
+                let _ = pool.add_external_transactions(pruned_old_transactions).await;
+                let _ = pool
+                    .add_transactions(TransactionOrigin::Private, pruned_conditional_transactions)
+                    .await;
+            }
             CanonStateNotification::Commit { new } => {
                 let (blocks, state) = new.inner();
                 let tip = blocks.tip();
@@ -410,7 +501,9 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                     changed_accounts.push(acc);
                 }
 
-                let mined_transactions = blocks.transaction_hashes().collect();
+                let mined_transactions: Vec<_> = blocks.transaction_hashes().collect();
+                mined_conditionals.insert_mined(&pool, tip.number, &mined_transactions);
+                mined_conditionals.remove_below(tip.number.saturating_sub(max_update_depth));
 
                 // check if the range of the commit is canonical with the pool's block
                 if first_block.parent_hash != pool_info.last_seen_block_hash {
@@ -430,6 +523,9 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                 };
                 pool.on_canonical_state_change(update);
 
+                // the preconditions of conditional transactions may no longer hold on the new tip
+                remove_failed_conditionals(&client, &pool, &tip.block);
+
                 // keep track of mined blob transactions
                 blob_store_tracker.add_new_chain_blocks(&blocks);
             }
@@ -437,6 +533,48 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
     }
 }
 
+/// Keeps track of the conditionals of mined conditional transactions, so they can be restored when
+/// the transactions are re-injected into the pool after a reorg.
+#[derive(Debug, Default)]
+struct MinedConditionals {
+    /// The conditional of each mined transaction, by the block it was mined in.
+    conditionals: HashMap<TxHash, (BlockNumber, TransactionConditional)>,
+}
+
+impl MinedConditionals {
+    /// Records the conditionals of all pool transactions that were mined in the given block.
+    ///
+    /// This must be called before the mined transactions are removed from the pool.
+    fn insert_mined<'a, P, I>(&mut self, pool: &P, block_number: BlockNumber, mined: I)
+    where
+        P: TransactionPool,
+        I: IntoIterator<Item = &'a TxHash>,
+    {
+        let transactions = pool.get_all(mined.into_iter().copied().collect());
+        for tx in transactions {
+            if let Some(conditional) = tx.transaction.conditional() {
+                self.conditionals.insert(*tx.hash(), (block_number, conditional.clone()));
+            }
+        }
+    }
+
+    /// Removes the conditional of a transaction that was reorged out.
+    fn remove(&mut self, hash: &TxHash) -> Option<TransactionConditional> {
+        self.conditionals.remove(hash).map(|(_, conditional)| conditional)
+    }
+
+    /// Removes all conditionals of transactions mined before the given block, which are too deep
+    /// to be reorged.
+    fn remove_below(&mut self, block_number: BlockNumber) {
+        self.conditionals.retain(|_, (mined, _)| *mined >= block_number);
+    }
+
+    /// Removes all conditionals of finalized transactions.
+    fn on_finalized_block(&mut self, finalized: BlockNumber) {
+        self.remove_below(finalized + 1);
+    }
+}
+
 struct FinalizedBlockTracker {
     last_finalized_block: Option<BlockNumber>,
 }
@@ -548,6 +686,54 @@ where
     Ok(res)
 }
 
+/// Converts the transactions of reverted blocks back into pool transactions.
+///
+/// Returns the transactions whose conditional could be restored, which need to be submitted
+/// privately again, and the remaining transactions.
+fn pruned_transactions<P, I>(
+    pool: &P,
+    transactions: I,
+    mined_conditionals: &mut MinedConditionals,
+) -> (Vec<P::Transaction>, Vec<P::Transaction>)
+where
+    P: TransactionPool,
+    I: Iterator<Item = TransactionSignedEcRecovered>,
+{
+    let mut conditional_transactions = Vec::new();
+    let mut other_transactions = Vec::new();
+    for tx in transactions {
+        let mut tx = if tx.is_eip4844() {
+            // reorged blobs no longer include the blob, which is necessary for validating the
+            // transaction. Even though the transaction could have been validated previously, we
+            // still need the blob in order to accurately set the transaction's encoded-length
+            // which is propagated over the network.
+            let Some(tx) = pool
+                .get_blob(tx.hash)
+                .ok()
+                .flatten()
+                .and_then(|sidecar| {
+                    PooledTransactionsElementEcRecovered::try_from_blob_transaction(tx, sidecar)
+                        .ok()
+                })
+                .map(P::Transaction::from_recovered_pooled_transaction)
+            else {
+                continue
+            };
+            tx
+        } else {
+            P::Transaction::from_recovered_transaction(tx)
+        };
+
+        match mined_conditionals.remove(tx.hash()) {
+            Some(conditional) if tx.set_conditional(conditional).is_ok() => {
+                conditional_transactions.push(tx)
+            }
+            _ => other_transactions.push(tx),
+        }
+    }
+    (conditional_transactions, other_transactions)
+}
+
 /// Extracts all changed accounts from the BundleState
 fn changed_accounts_iter(
     state: &BundleStateWithReceipts,
@@ -558,8 +744,93 @@ fn changed_accounts_iter(
         .map(|(address, acc)| ChangedAccount { address, nonce: acc.nonce, balance: acc.balance })
 }
 
+/// Removes all conditional transactions whose preconditions don't hold on the block after the given
+/// block anymore.
+///
+/// If the state of the block is not available, no transactions are removed.
+fn remove_failed_conditionals<Client, P>(client: &Client, pool: &P, block: &SealedBlock)
+where
+    Client: StateProviderFactory,
+    P: TransactionPool,
+{
+    let AllPoolTransactions { pending, queued } = pool.all_transactions();
+    let mut conditionals = pending
+        .into_iter()
+        .chain(queued)
+        .filter(|tx| tx.transaction.conditional().is_some())
+        .peekable();
+    if conditionals.peek().is_none() {
+        return
+    }
+
+    let state = match client.state_by_block_hash(block.hash()) {
+        Ok(state) => state,
+        Err(err) => {
+            debug!(target: "txpool", %err, block=?block.hash(), "failed to load state to check conditional transactions");
+            return
+        }
+    };
+
+    let mut failed = Vec::new();
+    for tx in conditionals {
+        let Some(conditional) = tx.transaction.conditional() else { continue };
+        match conditional.check_next_block(&block.header, &*state) {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => {
+                trace!(target: "txpool", %err, hash=?tx.hash(), "conditional transaction no longer valid");
+                failed.push(*tx.hash());
+            }
+            Err(err) => {
+                debug!(target: "txpool", %err, hash=?tx.hash(), "failed to check conditional transaction");
+            }
+        }
+    }
+
+    if !failed.is_empty() {
+        pool.remove_transactions(failed);
+    }
+}
+
+/// A transaction stored in the transactions backup file, together with its origin.
+///
+/// Transactions are stored in their pooled form, so blob transactions include their sidecar.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+#[rlp(trailing)]
+struct BackupTransaction {
+    /// The [TransactionOrigin] of the transaction, see [origin_to_u8].
+    origin: u8,
+    /// The transaction in its pooled form.
+    transaction: PooledTransactionsElement,
+    /// The conditional of a conditional transaction.
+    conditional: Option<TransactionConditional>,
+}
+
+/// Encodes a [TransactionOrigin] for the transactions backup file.
+const fn origin_to_u8(origin: TransactionOrigin) -> u8 {
+    match origin {
+        TransactionOrigin::Local => 0,
+        TransactionOrigin::External => 1,
+        TransactionOrigin::Private => 2,
+    }
+}
+
+/// Decodes a [TransactionOrigin] from the transactions backup file.
+fn origin_from_u8(origin: u8) -> Result<TransactionOrigin, alloy_rlp::Error> {
+    match origin {
+        0 => Ok(TransactionOrigin::Local),
+        1 => Ok(TransactionOrigin::External),
+        2 => Ok(TransactionOrigin::Private),
+        _ => Err(alloy_rlp::Error::Custom("unknown transaction origin")),
+    }
+}
+
 /// Loads transactions from a file, decodes them from the RLP format, and inserts them
 /// into the transaction pool on node boot up.
+///
+/// All transactions are validated again by the pool's validator before they're inserted.
+/// Files written before all transactions were backed up only contain local transactions, which
+/// are still supported.
+///
 /// The file is removed after the transactions have been successfully processed.
 async fn load_and_reinsert_transactions<P>(
     pool: P,
@@ -579,46 +850,99 @@ where
         return Ok(())
     }
 
-    let txs_signed: Vec<TransactionSigned> = alloy_rlp::Decodable::decode(&mut data.as_slice())?;
+    let mut transactions_by_origin = HashMap::<TransactionOrigin, Vec<P::Transaction>>::new();
+    match <Vec<BackupTransaction> as alloy_rlp::Decodable>::decode(&mut data.as_slice()) {
+        Ok(backup) => {
+            for BackupTransaction { origin, transaction, conditional } in backup {
+                let origin = origin_from_u8(origin)?;
+                let Ok(transaction) = transaction.try_into_ecrecovered() else { continue };
+                let mut transaction =
+                    <P::Transaction>::from_recovered_pooled_transaction(transaction);
+                if let Some(conditional) = conditional {
+                    if transaction.set_conditional(conditional).is_err() {
+                        continue
+                    }
+                }
+                transactions_by_origin.entry(origin).or_default().push(transaction);
+            }
+        }
+        Err(_) => {
+            // legacy format: local transactions only
+            let txs_signed: Vec<TransactionSigned> =
+                alloy_rlp::Decodable::decode(&mut data.as_slice())?;
+            transactions_by_origin.insert(
+                TransactionOrigin::Local,
+                txs_signed
+                    .into_iter()
+                    .filter_map(|tx| {
+                        tx.try_ecrecovered().map(<P::Transaction>::from_recovered_transaction)
+                    })
+                    .collect(),
+            );
+        }
+    }
 
-    let pool_transactions = txs_signed
-        .into_iter()
-        .filter_map(|tx| tx.try_ecrecovered().map(<P::Transaction>::from_recovered_transaction))
-        .collect::<Vec<_>>();
-    let outcome = pool.add_transactions(crate::TransactionOrigin::Local, pool_transactions).await;
+    let mut num_txs = 0;
+    for (origin, transactions) in transactions_by_origin {
+        num_txs += pool.add_transactions(origin, transactions).await.len();
+    }
 
-    info!(target: "txpool", txs_file =?file_path, num_txs=%outcome.len(), "Successfully reinserted local transactions from file");
+    info!(target: "txpool", txs_file =?file_path, %num_txs, "Successfully reinserted transactions from file");
     reth_primitives::fs::remove_file(file_path)?;
     Ok(())
 }
 
-fn save_local_txs_backup<P>(pool: P, file_path: &Path)
+fn save_txs_backup<P>(pool: P, file_path: &Path, all_transactions: bool)
 where
     P: TransactionPool,
 {
-    let local_transactions = pool.get_local_transactions();
-    if local_transactions.is_empty() {
-        trace!(target: "txpool", "no local transactions to save");
-        return
+    let origins: &[TransactionOrigin] = if all_transactions {
+        &[TransactionOrigin::Local, TransactionOrigin::External, TransactionOrigin::Private]
+    } else {
+        &[TransactionOrigin::Local]
+    };
+
+    let mut transactions = Vec::new();
+    for origin in origins {
+        let pool_transactions = pool.get_transactions_by_origin(*origin);
+        if pool_transactions.is_empty() {
+            continue
+        }
+        let hashes = pool_transactions.iter().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        let mut conditionals = pool_transactions
+            .iter()
+            .filter_map(|tx| Some((*tx.hash(), tx.transaction.conditional()?.clone())))
+            .collect::<HashMap<_, _>>();
+
+        // blob transactions are only included if their sidecar is still in the blob store
+        transactions.extend(
+            pool.get_pooled_transaction_elements(hashes, GetPooledTransactionLimit::None)
+                .into_iter()
+                .map(|transaction| BackupTransaction {
+                    origin: origin_to_u8(*origin),
+                    conditional: conditionals.remove(transaction.hash()),
+                    transaction,
+                }),
+        );
     }
 
-    let local_transactions = local_transactions
-        .into_iter()
-        .map(|tx| tx.to_recovered_transaction().into_signed())
-        .collect::<Vec<_>>();
+    if transactions.is_empty() {
+        trace!(target: "txpool", "no transactions to save");
+        return
+    }
 
-    let num_txs = local_transactions.len();
+    let num_txs = transactions.len();
     let mut buf = alloy_rlp::BytesMut::new();
-    alloy_rlp::encode_list(&local_transactions, &mut buf);
-    info!(target: "txpool", txs_file =?file_path, num_txs=%num_txs, "Saving current local transactions");
+    alloy_rlp::encode_list(&transactions, &mut buf);
+    info!(target: "txpool", txs_file =?file_path, num_txs=%num_txs, all_transactions, "Saving current transactions");
     let parent_dir = file_path.parent().map(std::fs::create_dir_all).transpose();
 
     match parent_dir.map(|_| reth_primitives::fs::write(file_path, buf)) {
         Ok(_) => {
-            info!(target: "txpool", txs_file=?file_path, "Wrote local transactions to file");
+            info!(target: "txpool", txs_file=?file_path, "Wrote transactions to file");
         }
         Err(err) => {
-            warn!(target: "txpool", %err, txs_file=?file_path, "Failed to write local transactions to file");
+            warn!(target: "txpool", %err, txs_file=?file_path, "Failed to write transactions to file");
         }
     }
 }
@@ -639,6 +963,9 @@ pub enum TransactionsBackupError {
 
 /// Task which manages saving local transactions to the persistent file in case of shutdown.
 /// Reloads the transactions from the file on the boot up and inserts them into the pool.
+///
+/// If [LocalTransactionBackupConfig::all_transactions] is set, all transactions in the pool are
+/// saved instead.
 pub async fn backup_local_transactions_task<P>(
     shutdown: reth_tasks::shutdown::GracefulShutdown,
     pool: P,
@@ -658,7 +985,7 @@ pub async fn backup_local_transactions_task<P>(
     let graceful_guard = shutdown.await;
 
     // write transactions to disk
-    save_local_txs_backup(pool, &transactions_path);
+    save_txs_backup(pool, &transactions_path, config.all_transactions);
 
     drop(graceful_guard)
 }
@@ -672,7 +999,8 @@ mod tests {
         CoinbaseTipOrdering, EthPooledTransaction, Pool, PoolTransaction, TransactionOrigin,
     };
     use reth_primitives::{
-        fs, hex, FromRecoveredPooledTransaction, PooledTransactionsElement, MAINNET, U256,
+        fs, hex, Block, FromRecoveredPooledTransaction, PooledTransactionsElement, B256, MAINNET,
+        U256,
     };
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
     use reth_tasks::TaskManager;
@@ -731,10 +1059,109 @@ mod tests {
 
         let data = fs::read(transactions_path).unwrap();
 
-        let txs: Vec<TransactionSigned> =
+        let txs: Vec<BackupTransaction> =
             alloy_rlp::Decodable::decode(&mut data.as_slice()).unwrap();
         assert_eq!(txs.len(), 1);
 
         temp_dir.close().unwrap();
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_save_and_reload_all_txs_backup() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let transactions_path = temp_dir.path().join(FILENAME).with_extension(EXTENSION);
+        let tx_bytes = hex!("02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507");
+        let tx = PooledTransactionsElement::decode_enveloped(tx_bytes.into()).unwrap();
+        let provider = MockEthProvider::default();
+        let transaction = EthPooledTransaction::from_recovered_pooled_transaction(
+            tx.try_into_ecrecovered().unwrap(),
+        );
+        let sender = hex!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326").into();
+        provider.add_account(sender, ExtendedAccount::new(42, U256::MAX));
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .build(provider, blob_store.clone());
+
+        let new_pool = || {
+            Pool::new(
+                validator.clone(),
+                CoinbaseTipOrdering::default(),
+                blob_store.clone(),
+                Default::default(),
+            )
+        };
+
+        let txpool = new_pool();
+        txpool.add_transaction(TransactionOrigin::External, transaction.clone()).await.unwrap();
+        assert!(txpool.get_local_transactions().is_empty());
+
+        save_txs_backup(txpool, &transactions_path, true);
+
+        let data = fs::read(&transactions_path).unwrap();
+        let txs: Vec<BackupTransaction> =
+            alloy_rlp::Decodable::decode(&mut data.as_slice()).unwrap();
+        assert_eq!(txs.len(), 1);
+        assert_eq!(txs[0].origin, origin_to_u8(TransactionOrigin::External));
+
+        // transactions are validated and inserted with their original origin
+        let txpool = new_pool();
+        load_and_reinsert_transactions(txpool.clone(), &transactions_path).await.unwrap();
+        let txs = txpool.get_transactions_by_origin(TransactionOrigin::External);
+        assert_eq!(txs.len(), 1);
+        assert_eq!(*txs[0].hash(), *transaction.hash());
+        assert!(!transactions_path.exists());
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_save_and_reload_conditional_txs_backup() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let transactions_path = temp_dir.path().join(FILENAME).with_extension(EXTENSION);
+        let tx_bytes = hex!("02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507");
+        let tx = PooledTransactionsElement::decode_enveloped(tx_bytes.into()).unwrap();
+        let provider = MockEthProvider::default();
+        let mut transaction = EthPooledTransaction::from_recovered_pooled_transaction(
+            tx.try_into_ecrecovered().unwrap(),
+        );
+        // only holds for the block after the latest block
+        let conditional =
+            TransactionConditional { block_number_min: Some(1), ..Default::default() };
+        transaction.set_conditional(conditional.clone()).unwrap();
+        let sender = hex!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326").into();
+        provider.add_account(sender, ExtendedAccount::new(42, U256::MAX));
+        provider.add_block(B256::random(), Block::default());
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .build(provider, blob_store.clone());
+
+        let new_pool = || {
+            Pool::new(
+                validator.clone(),
+                CoinbaseTipOrdering::default(),
+                blob_store.clone(),
+                Default::default(),
+            )
+        };
+
+        let txpool = new_pool();
+        txpool.add_transaction(TransactionOrigin::Private, transaction.clone()).await.unwrap();
+
+        save_txs_backup(txpool, &transactions_path, true);
+
+        let data = fs::read(&transactions_path).unwrap();
+        let txs: Vec<BackupTransaction> =
+            alloy_rlp::Decodable::decode(&mut data.as_slice()).unwrap();
+        assert_eq!(txs.len(), 1);
+        assert_eq!(txs[0].conditional, Some(conditional.clone()));
+
+        // the conditional is restored
+        let txpool = new_pool();
+        load_and_reinsert_transactions(txpool.clone(), &transactions_path).await.unwrap();
+        let txs = txpool.get_transactions_by_origin(TransactionOrigin::Private);
+        assert_eq!(txs.len(), 1);
+        assert_eq!(txs[0].transaction.conditional(), Some(&conditional));
+
+        temp_dir.close().unwrap();
+    }
 }
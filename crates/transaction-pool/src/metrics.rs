@@ -1,5 +1,6 @@
 //! Transaction pool metrics.
 
+use crate::error::InvalidPoolTransactionError;
 use reth_metrics::{
     metrics::{Counter, Gauge},
     Metrics,
@@ -93,3 +94,41 @@ impl MaintainPoolMetrics {
         self.drift_count.increment(1);
     }
 }
+
+/// Transaction pool admission policy metrics
+#[derive(Metrics)]
+#[metrics(scope = "transaction_pool.admission_policy")]
+pub struct AdmissionPolicyMetrics {
+    /// Number of transactions rejected because of their sender
+    pub(crate) rejected_sender: Counter,
+    /// Number of transactions rejected because of their recipient
+    pub(crate) rejected_recipient: Counter,
+    /// Number of transactions rejected because of a priority fee below the minimum
+    pub(crate) rejected_priority_fee: Counter,
+    /// Number of transactions rejected because of their calldata size
+    pub(crate) rejected_calldata_size: Counter,
+    /// Number of transactions rejected because of a blocked function selector
+    pub(crate) rejected_selector: Counter,
+    /// Number of transactions rejected by other policies
+    pub(crate) rejected_other: Counter,
+}
+
+impl AdmissionPolicyMetrics {
+    #[inline]
+    pub(crate) fn record_rejection(&self, err: &InvalidPoolTransactionError) {
+        match err {
+            InvalidPoolTransactionError::SenderNotAllowed(_) => self.rejected_sender.increment(1),
+            InvalidPoolTransactionError::RecipientNotAllowed(_) => {
+                self.rejected_recipient.increment(1)
+            }
+            InvalidPoolTransactionError::PriorityFeeBelowMinimum { .. } => {
+                self.rejected_priority_fee.increment(1)
+            }
+            InvalidPoolTransactionError::ExceedsMaxCalldataSize(_, _) => {
+                self.rejected_calldata_size.increment(1)
+            }
+            InvalidPoolTransactionError::BlockedSelector(_) => self.rejected_selector.increment(1),
+            _ => self.rejected_other.increment(1),
+        }
+    }
+}
@@ -13,7 +13,7 @@ use crate::{
     validate::ValidTransaction,
     AllPoolTransactions, AllTransactionsEvents, BestTransactions, BlockInfo, EthPooledTransaction,
     NewTransactionEvent, PoolResult, PoolSize, PoolTransaction, PooledTransactionsElement,
-    PropagatedTransactions, TransactionEvents, TransactionOrigin, TransactionPool,
+    PropagatedTransactions, SubPool, TransactionEvents, TransactionOrigin, TransactionPool,
     TransactionValidationOutcome, TransactionValidator, ValidPoolTransaction,
 };
 use reth_eth_wire::HandleAnnouncement;
@@ -167,6 +167,13 @@ impl TransactionPool for NoopTransactionPool {
         AllPoolTransactions::default()
     }
 
+    fn subpool_transactions(
+        &self,
+        _subpool: SubPool,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        vec![]
+    }
+
     fn remove_transactions(
         &self,
         _hashes: Vec<TxHash>,
@@ -64,6 +64,11 @@ impl<T: PoolTransaction> BlobTransactions<T> {
         id
     }
 
+    /// Returns an iterator over all transactions in the pool
+    pub(crate) fn all(&self) -> impl Iterator<Item = Arc<ValidPoolTransaction<T>>> + '_ {
+        self.by_id.values().map(|tx| tx.transaction.clone())
+    }
+
     /// Removes the transaction from the pool
     pub(crate) fn remove_transaction(
         &mut self,
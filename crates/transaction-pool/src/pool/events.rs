@@ -1,4 +1,4 @@
-use crate::{traits::PropagateKind, PoolTransaction, ValidPoolTransaction};
+use crate::{pool::state::SubPool, traits::PropagateKind, PoolTransaction, ValidPoolTransaction};
 use reth_primitives::{TxHash, B256};
 use std::sync::Arc;
 
@@ -28,8 +28,26 @@ pub enum FullTransactionEvent<T: PoolTransaction> {
         /// The transaction that replaced the event subject.
         replaced_by: TxHash,
     },
+    /// Transaction was moved from one sub-pool to another.
+    Moved {
+        /// The hash of the moved transaction.
+        tx_hash: TxHash,
+        /// The sub-pool the transaction was moved from.
+        from: SubPool,
+        /// The sub-pool the transaction was moved to.
+        to: SubPool,
+    },
     /// Transaction was dropped due to configured limits.
     Discarded(TxHash),
+    /// Transaction was removed from the pool, or rejected before it was added.
+    ///
+    /// This follows the [FullTransactionEvent::Discarded] event of the same transaction.
+    DiscardedWithReason {
+        /// The hash of the discarded transaction.
+        tx_hash: TxHash,
+        /// Why the transaction was discarded.
+        reason: DiscardReason,
+    },
     /// Transaction became invalid indefinitely.
     Invalid(TxHash),
     /// Transaction was propagated to peers.
@@ -47,13 +65,33 @@ impl<T: PoolTransaction> Clone for FullTransactionEvent<T> {
             Self::Replaced { transaction, replaced_by } => {
                 Self::Replaced { transaction: Arc::clone(transaction), replaced_by: *replaced_by }
             }
+            Self::Moved { tx_hash, from, to } => {
+                Self::Moved { tx_hash: *tx_hash, from: *from, to: *to }
+            }
             Self::Discarded(hash) => Self::Discarded(*hash),
+            Self::DiscardedWithReason { tx_hash, reason } => {
+                Self::DiscardedWithReason { tx_hash: *tx_hash, reason: *reason }
+            }
             Self::Invalid(hash) => Self::Invalid(*hash),
             Self::Propagated(propagated) => Self::Propagated(Arc::clone(propagated)),
         }
     }
 }
 
+/// Why a transaction was discarded, see [FullTransactionEvent::DiscardedWithReason].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DiscardReason {
+    /// The transaction failed validation and was never added to the pool.
+    Invalid,
+    /// The nonce of the transaction is lower than the nonce of its sender on chain.
+    NonceTooLow,
+    /// The transaction was evicted to respect the size limits of the pool.
+    SizeLimit,
+    /// The transaction was explicitly removed from the pool.
+    Removed,
+}
+
 /// Various events that describe status changes of a transaction.
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
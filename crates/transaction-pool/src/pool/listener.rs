@@ -1,7 +1,10 @@
 //! Listeners for the transaction-pool
 
 use crate::{
-    pool::events::{FullTransactionEvent, TransactionEvent},
+    pool::{
+        events::{DiscardReason, FullTransactionEvent, TransactionEvent},
+        state::SubPool,
+    },
     traits::PropagateKind,
     PoolTransaction, ValidPoolTransaction,
 };
@@ -160,9 +163,24 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
         );
     }
 
+    /// Notify listeners about a transaction that was moved between sub-pools.
+    ///
+    /// This is only broadcast to listeners for all transactions.
+    pub(crate) fn moved(&mut self, tx: &TxHash, from: SubPool, to: SubPool) {
+        self.all_events_broadcaster.broadcast(FullTransactionEvent::Moved {
+            tx_hash: *tx,
+            from,
+            to,
+        });
+    }
+
     /// Notify listeners about a transaction that was discarded.
-    pub(crate) fn discarded(&mut self, tx: &TxHash) {
+    ///
+    /// Listeners for all transactions are also notified about the reason.
+    pub(crate) fn discarded(&mut self, tx: &TxHash, reason: DiscardReason) {
         self.broadcast_event(tx, TransactionEvent::Discarded, FullTransactionEvent::Discarded(*tx));
+        self.all_events_broadcaster
+            .broadcast(FullTransactionEvent::DiscardedWithReason { tx_hash: *tx, reason });
     }
 
     /// Notify listeners that the transaction was mined
@@ -72,6 +72,7 @@ use crate::{
         listener::PoolEventBroadcast,
         state::SubPool,
         txpool::{SenderInfo, TxPool},
+        update::MovedTransaction,
     },
     traits::{
         AllPoolTransactions, BestTransactionsAttributes, BlockInfo, NewTransactionEvent, PoolSize,
@@ -106,7 +107,7 @@ use crate::{
 use alloy_rlp::Encodable;
 pub use best::BestTransactionFilter;
 pub use blob::{blob_tx_priority, fee_delta};
-pub use events::{FullTransactionEvent, TransactionEvent};
+pub use events::{DiscardReason, FullTransactionEvent, TransactionEvent};
 pub use listener::{AllTransactionsEvents, TransactionEvents};
 pub use parked::{BasefeeOrd, ParkedOrd, ParkedPool};
 pub use pending::PendingPool;
@@ -192,7 +193,11 @@ where
     }
     /// Returns the currently tracked block
     pub(crate) fn set_block_info(&self, info: BlockInfo) {
-        self.pool.write().set_block_info(info)
+        let UpdateOutcome { promoted, moved, .. } = self.pool.write().apply_block_info(info);
+        let mut listener = self.event_listener.write();
+
+        moved.iter().for_each(|tx| listener.moved(&tx.hash, tx.from, tx.to));
+        promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
     }
 
     /// Returns the internal `SenderId` for this address
@@ -359,7 +364,7 @@ where
         );
 
         // This will discard outdated transactions based on the account's nonce
-        self.delete_discarded_blobs(outcome.discarded.iter());
+        self.delete_discarded_blobs(outcome.discarded.iter().map(|(tx, _)| tx));
 
         // notify listeners about updates
         self.notify_on_new_state(outcome);
@@ -370,16 +375,17 @@ where
     /// This will either promote or discard transactions based on the new account state.
     pub(crate) fn update_accounts(&self, accounts: Vec<ChangedAccount>) {
         let changed_senders = self.changed_senders(accounts.into_iter());
-        let UpdateOutcome { promoted, discarded } =
+        let UpdateOutcome { promoted, discarded, moved } =
             self.pool.write().update_accounts(changed_senders);
         let mut listener = self.event_listener.write();
 
+        moved.iter().for_each(|tx| listener.moved(&tx.hash, tx.from, tx.to));
         promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
-        discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
+        discarded.iter().for_each(|(tx, reason)| listener.discarded(tx.hash(), *reason));
 
         // This deletes outdated blob txs from the blob store, based on the account's nonce. This is
         // called during txpool maintenance when the pool drifted.
-        self.delete_discarded_blobs(discarded.iter());
+        self.delete_discarded_blobs(discarded.iter().map(|(tx, _)| tx));
     }
 
     /// Add a single validated transaction into the pool.
@@ -447,7 +453,7 @@ where
                 self.notify_event_listeners(&added);
 
                 if let Some(discarded) = added.discarded_transactions() {
-                    self.delete_discarded_blobs(discarded.iter());
+                    self.delete_discarded_blobs(discarded.iter().map(|(tx, _)| tx));
                 }
 
                 // Notify listeners for _all_ transactions
@@ -457,12 +463,12 @@ where
             }
             TransactionValidationOutcome::Invalid(tx, err) => {
                 let mut listener = self.event_listener.write();
-                listener.discarded(tx.hash());
+                listener.discarded(tx.hash(), DiscardReason::Invalid);
                 Err(PoolError::new(*tx.hash(), err))
             }
             TransactionValidationOutcome::Error(tx_hash, err) => {
                 let mut listener = self.event_listener.write();
-                listener.discarded(&tx_hash);
+                listener.discarded(&tx_hash, DiscardReason::Invalid);
                 Err(PoolError::other(tx_hash, err))
             }
         }
@@ -500,7 +506,7 @@ where
 
         {
             let mut listener = self.event_listener.write();
-            discarded.iter().for_each(|tx| listener.discarded(tx));
+            discarded.iter().for_each(|tx| listener.discarded(tx, DiscardReason::SizeLimit));
         }
 
         // It may happen that a newly added transaction is immediately discarded, so we need to
@@ -587,14 +593,15 @@ where
             })
         }
 
-        let OnNewCanonicalStateOutcome { mined, promoted, discarded, block_hash } = outcome;
+        let OnNewCanonicalStateOutcome { mined, promoted, discarded, moved, block_hash } = outcome;
 
         // broadcast specific transaction events
         let mut listener = self.event_listener.write();
 
         mined.iter().for_each(|tx| listener.mined(tx, block_hash));
+        moved.iter().for_each(|tx| listener.moved(&tx.hash, tx.from, tx.to));
         promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
-        discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
+        discarded.iter().for_each(|(tx, reason)| listener.discarded(tx.hash(), *reason));
     }
 
     /// Fire events for the newly added transaction if there are any.
@@ -603,11 +610,13 @@ where
 
         match tx {
             AddedTransaction::Pending(tx) => {
-                let AddedPendingTransaction { transaction, promoted, discarded, replaced } = tx;
+                let AddedPendingTransaction { transaction, promoted, discarded, moved, replaced } =
+                    tx;
 
                 listener.pending(transaction.hash(), replaced.clone());
+                moved.iter().for_each(|tx| listener.moved(&tx.hash, tx.from, tx.to));
                 promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
-                discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
+                discarded.iter().for_each(|(tx, reason)| listener.discarded(tx.hash(), *reason));
             }
             AddedTransaction::Parked { transaction, replaced, .. } => {
                 listener.queued(transaction.hash());
@@ -643,6 +652,14 @@ where
         self.get_pool_data().queued_transactions()
     }
 
+    /// Returns all transactions from the given sub-pool
+    pub(crate) fn subpool_transactions(
+        &self,
+        subpool: SubPool,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.get_pool_data().subpool_transactions(subpool)
+    }
+
     /// Returns all transactions in the pool
     pub(crate) fn all_transactions(&self) -> AllPoolTransactions<T::Transaction> {
         let pool = self.get_pool_data();
@@ -664,7 +681,7 @@ where
 
         let mut listener = self.event_listener.write();
 
-        removed.iter().for_each(|tx| listener.discarded(tx.hash()));
+        removed.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::Removed));
 
         removed
     }
@@ -909,8 +926,10 @@ pub struct AddedPendingTransaction<T: PoolTransaction> {
     replaced: Option<Arc<ValidPoolTransaction<T>>>,
     /// transactions promoted to the pending queue
     promoted: Vec<Arc<ValidPoolTransaction<T>>>,
-    /// transactions that failed and became discarded
-    discarded: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// transactions that failed and became discarded, and why
+    discarded: Vec<(Arc<ValidPoolTransaction<T>>, DiscardReason)>,
+    /// transactions that were moved between sub-pools
+    moved: Vec<MovedTransaction>,
 }
 
 impl<T: PoolTransaction> AddedPendingTransaction<T> {
@@ -1018,7 +1037,9 @@ impl<T: PoolTransaction> AddedTransaction<T> {
     }
 
     /// Returns the discarded transactions if there were any
-    pub(crate) fn discarded_transactions(&self) -> Option<&[Arc<ValidPoolTransaction<T>>]> {
+    pub(crate) fn discarded_transactions(
+        &self,
+    ) -> Option<&[(Arc<ValidPoolTransaction<T>>, DiscardReason)]> {
         match self {
             AddedTransaction::Pending(tx) => Some(&tx.discarded),
             AddedTransaction::Parked { .. } => None,
@@ -1078,8 +1099,10 @@ pub(crate) struct OnNewCanonicalStateOutcome<T: PoolTransaction> {
     pub(crate) mined: Vec<TxHash>,
     /// Transactions promoted to the pending pool.
     pub(crate) promoted: Vec<Arc<ValidPoolTransaction<T>>>,
-    /// transaction that were discarded during the update
-    pub(crate) discarded: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// transaction that were discarded during the update, and why
+    pub(crate) discarded: Vec<(Arc<ValidPoolTransaction<T>>, DiscardReason)>,
+    /// Transactions that were moved between sub-pools.
+    pub(crate) moved: Vec<MovedTransaction>,
 }
 
 impl<T: PoolTransaction> OnNewCanonicalStateOutcome<T> {
@@ -1,24 +1,25 @@
 //! The internal transaction pool implementation.
 
 use crate::{
-    config::{LocalTransactionConfig, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER},
+    config::{LocalTransactionConfig, SenderShareConfig, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER},
     error::{Eip4844PoolTransactionError, InvalidPoolTransactionError, PoolError, PoolErrorKind},
     identifier::{SenderId, TransactionId},
     metrics::TxPoolMetrics,
     pool::{
         best::BestTransactions,
         blob::BlobTransactions,
+        events::DiscardReason,
         parked::{BasefeeOrd, ParkedPool, QueuedOrd},
         pending::PendingPool,
         state::{SubPool, TxState},
-        update::{Destination, PoolUpdate},
+        update::{Destination, MovedTransaction, PoolUpdate},
         AddedPendingTransaction, AddedTransaction, OnNewCanonicalStateOutcome,
     },
     traits::{BestTransactionsAttributes, BlockInfo, PoolSize},
-    PoolConfig, PoolResult, PoolTransaction, PriceBumpConfig, TransactionOrdering,
+    PoolConfig, PoolResult, PoolTransaction, PriceBumpConfig, SubPoolLimit, TransactionOrdering,
     ValidPoolTransaction, U256,
 };
-use fnv::FnvHashMap;
+use fnv::{FnvHashMap, FnvHashSet};
 use reth_primitives::{
     constants::{
         eip4844::BLOB_TX_MIN_BLOB_GASPRICE, ETHEREUM_BLOCK_GAS_LIMIT, MIN_PROTOCOL_BASE_FEE,
@@ -27,7 +28,7 @@ use reth_primitives::{
 };
 use std::{
     cmp::Ordering,
-    collections::{btree_map::Entry, hash_map, BTreeMap, HashMap, HashSet},
+    collections::{btree_map::Entry, hash_map, BTreeMap, BinaryHeap, HashMap, HashSet},
     fmt,
     ops::Bound::{Excluded, Unbounded},
     sync::Arc,
@@ -70,6 +71,8 @@ pub struct TxPool<T: TransactionOrdering> {
     blob_pool: BlobTransactions<T::Transaction>,
     /// All transactions in the pool.
     all_transactions: AllTransactions<T::Transaction>,
+    /// The number of transactions of each sender in the sub-pools, indexed by [SubPool].
+    subpool_senders: [SubPoolSenders; 4],
     /// Transaction pool metrics
     metrics: TxPoolMetrics,
 }
@@ -86,6 +89,13 @@ impl<T: TransactionOrdering> TxPool<T> {
             basefee_pool: Default::default(),
             blob_pool: Default::default(),
             all_transactions: AllTransactions::new(&config),
+            subpool_senders: [
+                &config.queued_limit,
+                &config.basefee_limit,
+                &config.blob_limit,
+                &config.pending_limit,
+            ]
+            .map(|limit| SubPoolSenders::new(&config.sender_share, limit)),
             config,
             metrics: Default::default(),
         }
@@ -141,7 +151,12 @@ impl<T: TransactionOrdering> TxPool<T> {
     }
 
     /// Updates the tracked blob fee
-    fn update_blob_fee(&mut self, mut pending_blob_fee: u128, base_fee_update: Ordering) {
+    fn update_blob_fee(
+        &mut self,
+        mut pending_blob_fee: u128,
+        base_fee_update: Ordering,
+        outcome: &mut UpdateOutcome<T::Transaction>,
+    ) {
         std::mem::swap(&mut self.all_transactions.pending_fees.blob_fee, &mut pending_blob_fee);
         match (self.all_transactions.pending_fees.blob_fee.cmp(&pending_blob_fee), base_fee_update)
         {
@@ -164,7 +179,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                         tx.subpool = tx.state.into();
                         tx.subpool
                     };
-                    self.add_transaction_to_subpool(to, tx);
+                    self.on_fee_update_move(SubPool::Pending, to, tx, outcome);
                 }
             }
             (Ordering::Less, Ordering::Equal) | (_, Ordering::Less) => {
@@ -181,7 +196,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                         tx.subpool = tx.state.into();
                         tx.subpool
                     };
-                    self.add_transaction_to_subpool(to, tx);
+                    self.on_fee_update_move(SubPool::Blob, to, tx, outcome);
                 }
             }
             (Ordering::Less, Ordering::Greater) => {
@@ -198,7 +213,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                         tx.subpool = tx.state.into();
                         tx.subpool
                     };
-                    self.add_transaction_to_subpool(to, tx);
+                    self.on_fee_update_move(SubPool::Pending, to, tx, outcome);
                 }
 
                 // decreased blob fee or base fee: recheck blob pool and promote all that are now
@@ -214,7 +229,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                         tx.subpool = tx.state.into();
                         tx.subpool
                     };
-                    self.add_transaction_to_subpool(to, tx);
+                    self.on_fee_update_move(SubPool::Blob, to, tx, outcome);
                 }
             }
         }
@@ -224,7 +239,11 @@ impl<T: TransactionOrdering> TxPool<T> {
     ///
     /// Depending on the change in direction of the basefee, this will promote or demote
     /// transactions from the basefee pool.
-    fn update_basefee(&mut self, mut pending_basefee: u64) -> Ordering {
+    fn update_basefee(
+        &mut self,
+        mut pending_basefee: u64,
+        outcome: &mut UpdateOutcome<T::Transaction>,
+    ) -> Ordering {
         std::mem::swap(&mut self.all_transactions.pending_fees.base_fee, &mut pending_basefee);
         match self.all_transactions.pending_fees.base_fee.cmp(&pending_basefee) {
             Ordering::Equal => {
@@ -243,7 +262,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                         tx.subpool = tx.state.into();
                         tx.subpool
                     };
-                    self.add_transaction_to_subpool(to, tx);
+                    self.on_fee_update_move(SubPool::Pending, to, tx, outcome);
                 }
 
                 Ordering::Greater
@@ -260,7 +279,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                         tx.subpool = tx.state.into();
                         tx.subpool
                     };
-                    self.add_transaction_to_subpool(to, tx);
+                    self.on_fee_update_move(SubPool::BaseFee, to, tx, outcome);
                 }
 
                 Ordering::Less
@@ -268,10 +287,36 @@ impl<T: TransactionOrdering> TxPool<T> {
         }
     }
 
+    /// Moves a transaction that was removed from the `from` sub-pool after a fee update into its
+    /// new sub-pool, and records the move in the outcome.
+    fn on_fee_update_move(
+        &mut self,
+        from: SubPool,
+        to: SubPool,
+        tx: Arc<ValidPoolTransaction<T::Transaction>>,
+        outcome: &mut UpdateOutcome<T::Transaction>,
+    ) {
+        self.subpool_senders[from as usize].remove(tx.sender_id());
+        if from != to {
+            outcome.moved.push(MovedTransaction { hash: *tx.hash(), from, to });
+            if to.is_pending() {
+                outcome.promoted.push(tx.clone());
+            }
+        }
+        self.add_transaction_to_subpool(to, tx);
+    }
+
     /// Sets the current block info for the pool.
     ///
     /// This will also apply updates to the pool based on the new base fee
     pub fn set_block_info(&mut self, info: BlockInfo) {
+        self.apply_block_info(info);
+    }
+
+    /// Sets the current block info for the pool, see [Self::set_block_info].
+    ///
+    /// Returns the transactions that were moved between sub-pools because of the new fees.
+    pub(crate) fn apply_block_info(&mut self, info: BlockInfo) -> UpdateOutcome<T::Transaction> {
         let BlockInfo {
             last_seen_block_hash,
             last_seen_block_number,
@@ -280,11 +325,13 @@ impl<T: TransactionOrdering> TxPool<T> {
         } = info;
         self.all_transactions.last_seen_block_hash = last_seen_block_hash;
         self.all_transactions.last_seen_block_number = last_seen_block_number;
-        let basefee_ordering = self.update_basefee(pending_basefee);
+        let mut outcome = UpdateOutcome::default();
+        let basefee_ordering = self.update_basefee(pending_basefee, &mut outcome);
 
         if let Some(blob_fee) = pending_blob_fee {
-            self.update_blob_fee(blob_fee, basefee_ordering)
+            self.update_blob_fee(blob_fee, basefee_ordering, &mut outcome)
         }
+        outcome
     }
 
     /// Returns an iterator that yields transactions that are ready to be included in the block.
@@ -333,6 +380,19 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.basefee_pool.all().chain(self.queued_pool.all()).collect()
     }
 
+    /// Returns all transactions from the given sub-pool
+    pub(crate) fn subpool_transactions(
+        &self,
+        subpool: SubPool,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        match subpool {
+            SubPool::Pending => self.pending_pool.all().collect(),
+            SubPool::BaseFee => self.basefee_pool.all().collect(),
+            SubPool::Queued => self.queued_pool.all().collect(),
+            SubPool::Blob => self.blob_pool.all().collect(),
+        }
+    }
+
     /// Returns queued and pending transactions for the specified sender
     pub fn queued_and_pending_txs_by_sender(
         &self,
@@ -425,11 +485,17 @@ impl<T: TransactionOrdering> TxPool<T> {
             }
         }
 
-        let UpdateOutcome { promoted, discarded } = self.update_accounts(changed_senders);
+        let UpdateOutcome { promoted, discarded, moved } = self.update_accounts(changed_senders);
 
         self.metrics.performed_state_updates.increment(1);
 
-        OnNewCanonicalStateOutcome { block_hash, mined: mined_transactions, promoted, discarded }
+        OnNewCanonicalStateOutcome {
+            block_hash,
+            mined: mined_transactions,
+            promoted,
+            discarded,
+            moved,
+        }
     }
 
     /// Update sub-pools size metrics.
@@ -493,7 +559,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                 self.add_new_transaction(transaction.clone(), replaced_tx.clone(), move_to);
                 // Update inserted transactions metric
                 self.metrics.inserted_transactions.increment(1);
-                let UpdateOutcome { promoted, discarded } = self.process_updates(updates);
+                let UpdateOutcome { promoted, discarded, moved } = self.process_updates(updates);
 
                 let replaced = replaced_tx.map(|(tx, _)| tx);
 
@@ -503,6 +569,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                         transaction,
                         promoted,
                         discarded,
+                        moved,
                         replaced,
                     })
                 } else {
@@ -572,20 +639,19 @@ impl<T: TransactionOrdering> TxPool<T> {
         let mut outcome = UpdateOutcome::default();
         for PoolUpdate { id, hash, current, destination } in updates {
             match destination {
-                Destination::Discard => {
+                Destination::Discard(reason) => {
                     // remove the transaction from the pool and subpool
                     if let Some(tx) = self.prune_transaction_by_hash(&hash) {
-                        outcome.discarded.push(tx);
+                        outcome.discarded.push((tx, reason));
                     }
                     self.metrics.removed_transactions.increment(1);
                 }
                 Destination::Pool(move_to) => {
                     debug_assert_ne!(&move_to, &current, "destination must be different");
-                    let moved = self.move_transaction(current, move_to, &id);
+                    let Some(tx) = self.move_transaction(current, move_to, &id) else { continue };
+                    outcome.moved.push(MovedTransaction { hash, from: current, to: move_to });
                     if matches!(move_to, SubPool::Pending) {
-                        if let Some(tx) = moved {
-                            outcome.promoted.push(tx);
-                        }
+                        outcome.promoted.push(tx);
                     }
                 }
             }
@@ -662,12 +728,14 @@ impl<T: TransactionOrdering> TxPool<T> {
         pool: SubPool,
         tx: &TransactionId,
     ) -> Option<Arc<ValidPoolTransaction<T::Transaction>>> {
-        match pool {
+        let tx = match pool {
             SubPool::Queued => self.queued_pool.remove_transaction(tx),
             SubPool::Pending => self.pending_pool.remove_transaction(tx),
             SubPool::BaseFee => self.basefee_pool.remove_transaction(tx),
             SubPool::Blob => self.blob_pool.remove_transaction(tx),
-        }
+        }?;
+        self.subpool_senders[pool as usize].remove(tx.sender_id());
+        Some(tx)
     }
 
     /// Removes the transaction from the given pool and advance sub-pool internal state, with the
@@ -677,12 +745,14 @@ impl<T: TransactionOrdering> TxPool<T> {
         pool: SubPool,
         tx: &TransactionId,
     ) -> Option<Arc<ValidPoolTransaction<T::Transaction>>> {
-        match pool {
+        let tx = match pool {
             SubPool::Pending => self.pending_pool.prune_transaction(tx),
             SubPool::Queued => self.queued_pool.remove_transaction(tx),
             SubPool::BaseFee => self.basefee_pool.remove_transaction(tx),
             SubPool::Blob => self.blob_pool.remove_transaction(tx),
-        }
+        }?;
+        self.subpool_senders[pool as usize].remove(tx.sender_id());
+        Some(tx)
     }
 
     /// Removes _only_ the descendants of the given transaction from the __entire__ pool.
@@ -716,6 +786,7 @@ impl<T: TransactionOrdering> TxPool<T> {
         pool: SubPool,
         tx: Arc<ValidPoolTransaction<T::Transaction>>,
     ) {
+        self.subpool_senders[pool as usize].insert(tx.sender_id());
         match pool {
             SubPool::Queued => {
                 self.queued_pool.add_transaction(tx);
@@ -759,8 +830,11 @@ impl<T: TransactionOrdering> TxPool<T> {
 
         // Helper macro that discards the worst transactions for the pools
         macro_rules! discard_worst {
-            ($this:ident, $removed:ident, [$($limit:ident => $pool:ident),* $(,)*]) => {
+            ($this:ident, $removed:ident, [$($limit:ident => $pool:ident($subpool:path)),* $(,)*]) => {
                 $ (
+                // first evict transactions of senders that occupy more than their share
+                $this.discard_over_share($subpool, $this.config.$limit, &mut $removed);
+
                 while $this
                         .config
                         .$limit
@@ -789,6 +863,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                         // 2. remove all transactions from the total set
                         for tx in removed_from_subpool {
                             $this.all_transactions.remove_transaction(tx.id());
+                            $this.subpool_senders[$subpool as usize].remove(tx.sender_id());
 
                             let id = *tx.id();
 
@@ -806,16 +881,92 @@ impl<T: TransactionOrdering> TxPool<T> {
 
         discard_worst!(
             self, removed, [
-                pending_limit => pending_pool,
-                basefee_limit => basefee_pool,
-                blob_limit    => blob_pool,
-                queued_limit  => queued_pool,
+                pending_limit => pending_pool(SubPool::Pending),
+                basefee_limit => basefee_pool(SubPool::BaseFee),
+                blob_limit    => blob_pool(SubPool::Blob),
+                queued_limit  => queued_pool(SubPool::Queued),
             ]
         );
 
         removed
     }
 
+    /// Returns whether the given subpool exceeds the [SubPoolLimit].
+    fn is_subpool_exceeded(&self, pool: SubPool, limit: &SubPoolLimit) -> bool {
+        match pool {
+            SubPool::Queued => limit.is_exceeded(self.queued_pool.len(), self.queued_pool.size()),
+            SubPool::Pending => {
+                limit.is_exceeded(self.pending_pool.len(), self.pending_pool.size())
+            }
+            SubPool::BaseFee => {
+                limit.is_exceeded(self.basefee_pool.len(), self.basefee_pool.size())
+            }
+            SubPool::Blob => limit.is_exceeded(self.blob_pool.len(), self.blob_pool.size()),
+        }
+    }
+
+    /// Evicts transactions of senders that occupy more than their share of the given subpool, see
+    /// [SenderShareConfig](crate::SenderShareConfig).
+    ///
+    /// This is done until the subpool is within the [SubPoolLimit] or all senders are within their
+    /// share. Transactions are evicted from the sender that exceeds its share the most, highest
+    /// nonce first, similar to the account slot accounting of geth.
+    ///
+    /// Removed transactions and their descendants are added to `removed`.
+    fn discard_over_share(
+        &mut self,
+        pool: SubPool,
+        limit: SubPoolLimit,
+        removed: &mut Vec<Arc<ValidPoolTransaction<T::Transaction>>>,
+    ) {
+        if !self.is_subpool_exceeded(pool, &limit) {
+            return
+        }
+
+        // senders that exceed their share, by the number of transactions above their share
+        let mut offenders = BinaryHeap::new();
+        for sender in &self.subpool_senders[pool as usize].above_share {
+            let Some((_, tx)) =
+                self.all_transactions.txs_iter(*sender).find(|(_, tx)| tx.subpool == pool)
+            else {
+                continue
+            };
+            let tx = &tx.transaction;
+            let is_local = self.config.local_transactions_config.is_local(tx.origin, tx.sender());
+            let max_txs = self.config.sender_share.max_sender_txs(&limit, is_local);
+            let count = self.subpool_senders[pool as usize].count(*sender);
+            if count > max_txs {
+                offenders.push((count - max_txs, *sender, max_txs));
+            }
+        }
+
+        while self.is_subpool_exceeded(pool, &limit) {
+            let Some((excess, sender, max_txs)) = offenders.pop() else { break };
+
+            // the transaction of the sender with the highest nonce in the subpool
+            let Some(id) = self
+                .all_transactions
+                .txs_iter(sender)
+                .filter(|(_, tx)| tx.subpool == pool)
+                .last()
+                .map(|(id, _)| *id)
+            else {
+                continue
+            };
+
+            trace!(target: "txpool", ?id, ?pool, excess, "discarding transaction of sender over its share");
+
+            let Some(tx) = self.remove_transaction(&id) else { continue };
+            removed.push(tx);
+            self.remove_descendants(&id, removed);
+
+            let count = self.subpool_senders[pool as usize].count(sender);
+            if count > max_txs {
+                offenders.push((count - max_txs, sender, max_txs));
+            }
+        }
+    }
+
     /// Number of transactions in the entire pool
     pub(crate) fn len(&self) -> usize {
         self.all_transactions.len()
@@ -843,6 +994,69 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.basefee_pool.assert_invariants();
         self.queued_pool.assert_invariants();
         self.blob_pool.assert_invariants();
+
+        for pool in [SubPool::Queued, SubPool::BaseFee, SubPool::Blob, SubPool::Pending] {
+            let mut counts = FnvHashMap::<SenderId, usize>::default();
+            for (id, _) in self.all_transactions.txs.iter().filter(|(_, tx)| tx.subpool == pool) {
+                *counts.entry(id.sender).or_default() += 1;
+            }
+            assert_eq!(
+                counts, self.subpool_senders[pool as usize].counts,
+                "sender counts of {pool:?} must match the transactions in the sub-pool"
+            );
+        }
+    }
+}
+
+/// Tracks the number of transactions each sender has in a sub-pool.
+#[derive(Debug, Default)]
+struct SubPoolSenders {
+    /// Number of transactions by sender.
+    counts: FnvHashMap<SenderId, usize>,
+    /// Senders with more transactions than the smaller of the local and non-local sender share,
+    /// which may exceed their share.
+    above_share: FnvHashSet<SenderId>,
+    /// The smaller of the local and non-local sender share, in transactions.
+    min_share: usize,
+}
+
+impl SubPoolSenders {
+    /// Creates an empty instance for a sub-pool with the given [SubPoolLimit].
+    fn new(sender_share: &SenderShareConfig, limit: &SubPoolLimit) -> Self {
+        Self {
+            min_share: sender_share
+                .max_sender_txs(limit, false)
+                .min(sender_share.max_sender_txs(limit, true)),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the number of transactions of the sender.
+    fn count(&self, sender: SenderId) -> usize {
+        self.counts.get(&sender).copied().unwrap_or_default()
+    }
+
+    /// Records a transaction of the sender that was added to the sub-pool.
+    fn insert(&mut self, sender: SenderId) {
+        let count = self.counts.entry(sender).or_default();
+        *count += 1;
+        if *count > self.min_share {
+            self.above_share.insert(sender);
+        }
+    }
+
+    /// Records a transaction of the sender that was removed from the sub-pool.
+    fn remove(&mut self, sender: SenderId) {
+        if let hash_map::Entry::Occupied(mut entry) = self.counts.entry(sender) {
+            let count = entry.get_mut();
+            *count -= 1;
+            if *count <= self.min_share {
+                self.above_share.remove(&sender);
+            }
+            if *count == 0 {
+                entry.remove();
+            }
+        }
     }
 }
 
@@ -1038,7 +1252,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
                         id: *tx.transaction.id(),
                         hash: *tx.transaction.hash(),
                         current: tx.subpool,
-                        destination: Destination::Discard,
+                        destination: Destination::Discard(DiscardReason::NonceTooLow),
                     });
                     continue 'transactions
                 }
@@ -1760,13 +1974,15 @@ impl<T: PoolTransaction> PoolInternalTransaction<T> {
 pub(crate) struct UpdateOutcome<T: PoolTransaction> {
     /// transactions promoted to the pending pool
     pub(crate) promoted: Vec<Arc<ValidPoolTransaction<T>>>,
-    /// transaction that failed and were discarded
-    pub(crate) discarded: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// transaction that failed and were discarded, and why
+    pub(crate) discarded: Vec<(Arc<ValidPoolTransaction<T>>, DiscardReason)>,
+    /// all transactions that were moved between sub-pools, including the promoted ones
+    pub(crate) moved: Vec<MovedTransaction>,
 }
 
 impl<T: PoolTransaction> Default for UpdateOutcome<T> {
     fn default() -> Self {
-        Self { promoted: vec![], discarded: vec![] }
+        Self { promoted: vec![], discarded: vec![], moved: vec![] }
     }
 }
 
@@ -1825,7 +2041,7 @@ mod tests {
     use crate::{
         test_utils::{MockOrdering, MockTransaction, MockTransactionFactory, MockTransactionSet},
         traits::TransactionOrigin,
-        SubPoolLimit,
+        SenderShareConfig, SubPoolLimit,
     };
 
     #[test]
@@ -2590,7 +2806,7 @@ mod tests {
 
         assert_eq!(pool.pending_pool.len(), 1);
 
-        pool.update_basefee((tx.max_fee_per_gas() + 1) as u64);
+        pool.update_basefee((tx.max_fee_per_gas() + 1) as u64, &mut Default::default());
 
         assert!(pool.pending_pool.is_empty());
         assert_eq!(pool.basefee_pool.len(), 1);
@@ -2737,7 +2953,7 @@ mod tests {
 
         // set the base fee of the pool
         let pool_base_fee = 100;
-        pool.update_basefee(pool_base_fee);
+        pool.update_basefee(pool_base_fee, &mut Default::default());
 
         // 2 txs, that should put the pool over the size limit but not max txs
         let a_txs = MockTransactionSet::dependent(a_sender, 0, 2, TxType::EIP1559)
@@ -2789,6 +3005,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn discard_senders_over_share() {
+        let mut f = MockTransactionFactory::default();
+        let queued_limit = SubPoolLimit::new(100, usize::MAX);
+        let mut pool = TxPool::new(
+            MockOrdering::default(),
+            PoolConfig {
+                queued_limit,
+                max_account_slots: 100,
+                sender_share: SenderShareConfig { max_share: 10, max_local_share: 50 },
+                ..Default::default()
+            },
+        );
+
+        // a single sender with 50 queued transactions
+        let spammer = Address::random();
+        let mut spammer_tx = MockTransaction::eip1559().with_sender(spammer).inc_nonce();
+        for _ in 0..50 {
+            let validated = f.validated(spammer_tx.clone());
+            pool.add_transaction(validated, U256::from(1_000), 0).unwrap();
+            spammer_tx = spammer_tx.next();
+        }
+
+        // 60 senders with one queued transaction each
+        for _ in 0..60 {
+            let validated = f.validated(MockTransaction::eip1559().inc_nonce());
+            pool.add_transaction(validated, U256::from(1_000), 0).unwrap();
+        }
+        assert_eq!(pool.size().queued, 110);
+
+        // only the transactions with the highest nonces of the spammer are discarded
+        let removed = pool.discard_worst();
+        pool.assert_invariants();
+        assert_eq!(removed.len(), 10);
+        assert!(removed.iter().all(|tx| tx.sender() == spammer && tx.nonce() > 40));
+        assert_eq!(pool.size().queued, queued_limit.max_txs);
+    }
+
     #[test]
     fn discard_blobs_at_capacity() {
         let mut f = MockTransactionFactory::default();
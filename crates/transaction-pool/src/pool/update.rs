@@ -1,6 +1,9 @@
 //! Support types for updating the pool.
 
-use crate::{identifier::TransactionId, pool::state::SubPool};
+use crate::{
+    identifier::TransactionId,
+    pool::{events::DiscardReason, state::SubPool},
+};
 use reth_primitives::TxHash;
 
 /// A change of the transaction's location
@@ -21,8 +24,19 @@ pub(crate) struct PoolUpdate {
 /// Where to move an existing transaction.
 #[derive(Debug)]
 pub(crate) enum Destination {
-    /// Discard the transaction.
-    Discard,
+    /// Discard the transaction for the given reason.
+    Discard(DiscardReason),
     /// Move transaction to pool
     Pool(SubPool),
 }
+
+/// A transaction that was moved from one sub-pool to another.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct MovedTransaction {
+    /// Hash of the transaction.
+    pub(crate) hash: TxHash,
+    /// Where the transaction was held.
+    pub(crate) from: SubPool,
+    /// Where the transaction was moved to.
+    pub(crate) to: SubPool,
+}
@@ -5,7 +5,7 @@ use crate::{
     error::PoolResult,
     pool::{state::SubPool, BestTransactionFilter, TransactionEvents},
     validate::ValidPoolTransaction,
-    AllTransactionsEvents,
+    AllTransactionsEvents, TransactionConditional,
 };
 use futures_util::{ready, Stream};
 use reth_eth_wire::HandleAnnouncement;
@@ -267,6 +267,16 @@ pub trait TransactionPool: Send + Sync + Clone {
     /// Consumer: RPC
     fn all_transactions(&self) -> AllPoolTransactions<Self::Transaction>;
 
+    /// Returns all transactions of the given [SubPool].
+    ///
+    /// Unlike [Self::queued_transactions], this distinguishes between the parked sub-pools.
+    ///
+    /// Consumer: RPC
+    fn subpool_transactions(
+        &self,
+        subpool: SubPool,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
+
     /// Removes all transactions corresponding to the given hashes.
     ///
     /// Also removes all _dependent_ transactions.
@@ -520,7 +530,7 @@ pub struct NewBlobSidecar {
 ///
 /// Depending on where the transaction was picked up, it affects how the transaction is handled
 /// internally, e.g. limits for simultaneous transaction of one sender.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum TransactionOrigin {
     /// Transaction is coming from a local source.
     Local,
@@ -824,6 +834,21 @@ pub trait PoolTransaction:
     /// Returns chain_id
     fn chain_id(&self) -> Option<u64>;
 
+    /// Returns the preconditions of the transaction, if it was submitted with any.
+    fn conditional(&self) -> Option<&TransactionConditional> {
+        None
+    }
+
+    /// Attaches preconditions to the transaction.
+    ///
+    /// Returns the preconditions back if the transaction type doesn't support them.
+    fn set_conditional(
+        &mut self,
+        conditional: TransactionConditional,
+    ) -> Result<(), TransactionConditional> {
+        Err(conditional)
+    }
+
     /// Returns whether or not the transaction is an Optimism Deposited transaction.
     #[cfg(feature = "optimism")]
     fn is_deposit(&self) -> bool;
@@ -872,6 +897,9 @@ pub struct EthPooledTransaction {
 
     /// The blob side car for this transaction
     pub(crate) blob_sidecar: EthBlobTransactionSidecar,
+
+    /// The preconditions of this transaction, see [TransactionConditional].
+    pub(crate) conditional: Option<Box<TransactionConditional>>,
 }
 
 /// Represents the blob sidecar of the [EthPooledTransaction].
@@ -911,7 +939,7 @@ impl EthPooledTransaction {
             cost += U256::from(blob_tx.max_fee_per_blob_gas * blob_tx.blob_gas() as u128);
         }
 
-        Self { transaction, cost, encoded_length, blob_sidecar }
+        Self { transaction, cost, encoded_length, blob_sidecar, conditional: None }
     }
 
     /// Return the reference to the underlying transaction.
@@ -1054,6 +1082,18 @@ impl PoolTransaction for EthPooledTransaction {
         self.transaction.chain_id()
     }
 
+    fn conditional(&self) -> Option<&TransactionConditional> {
+        self.conditional.as_deref()
+    }
+
+    fn set_conditional(
+        &mut self,
+        conditional: TransactionConditional,
+    ) -> Result<(), TransactionConditional> {
+        self.conditional = Some(Box::new(conditional));
+        Ok(())
+    }
+
     /// Returns whether or not the transaction is an Optimism Deposited transaction.
     #[cfg(feature = "optimism")]
     fn is_deposit(&self) -> bool {
@@ -4,7 +4,10 @@ use crate::{
     blobstore::BlobStore,
     error::{Eip4844PoolTransactionError, InvalidPoolTransactionError},
     traits::TransactionOrigin,
-    validate::{ValidTransaction, ValidationTask, MAX_INIT_CODE_BYTE_SIZE},
+    validate::{
+        AdmissionPolicies, AdmissionPolicyConfig, ValidTransaction, ValidationTask,
+        MAX_INIT_CODE_BYTE_SIZE,
+    },
     EthBlobTransactionSidecar, EthPoolTransaction, LocalTransactionConfig, PoolTransaction,
     TransactionValidationOutcome, TransactionValidationTaskExecutor, TransactionValidator,
 };
@@ -122,6 +125,8 @@ pub(crate) struct EthTransactionValidatorInner<Client, T> {
     local_transactions_config: LocalTransactionConfig,
     /// Maximum size in bytes a single transaction can have in order to be accepted into the pool.
     max_tx_input_bytes: usize,
+    /// Policies that decide whether a transaction is admitted into the pool.
+    admission_policies: AdmissionPolicies<T>,
     /// Marker for the transaction type
     _marker: PhantomData<T>,
 }
@@ -291,11 +296,44 @@ where
             }
         }
 
-        let account = match self
-            .client
-            .latest()
-            .and_then(|state| state.basic_account(transaction.sender()))
-        {
+        // configured admission policies
+        if let Err(err) = self.admission_policies.check(origin, &transaction) {
+            return TransactionValidationOutcome::Invalid(transaction, err)
+        }
+
+        let state = match self.client.latest() {
+            Ok(state) => state,
+            Err(err) => {
+                return TransactionValidationOutcome::Error(*transaction.hash(), Box::new(err))
+            }
+        };
+
+        // preconditions of conditional transactions
+        if let Some(conditional) = transaction.conditional() {
+            let header = match self.client.latest_header() {
+                Ok(Some(header)) => header,
+                Ok(None) => {
+                    return TransactionValidationOutcome::Error(
+                        *transaction.hash(),
+                        "Latest header should be found".into(),
+                    )
+                }
+                Err(err) => {
+                    return TransactionValidationOutcome::Error(*transaction.hash(), Box::new(err))
+                }
+            };
+            match conditional.check_next_block(&header, &*state) {
+                Ok(Ok(())) => {}
+                Ok(Err(err)) => {
+                    return TransactionValidationOutcome::Invalid(transaction, err.into())
+                }
+                Err(err) => {
+                    return TransactionValidationOutcome::Error(*transaction.hash(), Box::new(err))
+                }
+            }
+        }
+
+        let account = match state.basic_account(transaction.sender()) {
             Ok(account) => account.unwrap_or_default(),
             Err(err) => {
                 return TransactionValidationOutcome::Error(*transaction.hash(), Box::new(err))
@@ -486,6 +524,8 @@ pub struct EthTransactionValidatorBuilder {
     local_transactions_config: LocalTransactionConfig,
     /// Max size in bytes of a single transaction allowed
     max_tx_input_bytes: usize,
+    /// Configuration of the built-in admission policies
+    admission_policy_config: AdmissionPolicyConfig,
 }
 
 impl EthTransactionValidatorBuilder {
@@ -502,6 +542,7 @@ impl EthTransactionValidatorBuilder {
             kzg_settings: Arc::clone(&MAINNET_KZG_TRUSTED_SETUP),
             local_transactions_config: Default::default(),
             max_tx_input_bytes: DEFAULT_MAX_TX_INPUT_BYTES,
+            admission_policy_config: Default::default(),
 
             // by default all transaction types are allowed
             eip2718: true,
@@ -602,6 +643,17 @@ impl EthTransactionValidatorBuilder {
         self
     }
 
+    /// Sets the configuration of the built-in admission policies.
+    ///
+    /// See also [AdmissionPolicies::from_config]
+    pub fn with_admission_policy_config(
+        mut self,
+        admission_policy_config: AdmissionPolicyConfig,
+    ) -> Self {
+        self.admission_policy_config = admission_policy_config;
+        self
+    }
+
     /// Builds a the [EthTransactionValidator] without spawning validator tasks.
     pub fn build<Client, Tx, S>(
         self,
@@ -609,6 +661,25 @@ impl EthTransactionValidatorBuilder {
         blob_store: S,
     ) -> EthTransactionValidator<Client, Tx>
     where
+        Tx: PoolTransaction,
+        S: BlobStore,
+    {
+        self.build_with_admission_policies(client, blob_store, Default::default())
+    }
+
+    /// Builds a the [EthTransactionValidator] with additional [AdmissionPolicies] without spawning
+    /// validator tasks.
+    ///
+    /// The given policies are checked after the built-in policies, see
+    /// [Self::with_admission_policy_config].
+    pub fn build_with_admission_policies<Client, Tx, S>(
+        self,
+        client: Client,
+        blob_store: S,
+        admission_policies: AdmissionPolicies<Tx>,
+    ) -> EthTransactionValidator<Client, Tx>
+    where
+        Tx: PoolTransaction,
         S: BlobStore,
     {
         let Self {
@@ -623,6 +694,7 @@ impl EthTransactionValidatorBuilder {
             kzg_settings,
             local_transactions_config,
             max_tx_input_bytes,
+            admission_policy_config,
             ..
         } = self;
 
@@ -642,6 +714,8 @@ impl EthTransactionValidatorBuilder {
             kzg_settings,
             local_transactions_config,
             max_tx_input_bytes,
+            admission_policies: AdmissionPolicies::from_config(admission_policy_config)
+                .with_policies(admission_policies),
             _marker: Default::default(),
         };
 
@@ -661,6 +735,7 @@ impl EthTransactionValidatorBuilder {
         blob_store: S,
     ) -> TransactionValidationTaskExecutor<EthTransactionValidator<Client, Tx>>
     where
+        Tx: PoolTransaction,
         T: TaskSpawner,
         S: BlobStore,
     {
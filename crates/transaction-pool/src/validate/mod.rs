@@ -13,11 +13,18 @@ use std::{fmt, time::Instant};
 
 mod constants;
 mod eth;
+mod policy;
 mod task;
 
 /// A `TransactionValidator` implementation that validates ethereum transaction.
 pub use eth::*;
 
+/// Configurable pool admission policies.
+pub use policy::{
+    AddressList, AdmissionPolicies, AdmissionPolicy, AdmissionPolicyConfig, BlockedSelectorsPolicy,
+    MaxCalldataSizePolicy, MinimumPriorityFeePolicy, RecipientPolicy, SenderPolicy,
+};
+
 /// A spawnable task that performs transaction validation.
 pub use task::{TransactionValidationTaskExecutor, ValidationTask};
 
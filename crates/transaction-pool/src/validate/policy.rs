@@ -0,0 +1,356 @@
+//! Configurable pool admission policies.
+
+use crate::{
+    error::InvalidPoolTransactionError, metrics::AdmissionPolicyMetrics, traits::TransactionOrigin,
+    PoolTransaction,
+};
+use reth_primitives::{Address, Selector};
+use std::{collections::HashSet, fmt};
+
+/// A policy that decides whether a transaction is admitted into the pool.
+///
+/// Policies are checked by the [EthTransactionValidator](crate::EthTransactionValidator) after the
+/// stateless checks of the transaction passed, but before any state is loaded, so they should
+/// only rely on the transaction itself.
+pub trait AdmissionPolicy<T>: fmt::Debug + Send + Sync {
+    /// Returns an error if the transaction must not be admitted into the pool.
+    fn check(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), InvalidPoolTransactionError>;
+}
+
+/// Configuration for the built-in [AdmissionPolicy] implementations.
+///
+/// By default, no transaction is rejected.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AdmissionPolicyConfig {
+    /// Senders that are allowed and denied.
+    pub senders: AddressList,
+    /// Recipients that are allowed and denied.
+    pub recipients: AddressList,
+    /// Minimum priority fee, or gas price for legacy transactions, of non-local transactions.
+    pub minimum_priority_fee: Option<u128>,
+    /// Maximum size in bytes of the transaction's input.
+    pub max_calldata_size: Option<usize>,
+    /// Function selectors that must not be called.
+    pub blocked_selectors: HashSet<Selector>,
+}
+
+impl AdmissionPolicyConfig {
+    /// Returns `true` if the config doesn't reject any transaction.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// A list of allowed and denied addresses.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AddressList {
+    /// If not empty, only these addresses are allowed.
+    pub allow: HashSet<Address>,
+    /// Addresses that are denied.
+    pub deny: HashSet<Address>,
+}
+
+impl AddressList {
+    /// Returns `true` if the list doesn't restrict any address.
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty()
+    }
+
+    /// Returns `true` if the address is allowed.
+    pub fn is_allowed(&self, address: &Address) -> bool {
+        (self.allow.is_empty() || self.allow.contains(address)) && !self.deny.contains(address)
+    }
+}
+
+/// Rejects transactions whose sender is not allowed by the [AddressList].
+#[derive(Debug, Clone)]
+pub struct SenderPolicy(pub AddressList);
+
+impl<T: PoolTransaction> AdmissionPolicy<T> for SenderPolicy {
+    fn check(
+        &self,
+        _: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        let sender = transaction.sender();
+        if !self.0.is_allowed(&sender) {
+            return Err(InvalidPoolTransactionError::SenderNotAllowed(sender))
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions whose recipient is not allowed by the [AddressList].
+///
+/// Contract creations have no recipient and are not affected.
+#[derive(Debug, Clone)]
+pub struct RecipientPolicy(pub AddressList);
+
+impl<T: PoolTransaction> AdmissionPolicy<T> for RecipientPolicy {
+    fn check(
+        &self,
+        _: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        if let Some(to) = transaction.to() {
+            if !self.0.is_allowed(&to) {
+                return Err(InvalidPoolTransactionError::RecipientNotAllowed(to))
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Rejects non-local transactions with a priority fee, or gas price for legacy transactions, below
+/// the minimum.
+#[derive(Debug, Clone, Copy)]
+pub struct MinimumPriorityFeePolicy(pub u128);
+
+impl<T: PoolTransaction> AdmissionPolicy<T> for MinimumPriorityFeePolicy {
+    fn check(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        let fee = transaction.priority_fee_or_price();
+        if !origin.is_local() && fee < self.0 {
+            return Err(InvalidPoolTransactionError::PriorityFeeBelowMinimum {
+                fee,
+                minimum: self.0,
+            })
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions with an input larger than the maximum size in bytes.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxCalldataSizePolicy(pub usize);
+
+impl<T: PoolTransaction> AdmissionPolicy<T> for MaxCalldataSizePolicy {
+    fn check(
+        &self,
+        _: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        let size = transaction.input().len();
+        if size > self.0 {
+            return Err(InvalidPoolTransactionError::ExceedsMaxCalldataSize(size, self.0))
+        }
+        Ok(())
+    }
+}
+
+/// Rejects calls of blocked function selectors.
+///
+/// The selector is the first four bytes of the input of a transaction with a recipient.
+#[derive(Debug, Clone)]
+pub struct BlockedSelectorsPolicy(pub HashSet<Selector>);
+
+impl<T: PoolTransaction> AdmissionPolicy<T> for BlockedSelectorsPolicy {
+    fn check(
+        &self,
+        _: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        if transaction.to().is_none() {
+            return Ok(())
+        }
+        if let Some(selector) = transaction.input().get(..4).map(Selector::from_slice) {
+            if self.0.contains(&selector) {
+                return Err(InvalidPoolTransactionError::BlockedSelector(selector))
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A set of [AdmissionPolicy]s that are checked in order.
+///
+/// Rejections are recorded in the [AdmissionPolicyMetrics].
+pub struct AdmissionPolicies<T> {
+    policies: Vec<Box<dyn AdmissionPolicy<T>>>,
+    metrics: AdmissionPolicyMetrics,
+}
+
+impl<T> AdmissionPolicies<T> {
+    /// Creates an empty set of policies.
+    pub fn new() -> Self {
+        Self { policies: Vec::new(), metrics: Default::default() }
+    }
+
+    /// Creates the built-in policies from the given [AdmissionPolicyConfig].
+    pub fn from_config(config: AdmissionPolicyConfig) -> Self
+    where
+        T: PoolTransaction,
+    {
+        let AdmissionPolicyConfig {
+            senders,
+            recipients,
+            minimum_priority_fee,
+            max_calldata_size,
+            blocked_selectors,
+        } = config;
+
+        let mut policies = Self::new();
+        if !senders.is_empty() {
+            policies = policies.with_policy(SenderPolicy(senders));
+        }
+        if !recipients.is_empty() {
+            policies = policies.with_policy(RecipientPolicy(recipients));
+        }
+        if let Some(minimum_priority_fee) = minimum_priority_fee {
+            policies = policies.with_policy(MinimumPriorityFeePolicy(minimum_priority_fee));
+        }
+        if let Some(max_calldata_size) = max_calldata_size {
+            policies = policies.with_policy(MaxCalldataSizePolicy(max_calldata_size));
+        }
+        if !blocked_selectors.is_empty() {
+            policies = policies.with_policy(BlockedSelectorsPolicy(blocked_selectors));
+        }
+        policies
+    }
+
+    /// Adds a policy that is checked after all existing ones.
+    pub fn with_policy<P>(mut self, policy: P) -> Self
+    where
+        P: AdmissionPolicy<T> + 'static,
+    {
+        self.policies.push(Box::new(policy));
+        self
+    }
+
+    /// Adds all policies of `other`, which are checked after all existing ones.
+    pub fn with_policies(mut self, other: Self) -> Self {
+        self.policies.extend(other.policies);
+        self
+    }
+
+    /// Returns `true` if there are no policies.
+    pub fn is_empty(&self) -> bool {
+        self.policies.is_empty()
+    }
+
+    /// Checks the transaction against all policies and returns the first rejection.
+    pub fn check(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        for policy in &self.policies {
+            if let Err(err) = policy.check(origin, transaction) {
+                self.metrics.record_rejection(&err);
+                return Err(err)
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T> Default for AdmissionPolicies<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for AdmissionPolicies<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AdmissionPolicies")
+            .field("policies", &self.policies)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+    use reth_primitives::{constants::MIN_PROTOCOL_BASE_FEE, Bytes};
+
+    #[test]
+    fn address_list() {
+        let (a, b) = (Address::random(), Address::random());
+
+        let list = AddressList::default();
+        assert!(list.is_allowed(&a) && list.is_allowed(&b));
+
+        let list = AddressList { deny: HashSet::from([a]), ..Default::default() };
+        assert!(!list.is_allowed(&a) && list.is_allowed(&b));
+
+        let list = AddressList { allow: HashSet::from([a]), ..Default::default() };
+        assert!(list.is_allowed(&a) && !list.is_allowed(&b));
+
+        let list = AddressList { allow: HashSet::from([a]), deny: HashSet::from([a]) };
+        assert!(!list.is_allowed(&a) && !list.is_allowed(&b));
+    }
+
+    #[test]
+    fn check_policies() {
+        let transaction = MockTransaction::eip1559().with_input(Bytes::from(vec![1, 2, 3, 4, 5]));
+        let sender = transaction.sender();
+        let recipient = transaction.to().unwrap();
+        let selector = Selector::from([1, 2, 3, 4]);
+        let check = |config: AdmissionPolicyConfig, origin| {
+            AdmissionPolicies::from_config(config).check(origin, &transaction)
+        };
+
+        assert!(AdmissionPolicies::<MockTransaction>::from_config(Default::default()).is_empty());
+        assert!(check(Default::default(), TransactionOrigin::External).is_ok());
+
+        assert!(matches!(
+            check(
+                AdmissionPolicyConfig {
+                    senders: AddressList { deny: HashSet::from([sender]), ..Default::default() },
+                    ..Default::default()
+                },
+                TransactionOrigin::External
+            ),
+            Err(InvalidPoolTransactionError::SenderNotAllowed(address)) if address == sender
+        ));
+        assert!(matches!(
+            check(
+                AdmissionPolicyConfig {
+                    recipients: AddressList {
+                        allow: HashSet::from([Address::random()]),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                TransactionOrigin::External
+            ),
+            Err(InvalidPoolTransactionError::RecipientNotAllowed(address)) if address == recipient
+        ));
+
+        let minimum_priority_fee = AdmissionPolicyConfig {
+            minimum_priority_fee: Some(MIN_PROTOCOL_BASE_FEE as u128 + 1),
+            ..Default::default()
+        };
+        assert!(matches!(
+            check(minimum_priority_fee.clone(), TransactionOrigin::External),
+            Err(InvalidPoolTransactionError::PriorityFeeBelowMinimum { .. })
+        ));
+        assert!(check(minimum_priority_fee, TransactionOrigin::Local).is_ok());
+
+        assert!(matches!(
+            check(
+                AdmissionPolicyConfig { max_calldata_size: Some(4), ..Default::default() },
+                TransactionOrigin::Local
+            ),
+            Err(InvalidPoolTransactionError::ExceedsMaxCalldataSize(5, 4))
+        ));
+        assert!(matches!(
+            check(
+                AdmissionPolicyConfig {
+                    blocked_selectors: HashSet::from([selector]),
+                    ..Default::default()
+                },
+                TransactionOrigin::Local
+            ),
+            Err(InvalidPoolTransactionError::BlockedSelector(blocked)) if blocked == selector
+        ));
+    }
+}
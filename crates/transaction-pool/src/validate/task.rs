@@ -100,6 +100,7 @@ impl TransactionValidationTaskExecutor<()> {
 impl<Client, Tx> TransactionValidationTaskExecutor<EthTransactionValidator<Client, Tx>>
 where
     Client: BlockReaderIdExt,
+    Tx: PoolTransaction,
 {
     /// Creates a new instance for the given [ChainSpec]
     ///
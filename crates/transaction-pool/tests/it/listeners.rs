@@ -2,8 +2,8 @@ use assert_matches::assert_matches;
 use reth_transaction_pool::{
     noop::MockTransactionValidator,
     test_utils::{MockTransactionFactory, TestPoolBuilder},
-    FullTransactionEvent, TransactionEvent, TransactionListenerKind, TransactionOrigin,
-    TransactionPool,
+    BlockInfo, DiscardReason, FullTransactionEvent, PoolTransaction, SubPool, TransactionEvent,
+    TransactionListenerKind, TransactionOrigin, TransactionPool,
 };
 use std::{future::poll_fn, task::Poll};
 use tokio_stream::StreamExt;
@@ -41,6 +41,68 @@ async fn txpool_listener_all() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_listener_all_discarded() {
+    let txpool = TestPoolBuilder::default();
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let transaction = mock_tx_factory.create_eip1559();
+    let hash = transaction.transaction.get_hash();
+
+    let mut all_tx_events = txpool.all_transactions_event_listener();
+
+    let added_result =
+        txpool.add_transaction(TransactionOrigin::External, transaction.transaction.clone()).await;
+    assert_matches!(added_result, Ok(_));
+    assert_matches!(all_tx_events.next().await, Some(FullTransactionEvent::Pending(_)));
+
+    txpool.remove_transactions(vec![hash]);
+    assert_matches!(
+        all_tx_events.next().await,
+        Some(FullTransactionEvent::Discarded(tx_hash)) if tx_hash == hash
+    );
+    assert_matches!(
+        all_tx_events.next().await,
+        Some(FullTransactionEvent::DiscardedWithReason { tx_hash, reason: DiscardReason::Removed })
+            if tx_hash == hash
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_listener_all_moved_on_fee_update() {
+    let txpool = TestPoolBuilder::default();
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let transaction = mock_tx_factory.create_eip1559();
+    let hash = transaction.transaction.get_hash();
+    let max_fee = transaction.transaction.max_fee_per_gas() as u64;
+
+    let mut all_tx_events = txpool.all_transactions_event_listener();
+
+    let added_result =
+        txpool.add_transaction(TransactionOrigin::External, transaction.transaction.clone()).await;
+    assert_matches!(added_result, Ok(_));
+    assert_matches!(all_tx_events.next().await, Some(FullTransactionEvent::Pending(_)));
+
+    // increased base fee demotes the transaction
+    txpool.set_block_info(BlockInfo { pending_basefee: max_fee + 1, ..Default::default() });
+    assert_matches!(
+        all_tx_events.next().await,
+        Some(FullTransactionEvent::Moved { tx_hash, from: SubPool::Pending, to: SubPool::BaseFee })
+            if tx_hash == hash
+    );
+
+    // decreased base fee promotes it again
+    txpool.set_block_info(BlockInfo { pending_basefee: max_fee, ..Default::default() });
+    assert_matches!(
+        all_tx_events.next().await,
+        Some(FullTransactionEvent::Moved { tx_hash, from: SubPool::BaseFee, to: SubPool::Pending })
+            if tx_hash == hash
+    );
+    assert_matches!(
+        all_tx_events.next().await,
+        Some(FullTransactionEvent::Pending(tx_hash)) if tx_hash == hash
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn txpool_listener_propagate_only() {
     let txpool =
@@ -39,6 +39,15 @@ pub use state::*;
 /// Merkle proof generation.
 pub mod proof;
 
+/// Decoding of trie nodes.
+pub mod trie_node;
+
+/// Verification of range proofs.
+pub mod range_proof;
+
+/// State root computation from execution witnesses.
+pub mod witness;
+
 /// The implementation of the Merkle Patricia Trie.
 mod trie;
 pub use trie::{StateRoot, StorageRoot};
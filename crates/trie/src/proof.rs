@@ -1,37 +1,69 @@
 use crate::{
-    hashed_cursor::{HashedCursorFactory, HashedStorageCursor},
+    hashed_cursor::{HashedAccountCursor, HashedCursorFactory, HashedStorageCursor},
     node_iter::{AccountNode, AccountNodeIter, StorageNode, StorageNodeIter},
-    prefix_set::PrefixSetMut,
+    prefix_set::{PrefixSetMut, TriePrefixSets},
     trie_cursor::{DatabaseAccountTrieCursor, DatabaseStorageTrieCursor},
+    trie_node::{ChildRef, TrieNode},
     walker::TrieWalker,
-    StateRootError, StorageRootError,
+    HashedPostState, StateRootError, StorageRootError,
 };
 use alloy_rlp::{BufMut, Encodable};
 use reth_db::{tables, transaction::DbTx};
 use reth_primitives::{
     constants::EMPTY_ROOT_HASH,
     keccak256,
-    trie::{AccountProof, HashBuilder, Nibbles, StorageProof, TrieAccount},
-    Address, B256,
+    trie::{AccountProof, ExecutionWitness, HashBuilder, Nibbles, StorageProof, TrieAccount},
+    Address, Bytes, B256, KECCAK_EMPTY,
 };
+use std::collections::{BTreeMap, HashMap};
 
 /// A struct for generating merkle proofs.
 ///
-/// Proof generator adds the target address and slots to the prefix set, enables the proof retainer
-/// on the hash builder and follows the same algorithm as the state root calculator.
+/// Proof generator adds the target addresses and slots to the prefix set, enables the proof
+/// retainer on the hash builder and follows the same algorithm as the state root calculator.
 /// See `StateRoot::root` for more info.
+///
+/// Proofs of a state other than the one in the database, e.g. of a historical block, can be
+/// generated by overlaying the state with [Proof::with_hashed_cursor_factory] and
+/// [Proof::with_prefix_sets]. See `HashedPostState::account_proof`.
 #[derive(Debug)]
 pub struct Proof<'a, TX, H> {
     /// A reference to the database transaction.
     tx: &'a TX,
     /// The factory for hashed cursors.
     hashed_cursor_factory: H,
+    /// A set of prefixes of the accounts and storage slots that differ from the database.
+    prefix_sets: TriePrefixSets,
+}
+
+/// The merkle proofs of multiple accounts, see [Proof::multiproof].
+#[derive(PartialEq, Eq, Default, Debug)]
+pub struct MultiProof {
+    /// The state root.
+    pub root: B256,
+    /// The proofs of the target accounts, keyed by address.
+    pub account_proofs: HashMap<Address, AccountProof>,
 }
 
 impl<'a, TX> Proof<'a, TX, &'a TX> {
     /// Create a new [Proof] instance.
     pub fn new(tx: &'a TX) -> Self {
-        Self { tx, hashed_cursor_factory: tx }
+        Self { tx, hashed_cursor_factory: tx, prefix_sets: TriePrefixSets::default() }
+    }
+}
+
+impl<'a, TX, H> Proof<'a, TX, H> {
+    /// Set the hashed cursor factory.
+    pub fn with_hashed_cursor_factory<HF>(self, hashed_cursor_factory: HF) -> Proof<'a, TX, HF> {
+        Proof { tx: self.tx, hashed_cursor_factory, prefix_sets: self.prefix_sets }
+    }
+
+    /// Set the prefix sets of the state that is overlaid by the hashed cursor factory.
+    /// The intermediate nodes along these prefixes are recomputed instead of read from the
+    /// database.
+    pub fn with_prefix_sets(mut self, prefix_sets: TriePrefixSets) -> Self {
+        self.prefix_sets = prefix_sets;
+        self
     }
 }
 
@@ -46,23 +78,36 @@ where
         address: Address,
         slots: &[B256],
     ) -> Result<AccountProof, StateRootError> {
-        let target_hashed_address = keccak256(address);
-        let target_nibbles = Nibbles::unpack(target_hashed_address);
-        let mut account_proof = AccountProof::new(address);
+        let mut multiproof = self.multiproof(&HashMap::from([(address, slots.to_vec())]))?;
+        Ok(multiproof.account_proofs.remove(&address).unwrap_or_else(|| AccountProof::new(address)))
+    }
+
+    /// Generate the proofs of multiple accounts and their storage slots in a single pass over
+    /// the account trie.
+    pub fn multiproof(
+        &self,
+        targets: &HashMap<Address, Vec<B256>>,
+    ) -> Result<MultiProof, StateRootError> {
+        let hashed_targets = targets
+            .iter()
+            .map(|(address, slots)| (keccak256(address), (*address, slots.as_slice())))
+            .collect::<HashMap<_, _>>();
+        let target_nibbles = hashed_targets.keys().map(Nibbles::unpack).collect::<Vec<_>>();
 
         let hashed_account_cursor = self.hashed_cursor_factory.hashed_account_cursor()?;
         let trie_cursor =
             DatabaseAccountTrieCursor::new(self.tx.cursor_read::<tables::AccountsTrie>()?);
 
         // Create the walker.
-        let mut prefix_set = PrefixSetMut::default();
-        prefix_set.insert(target_nibbles.clone());
+        let prefix_set = PrefixSetMut::from(
+            self.prefix_sets.account_prefix_set.iter().chain(&target_nibbles).cloned(),
+        );
         let walker = TrieWalker::new(trie_cursor, prefix_set.freeze());
 
         // Create a hash builder to rebuild the root node since it is not available in the database.
-        let mut hash_builder =
-            HashBuilder::default().with_proof_retainer(Vec::from([target_nibbles.clone()]));
+        let mut hash_builder = HashBuilder::default().with_proof_retainer(target_nibbles.clone());
 
+        let mut account_proofs = HashMap::with_capacity(targets.len());
         let mut account_rlp = Vec::with_capacity(128);
         let mut account_node_iter = AccountNodeIter::new(walker, hashed_account_cursor);
         while let Some(account_node) = account_node_iter.try_next()? {
@@ -71,14 +116,17 @@ where
                     hash_builder.add_branch(node.key, node.value, node.children_are_in_trie);
                 }
                 AccountNode::Leaf(hashed_address, account) => {
-                    let storage_root = if hashed_address == target_hashed_address {
-                        let (storage_root, storage_proofs) =
-                            self.storage_root_with_proofs(hashed_address, slots)?;
-                        account_proof.set_account(account, storage_root, storage_proofs);
-                        storage_root
-                    } else {
-                        self.storage_root(hashed_address)?
-                    };
+                    let storage_root =
+                        if let Some((address, slots)) = hashed_targets.get(&hashed_address) {
+                            let (storage_root, storage_proofs) =
+                                self.storage_root_with_proofs(hashed_address, slots)?;
+                            let mut account_proof = AccountProof::new(*address);
+                            account_proof.set_account(account, storage_root, storage_proofs);
+                            account_proofs.insert(*address, account_proof);
+                            storage_root
+                        } else {
+                            self.storage_root(hashed_address)?
+                        };
 
                     account_rlp.clear();
                     let account = TrieAccount::from((account, storage_root));
@@ -89,12 +137,178 @@ where
             }
         }
 
-        let _ = hash_builder.root();
+        let root = hash_builder.root();
 
-        let proofs = hash_builder.take_proofs();
-        account_proof.set_proof(proofs.values().cloned().collect());
+        let all_proof_nodes = hash_builder.take_proofs();
+        for (hashed_address, (address, _)) in hashed_targets {
+            let nibbles = Nibbles::unpack(hashed_address);
+            // Accounts that don't exist are proven by the nodes on the path to where they would be.
+            let account_proof =
+                account_proofs.entry(address).or_insert_with(|| AccountProof::new(address));
+            // Iterate over all proof nodes and find the matching ones.
+            // The filtered results are guaranteed to be in order.
+            let matching_proof_nodes = all_proof_nodes
+                .iter()
+                .filter(|(path, _)| nibbles.starts_with(path))
+                .map(|(_, node)| node.clone());
+            account_proof.set_proof(matching_proof_nodes.collect());
+        }
 
-        Ok(account_proof)
+        Ok(MultiProof { root, account_proofs })
+    }
+
+    /// Generate the [ExecutionWitness] of the given accounts and storage slots, i.e. the nodes on
+    /// the paths to them and the bytecodes of the accounts.
+    ///
+    /// The witness is sufficient to recompute the state root after applying the given changes,
+    /// see [witness_state_root](crate::witness::witness_state_root). Removing a key may collapse
+    /// the branch nodes on its path into their remaining child, so the children of those branch
+    /// nodes are included as well.
+    pub fn witness(
+        &self,
+        targets: &HashMap<Address, Vec<B256>>,
+        changes: &HashedPostState,
+    ) -> Result<ExecutionWitness, StateRootError> {
+        let mut witness = ExecutionWitness::default();
+        let mut hashed_account_cursor = self.hashed_cursor_factory.hashed_account_cursor()?;
+
+        let mut account_targets = Vec::with_capacity(targets.len());
+        let mut removed_accounts = Vec::new();
+        for (address, slots) in targets {
+            let hashed_address = keccak256(address);
+            let nibbles = Nibbles::unpack(hashed_address);
+            if changes.accounts.get(&hashed_address).is_some_and(Option::is_none) {
+                removed_accounts.push(nibbles.clone());
+            }
+            account_targets.push(nibbles);
+
+            // the storage trie of a wiped account is rebuilt from its changes alone
+            let storage_changes =
+                changes.storages.get(&hashed_address).filter(|storage| !storage.wiped);
+            let mut slot_targets = Vec::with_capacity(slots.len());
+            let mut removed_slots = Vec::new();
+            for slot in slots {
+                let hashed_slot = keccak256(slot);
+                let nibbles = Nibbles::unpack(hashed_slot);
+                let value = storage_changes.and_then(|storage| storage.storage.get(&hashed_slot));
+                if value.is_some_and(|value| value.is_zero()) {
+                    removed_slots.push(nibbles.clone());
+                }
+                slot_targets.push(nibbles);
+            }
+            if !slot_targets.is_empty() {
+                let nodes = with_branch_children(slot_targets, &removed_slots, |targets| {
+                    self.storage_trie_nodes(hashed_address, targets).map(|(_, nodes)| nodes)
+                })?;
+                witness.state.extend(nodes.into_values().map(|node| (keccak256(&node), node)));
+            }
+
+            let account = hashed_account_cursor
+                .seek(hashed_address)?
+                .filter(|(key, _)| *key == hashed_address)
+                .map(|(_, account)| account);
+            let code_hash = account.and_then(|account| account.bytecode_hash);
+            if let Some(code_hash) = code_hash.filter(|hash| *hash != KECCAK_EMPTY) {
+                if let Some(bytecode) = self.tx.get::<tables::Bytecodes>(code_hash)? {
+                    witness.codes.insert(code_hash, bytecode.original_bytes());
+                }
+            }
+        }
+
+        let nodes = with_branch_children(account_targets, &removed_accounts, |targets| {
+            self.account_trie_nodes(targets).map(|(_, nodes)| nodes)
+        })?;
+        witness.state.extend(nodes.into_values().map(|node| (keccak256(&node), node)));
+
+        Ok(witness)
+    }
+
+    /// Collect the nodes of the account trie on the paths to the given targets, keyed by their
+    /// path.
+    ///
+    /// A target may be a partial path, in which case the node at that path is included as well.
+    /// The nodes of the paths to the first and last key of a range prove the range, see
+    /// `snap/1`.
+    pub fn account_trie_nodes(
+        &self,
+        targets: Vec<Nibbles>,
+    ) -> Result<(B256, BTreeMap<Nibbles, Bytes>), StateRootError> {
+        let hashed_account_cursor = self.hashed_cursor_factory.hashed_account_cursor()?;
+        let trie_cursor =
+            DatabaseAccountTrieCursor::new(self.tx.cursor_read::<tables::AccountsTrie>()?);
+
+        let prefix_set =
+            PrefixSetMut::from(self.prefix_sets.account_prefix_set.iter().chain(&targets).cloned());
+        let walker = TrieWalker::new(trie_cursor, prefix_set.freeze());
+
+        let mut hash_builder = HashBuilder::default().with_proof_retainer(targets);
+        let mut account_rlp = Vec::with_capacity(128);
+        let mut account_node_iter = AccountNodeIter::new(walker, hashed_account_cursor);
+        while let Some(account_node) = account_node_iter.try_next()? {
+            match account_node {
+                AccountNode::Branch(node) => {
+                    hash_builder.add_branch(node.key, node.value, node.children_are_in_trie);
+                }
+                AccountNode::Leaf(hashed_address, account) => {
+                    let storage_root = self.storage_root(hashed_address)?;
+
+                    account_rlp.clear();
+                    let account = TrieAccount::from((account, storage_root));
+                    account.encode(&mut account_rlp as &mut dyn BufMut);
+
+                    hash_builder.add_leaf(Nibbles::unpack(hashed_address), &account_rlp);
+                }
+            }
+        }
+
+        let root = hash_builder.root();
+        Ok((root, hash_builder.take_proofs()))
+    }
+
+    /// Collect the nodes of the storage trie of the given account on the paths to the given
+    /// targets, keyed by their path. See [Proof::account_trie_nodes].
+    pub fn storage_trie_nodes(
+        &self,
+        hashed_address: B256,
+        targets: Vec<Nibbles>,
+    ) -> Result<(B256, BTreeMap<Nibbles, Bytes>), StorageRootError> {
+        let mut hashed_storage_cursor = self.hashed_cursor_factory.hashed_storage_cursor()?;
+
+        // short circuit on empty storage
+        if hashed_storage_cursor.is_storage_empty(hashed_address)? {
+            return Ok((EMPTY_ROOT_HASH, BTreeMap::default()))
+        }
+
+        let overlay_prefix_set = self.prefix_sets.storage_prefix_sets.get(&hashed_address);
+        let prefix_set = PrefixSetMut::from(
+            overlay_prefix_set.into_iter().flat_map(|set| set.iter()).chain(&targets).cloned(),
+        )
+        .freeze();
+        let trie_cursor = DatabaseStorageTrieCursor::new(
+            self.tx.cursor_dup_read::<tables::StoragesTrie>()?,
+            hashed_address,
+        );
+        let walker = TrieWalker::new(trie_cursor, prefix_set);
+
+        let mut hash_builder = HashBuilder::default().with_proof_retainer(targets);
+        let mut storage_node_iter =
+            StorageNodeIter::new(walker, hashed_storage_cursor, hashed_address);
+        while let Some(node) = storage_node_iter.try_next()? {
+            match node {
+                StorageNode::Branch(node) => {
+                    hash_builder.add_branch(node.key, node.value, node.children_are_in_trie);
+                }
+                StorageNode::Leaf(hashed_slot, value) => {
+                    hash_builder.add_leaf(
+                        Nibbles::unpack(hashed_slot),
+                        alloy_rlp::encode_fixed_size(&value).as_ref(),
+                    );
+                }
+            }
+        }
+
+        let root = hash_builder.root();
+        Ok((root, hash_builder.take_proofs()))
     }
 
     /// Compute storage root.
@@ -119,7 +333,15 @@ where
         }
 
         let target_nibbles = proofs.iter().map(|p| p.nibbles.clone()).collect::<Vec<_>>();
-        let prefix_set = PrefixSetMut::from(target_nibbles.clone()).freeze();
+        let overlay_prefix_set = self.prefix_sets.storage_prefix_sets.get(&hashed_address);
+        let prefix_set = PrefixSetMut::from(
+            overlay_prefix_set
+                .into_iter()
+                .flat_map(|set| set.iter())
+                .chain(&target_nibbles)
+                .cloned(),
+        )
+        .freeze();
         let trie_cursor = DatabaseStorageTrieCursor::new(
             self.tx.cursor_dup_read::<tables::StoragesTrie>()?,
             hashed_address,
@@ -161,17 +383,56 @@ where
     }
 }
 
+/// Collects the trie nodes on the paths to the given targets with `collect`, along with the
+/// children of the branch nodes on the paths to the removed keys.
+///
+/// The children are needed to collapse a branch node that is left with a single child. Children
+/// that are embedded in their parent are already part of it.
+fn with_branch_children<E>(
+    mut targets: Vec<Nibbles>,
+    removed: &[Nibbles],
+    mut collect: impl FnMut(Vec<Nibbles>) -> Result<BTreeMap<Nibbles, Bytes>, E>,
+) -> Result<BTreeMap<Nibbles, Bytes>, E> {
+    let nodes = collect(targets.clone())?;
+    if removed.is_empty() {
+        return Ok(nodes)
+    }
+
+    let mut children = Vec::new();
+    for (path, node) in &nodes {
+        if !removed.iter().any(|key| key.starts_with(path)) {
+            continue
+        }
+        let Ok(TrieNode::Branch(branch)) = TrieNode::decode(node) else { continue };
+        for (nibble, child) in branch.iter().enumerate() {
+            if matches!(child, ChildRef::Hash(_)) {
+                let mut child_path = path.clone();
+                child_path.push(nibble as u8);
+                children.push(child_path);
+            }
+        }
+    }
+    if children.is_empty() {
+        return Ok(nodes)
+    }
+
+    targets.extend(children);
+    collect(targets)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::StateRoot;
+    use crate::{HashedPostState, HashedStorage, StateRoot};
     use alloy_chains::Chain;
     use once_cell::sync::Lazy;
-    use reth_db::database::Database;
+    use reth_db::{database::Database, transaction::DbTxMut};
     use reth_interfaces::RethResult;
-    use reth_primitives::{Account, Bytes, ChainSpec, StorageEntry, HOLESKY, MAINNET, U256};
+    use reth_primitives::{
+        Account, Bytecode, Bytes, ChainSpec, StorageEntry, HOLESKY, MAINNET, U256,
+    };
     use reth_provider::{test_utils::create_test_provider_factory, HashingWriter, ProviderFactory};
-    use std::{str::FromStr, sync::Arc};
+    use std::{collections::HashSet, str::FromStr, sync::Arc};
 
     /*
         World State (sampled from <https://ethereum.stackexchange.com/questions/268/ethereum-block-architecture/6413#6413>)
@@ -442,4 +703,202 @@ mod tests {
         let account_proof = Proof::new(provider.tx_ref()).account_proof(target, &slots).unwrap();
         similar_asserts::assert_eq!(account_proof, expected);
     }
+
+    #[test]
+    fn testspec_multiproof() {
+        // Create test database and insert genesis accounts.
+        let factory = create_test_provider_factory();
+        insert_genesis(&factory, TEST_SPEC.clone()).unwrap();
+
+        let targets = HashMap::from([
+            (Address::from_str("0x2031f89b3ea8014eb51a78c316e42af3e0d7695f").unwrap(), Vec::new()),
+            (Address::from_str("0x33f0fc440b8477fcfbe9d0bf8649e7dea9baedb2").unwrap(), Vec::new()),
+            (
+                Address::from_str("0x1ed9b1dd266b607ee278726d324b855a093394a6").unwrap(),
+                Vec::from([B256::with_last_byte(1)]),
+            ),
+            // non-existent
+            (Address::from_str("0x000d836201318ec6899a67540690382780743281").unwrap(), Vec::new()),
+        ]);
+
+        let provider = factory.provider().unwrap();
+        let proof = Proof::new(provider.tx_ref());
+        let multiproof = proof.multiproof(&targets).unwrap();
+        assert_eq!(multiproof.root, StateRoot::from_tx(provider.tx_ref()).root().unwrap());
+        assert_eq!(multiproof.account_proofs.len(), targets.len());
+        for (target, slots) in targets {
+            similar_asserts::assert_eq!(
+                multiproof.account_proofs[&target],
+                proof.account_proof(target, &slots).unwrap(),
+                "proof for {target:?} does not match"
+            );
+        }
+    }
+
+    #[test]
+    fn testspec_account_trie_nodes() {
+        // Create test database and insert genesis accounts.
+        let factory = create_test_provider_factory();
+        insert_genesis(&factory, TEST_SPEC.clone()).unwrap();
+
+        let target = Address::from_str("0x1ed9b1dd266b607ee278726d324b855a093394a6").unwrap();
+        let provider = factory.provider().unwrap();
+        let proof = Proof::new(provider.tx_ref());
+        let (root, nodes) =
+            proof.account_trie_nodes(Vec::from([Nibbles::unpack(keccak256(target))])).unwrap();
+        assert_eq!(root, StateRoot::from_tx(provider.tx_ref()).root().unwrap());
+
+        // The nodes on the path to an account are its proof.
+        let account_proof = proof.account_proof(target, &[]).unwrap();
+        assert_eq!(nodes.into_values().collect::<Vec<_>>(), account_proof.proof);
+
+        // A partial path only retains the nodes up to and including the node at that path.
+        let (_, nodes) = proof.account_trie_nodes(Vec::from([Nibbles::default()])).unwrap();
+        assert_eq!(nodes.into_values().collect::<Vec<_>>(), account_proof.proof[..1]);
+    }
+
+    #[test]
+    fn testspec_post_state_proof() {
+        // Create test database and insert genesis accounts.
+        let factory = create_test_provider_factory();
+        insert_genesis(&factory, TEST_SPEC.clone()).unwrap();
+
+        let target = Address::from_str("0x1ed9b1dd266b607ee278726d324b855a093394a6").unwrap();
+        let account = Account { nonce: 1, balance: U256::from(1), bytecode_hash: None };
+        let hashed_state =
+            HashedPostState::default().with_accounts([(keccak256(target), Some(account))]);
+
+        // Generate the proof with the account change overlaid on top of the database.
+        let provider = factory.provider().unwrap();
+        let post_state_proof = hashed_state.account_proof(provider.tx_ref(), target, &[]).unwrap();
+        assert_eq!(post_state_proof.info, Some(account));
+        drop(provider);
+
+        // Write the account change to the database and generate the proof again.
+        let mut provider = factory.provider_rw().unwrap();
+        provider.insert_account_for_hashing([(target, Some(account))]).unwrap();
+        let (_, updates) = StateRoot::from_tx(provider.tx_ref())
+            .with_prefix_sets(hashed_state.construct_prefix_sets())
+            .root_with_updates()
+            .unwrap();
+        updates.flush(provider.tx_mut()).unwrap();
+        provider.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        let account_proof = Proof::new(provider.tx_ref()).account_proof(target, &[]).unwrap();
+        similar_asserts::assert_eq!(post_state_proof, account_proof);
+    }
+
+    #[test]
+    fn holesky_deposit_contract_witness() {
+        // Create test database and insert genesis accounts and the deposit contract code.
+        let factory = create_test_provider_factory();
+        insert_genesis(&factory, HOLESKY.clone()).unwrap();
+
+        let target = Address::from_str("0x4242424242424242424242424242424242424242").unwrap();
+        let code = HOLESKY.genesis().alloc[&target].code.clone().unwrap();
+        let provider = factory.provider_rw().unwrap();
+        provider
+            .tx_ref()
+            .put::<tables::Bytecodes>(keccak256(&code), Bytecode::new_raw(code))
+            .unwrap();
+        provider.commit().unwrap();
+
+        let slots = Vec::from([B256::with_last_byte(0x22), B256::with_last_byte(0x23)]);
+        let provider = factory.provider().unwrap();
+        let proof = Proof::new(provider.tx_ref());
+        let witness = proof
+            .witness(&HashMap::from([(target, slots.clone())]), &HashedPostState::default())
+            .unwrap();
+        let account_proof = proof.account_proof(target, &slots).unwrap();
+
+        let code_hash = account_proof.info.unwrap().bytecode_hash.unwrap();
+        assert_eq!(witness.codes.len(), 1);
+        assert_eq!(keccak256(&witness.codes[&code_hash]), code_hash);
+
+        let nodes = account_proof
+            .proof
+            .iter()
+            .chain(account_proof.storage_proofs.iter().flat_map(|proof| proof.proof.iter()))
+            .collect::<Vec<_>>();
+        for node in &nodes {
+            assert_eq!(witness.state.get(&keccak256(node)), Some(*node));
+        }
+        assert_eq!(witness.state.len(), nodes.iter().collect::<HashSet<_>>().len());
+    }
+
+    #[test]
+    fn witness_state_root_from_witness_alone() {
+        // Create test database with the genesis accounts and a contract with storage.
+        let factory = create_test_provider_factory();
+        insert_genesis(&factory, TEST_SPEC.clone()).unwrap();
+
+        let contract = Address::with_last_byte(0xcc);
+        let contract_account = Account { nonce: 1, balance: U256::ZERO, bytecode_hash: None };
+        let mut provider = factory.provider_rw().unwrap();
+        provider.insert_account_for_hashing([(contract, Some(contract_account))]).unwrap();
+        provider
+            .insert_storage_for_hashing([(
+                contract,
+                (1..=20u64).map(|slot| StorageEntry {
+                    key: B256::from(U256::from(slot)),
+                    value: U256::from(slot),
+                }),
+            )])
+            .unwrap();
+        let (_, updates) = StateRoot::from_tx(provider.tx_ref()).root_with_updates().unwrap();
+        updates.flush(provider.tx_mut()).unwrap();
+        provider.commit().unwrap();
+
+        // Remove an account, so that its branch collapses into the remaining sibling, update and
+        // insert accounts, and clear, update and insert storage slots.
+        let removed = Address::from_str("0x33f0fc440b8477fcfbe9d0bf8649e7dea9baedb2").unwrap();
+        let updated = Address::from_str("0x2031f89b3ea8014eb51a78c316e42af3e0d7695f").unwrap();
+        let inserted = Address::with_last_byte(0x42);
+        let account = Account { nonce: 1, balance: U256::from(1), bytecode_hash: None };
+        let slots = [1u64, 2, 3, 4, 5, 6, 7, 100].map(|slot| B256::from(U256::from(slot))).to_vec();
+        let changes = HashedPostState::default()
+            .with_accounts([
+                (keccak256(removed), None),
+                (keccak256(updated), Some(account)),
+                (keccak256(inserted), Some(account)),
+                (keccak256(contract), Some(contract_account)),
+            ])
+            .with_storages([(
+                keccak256(contract),
+                HashedStorage::from_iter(
+                    false,
+                    slots.iter().map(|slot| {
+                        let value = if U256::from_be_bytes(slot.0) > U256::from(6) {
+                            U256::from(1000)
+                        } else {
+                            U256::ZERO
+                        };
+                        (keccak256(slot), value)
+                    }),
+                ),
+            )]);
+        let targets = HashMap::from([
+            (removed, Vec::new()),
+            (updated, Vec::new()),
+            (inserted, Vec::new()),
+            (contract, slots),
+        ]);
+
+        let provider = factory.provider().unwrap();
+        let root = StateRoot::from_tx(provider.tx_ref()).root().unwrap();
+        let expected = changes.state_root(provider.tx_ref()).unwrap();
+        assert_ne!(root, expected);
+
+        let witness = Proof::new(provider.tx_ref()).witness(&targets, &changes).unwrap();
+        assert_eq!(crate::witness::witness_state_root(root, &witness, &changes), Ok(expected));
+
+        // Without the children of the collapsing branches, the root can't be recomputed.
+        let witness =
+            Proof::new(provider.tx_ref()).witness(&targets, &HashedPostState::default()).unwrap();
+        assert!(matches!(
+            crate::witness::witness_state_root(root, &witness, &changes),
+            Err(crate::witness::WitnessError::MissingNode(_))
+        ));
+    }
 }
@@ -0,0 +1,285 @@
+//! Verification of range proofs, as they are served by `snap/1`.
+
+use crate::trie_node::{ChildRef, TrieNode};
+use reth_primitives::{
+    keccak256,
+    trie::{HashBuilder, Nibbles},
+    Bytes, B256,
+};
+use std::collections::HashMap;
+
+/// Errors of [verify_range_proof].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RangeProofError {
+    /// The keys of the range are not strictly ascending or below the origin.
+    #[error("keys are not ascending from the origin")]
+    UnorderedKeys,
+    /// A node on the path to one of the boundaries is missing from the proof.
+    #[error("proof node {0} is missing")]
+    MissingNode(B256),
+    /// A node of the proof could not be decoded.
+    #[error("invalid proof node: {0}")]
+    InvalidNode(alloy_rlp::Error),
+    /// A node next to the range is embedded in its parent and can't be represented by its hash.
+    #[error("embedded node next to the range")]
+    InlineNode,
+    /// The proof of an empty range proves entries after the origin.
+    #[error("entries after the origin of an empty range")]
+    MissingEntries,
+    /// The root of the range and the proof doesn't match the expected root.
+    #[error("root mismatch: got {got}, expected {expected}")]
+    RootMismatch {
+        /// The root of the range and the proof.
+        got: B256,
+        /// The expected root.
+        expected: B256,
+    },
+}
+
+/// Verifies that the given leaves are exactly the leaves of the trie with the given root from the
+/// origin up to the last leaf, and returns whether the trie has more leaves after the range.
+///
+/// The proof consists of the nodes on the paths to the origin and to the last leaf. Without a
+/// proof, the leaves are expected to be the complete trie. Without leaves, the proof of the origin
+/// is expected to prove that there are no leaves at or after the origin.
+///
+/// The root is recomputed from the leaves and the hashes of the subtries left and right of the
+/// boundary paths, which are taken from the proof.
+pub fn verify_range_proof(
+    root: B256,
+    origin: B256,
+    leaves: &[(B256, Vec<u8>)],
+    proof: &[Bytes],
+) -> Result<bool, RangeProofError> {
+    let mut prev = None;
+    for (key, _) in leaves {
+        if *key < origin || prev.is_some_and(|prev| prev >= key) {
+            return Err(RangeProofError::UnorderedKeys)
+        }
+        prev = Some(key);
+    }
+
+    let mut hash_builder = HashBuilder::default();
+    if proof.is_empty() {
+        for (key, value) in leaves {
+            hash_builder.add_leaf(Nibbles::unpack(key), value);
+        }
+        let got = hash_builder.root();
+        return if got == root {
+            Ok(false)
+        } else {
+            Err(RangeProofError::RootMismatch { got, expected: root })
+        }
+    }
+
+    let last = leaves.last().map_or(origin, |(key, _)| *key);
+    let mut collector = BoundaryCollector {
+        nodes: proof.iter().map(|node| (keccak256(node), node)).collect(),
+        left: Nibbles::unpack(origin),
+        right: Nibbles::unpack(last),
+        left_siblings: Vec::new(),
+        right_siblings: Vec::new(),
+    };
+    collector.collect(&ChildRef::Hash(root), Nibbles::default())?;
+
+    let has_more = !collector.right_siblings.is_empty();
+    if leaves.is_empty() && has_more {
+        return Err(RangeProofError::MissingEntries)
+    }
+
+    for (path, hash) in collector.left_siblings {
+        hash_builder.add_branch(path, hash, false);
+    }
+    for (key, value) in leaves {
+        hash_builder.add_leaf(Nibbles::unpack(key), value);
+    }
+    for (path, hash) in collector.right_siblings {
+        hash_builder.add_branch(path, hash, false);
+    }
+    let got = hash_builder.root();
+    if got != root {
+        return Err(RangeProofError::RootMismatch { got, expected: root })
+    }
+    Ok(has_more)
+}
+
+/// The position of a subtrie relative to the proven range.
+enum Position {
+    /// All keys of the subtrie are before the range.
+    Left,
+    /// All keys of the subtrie are within the range.
+    Inside,
+    /// All keys of the subtrie are after the range.
+    Right,
+    /// The subtrie is on the path to a boundary of the range.
+    Boundary,
+}
+
+/// Collects the hashes of the subtries next to the paths to the boundaries of a range.
+struct BoundaryCollector<'a> {
+    /// The nodes of the proof, keyed by their hash.
+    nodes: HashMap<B256, &'a Bytes>,
+    /// The path to the first key of the range.
+    left: Nibbles,
+    /// The path to the last key of the range.
+    right: Nibbles,
+    /// The subtries before the range, in order.
+    left_siblings: Vec<(Nibbles, B256)>,
+    /// The subtries after the range, in order.
+    right_siblings: Vec<(Nibbles, B256)>,
+}
+
+impl BoundaryCollector<'_> {
+    /// Returns the position of the subtrie at the given path.
+    fn position(&self, path: &[u8]) -> Position {
+        let (left, right) = (self.left.as_slice(), self.right.as_slice());
+        if left.starts_with(path) || right.starts_with(path) {
+            // A full path of a leaf on the boundary is part of the range.
+            if path.len() == left.len() {
+                return Position::Inside
+            }
+            return Position::Boundary
+        }
+        if path < &left[..path.len()] {
+            Position::Left
+        } else if path > &right[..path.len()] {
+            Position::Right
+        } else {
+            Position::Inside
+        }
+    }
+
+    /// Records the subtrie of the node at the given path by its position.
+    fn record(
+        &mut self,
+        position: Position,
+        node: &ChildRef,
+        path: Nibbles,
+    ) -> Result<(), RangeProofError> {
+        let siblings = match position {
+            Position::Left => &mut self.left_siblings,
+            Position::Right => &mut self.right_siblings,
+            Position::Inside | Position::Boundary => return Ok(()),
+        };
+        match node {
+            ChildRef::Empty => {}
+            ChildRef::Hash(hash) => siblings.push((path, *hash)),
+            ChildRef::Inline(_) => return Err(RangeProofError::InlineNode),
+        }
+        Ok(())
+    }
+
+    /// Walks the node at the given path along the boundary paths.
+    fn collect(&mut self, node: &ChildRef, path: Nibbles) -> Result<(), RangeProofError> {
+        let position = self.position(path.as_slice());
+        if !matches!(position, Position::Boundary) {
+            return self.record(position, node, path)
+        }
+
+        let decoded = match node {
+            ChildRef::Empty => return Ok(()),
+            ChildRef::Hash(hash) => {
+                let encoded = self.nodes.get(hash).ok_or(RangeProofError::MissingNode(*hash))?;
+                TrieNode::decode(encoded)
+            }
+            ChildRef::Inline(encoded) => TrieNode::decode(encoded),
+        }
+        .map_err(RangeProofError::InvalidNode)?;
+
+        match decoded {
+            TrieNode::Branch(children) => {
+                for (nibble, child) in children.iter().enumerate() {
+                    let mut child_path = path.clone();
+                    child_path.push(nibble as u8);
+                    self.collect(child, child_path)?;
+                }
+            }
+            TrieNode::Extension { key, child } => {
+                let mut child_path = path.clone();
+                child_path.extend_from_slice(&key);
+                match self.position(child_path.as_slice()) {
+                    Position::Boundary => self.collect(&child, child_path)?,
+                    position => self.record(position, node, path)?,
+                }
+            }
+            TrieNode::Leaf { key, .. } => {
+                let mut full_path = path.clone();
+                full_path.extend_from_slice(&key);
+                let position = self.position(full_path.as_slice());
+                self.record(position, node, path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::U256;
+    use std::collections::BTreeMap;
+
+    /// Returns the leaves of a trie with the given number of keys, its root and the nodes on the
+    /// paths to the given targets.
+    fn trie(count: u8, targets: &[B256]) -> (Vec<(B256, Vec<u8>)>, B256, Vec<Bytes>) {
+        let leaves = (0..count)
+            .map(|idx| (keccak256([idx]), alloy_rlp::encode([idx; 40])))
+            .collect::<BTreeMap<_, _>>();
+        let mut hash_builder = HashBuilder::default()
+            .with_proof_retainer(targets.iter().map(Nibbles::unpack).collect());
+        for (key, value) in &leaves {
+            hash_builder.add_leaf(Nibbles::unpack(key), value);
+        }
+        let root = hash_builder.root();
+        let proof = hash_builder.take_proofs().into_values().collect();
+        (leaves.into_iter().collect(), root, proof)
+    }
+
+    #[test]
+    fn verify_complete_trie() {
+        let (leaves, root, _) = trie(20, &[]);
+        assert_eq!(verify_range_proof(root, B256::ZERO, &leaves, &[]), Ok(false));
+        assert!(verify_range_proof(root, B256::ZERO, &leaves[1..], &[]).is_err());
+    }
+
+    #[test]
+    fn verify_partial_ranges() {
+        let (leaves, root, _) = trie(50, &[]);
+        for (start, end) in [(0, 10), (5, 30), (30, 50), (49, 50)] {
+            let origin = if start == 0 { B256::ZERO } else { leaves[start].0 };
+            let (_, _, proof) = trie(50, &[origin, leaves[end - 1].0]);
+            let range = &leaves[start..end];
+            assert_eq!(verify_range_proof(root, origin, range, &proof), Ok(end < leaves.len()));
+
+            // A missing leaf within the range is detected.
+            if range.len() > 2 {
+                let mut incomplete = range.to_vec();
+                incomplete.remove(1);
+                assert!(verify_range_proof(root, origin, &incomplete, &proof).is_err());
+            }
+
+            // A modified value is detected.
+            let mut modified = range.to_vec();
+            modified[0].1 = alloy_rlp::encode([0xff; 40]);
+            assert!(verify_range_proof(root, origin, &modified, &proof).is_err());
+        }
+    }
+
+    #[test]
+    fn verify_empty_range() {
+        let (leaves, root, _) = trie(50, &[]);
+
+        // There are no leaves after the last one.
+        let origin = B256::from(U256::from_be_bytes(leaves[49].0 .0) + U256::from(1));
+        let (_, _, proof) = trie(50, &[origin]);
+        assert_eq!(verify_range_proof(root, origin, &[], &proof), Ok(false));
+
+        // Hiding the leaves after the origin is detected.
+        let origin = leaves[40].0;
+        let (_, _, proof) = trie(50, &[origin]);
+        assert_eq!(
+            verify_range_proof(root, origin, &[], &proof),
+            Err(RangeProofError::MissingEntries)
+        );
+    }
+}
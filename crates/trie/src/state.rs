@@ -1,6 +1,7 @@
 use crate::{
     hashed_cursor::HashedPostStateCursorFactory,
     prefix_set::{PrefixSetMut, TriePrefixSets},
+    proof::{MultiProof, Proof},
     updates::TrieUpdates,
     StateRoot, StateRootError,
 };
@@ -12,8 +13,10 @@ use reth_db::{
     DatabaseError,
 };
 use reth_primitives::{
-    keccak256, revm::compat::into_reth_acc, trie::Nibbles, Account, Address, BlockNumber, B256,
-    U256,
+    keccak256,
+    revm::compat::into_reth_acc,
+    trie::{AccountProof, ExecutionWitness, Nibbles},
+    Account, Address, BlockNumber, B256, U256,
 };
 use revm::db::BundleAccount;
 use std::{
@@ -248,6 +251,56 @@ impl HashedPostState {
             .with_prefix_sets(prefix_sets)
             .root_with_updates()
     }
+
+    /// Generate the account proof with storage proofs of the given slots for the state of the
+    /// database with this [HashedPostState] overlaid on top.
+    ///
+    /// Overlaying the reverts from [Self::from_revert_range] produces proofs at historical blocks.
+    pub fn account_proof<TX: DbTx>(
+        &self,
+        tx: &TX,
+        address: Address,
+        slots: &[B256],
+    ) -> Result<AccountProof, StateRootError> {
+        let sorted = self.clone().into_sorted();
+        let prefix_sets = self.construct_prefix_sets();
+        Proof::new(tx)
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(tx, &sorted))
+            .with_prefix_sets(prefix_sets)
+            .account_proof(address, slots)
+    }
+
+    /// Generate the proofs of multiple accounts and their storage slots for this
+    /// [HashedPostState]. See [Self::account_proof] for more info.
+    pub fn multiproof<TX: DbTx>(
+        &self,
+        tx: &TX,
+        targets: &HashMap<Address, Vec<B256>>,
+    ) -> Result<MultiProof, StateRootError> {
+        let sorted = self.clone().into_sorted();
+        let prefix_sets = self.construct_prefix_sets();
+        Proof::new(tx)
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(tx, &sorted))
+            .with_prefix_sets(prefix_sets)
+            .multiproof(targets)
+    }
+
+    /// Generate the [ExecutionWitness] of the given accounts and storage slots for this
+    /// [HashedPostState], that is sufficient to apply the given changes on top of it. See
+    /// [Self::account_proof] and [Proof::witness] for more info.
+    pub fn witness<TX: DbTx>(
+        &self,
+        tx: &TX,
+        targets: &HashMap<Address, Vec<B256>>,
+        changes: &HashedPostState,
+    ) -> Result<ExecutionWitness, StateRootError> {
+        let sorted = self.clone().into_sorted();
+        let prefix_sets = self.construct_prefix_sets();
+        Proof::new(tx)
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(tx, &sorted))
+            .with_prefix_sets(prefix_sets)
+            .witness(targets, changes)
+    }
 }
 
 /// Representation of in-memory hashed storage.
@@ -0,0 +1,161 @@
+//! Decoding of RLP encoded trie nodes, as they are exchanged in proofs.
+
+use alloy_rlp::{Decodable, Header, EMPTY_STRING_CODE};
+use reth_primitives::{trie::Nibbles, Bytes, B256};
+
+/// A reference to a child node.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum ChildRef {
+    /// There is no child.
+    #[default]
+    Empty,
+    /// The child is referenced by the hash of its RLP encoding.
+    Hash(B256),
+    /// The child is embedded in its parent, because its RLP encoding is shorter than a hash.
+    Inline(Bytes),
+}
+
+impl ChildRef {
+    /// Returns true if there is no child.
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Self::Empty)
+    }
+}
+
+/// A decoded trie node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TrieNode {
+    /// A branch node with a child for every nibble. Branch nodes of tries with fixed size keys
+    /// never have a value.
+    Branch([ChildRef; 16]),
+    /// An extension node with the shared nibbles of its child.
+    Extension {
+        /// The nibbles shared by all leaves below the node.
+        key: Nibbles,
+        /// The child node.
+        child: ChildRef,
+    },
+    /// A leaf node with the remaining nibbles of its key.
+    Leaf {
+        /// The remaining nibbles of the key.
+        key: Nibbles,
+        /// The value of the leaf.
+        value: Bytes,
+    },
+}
+
+impl TrieNode {
+    /// Decodes a node from its RLP encoding.
+    pub fn decode(mut buf: &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(&mut buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        if buf.len() != header.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: buf.len(),
+            })
+        }
+
+        let mut items = Vec::with_capacity(17);
+        while !buf.is_empty() {
+            let mut item = buf;
+            let header = Header::decode(&mut item)?;
+            let len = buf.len() - item.len() + header.payload_length;
+            if len > buf.len() {
+                return Err(alloy_rlp::Error::InputTooShort)
+            }
+            items.push(&buf[..len]);
+            buf = &buf[len..];
+        }
+
+        match items.len() {
+            17 => {
+                if items[16] != [EMPTY_STRING_CODE] {
+                    return Err(alloy_rlp::Error::Custom("branch node with value"))
+                }
+                let mut children: [ChildRef; 16] = Default::default();
+                for (child, item) in children.iter_mut().zip(items) {
+                    *child = decode_child(item)?;
+                }
+                Ok(Self::Branch(children))
+            }
+            2 => {
+                let path = Bytes::decode(&mut &items[0][..])?;
+                let (key, is_leaf) = decode_path(&path)?;
+                if is_leaf {
+                    Ok(Self::Leaf { key, value: Bytes::decode(&mut &items[1][..])? })
+                } else {
+                    Ok(Self::Extension { key, child: decode_child(items[1])? })
+                }
+            }
+            _ => Err(alloy_rlp::Error::Custom("invalid number of trie node items")),
+        }
+    }
+}
+
+/// Decodes the reference to a child from its raw RLP item.
+fn decode_child(item: &[u8]) -> alloy_rlp::Result<ChildRef> {
+    match item.first() {
+        Some(&EMPTY_STRING_CODE) if item.len() == 1 => Ok(ChildRef::Empty),
+        Some(first) if *first >= alloy_rlp::EMPTY_LIST_CODE => {
+            Ok(ChildRef::Inline(Bytes::copy_from_slice(item)))
+        }
+        _ => Ok(ChildRef::Hash(B256::decode(&mut &item[..])?)),
+    }
+}
+
+/// Decodes a hex-prefix encoded path and returns its nibbles and whether it's the path of a leaf.
+fn decode_path(path: &[u8]) -> alloy_rlp::Result<(Nibbles, bool)> {
+    let Some((first, rest)) = path.split_first() else {
+        return Err(alloy_rlp::Error::Custom("empty trie node path"))
+    };
+    let flag = first >> 4;
+    if flag > 3 {
+        return Err(alloy_rlp::Error::Custom("invalid trie node path flag"))
+    }
+    let mut nibbles = Vec::with_capacity(rest.len() * 2 + 1);
+    if flag & 1 != 0 {
+        nibbles.push(first & 0x0f);
+    }
+    for byte in rest {
+        nibbles.push(byte >> 4);
+        nibbles.push(byte & 0x0f);
+    }
+    Ok((Nibbles::from_nibbles_unchecked(nibbles), flag & 2 != 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{keccak256, trie::HashBuilder};
+
+    #[test]
+    fn decode_proof_nodes() {
+        let keys = [B256::with_last_byte(1), B256::with_last_byte(2), B256::repeat_byte(0xff)];
+        let target = Nibbles::unpack(keys[0]);
+        let mut hash_builder = HashBuilder::default().with_proof_retainer(vec![target.clone()]);
+        for key in keys {
+            hash_builder.add_leaf(Nibbles::unpack(key), &alloy_rlp::encode([0xaa; 40]));
+        }
+        let root = hash_builder.root();
+        let proof = hash_builder.take_proofs();
+
+        // The root is a branch that references the extension to the two leaves with a shared
+        // prefix and the leaf of the third key.
+        let TrieNode::Branch(children) = TrieNode::decode(&proof[&Nibbles::default()]).unwrap()
+        else {
+            panic!("expected branch")
+        };
+        assert_eq!(keccak256(&proof[&Nibbles::default()]), root);
+        assert_eq!(children.iter().filter(|child| !child.is_empty()).count(), 2);
+        let ChildRef::Hash(child) = &children[0] else { panic!("expected hash") };
+        let extension = &proof[&Nibbles::from_nibbles_unchecked([0])];
+        assert_eq!(keccak256(extension), *child);
+        assert!(matches!(TrieNode::decode(extension).unwrap(), TrieNode::Extension { .. }));
+
+        let leaf = TrieNode::decode(&proof[&target]).unwrap();
+        assert!(matches!(leaf, TrieNode::Leaf { key, .. } if key.is_empty()));
+    }
+}
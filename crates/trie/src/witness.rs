@@ -0,0 +1,348 @@
+//! Computation of the state root from an [ExecutionWitness], without access to the database.
+
+use crate::{
+    trie_node::{ChildRef, TrieNode},
+    HashedPostState,
+};
+use alloy_rlp::{BufMut, Decodable, Encodable, Header, EMPTY_STRING_CODE};
+use reth_primitives::{
+    constants::EMPTY_ROOT_HASH,
+    keccak256,
+    trie::{ExecutionWitness, Nibbles, TrieAccount},
+    Bytes, B256,
+};
+use std::collections::BTreeMap;
+
+/// Errors of [witness_state_root].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum WitnessError {
+    /// A node that is needed to apply the changes is missing from the witness.
+    #[error("witness node {0} is missing")]
+    MissingNode(B256),
+    /// A node of the witness or an account leaf could not be decoded.
+    #[error("invalid witness node: {0}")]
+    InvalidNode(alloy_rlp::Error),
+}
+
+/// Computes the state root after applying the changes to the state with the given root, using
+/// only the trie nodes of the witness.
+///
+/// The witness must contain the nodes on the paths to every changed account and storage slot,
+/// and the children of the branch nodes that collapse when keys are removed, as generated by
+/// `Proof::witness`.
+pub fn witness_state_root(
+    root: B256,
+    witness: &ExecutionWitness,
+    changes: &HashedPostState,
+) -> Result<B256, WitnessError> {
+    let nodes = &witness.state;
+
+    let mut hashed_addresses =
+        changes.accounts.keys().chain(changes.storages.keys()).copied().collect::<Vec<_>>();
+    hashed_addresses.sort_unstable();
+    hashed_addresses.dedup();
+
+    let mut accounts = Node::Hash(root);
+    for hashed_address in hashed_addresses {
+        let path = Nibbles::unpack(hashed_address);
+        let account = changes.accounts.get(&hashed_address);
+        if account.is_some_and(Option::is_none) {
+            accounts = accounts.remove(path.as_slice(), nodes)?;
+            continue
+        }
+
+        let previous = accounts
+            .get(path.as_slice(), nodes)?
+            .map(|leaf| TrieAccount::decode(&mut leaf.as_slice()))
+            .transpose()
+            .map_err(WitnessError::InvalidNode)?;
+        let previous_storage_root =
+            previous.map_or(EMPTY_ROOT_HASH, |account| account.storage_root());
+
+        let storage_root = match changes.storages.get(&hashed_address) {
+            Some(storage) => {
+                let mut slots =
+                    Node::Hash(if storage.wiped { EMPTY_ROOT_HASH } else { previous_storage_root });
+                for (hashed_slot, value) in &storage.storage {
+                    let path = Nibbles::unpack(hashed_slot);
+                    slots = if value.is_zero() {
+                        slots.remove(path.as_slice(), nodes)?
+                    } else {
+                        slots.insert(
+                            path.as_slice(),
+                            alloy_rlp::encode_fixed_size(value).to_vec(),
+                            nodes,
+                        )?
+                    };
+                }
+                slots.root()
+            }
+            None => previous_storage_root,
+        };
+
+        let account = match account.copied().flatten() {
+            Some(account) => TrieAccount::from((account, storage_root)),
+            // only the storage of the account changed
+            None => match previous {
+                Some(previous) => previous.with_storage_root(storage_root),
+                None => continue,
+            },
+        };
+        accounts = accounts.insert(path.as_slice(), alloy_rlp::encode(account), nodes)?;
+    }
+
+    Ok(accounts.root())
+}
+
+/// A node of a trie that is partially resolved from the nodes of a witness.
+#[derive(Debug, Default)]
+enum Node {
+    /// There is no node.
+    #[default]
+    Empty,
+    /// A node that is only known by its hash.
+    Hash(B256),
+    /// A leaf node with the remaining nibbles of its key.
+    Leaf { key: Vec<u8>, value: Vec<u8> },
+    /// An extension node with the shared nibbles of its child.
+    Extension { key: Vec<u8>, child: Box<Node> },
+    /// A branch node with a child for every nibble.
+    Branch(Box<[Node; 16]>),
+}
+
+impl Node {
+    /// Creates the node of a child reference.
+    fn from_child(child: ChildRef) -> Result<Self, WitnessError> {
+        match child {
+            ChildRef::Empty => Ok(Self::Empty),
+            ChildRef::Hash(hash) => Ok(Self::Hash(hash)),
+            ChildRef::Inline(encoded) => Self::decode(&encoded),
+        }
+    }
+
+    /// Decodes a node from its RLP encoding.
+    fn decode(encoded: &[u8]) -> Result<Self, WitnessError> {
+        match TrieNode::decode(encoded).map_err(WitnessError::InvalidNode)? {
+            TrieNode::Branch(children) => {
+                let mut branch: [Self; 16] = Default::default();
+                for (node, child) in branch.iter_mut().zip(children) {
+                    *node = Self::from_child(child)?;
+                }
+                Ok(Self::Branch(Box::new(branch)))
+            }
+            TrieNode::Extension { key, child } => {
+                let child = Box::new(Self::from_child(child)?);
+                Ok(Self::Extension { key: key.as_slice().to_vec(), child })
+            }
+            TrieNode::Leaf { key, value } => {
+                Ok(Self::Leaf { key: key.as_slice().to_vec(), value: value.to_vec() })
+            }
+        }
+    }
+
+    /// Resolves the node from the witness if it's only known by its hash.
+    fn resolved(self, nodes: &BTreeMap<B256, Bytes>) -> Result<Self, WitnessError> {
+        match self {
+            Self::Hash(hash) if hash == EMPTY_ROOT_HASH => Ok(Self::Empty),
+            Self::Hash(hash) => {
+                Self::decode(nodes.get(&hash).ok_or(WitnessError::MissingNode(hash))?)
+            }
+            node => Ok(node),
+        }
+    }
+
+    /// Returns the value of the leaf at the given path.
+    fn get(
+        &mut self,
+        path: &[u8],
+        nodes: &BTreeMap<B256, Bytes>,
+    ) -> Result<Option<Vec<u8>>, WitnessError> {
+        *self = std::mem::take(self).resolved(nodes)?;
+        match self {
+            Self::Leaf { key, value } => Ok((key.as_slice() == path).then(|| value.clone())),
+            Self::Extension { key, child } => match path.strip_prefix(key.as_slice()) {
+                Some(rest) => child.get(rest, nodes),
+                None => Ok(None),
+            },
+            Self::Branch(children) => {
+                let (nibble, rest) = path.split_first().expect("keys have a fixed size");
+                children[*nibble as usize].get(rest, nodes)
+            }
+            Self::Empty | Self::Hash(_) => Ok(None),
+        }
+    }
+
+    /// Inserts or updates the leaf at the given path.
+    fn insert(
+        self,
+        path: &[u8],
+        value: Vec<u8>,
+        nodes: &BTreeMap<B256, Bytes>,
+    ) -> Result<Self, WitnessError> {
+        Ok(match self.resolved(nodes)? {
+            Self::Leaf { key, .. } if key == path => Self::Leaf { key, value },
+            Self::Leaf { key, value: other } => {
+                let common = common_prefix_length(&key, path);
+                let mut children: [Self; 16] = Default::default();
+                children[key[common] as usize] =
+                    Self::Leaf { key: key[common + 1..].to_vec(), value: other };
+                children[path[common] as usize] =
+                    Self::Leaf { key: path[common + 1..].to_vec(), value };
+                Self::extension(path[..common].to_vec(), Self::Branch(Box::new(children)))
+            }
+            Self::Extension { key, child } => {
+                let common = common_prefix_length(&key, path);
+                if common == key.len() {
+                    let child = child.insert(&path[common..], value, nodes)?;
+                    Self::Extension { key, child: Box::new(child) }
+                } else {
+                    let mut children: [Self; 16] = Default::default();
+                    children[key[common] as usize] =
+                        Self::extension(key[common + 1..].to_vec(), *child);
+                    children[path[common] as usize] =
+                        Self::Leaf { key: path[common + 1..].to_vec(), value };
+                    Self::extension(path[..common].to_vec(), Self::Branch(Box::new(children)))
+                }
+            }
+            Self::Branch(mut children) => {
+                let (nibble, rest) = path.split_first().expect("keys have a fixed size");
+                let child = &mut children[*nibble as usize];
+                *child = std::mem::take(child).insert(rest, value, nodes)?;
+                Self::Branch(children)
+            }
+            Self::Empty | Self::Hash(_) => Self::Leaf { key: path.to_vec(), value },
+        })
+    }
+
+    /// Removes the leaf at the given path, if it exists.
+    ///
+    /// A branch that is left with a single child is collapsed into it, which requires the child
+    /// to be resolved.
+    fn remove(self, path: &[u8], nodes: &BTreeMap<B256, Bytes>) -> Result<Self, WitnessError> {
+        Ok(match self.resolved(nodes)? {
+            Self::Leaf { key, .. } if key == path => Self::Empty,
+            Self::Extension { key, child } if path.starts_with(&key) => {
+                let child = child.remove(&path[key.len()..], nodes)?;
+                child.with_prefix(key, nodes)?
+            }
+            Self::Branch(mut children) => {
+                let (nibble, rest) = path.split_first().expect("keys have a fixed size");
+                let child = &mut children[*nibble as usize];
+                *child = std::mem::take(child).remove(rest, nodes)?;
+
+                let mut remaining = children
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, child)| !matches!(child, Self::Empty))
+                    .map(|(nibble, _)| nibble);
+                match (remaining.next(), remaining.next()) {
+                    (None, _) => Self::Empty,
+                    (Some(only), None) => {
+                        let child = std::mem::take(&mut children[only]);
+                        child.with_prefix(vec![only as u8], nodes)?
+                    }
+                    _ => Self::Branch(children),
+                }
+            }
+            node => node,
+        })
+    }
+
+    /// Prepends the given nibbles to the path of this node.
+    fn with_prefix(
+        self,
+        prefix: Vec<u8>,
+        nodes: &BTreeMap<B256, Bytes>,
+    ) -> Result<Self, WitnessError> {
+        Ok(match self.resolved(nodes)? {
+            Self::Leaf { key, value } => Self::Leaf { key: [prefix, key].concat(), value },
+            Self::Extension { key, child } => {
+                Self::Extension { key: [prefix, key].concat(), child }
+            }
+            Self::Empty => Self::Empty,
+            branch => Self::extension(prefix, branch),
+        })
+    }
+
+    /// Returns an extension with the given key to the child, or the child if the key is empty.
+    fn extension(key: Vec<u8>, child: Self) -> Self {
+        if key.is_empty() {
+            child
+        } else {
+            Self::Extension { key, child: Box::new(child) }
+        }
+    }
+
+    /// Returns the root hash of the trie with this node as its root.
+    fn root(&self) -> B256 {
+        match self {
+            Self::Empty => EMPTY_ROOT_HASH,
+            Self::Hash(hash) => *hash,
+            node => keccak256(node.rlp()),
+        }
+    }
+
+    /// Returns the RLP encoding of the node.
+    fn rlp(&self) -> Vec<u8> {
+        let mut payload = Vec::new();
+        match self {
+            Self::Leaf { key, value } => {
+                encode_path(key, true).as_slice().encode(&mut payload);
+                value.as_slice().encode(&mut payload);
+            }
+            Self::Extension { key, child } => {
+                encode_path(key, false).as_slice().encode(&mut payload);
+                child.encode_reference(&mut payload);
+            }
+            Self::Branch(children) => {
+                for child in children.iter() {
+                    child.encode_reference(&mut payload);
+                }
+                payload.put_u8(EMPTY_STRING_CODE);
+            }
+            Self::Empty | Self::Hash(_) => unreachable!("only resolved nodes are encoded"),
+        }
+
+        let mut out = Vec::with_capacity(payload.len() + 3);
+        Header { list: true, payload_length: payload.len() }.encode(&mut out);
+        out.extend_from_slice(&payload);
+        out
+    }
+
+    /// Encodes the reference to this node in its parent, i.e. the node itself if its encoding is
+    /// shorter than a hash, or its hash otherwise.
+    fn encode_reference(&self, out: &mut dyn BufMut) {
+        match self {
+            Self::Empty => out.put_u8(EMPTY_STRING_CODE),
+            Self::Hash(hash) => hash.encode(out),
+            node => {
+                let rlp = node.rlp();
+                if rlp.len() < B256::len_bytes() {
+                    out.put_slice(&rlp);
+                } else {
+                    keccak256(&rlp).encode(out);
+                }
+            }
+        }
+    }
+}
+
+/// Returns the length of the common prefix of the given paths.
+fn common_prefix_length(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(a, b)| a == b).count()
+}
+
+/// Returns the hex-prefix encoding of the given nibbles.
+fn encode_path(nibbles: &[u8], is_leaf: bool) -> Vec<u8> {
+    let flag = if is_leaf { 0x20 } else { 0x00 };
+    let mut encoded = Vec::with_capacity(nibbles.len() / 2 + 1);
+    let rest = if nibbles.len() % 2 == 1 {
+        encoded.push(flag | 0x10 | nibbles[0]);
+        &nibbles[1..]
+    } else {
+        encoded.push(flag);
+        nibbles
+    };
+    encoded.extend(rest.chunks_exact(2).map(|pair| (pair[0] << 4) | pair[1]));
+    encoded
+}